@@ -129,11 +129,13 @@ where
             url: websocket_address,
             batch_delay: default::batch_delay(),
         },
+        compat_mode: None,
         rpc_timeout: default::rpc_timeout(),
         trusted_node: default::trusted_node(),
         genesis_restart: None,
         account_prefix: chain_data.bech32_prefix,
-        key_name: String::new(),
+        key_name: String::new().into(),
+        wallet_selection: Default::default(),
         key_store_type: Store::default(),
         key_store_folder: None,
         store_prefix: "ibc".to_string(),
@@ -142,6 +144,8 @@ where
         gas_adjustment: None,
         gas_multiplier: Some(GasMultiplier::new(1.1).unwrap()),
         fee_granter: None,
+        fee_granters: Default::default(),
+        authz_granter: None,
         max_msg_num: MaxMsgNum::default(),
         max_tx_size: MaxTxSize::default(),
         max_grpc_decoding_size: default::max_grpc_decoding_size(),
@@ -159,7 +163,13 @@ where
         packet_filter: packet_filter.unwrap_or_default(),
         address_type: AddressType::default(),
         sequential_batch_tx: false,
+        dry_run: false,
         extension_options: Vec::new(),
+        retry: Default::default(),
+        cache: Default::default(),
+        failover: Default::default(),
+        icq: Default::default(),
+        max_node_lag: None,
     })
 }
 
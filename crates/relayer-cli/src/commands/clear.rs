@@ -8,6 +8,7 @@ use ibc_relayer::link::error::LinkError;
 use ibc_relayer::link::{Link, LinkParameters};
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::Height;
 
 use crate::application::app_config;
 use crate::cli_utils::spawn_chain_counterparty;
@@ -19,6 +20,8 @@ pub enum ClearCmds {
     /// Clear outstanding packets (i.e., packet-recv and packet-ack)
     /// on a given channel in both directions. The channel is identified
     /// by the chain, port, and channel IDs at one of its ends.
+    /// With `--from-height` and `--to-height`, the packets sent and
+    /// acknowledged on the chain between these heights are relayed instead.
     Packets(ClearPacketsCmd),
 }
 
@@ -63,6 +66,25 @@ pub struct ClearPacketsCmd {
         help = "use the given signing key for the counterparty chain (default: `counterparty_key_name` config)"
     )]
     counterparty_key_name: Option<String>,
+
+    #[clap(
+        long = "from-height",
+        value_name = "FROM_HEIGHT",
+        requires = "to-height",
+        help = "Relay again the packets sent and acknowledged on the chain from this height, \
+                using the events of the blocks instead of the pending packet commitments. \
+                Requires --to-height if used."
+    )]
+    from_height: Option<u64>,
+
+    #[clap(
+        long = "to-height",
+        value_name = "TO_HEIGHT",
+        requires = "from-height",
+        help = "Relay again the packets sent and acknowledged on the chain up to this height (inclusive). \
+                Requires --from-height if used."
+    )]
+    to_height: Option<u64>,
 }
 
 impl Override<Config> for ClearPacketsCmd {
@@ -75,7 +97,7 @@ impl Override<Config> for ClearPacketsCmd {
         })?;
 
         if let Some(ref key_name) = self.key_name {
-            chain_config.key_name = key_name.to_string();
+            chain_config.key_name = key_name.as_str().into();
         }
 
         Ok(config)
@@ -86,6 +108,23 @@ impl Runnable for ClearPacketsCmd {
     fn run(&self) {
         let config = app_config();
 
+        let heights = match (self.from_height, self.to_height) {
+            (Some(from_height), Some(to_height)) => {
+                let heights = Height::new(self.chain_id.version(), from_height)
+                    .and_then(|from| Ok((from, Height::new(self.chain_id.version(), to_height)?)));
+
+                match heights {
+                    Ok((from, to)) if from <= to => Some((from, to)),
+                    Ok(_) => {
+                        Output::error("`--from-height` must not be greater than `--to-height`")
+                            .exit()
+                    }
+                    Err(e) => Output::error(e).exit(),
+                }
+            }
+            _ => None,
+        };
+
         let chains = match spawn_chain_counterparty::<BaseChainHandle>(
             &config,
             &self.chain_id,
@@ -101,7 +140,7 @@ impl Runnable for ClearPacketsCmd {
         if let Some(ref counterparty_key_name) = self.counterparty_key_name {
             match chains.dst.config() {
                 Ok(mut dst_chain_cfg) => {
-                    dst_chain_cfg.key_name = counterparty_key_name.to_string();
+                    dst_chain_cfg.key_name = counterparty_key_name.as_str().into();
                 }
                 Err(e) => Output::error(e).exit(),
             }
@@ -121,6 +160,15 @@ impl Runnable for ClearPacketsCmd {
             Err(e) => Output::error(e).exit(),
         };
 
+        if let Some((from_height, to_height)) = heights {
+            // The events of the given heights are all emitted by `--chain`,
+            // they are relayed on the path which has this chain as source
+            match fwd_link.relay_packet_events_between(from_height, to_height) {
+                Ok(events) => Output::success(events).exit(),
+                Err(e) => Output::error(e).exit(),
+            }
+        }
+
         let rev_link = match fwd_link.reverse(false, false) {
             Ok(link) => link,
             Err(e) => Output::error(e).exit(),
@@ -175,6 +223,8 @@ mod tests {
                 channel_id: ChannelId::from_str("channel-07").unwrap(),
                 key_name: None,
                 counterparty_key_name: None,
+                from_height: None,
+                to_height: None,
             },
             ClearPacketsCmd::parse_from([
                 "test",
@@ -196,7 +246,9 @@ mod tests {
                 port_id: PortId::from_str("port_id").unwrap(),
                 channel_id: ChannelId::from_str("channel-07").unwrap(),
                 key_name: None,
-                counterparty_key_name: None
+                counterparty_key_name: None,
+                from_height: None,
+                to_height: None,
             },
             ClearPacketsCmd::parse_from([
                 "test",
@@ -219,6 +271,8 @@ mod tests {
                 channel_id: ChannelId::from_str("channel-07").unwrap(),
                 key_name: Some("key_name".to_owned()),
                 counterparty_key_name: None,
+                from_height: None,
+                to_height: None,
             },
             ClearPacketsCmd::parse_from([
                 "test",
@@ -243,6 +297,8 @@ mod tests {
                 channel_id: ChannelId::from_str("channel-07").unwrap(),
                 key_name: None,
                 counterparty_key_name: Some("counterparty_key_name".to_owned()),
+                from_height: None,
+                to_height: None,
             },
             ClearPacketsCmd::parse_from([
                 "test",
@@ -258,6 +314,50 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_clear_packets_heights() {
+        assert_eq!(
+            ClearPacketsCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                key_name: None,
+                counterparty_key_name: None,
+                from_height: Some(100),
+                to_height: Some(200),
+            },
+            ClearPacketsCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--channel",
+                "channel-07",
+                "--from-height",
+                "100",
+                "--to-height",
+                "200"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_clear_packets_from_height_without_to_height() {
+        assert!(ClearPacketsCmd::try_parse_from([
+            "test",
+            "--chain",
+            "chain_id",
+            "--port",
+            "port_id",
+            "--channel",
+            "channel-07",
+            "--from-height",
+            "100"
+        ])
+        .is_err())
+    }
+
     #[test]
     fn test_clear_packets_no_chan() {
         assert!(ClearPacketsCmd::try_parse_from([
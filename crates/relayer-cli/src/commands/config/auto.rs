@@ -129,14 +129,14 @@ impl Runnable for AutoCmd {
             // If a key is provided, use it
             if let Some(key_name) = key_option {
                 info!("{}: uses key \"{}\"", &chain_config.id, &key_name);
-                chain_config.key_name = key_name;
+                chain_config.key_name = key_name.into();
             } else {
                 // Otherwise, find the key in the keystore
                 let chain_id = &chain_config.id;
                 let key = find_key(chain_config);
                 if let Some(key) = key {
                     info!("{}: uses key '{}'", &chain_id, &key);
-                    chain_config.key_name = key;
+                    chain_config.key_name = key.into();
                 } else {
                     // If no key is found, warn the user and continue
                     warn!("No key found for chain: {}", chain_id);
@@ -2,6 +2,13 @@ use std::fs;
 
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc_relayer::chain::handle::BaseChainHandle;
+use ibc_relayer::config::Config;
+use ibc_relayer::registry::Registry;
+use ibc_relayer::supervisor::client_state_filter::FilterPolicy;
+use ibc_relayer::supervisor::scan::{ChainScanner, ScanMode};
 
 use crate::conclude::Output;
 use crate::config;
@@ -10,8 +17,22 @@ use crate::prelude::*;
 /// In order to validate the configuration file the command will check that the file exists,
 /// that it is readable and not empty. It will then check the validity of the fields inside
 /// the file.
-#[derive(Command, Debug, Parser)]
-pub struct ValidateCmd {}
+#[derive(Command, Debug, Parser, PartialEq, Eq)]
+pub struct ValidateCmd {
+    #[clap(
+        long = "shards",
+        help = "Scan the configured chains and list which instance owns each client, connection, channel and packet worker"
+    )]
+    shards: bool,
+}
+
+/// The instance in charge of a given worker object, as per the sharding configuration.
+#[derive(Debug, Serialize)]
+struct ShardAssignment {
+    object: String,
+    instance: u64,
+    local: bool,
+}
 
 impl Runnable for ValidateCmd {
     /// Validate the loaded configuration.
@@ -40,9 +61,74 @@ impl Runnable for ValidateCmd {
 
         // No need to output the underlying error, this is done already when the application boots.
         // See `application::CliApp::after_config`.
-        match config::validate_config(&config) {
-            Ok(_) => Output::success("configuration is valid").exit(),
-            Err(_) => Output::error("configuration is invalid").exit(),
+        if config::validate_config(&config).is_err() {
+            Output::error("configuration is invalid").exit()
+        }
+
+        if self.shards {
+            Output::success(shard_assignments(&config)).exit()
+        }
+
+        if config.sharding.is_enabled() {
+            Output::success_msg(format!(
+                "configuration is valid, relaying as sharded {}",
+                config.sharding
+            ))
+            .exit()
         }
+
+        Output::success("configuration is valid").exit()
+    }
+}
+
+/// Scan all the chains in the configuration and compute, for each worker object
+/// which could be spawned by the supervisor, the instance which is in charge of it.
+fn shard_assignments(config: &Config) -> Vec<ShardAssignment> {
+    let mut registry = Registry::<BaseChainHandle>::new(config.clone());
+    let mut client_state_filter = FilterPolicy::default();
+
+    let scan = ChainScanner::new(
+        config,
+        &mut registry,
+        &mut client_state_filter,
+        ScanMode::Full,
+    )
+    .scan_chains();
+
+    scan.objects()
+        .iter()
+        .filter_map(|object| {
+            config
+                .sharding
+                .shard_of(object)
+                .map(|instance| ShardAssignment {
+                    object: object.short_name(),
+                    instance,
+                    local: instance == config.sharding.instance,
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ValidateCmd;
+
+    use abscissa_core::clap::Parser;
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(
+            ValidateCmd { shards: false },
+            ValidateCmd::parse_from(["test"])
+        )
+    }
+
+    #[test]
+    fn test_validate_shards() {
+        assert_eq!(
+            ValidateCmd { shards: true },
+            ValidateCmd::parse_from(["test", "--shards"])
+        )
     }
 }
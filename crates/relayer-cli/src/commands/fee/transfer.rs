@@ -159,7 +159,7 @@ impl Override<Config> for FeeTransferCmd {
         })?;
 
         if let Some(ref key_name) = self.key_name {
-            src_chain_config.key_name = key_name.to_string();
+            src_chain_config.key_name = key_name.as_str().into();
         }
 
         Ok(config)
@@ -5,7 +5,11 @@ use abscissa_core::{Command, Runnable};
 mod add;
 mod balance;
 mod delete;
+mod export;
+mod feegrant;
+mod generate;
 mod list;
+mod rotate;
 
 /// `keys` subcommand
 #[derive(Command, Debug, Parser, Runnable)]
@@ -13,12 +17,25 @@ pub enum KeysCmd {
     /// Adds key to a configured chain or restores a key to a configured chain using a mnemonic
     Add(add::KeysAddCmd),
 
+    /// Generates a new key with a random mnemonic and adds it to a configured chain
+    Generate(generate::KeysGenerateCmd),
+
     /// Delete key(s) from a configured chain
     Delete(delete::KeysDeleteCmd),
 
     /// List keys configured on a chain
     List(list::KeysListCmd),
 
+    /// Export a key of a configured chain, to back it up
+    Export(export::KeysExportCmd),
+
+    /// Make another key the primary key of a chain of the running relayer, through its REST API
+    Rotate(rotate::KeysRotateCmd),
+
     /// Query balance for a key from a configured chain. If no key is given, the key is retrieved from the configuration file.
     Balance(balance::KeyBalanceCmd),
+
+    /// Inspect the fee grants used to pay for the transactions signed by the relayer's keys
+    #[clap(subcommand)]
+    Feegrant(feegrant::KeysFeegrantCmd),
 }
@@ -13,7 +13,8 @@ use ibc_relayer::{
     chain::ChainType,
     config::{ChainConfig, Config},
     keyring::{
-        AnySigningKeyPair, KeyRing, Secp256k1KeyPair, SigningKeyPair, SigningKeyPairSized, Store,
+        ledger::LedgerDevice, AnySigningKeyPair, CosmosKeyPair, KeyRing, LedgerKeyPair,
+        SigningKeyPair, SigningKeyPairSized, Store,
     },
 };
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
@@ -24,7 +25,7 @@ use crate::conclude::Output;
 
 /// The data structure that represents the arguments when invoking the `keys add` CLI command.
 ///
-/// The command has one argument and two exclusive flags:
+/// The command has one argument and three exclusive flags:
 ///
 /// The command to add a key from a file:
 ///
@@ -34,13 +35,19 @@ use crate::conclude::Output;
 ///
 /// `keys add [OPTIONS] --chain <CHAIN_ID> --mnemonic-file <MNEMONIC_FILE>`
 ///
-/// The key-file and mnemonic-file flags can't be given at the same time, this will cause a terminating error.
+/// The command to add a key held by a Ledger device:
+///
+/// `keys add [OPTIONS] --chain <CHAIN_ID> --ledger`
+///
+/// The key-file, mnemonic-file and ledger flags can't be given at the same time, this will cause a terminating error.
 /// If successful the key will be created or restored, depending on which flag was given.
 #[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
 #[clap(
     override_usage = "hermes keys add [OPTIONS] --chain <CHAIN_ID> --key-file <KEY_FILE>
 
-    hermes keys add [OPTIONS] --chain <CHAIN_ID> --mnemonic-file <MNEMONIC_FILE>"
+    hermes keys add [OPTIONS] --chain <CHAIN_ID> --mnemonic-file <MNEMONIC_FILE>
+
+    hermes keys add [OPTIONS] --chain <CHAIN_ID> --ledger"
 )]
 pub struct KeysAddCmd {
     #[clap(
@@ -71,6 +78,24 @@ pub struct KeysAddCmd {
     )]
     mnemonic_file: Option<PathBuf>,
 
+    #[clap(
+        long = "ledger",
+        required = true,
+        help_heading = "FLAGS",
+        help = "Add the key held by the Cosmos application of a Ledger device, which will be asked to confirm the address",
+        group = "add-restore"
+    )]
+    ledger: bool,
+
+    #[clap(
+        long = "speculos",
+        value_name = "ADDRESS",
+        requires = "ledger",
+        conflicts_with_all = &["key-file", "mnemonic-file"],
+        help = "Reach the Ledger device through the APDU port of a Speculos emulator at the given address, e.g. 127.0.0.1:9999"
+    )]
+    speculos: Option<String>,
+
     #[clap(
         long = "key-name",
         value_name = "KEY_NAME",
@@ -102,7 +127,7 @@ impl KeysAddCmd {
         let name = self
             .key_name
             .clone()
-            .unwrap_or_else(|| chain_config.key_name.clone());
+            .unwrap_or_else(|| chain_config.key_name.primary().to_string());
 
         let hd_path = StandardHDPath::from_str(&self.hd_path)
             .map_err(|_| eyre!("invalid derivation path: {}", self.hd_path))?;
@@ -131,6 +156,38 @@ impl Runnable for KeysAddCmd {
             Ok(result) => result,
         };
 
+        if self.ledger {
+            let device = match &self.speculos {
+                Some(address) => LedgerDevice::Speculos {
+                    address: address.clone(),
+                },
+                None => LedgerDevice::Hid,
+            };
+
+            let key = add_ledger_key(
+                &opts.config,
+                &opts.name,
+                device,
+                &opts.hd_path,
+                self.overwrite,
+            );
+
+            match key {
+                Ok(key) => Output::success_msg(format!(
+                    "Added Ledger key '{}' ({}) on chain {}",
+                    opts.name,
+                    key.account(),
+                    opts.config.id
+                ))
+                .exit(),
+                Err(e) => Output::error(format!(
+                    "An error occurred adding the Ledger key on chain {}: {}",
+                    self.chain_id, e
+                ))
+                .exit(),
+            }
+        }
+
         // Check if --key-file or --mnemonic-file was given as input.
         match (self.key_file.clone(), self.mnemonic_file.clone()) {
             (Some(key_file), _) => {
@@ -184,7 +241,7 @@ impl Runnable for KeysAddCmd {
             // The 'required' parameter for the flags will trigger an error if both flags have not been given.
             // And the 'group' parameter for the flags will trigger an error if both flags are given.
             _ => Output::error(
-                "--mnemonic-file and --key-file can't both be set or both None".to_string(),
+                "one of --mnemonic-file, --key-file and --ledger must be set".to_string(),
             )
             .exit(),
         }
@@ -200,7 +257,7 @@ pub fn add_key(
 ) -> eyre::Result<AnySigningKeyPair> {
     let key_pair = match config.r#type {
         ChainType::CosmosSdk => {
-            let mut keyring = KeyRing::new_secp256k1(
+            let mut keyring = KeyRing::new_cosmos(
                 Store::Test,
                 &config.account_prefix,
                 &config.id,
@@ -211,7 +268,7 @@ pub fn add_key(
 
             let key_contents =
                 fs::read_to_string(file).map_err(|_| eyre!("error reading the key file"))?;
-            let key_pair = Secp256k1KeyPair::from_seed_file(&key_contents, hd_path)?;
+            let key_pair = CosmosKeyPair::from_seed_file(&key_contents, hd_path)?;
 
             keyring.add_key(key_name, key_pair.clone())?;
             key_pair.into()
@@ -233,7 +290,7 @@ pub fn restore_key(
 
     let key_pair = match config.r#type {
         ChainType::CosmosSdk => {
-            let mut keyring = KeyRing::new_secp256k1(
+            let mut keyring = KeyRing::new_cosmos(
                 Store::Test,
                 &config.account_prefix,
                 &config.id,
@@ -242,7 +299,7 @@ pub fn restore_key(
 
             check_key_exists(&keyring, key_name, overwrite);
 
-            let key_pair = Secp256k1KeyPair::from_mnemonic(
+            let key_pair = CosmosKeyPair::from_mnemonic(
                 &mnemonic_content,
                 hdpath,
                 &config.address_type,
@@ -257,10 +314,48 @@ pub fn restore_key(
     Ok(key_pair)
 }
 
+pub fn add_ledger_key(
+    config: &ChainConfig,
+    key_name: &str,
+    device: LedgerDevice,
+    hd_path: &StandardHDPath,
+    overwrite: bool,
+) -> eyre::Result<AnySigningKeyPair> {
+    let key_pair = match config.r#type {
+        ChainType::CosmosSdk => {
+            let mut keyring = KeyRing::new_cosmos(
+                Store::Test,
+                &config.account_prefix,
+                &config.id,
+                &config.key_store_folder,
+            )?;
+
+            check_key_exists(&keyring, key_name, overwrite);
+
+            let key_pair = LedgerKeyPair::from_device(
+                device,
+                hd_path,
+                &config.address_type,
+                keyring.account_prefix(),
+                true,
+            )?;
+
+            keyring.add_key(key_name, key_pair.clone().into())?;
+            key_pair.into()
+        }
+    };
+
+    Ok(key_pair)
+}
+
 /// Check if the key with the given key name already exists.
 /// If it already exists and overwrite is false, abort the command with an error.
 /// If overwrite is true, output a warning message informing the key will be overwritten.
-fn check_key_exists<S: SigningKeyPairSized>(keyring: &KeyRing<S>, key_name: &str, overwrite: bool) {
+pub fn check_key_exists<S: SigningKeyPairSized>(
+    keyring: &KeyRing<S>,
+    key_name: &str,
+    overwrite: bool,
+) {
     if keyring.get_key(key_name).is_ok() {
         if overwrite {
             warn!("key {} will be overwritten", key_name);
@@ -286,6 +381,8 @@ mod tests {
                 chain_id: ChainId::from_string("chain_id"),
                 key_file: Some(PathBuf::from("key_file")),
                 mnemonic_file: None,
+                ledger: false,
+                speculos: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: false,
@@ -301,6 +398,8 @@ mod tests {
                 chain_id: ChainId::from_string("chain_id"),
                 key_file: None,
                 mnemonic_file: Some(PathBuf::from("mnemonic_file")),
+                ledger: false,
+                speculos: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: false
@@ -322,6 +421,8 @@ mod tests {
                 chain_id: ChainId::from_string("chain_id"),
                 key_file: Some(PathBuf::from("key_file")),
                 mnemonic_file: None,
+                ledger: false,
+                speculos: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: true,
@@ -344,6 +445,8 @@ mod tests {
                 chain_id: ChainId::from_string("chain_id"),
                 key_file: None,
                 mnemonic_file: Some(PathBuf::from("mnemonic_file")),
+                ledger: false,
+                speculos: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: true,
@@ -359,6 +462,57 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_keys_add_ledger_speculos() {
+        assert_eq!(
+            KeysAddCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                key_file: None,
+                mnemonic_file: None,
+                ledger: true,
+                speculos: Some("127.0.0.1:9999".to_string()),
+                key_name: None,
+                hd_path: "m/44'/118'/0'/0/0".to_string(),
+                overwrite: false,
+            },
+            KeysAddCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--ledger",
+                "--speculos",
+                "127.0.0.1:9999"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_keys_add_ledger_and_key_file() {
+        assert!(KeysAddCmd::try_parse_from([
+            "test",
+            "--chain",
+            "chain_id",
+            "--ledger",
+            "--key-file",
+            "key_file"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_keys_add_speculos_without_ledger() {
+        assert!(KeysAddCmd::try_parse_from([
+            "test",
+            "--chain",
+            "chain_id",
+            "--key-file",
+            "key_file",
+            "--speculos",
+            "127.0.0.1:9999"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_keys_add_no_file_nor_mnemonic() {
         assert!(KeysAddCmd::try_parse_from(["test", "--chain", "chain_id"]).is_err());
@@ -75,7 +75,7 @@ fn get_balance(chain: impl ChainHandle, key_name: Option<String>, denom: Option<
             // Retrieve the key name string to output.
             let key_name = key_name.unwrap_or_else(|| {
                 let chain_config = chain.config().unwrap_or_else(exit_with_unrecoverable_error);
-                chain_config.key_name
+                chain_config.key_name.primary().to_string()
             });
 
             Output::success_msg(format!(
@@ -95,7 +95,7 @@ fn get_balances(chain: impl ChainHandle, key_name: Option<String>) {
             // Retrieve the key name string to output.
             let key_name = key_name.unwrap_or_else(|| {
                 let chain_config = chain.config().unwrap_or_else(exit_with_unrecoverable_error);
-                chain_config.key_name
+                chain_config.key_name.primary().to_string()
             });
 
             let mut pretty_output = format!("Balances for key `{key_name}`:");
@@ -115,7 +115,7 @@ impl Runnable for KeysDeleteCmd {
 pub fn delete_key(config: &ChainConfig, key_name: &str) -> eyre::Result<()> {
     match config.r#type {
         ChainType::CosmosSdk => {
-            let mut keyring = KeyRing::new_secp256k1(
+            let mut keyring = KeyRing::new_cosmos(
                 Store::Test,
                 &config.account_prefix,
                 &config.id,
@@ -130,7 +130,7 @@ pub fn delete_key(config: &ChainConfig, key_name: &str) -> eyre::Result<()> {
 pub fn delete_all_keys(config: &ChainConfig) -> eyre::Result<()> {
     match config.r#type {
         ChainType::CosmosSdk => {
-            let mut keyring = KeyRing::new_secp256k1(
+            let mut keyring = KeyRing::new_cosmos(
                 Store::Test,
                 &config.account_prefix,
                 &config.id,
@@ -0,0 +1,159 @@
+use std::path::PathBuf;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use eyre::eyre;
+use ibc_relayer::{
+    chain::ChainType,
+    config::ChainConfig,
+    keyring::{KeyRing, Store},
+};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::application::app_config;
+use crate::conclude::Output;
+
+use super::generate::write_secret_file;
+
+/// The data structure that represents the arguments when invoking the `keys export` CLI command.
+///
+/// `keys export [OPTIONS] --chain <CHAIN_ID>`
+///
+/// Exports a key of the `Test` key store, as the JSON document it is stored as.
+/// The backup is restored by copying it to `<key_store_folder>/<CHAIN_ID>/keyring-test/<KEY_NAME>.json`.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct KeysExportCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "key-name",
+        value_name = "KEY_NAME",
+        help = "Name of the key (defaults to the `key_name` defined in the config)"
+    )]
+    key_name: Option<String>,
+
+    #[clap(
+        long = "output-file",
+        value_name = "OUTPUT_FILE",
+        help = "Write the key to the given file, readable by its owner only, instead of printing it"
+    )]
+    output_file: Option<PathBuf>,
+}
+
+impl Runnable for KeysExportCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain_config = match config.find_chain(&self.chain_id) {
+            Some(chain_config) => chain_config,
+            None => Output::error(format!(
+                "chain '{}' not found in configuration file",
+                self.chain_id
+            ))
+            .exit(),
+        };
+
+        let key_name = self
+            .key_name
+            .clone()
+            .unwrap_or_else(|| chain_config.key_name.primary().to_string());
+
+        let exported = match export_key(chain_config, &key_name) {
+            Ok(exported) => exported,
+            Err(e) => Output::error(format!(
+                "An error occurred exporting key '{}' of chain {}: {}",
+                key_name, self.chain_id, e
+            ))
+            .exit(),
+        };
+
+        match &self.output_file {
+            Some(file) => match write_secret_file(file, &exported) {
+                Ok(()) => Output::success_msg(format!(
+                    "Exported key '{}' of chain {} to {}",
+                    key_name,
+                    self.chain_id,
+                    file.display()
+                ))
+                .exit(),
+                Err(e) => Output::error(e).exit(),
+            },
+            None => Output::success_msg(exported).exit(),
+        }
+    }
+}
+
+/// Read a key from the `Test` key store of the given chain,
+/// as the JSON document it is stored as.
+pub fn export_key(config: &ChainConfig, key_name: &str) -> eyre::Result<String> {
+    match config.r#type {
+        ChainType::CosmosSdk => {
+            let keyring = KeyRing::new_cosmos(
+                Store::Test,
+                &config.account_prefix,
+                &config.id,
+                &config.key_store_folder,
+            )?;
+
+            let key_pair = keyring.get_key(key_name)?;
+
+            serde_json::to_string_pretty(&key_pair)
+                .map_err(|e| eyre!("failed to encode key '{}': {}", key_name, e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeysExportCmd;
+
+    use std::path::PathBuf;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_keys_export() {
+        assert_eq!(
+            KeysExportCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                key_name: None,
+                output_file: None,
+            },
+            KeysExportCmd::parse_from(["test", "--chain", "chain_id"])
+        )
+    }
+
+    #[test]
+    fn test_keys_export_to_file() {
+        assert_eq!(
+            KeysExportCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                key_name: Some("relayer".to_string()),
+                output_file: Some(PathBuf::from("backup.json")),
+            },
+            KeysExportCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--key-name",
+                "relayer",
+                "--output-file",
+                "backup.json"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_keys_export_no_chain() {
+        assert!(KeysExportCmd::try_parse_from(["test", "--key-name", "relayer"]).is_err());
+    }
+}
@@ -0,0 +1,13 @@
+//! `keys feegrant` subcommand
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+mod status;
+
+/// `keys feegrant` subcommand
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum KeysFeegrantCmd {
+    /// Query the fee allowances granted to the relayer's keys
+    Status(status::KeysFeegrantStatusCmd),
+}
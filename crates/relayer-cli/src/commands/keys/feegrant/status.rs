@@ -0,0 +1,207 @@
+use std::fmt::Write;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+use tendermint::Time;
+
+use ibc_relayer::account::{Balance, FeeAllowance, FeeAllowanceStatus};
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::application::app_config;
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{exit_with_unrecoverable_error, json, Output};
+
+/// The data structure that represents the arguments when invoking the `keys feegrant status` CLI command.
+///
+/// `keys feegrant status --chain <chain_id> --key-name <KEY_NAME>`
+///
+/// If no key name is given, the fee allowance of every key listed in the
+/// `key_name` of the chain configuration is displayed.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct KeysFeegrantStatusCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "key-name",
+        value_name = "KEY_NAME",
+        help = "(optional) name of the key (defaults to all the keys in the `key_name` defined in the config)"
+    )]
+    key_name: Option<String>,
+}
+
+/// The fee grant used to pay for the transactions signed by a key.
+#[derive(Debug, Serialize)]
+struct FeeGrantStatus {
+    key_name: String,
+    granter: Option<String>,
+    status: Option<FeeAllowanceStatus>,
+    allowance: Option<FeeAllowance>,
+}
+
+impl Runnable for KeysFeegrantStatusCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain_config = config
+            .find_chain(&self.chain_id)
+            .cloned()
+            .unwrap_or_else(|| {
+                Output::error(format!(
+                    "chain '{}' not found in configuration file",
+                    self.chain_id
+                ))
+                .exit()
+            });
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let key_names = match &self.key_name {
+            Some(key_name) => vec![key_name.clone()],
+            None => chain_config.key_name.iter().map(String::from).collect(),
+        };
+
+        let now = Time::now();
+
+        let statuses = key_names
+            .into_iter()
+            .map(|key_name| {
+                let granter = chain_config.fee_granter_for(&key_name).map(String::from);
+
+                let allowance = match granter {
+                    Some(_) => chain
+                        .query_fee_allowance(Some(key_name.clone()))
+                        .unwrap_or_else(|e| {
+                            Output::error(format!(
+                                "there was a problem querying the fee allowance of key `{key_name}`: {e}"
+                            ))
+                            .exit()
+                        }),
+                    None => None,
+                };
+
+                FeeGrantStatus {
+                    status: allowance.as_ref().map(|allowance| allowance.status(now)),
+                    key_name,
+                    granter,
+                    allowance,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if json() {
+            Output::success(statuses).exit()
+        }
+
+        let mut output = String::new();
+        for status in statuses {
+            write_status(&mut output, &status).unwrap_or_else(exit_with_unrecoverable_error);
+        }
+
+        Output::success_msg(output.trim_end()).exit()
+    }
+}
+
+fn write_status(output: &mut String, status: &FeeGrantStatus) -> core::fmt::Result {
+    write!(output, "Fee grant for key `{}`: ", status.key_name)?;
+
+    let (granter, allowance) = match (&status.granter, &status.allowance) {
+        (None, _) => return writeln!(output, "no fee granter configured"),
+        (Some(granter), None) => {
+            return writeln!(output, "no allowance found from granter `{granter}`")
+        }
+        (Some(granter), Some(allowance)) => (granter, allowance),
+    };
+
+    let state = status.status.unwrap_or(FeeAllowanceStatus::Active);
+
+    writeln!(output, "{state}")?;
+    writeln!(output, "\tgranter: {granter}")?;
+    writeln!(output, "\tgrantee: {}", allowance.grantee)?;
+    writeln!(output, "\tspend limit: {}", coins(&allowance.spend_limit))?;
+
+    if allowance.period_can_spend.is_some() {
+        writeln!(
+            output,
+            "\tremaining in period: {}",
+            coins(&allowance.period_can_spend)
+        )?;
+    }
+
+    match allowance.expiration {
+        Some(expiration) => writeln!(output, "\texpiration: {expiration}")?,
+        None => writeln!(output, "\texpiration: never")?,
+    }
+
+    if !allowance.allowed_messages.is_empty() {
+        writeln!(
+            output,
+            "\tallowed messages: {}",
+            allowance.allowed_messages.join(", ")
+        )?;
+    }
+
+    Ok(())
+}
+
+fn coins(coins: &Option<Vec<Balance>>) -> String {
+    match coins {
+        None => "unlimited".to_string(),
+        Some(coins) if coins.is_empty() => "none".to_string(),
+        Some(coins) => coins
+            .iter()
+            .map(|coin| format!("{}{}", coin.amount, coin.denom))
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeysFeegrantStatusCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_keys_feegrant_status_required_only() {
+        assert_eq!(
+            KeysFeegrantStatusCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                key_name: None,
+            },
+            KeysFeegrantStatusCmd::parse_from(["test", "--chain", "chain_id"])
+        )
+    }
+
+    #[test]
+    fn test_keys_feegrant_status_name() {
+        assert_eq!(
+            KeysFeegrantStatusCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                key_name: Some("kname".to_owned()),
+            },
+            KeysFeegrantStatusCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--key-name",
+                "kname"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_keys_feegrant_status_no_chain() {
+        assert!(KeysFeegrantStatusCmd::try_parse_from(["test"]).is_err())
+    }
+}
@@ -0,0 +1,291 @@
+use core::str::FromStr;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use eyre::eyre;
+use hdpath::StandardHDPath;
+use ibc_relayer::{
+    chain::ChainType,
+    config::ChainConfig,
+    keyring::{key_utils::generate_mnemonic, CosmosKeyPair, KeyRing, SigningKeyPair, Store},
+};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::application::app_config;
+use crate::conclude::{json, Output};
+
+use super::add::check_key_exists;
+
+/// The data structure that represents the arguments when invoking the `keys generate` CLI command.
+///
+/// `keys generate [OPTIONS] --chain <CHAIN_ID> --key-name <KEY_NAME>`
+///
+/// A new random 24-word mnemonic is generated, from which the key is derived and
+/// added to the key store of the chain. The mnemonic is only kept if
+/// it is printed with `--print-mnemonic` or saved with `--mnemonic-file`.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct KeysGenerateCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "key-name",
+        required = true,
+        value_name = "KEY_NAME",
+        help_heading = "REQUIRED",
+        help = "Name of the key"
+    )]
+    key_name: String,
+
+    #[clap(
+        long = "hd-path",
+        value_name = "HD_PATH",
+        help = "Derivation path for this key",
+        default_value = "m/44'/118'/0'/0/0"
+    )]
+    hd_path: String,
+
+    #[clap(
+        long = "overwrite",
+        help = "Overwrite the key if there is already one with the same key name"
+    )]
+    overwrite: bool,
+
+    #[clap(long = "print-mnemonic", help = "Print the mnemonic of the new key")]
+    print_mnemonic: bool,
+
+    #[clap(
+        long = "mnemonic-file",
+        value_name = "MNEMONIC_FILE",
+        help = "Write the mnemonic of the new key to the given file, readable by its owner only"
+    )]
+    mnemonic_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+struct GeneratedKey {
+    name: String,
+    address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mnemonic: Option<String>,
+}
+
+impl Runnable for KeysGenerateCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain_config = match config.find_chain(&self.chain_id) {
+            Some(chain_config) => chain_config,
+            None => Output::error(format!(
+                "chain '{}' not found in configuration file",
+                self.chain_id
+            ))
+            .exit(),
+        };
+
+        let hd_path = match StandardHDPath::from_str(&self.hd_path) {
+            Ok(hd_path) => hd_path,
+            Err(_) => Output::error(format!("invalid derivation path: {}", self.hd_path)).exit(),
+        };
+
+        let mnemonic = generate_mnemonic();
+
+        let key = generate_key(
+            chain_config,
+            &self.key_name,
+            &mnemonic,
+            &hd_path,
+            self.mnemonic_file.as_deref(),
+            self.overwrite,
+        );
+
+        let address = match key {
+            Ok(key) => key.account(),
+            Err(e) => Output::error(format!(
+                "An error occurred generating the key on chain {}: {}",
+                self.chain_id, e
+            ))
+            .exit(),
+        };
+
+        if !self.print_mnemonic && self.mnemonic_file.is_none() {
+            warn!(
+                "the mnemonic of key '{}' was neither printed nor saved, \
+                the key can only be backed up with `hermes keys export`",
+                self.key_name
+            );
+        }
+
+        let generated = GeneratedKey {
+            name: self.key_name.clone(),
+            address,
+            mnemonic: self.print_mnemonic.then_some(mnemonic),
+        };
+
+        if json() {
+            Output::success(generated).exit()
+        }
+
+        let mut msg = format!(
+            "Generated key '{}' ({}) on chain {}",
+            generated.name, generated.address, self.chain_id
+        );
+        if let Some(mnemonic) = generated.mnemonic {
+            msg.push_str(&format!("\nMnemonic: {mnemonic}"));
+        }
+        if let Some(file) = &self.mnemonic_file {
+            msg.push_str(&format!("\nMnemonic written to {}", file.display()));
+        }
+
+        Output::success_msg(msg).exit()
+    }
+}
+
+pub fn generate_key(
+    config: &ChainConfig,
+    key_name: &str,
+    mnemonic: &str,
+    hd_path: &StandardHDPath,
+    mnemonic_file: Option<&Path>,
+    overwrite: bool,
+) -> eyre::Result<CosmosKeyPair> {
+    let key_pair = match config.r#type {
+        ChainType::CosmosSdk => {
+            let mut keyring = KeyRing::new_cosmos(
+                Store::Test,
+                &config.account_prefix,
+                &config.id,
+                &config.key_store_folder,
+            )?;
+
+            check_key_exists(&keyring, key_name, overwrite);
+
+            let key_pair = CosmosKeyPair::from_mnemonic(
+                mnemonic,
+                hd_path,
+                &config.address_type,
+                keyring.account_prefix(),
+            )?;
+
+            // Save the mnemonic before storing the key, so that a key is never
+            // stored without its mnemonic when a file was asked for
+            if let Some(file) = mnemonic_file {
+                write_secret_file(file, &format!("{mnemonic}\n"))?;
+            }
+
+            keyring.add_key(key_name, key_pair.clone())?;
+            key_pair
+        }
+    };
+
+    Ok(key_pair)
+}
+
+/// Write secret material to a new file, which only its owner can read and write.
+pub fn write_secret_file(path: &Path, contents: &str) -> eyre::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options
+        .open(path)
+        .map_err(|e| eyre!("failed to create file {}: {}", path.display(), e))?;
+
+    file.write_all(contents.as_bytes())
+        .map_err(|e| eyre!("failed to write file {}: {}", path.display(), e))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::PathBuf;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_keys_generate() {
+        assert_eq!(
+            KeysGenerateCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                key_name: "relayer".to_string(),
+                hd_path: "m/44'/118'/0'/0/0".to_string(),
+                overwrite: false,
+                print_mnemonic: false,
+                mnemonic_file: None,
+            },
+            KeysGenerateCmd::parse_from(["test", "--chain", "chain_id", "--key-name", "relayer"])
+        )
+    }
+
+    #[test]
+    fn test_keys_generate_all_options() {
+        assert_eq!(
+            KeysGenerateCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                key_name: "relayer".to_string(),
+                hd_path: "m/44'/60'/0'/0/0".to_string(),
+                overwrite: true,
+                print_mnemonic: true,
+                mnemonic_file: Some(PathBuf::from("mnemonic.txt")),
+            },
+            KeysGenerateCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--key-name",
+                "relayer",
+                "--hd-path",
+                "m/44'/60'/0'/0/0",
+                "--overwrite",
+                "--print-mnemonic",
+                "--mnemonic-file",
+                "mnemonic.txt"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_keys_generate_no_key_name() {
+        assert!(KeysGenerateCmd::try_parse_from(["test", "--chain", "chain_id"]).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_secret_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("hermes-mnemonic-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        write_secret_file(&path, "word1 word2\n").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        // Existing files are never overwritten
+        assert!(write_secret_file(&path, "other").is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "word1 word2\n");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
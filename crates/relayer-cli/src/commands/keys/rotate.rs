@@ -0,0 +1,230 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use eyre::eyre;
+use ibc_relayer::{
+    config::{ChainConfig, RestConfig},
+    keyring::{KeyRing, Store},
+    supervisor::rotate_key::{KeyRotation, RotateKeyOptions},
+};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::application::app_config;
+use crate::conclude::{json, Output};
+
+/// The data structure that represents the arguments when invoking the `keys rotate` CLI command.
+///
+/// `keys rotate [OPTIONS] --chain <CHAIN_ID> --new-key <NEW_KEY>`
+///
+/// Asks the running relayer, through its REST API, to sign the transactions submitted to
+/// the chain with another key of the key store from now on. The new key must first be
+/// added to the key store, with `keys add` or `keys generate`.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct KeysRotateCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "new-key",
+        required = true,
+        value_name = "NEW_KEY",
+        help_heading = "REQUIRED",
+        help = "Name of the key of the key store which becomes the primary key of the chain"
+    )]
+    new_key: String,
+
+    #[clap(
+        long = "sweep",
+        help = "Send the balances of the old key to the new key, once its transactions in flight are confirmed"
+    )]
+    sweep: bool,
+}
+
+/// The body of the responses of the REST API
+#[derive(Debug, Deserialize)]
+#[serde(tag = "status", content = "result")]
+#[serde(rename_all = "lowercase")]
+enum RestResponse {
+    Success(KeyRotation),
+    Error { name: String, msg: String },
+}
+
+impl Runnable for KeysRotateCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain_config = match config.find_chain(&self.chain_id) {
+            Some(chain_config) => chain_config,
+            None => Output::error(format!(
+                "chain '{}' not found in configuration file",
+                self.chain_id
+            ))
+            .exit(),
+        };
+
+        if let Err(e) = check_key_exists(chain_config, &self.new_key) {
+            Output::error(e).exit()
+        }
+
+        if !config.rest.enabled || !config.rest.key_rotation {
+            Output::error(
+                "the REST API and its `key_rotation` must be enabled in the configuration \
+                of the running relayer to rotate its keys",
+            )
+            .exit()
+        }
+
+        let options = RotateKeyOptions {
+            new_key: self.new_key.clone(),
+            sweep: self.sweep,
+        };
+
+        let rotation = match request_rotation(&config.rest, &self.chain_id, &options) {
+            Ok(rotation) => rotation,
+            Err(e) => Output::error(format!(
+                "An error occurred rotating the key of chain {}: {}",
+                self.chain_id, e
+            ))
+            .exit(),
+        };
+
+        if !rotation.drained {
+            warn!(
+                "some transactions signed by key '{}' were still in flight after the rotation",
+                rotation.old_key_name
+            );
+        }
+
+        warn!(
+            "set `key_name = '{}'` in the configuration of chain {}, \
+            for the new key to still be used once the relayer is restarted",
+            rotation.new_key_name, self.chain_id
+        );
+
+        if json() {
+            Output::success(rotation).exit()
+        }
+
+        let mut msg = format!(
+            "Rotated the key of chain {} from '{}' ({}) to '{}' ({})",
+            self.chain_id,
+            rotation.old_key_name,
+            rotation.old_address,
+            rotation.new_key_name,
+            rotation.new_address
+        );
+        if let Some(swept) = &rotation.swept {
+            let coins = swept
+                .iter()
+                .map(|balance| format!("{}{}", balance.amount, balance.denom))
+                .collect::<Vec<_>>();
+            msg.push_str(&format!("\nSwept: {}", coins.join(", ")));
+        }
+        for payee in &rotation.registered_payees {
+            msg.push_str(&format!(
+                "\nRegistered counterparty payee {} on {}/{} of chain {}",
+                payee.counterparty_payee, payee.port_id, payee.channel_id, payee.chain_id
+            ));
+        }
+
+        Output::success_msg(msg).exit()
+    }
+}
+
+/// Check that the given key is in the `Test` key store of the given chain,
+/// where the running relayer looks it up.
+fn check_key_exists(config: &ChainConfig, key_name: &str) -> eyre::Result<()> {
+    let keyring = KeyRing::new_cosmos(
+        Store::Test,
+        &config.account_prefix,
+        &config.id,
+        &config.key_store_folder,
+    )?;
+
+    keyring
+        .get_key(key_name)
+        .map_err(|e| eyre!("key '{}' not found on chain {}: {}", key_name, config.id, e))?;
+
+    Ok(())
+}
+
+/// Ask the relayer serving the given REST API to rotate the key of the given chain.
+fn request_rotation(
+    rest: &RestConfig,
+    chain_id: &ChainId,
+    options: &RotateKeyOptions,
+) -> eyre::Result<KeyRotation> {
+    let url = format!(
+        "http://{}:{}/chain/{}/rotate-key",
+        rest.host, rest.port, chain_id
+    );
+
+    let rt = tokio::runtime::Runtime::new()?;
+
+    let response = rt.block_on(async {
+        reqwest::Client::new()
+            .post(&url)
+            .json(options)
+            .send()
+            .await?
+            .json::<RestResponse>()
+            .await
+    });
+
+    match response.map_err(|e| eyre!("failed to reach the REST API at {}: {}", url, e))? {
+        RestResponse::Success(rotation) => Ok(rotation),
+        RestResponse::Error { name, msg } => Err(eyre!("{} ({})", msg, name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeysRotateCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_keys_rotate() {
+        assert_eq!(
+            KeysRotateCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                new_key: "relayer-new".to_string(),
+                sweep: false,
+            },
+            KeysRotateCmd::parse_from(["test", "--chain", "chain_id", "--new-key", "relayer-new"])
+        )
+    }
+
+    #[test]
+    fn test_keys_rotate_sweep() {
+        assert_eq!(
+            KeysRotateCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                new_key: "relayer-new".to_string(),
+                sweep: true,
+            },
+            KeysRotateCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--new-key",
+                "relayer-new",
+                "--sweep"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_keys_rotate_no_new_key() {
+        assert!(KeysRotateCmd::try_parse_from(["test", "--chain", "chain_id"]).is_err());
+    }
+}
@@ -10,13 +10,14 @@ use abscissa_core::clap::Parser;
 use abscissa_core::{application::fatal_error, Runnable};
 use eyre::eyre;
 use itertools::Itertools;
-use tendermint_rpc::{client::CompatMode, Client, HttpClient};
+use tendermint_rpc::{Client, HttpClient};
 use tokio::runtime::Runtime as TokioRuntime;
 use tracing::{error, info, instrument};
 
 use ibc_relayer::{
+    chain::cosmos::failover::{EndpointKind, EndpointPool},
     chain::handle::Subscription,
-    config::{ChainConfig, EventSourceMode},
+    config::{ChainConfig, CompatMode, EventSourceMode},
     event::source::websocket::EventSource,
 };
 use ibc_relayer_types::{core::ics24_host::identifier::ChainId, events::IbcEvent};
@@ -148,9 +149,16 @@ fn subscribe(
         return Err(eyre!("unsupported event source mode, only 'push' is supported for listening to events"));
     };
 
-    let (mut event_source, tx_cmd) = EventSource::new(
+    let ws_endpoints = EndpointPool::new(
         chain_config.id.clone(),
+        EndpointKind::WebSocket,
         url.clone(),
+        chain_config.failover.websocket_addrs.iter().cloned(),
+    );
+
+    let (mut event_source, tx_cmd) = EventSource::new(
+        chain_config.id.clone(),
+        ws_endpoints.shared(),
         compat_mode,
         *batch_delay,
         rt,
@@ -176,10 +184,7 @@ fn detect_compatibility_mode(
 ) -> eyre::Result<CompatMode> {
     let client = HttpClient::new(config.rpc_addr.clone())?;
     let status = rt.block_on(client.status())?;
-    let compat_mode = CompatMode::from_version(status.node_info.version).unwrap_or_else(|e| {
-        warn!("Unsupported tendermint version, will use v0.37 compatibility mode but relaying might not work as desired: {e}");
-        CompatMode::V0_37
-    });
+    let compat_mode = CompatMode::select(config.compat_mode, &status.node_info.version.to_string());
     Ok(compat_mode)
 }
 
@@ -16,7 +16,9 @@ mod client;
 mod clients;
 mod connection;
 mod connections;
+mod icq;
 mod packet;
+mod stale_objects;
 mod transfer;
 mod tx;
 
@@ -55,6 +57,13 @@ pub enum QueryCmd {
     /// Query information about token transfers
     #[clap(subcommand)]
     Transfer(transfer::TransferCmd),
+
+    /// Query information about interchain queries (ICS-31)
+    #[clap(subcommand)]
+    Icq(icq::QueryIcqCmd),
+
+    /// Report the expired or frozen clients, stuck connections and channels, and idle channels of a chain
+    StaleObjects(stale_objects::QueryStaleObjectsCmd),
 }
 
 #[derive(Command, Debug, Parser, Runnable)]
@@ -73,6 +82,9 @@ pub enum QueryClientCmds {
 
     /// Query the client connections
     Connections(client::QueryClientConnectionsCmd),
+
+    /// Query the time left before the clients of a chain, or of all chains, expire
+    Expiry(client::QueryClientExpiryCmd),
 }
 
 #[derive(Command, Debug, Parser, Runnable)]
@@ -1,23 +1,29 @@
+use std::time::Duration;
+
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 use color_eyre::eyre::eyre;
+use serde::Serialize;
 
 use ibc_relayer::chain::handle::ChainHandle;
 use ibc_relayer::chain::requests::{
     IncludeProof, PageRequest, QueryClientConnectionsRequest, QueryClientEventRequest,
-    QueryClientStateRequest, QueryConsensusStateHeightsRequest, QueryConsensusStateRequest,
-    QueryHeight, QueryTxRequest,
+    QueryClientStateRequest, QueryClientStatesRequest, QueryConsensusStateHeightsRequest,
+    QueryConsensusStateRequest, QueryHeight, QueryTxRequest,
 };
+use ibc_relayer::client_state::IdentifiedAnyClientState;
 
 use ibc_relayer_types::core::ics02_client::client_state::ClientState;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::core::ics24_host::identifier::ClientId;
 use ibc_relayer_types::events::WithBlockDataType;
+use ibc_relayer_types::timestamp::Timestamp;
 use ibc_relayer_types::Height;
 
 use crate::application::app_config;
 use crate::cli_utils::spawn_chain_runtime;
-use crate::conclude::{exit_with_unrecoverable_error, Output};
+use crate::conclude::{exit_with_unrecoverable_error, json, Output};
+use crate::prelude::*;
 
 /// Query client state command
 #[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
@@ -295,8 +301,8 @@ impl Runnable for QueryClientStatusCmd {
     }
 }
 
-#[derive(Debug, serde::Serialize)]
-enum Status {
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) enum Status {
     Frozen,
     Expired,
     Active,
@@ -357,6 +363,186 @@ fn client_status(
     }
 }
 
+/// The status of the given client, and how long ago it was last updated
+pub(crate) fn client_staleness(
+    chain: &impl ChainHandle,
+    client: &IdentifiedAnyClientState,
+    now: Timestamp,
+) -> Result<(Status, Duration), ibc_relayer::error::Error> {
+    let (consensus_state, _) = chain.query_consensus_state(
+        QueryConsensusStateRequest {
+            client_id: client.client_id.clone(),
+            consensus_height: client.client_state.latest_height(),
+            query_height: QueryHeight::Latest,
+        },
+        IncludeProof::No,
+    )?;
+
+    let since_update = now
+        .duration_since(&consensus_state.timestamp())
+        .unwrap_or_default();
+
+    let status = if client.client_state.is_frozen() {
+        Status::Frozen
+    } else if client.client_state.expired(since_update) {
+        Status::Expired
+    } else {
+        Status::Active
+    };
+
+    Ok((status, since_update))
+}
+
+/// Query client expiry command
+///
+/// `query client expiry --chain <CHAIN_ID>`
+///
+/// `query client expiry --all`
+///
+/// If successful, the clients hosted by the chain, or by all the chains of the configuration
+/// file, are listed along with the time left before they expire, soonest first.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+#[clap(override_usage = "hermes query client expiry --chain <CHAIN_ID>
+
+    hermes query client expiry --all")]
+pub struct QueryClientExpiryCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "FLAGS",
+        help = "Identifier of the chain hosting the clients",
+        group = "chains"
+    )]
+    chain_id: Option<ChainId>,
+
+    #[clap(
+        long = "all",
+        required = true,
+        help_heading = "FLAGS",
+        help = "Query the clients hosted by all the chains of the configuration file",
+        group = "chains"
+    )]
+    all: bool,
+}
+
+/// The time left before a client expires
+#[derive(Debug, Serialize)]
+struct ClientExpiry {
+    chain_id: ChainId,
+    client_id: ClientId,
+    counterparty_chain_id: ChainId,
+    status: Status,
+    trusting_period_secs: Option<u64>,
+    last_update_secs_ago: u64,
+    /// `None` if the client has no trusting period, zero if it is expired or frozen
+    time_to_expiry_secs: Option<u64>,
+}
+
+impl Runnable for QueryClientExpiryCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain_ids: Vec<ChainId> = match &self.chain_id {
+            Some(chain_id) => vec![chain_id.clone()],
+            None => config.chains.iter().map(|chain| chain.id.clone()).collect(),
+        };
+
+        let mut expiries = Vec::new();
+
+        for chain_id in chain_ids {
+            let clients = spawn_chain_runtime(&config, &chain_id)
+                .map_err(|e| eyre!("{e}"))
+                .and_then(|chain| client_expiries(&chain));
+
+            match clients {
+                Ok(clients) => expiries.extend(clients),
+                // With `--all`, report the clients of the other chains nonetheless
+                Err(e) if self.all => warn!("skipping chain {chain_id}: {e}"),
+                Err(e) => exit_with_unrecoverable_error(e),
+            }
+        }
+
+        expiries.sort_by_key(|expiry| expiry.time_to_expiry_secs.unwrap_or(u64::MAX));
+
+        if json() {
+            Output::success(expiries).exit()
+        }
+
+        let lines = expiries
+            .iter()
+            .map(|expiry| {
+                let time_to_expiry = match expiry.time_to_expiry_secs {
+                    Some(0) => "expired".to_string(),
+                    Some(secs) => format!("expires in {}", format_secs(secs)),
+                    None => "never expires".to_string(),
+                };
+
+                format!(
+                    "{} {} (counterparty: {}): {:?}, {}, last updated {} ago",
+                    expiry.chain_id,
+                    expiry.client_id,
+                    expiry.counterparty_chain_id,
+                    expiry.status,
+                    time_to_expiry,
+                    format_secs(expiry.last_update_secs_ago)
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Output::success_msg(lines.join("\n")).exit()
+    }
+}
+
+/// The time left before each client hosted by the given chain expires
+fn client_expiries(chain: &impl ChainHandle) -> Result<Vec<ClientExpiry>, color_eyre::Report> {
+    let now = chain.query_application_status()?.timestamp;
+
+    let clients = chain.query_clients(QueryClientStatesRequest {
+        pagination: Some(PageRequest::all()),
+    })?;
+
+    let mut expiries = Vec::with_capacity(clients.len());
+
+    for client in clients {
+        let (status, since_update) = match client_staleness(chain, &client, now) {
+            Ok(staleness) => staleness,
+            Err(e) => {
+                warn!(
+                    "skipping client {} on chain {}: {}",
+                    client.client_id,
+                    chain.id(),
+                    e
+                );
+                continue;
+            }
+        };
+
+        let trusting_period = client.client_state.trusting_period();
+
+        let time_to_expiry = trusting_period.map(|trusting_period| match status {
+            Status::Active => trusting_period.saturating_sub(since_update),
+            Status::Expired | Status::Frozen => Duration::ZERO,
+        });
+
+        expiries.push(ClientExpiry {
+            chain_id: chain.id(),
+            counterparty_chain_id: client.client_state.chain_id(),
+            client_id: client.client_id,
+            status,
+            trusting_period_secs: trusting_period.map(|period| period.as_secs()),
+            last_update_secs_ago: since_update.as_secs(),
+            time_to_expiry_secs: time_to_expiry.map(|ttl| ttl.as_secs()),
+        });
+    }
+
+    Ok(expiries)
+}
+
+pub(crate) fn format_secs(secs: u64) -> humantime::FormattedDuration {
+    humantime::format_duration(Duration::from_secs(secs))
+}
+
 /// Query client connections command
 #[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
 pub struct QueryClientConnectionsCmd {
@@ -408,8 +594,8 @@ impl Runnable for QueryClientConnectionsCmd {
 #[cfg(test)]
 mod tests {
     use super::{
-        QueryClientConnectionsCmd, QueryClientConsensusCmd, QueryClientHeaderCmd,
-        QueryClientStateCmd, QueryClientStatusCmd,
+        QueryClientConnectionsCmd, QueryClientConsensusCmd, QueryClientExpiryCmd,
+        QueryClientHeaderCmd, QueryClientStateCmd, QueryClientStatusCmd,
     };
 
     use std::str::FromStr;
@@ -692,4 +878,38 @@ mod tests {
     fn test_query_client_status_no_client() {
         assert!(QueryClientStatusCmd::try_parse_from(["test", "--chain", "chain_id"]).is_err())
     }
+
+    #[test]
+    fn test_query_client_expiry_chain() {
+        assert_eq!(
+            QueryClientExpiryCmd {
+                chain_id: Some(ChainId::from_string("chain_id")),
+                all: false,
+            },
+            QueryClientExpiryCmd::parse_from(["test", "--chain", "chain_id"])
+        )
+    }
+
+    #[test]
+    fn test_query_client_expiry_all() {
+        assert_eq!(
+            QueryClientExpiryCmd {
+                chain_id: None,
+                all: true,
+            },
+            QueryClientExpiryCmd::parse_from(["test", "--all"])
+        )
+    }
+
+    #[test]
+    fn test_query_client_expiry_chain_and_all() {
+        assert!(
+            QueryClientExpiryCmd::try_parse_from(["test", "--chain", "chain_id", "--all"]).is_err()
+        )
+    }
+
+    #[test]
+    fn test_query_client_expiry_no_chain() {
+        assert!(QueryClientExpiryCmd::try_parse_from(["test"]).is_err())
+    }
 }
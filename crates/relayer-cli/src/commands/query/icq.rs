@@ -0,0 +1,13 @@
+//! `query icq` subcommand
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+mod pending;
+
+/// `query icq` subcommand
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum QueryIcqCmd {
+    /// Query the interchain queries (ICS-31) requested by a chain which have not been answered yet
+    Pending(pending::QueryIcqPendingCmd),
+}
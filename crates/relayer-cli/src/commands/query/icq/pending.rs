@@ -0,0 +1,146 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::chain::requests::QueryPendingCrossChainQueriesRequest;
+use ibc_relayer_types::applications::ics31_icq::events::CrossChainQueryPacket;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::Height;
+
+use crate::application::app_config;
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{exit_with_unrecoverable_error, json, Output};
+
+/// The data structure that represents the arguments when invoking the `query icq pending` CLI command.
+///
+/// The command has the following format:
+///
+/// `query icq pending --chain <CHAIN_ID> [--blocks <BLOCKS>]`
+///
+/// If successful the interchain queries requested by the chain in its latest blocks,
+/// which have not been answered yet, will be displayed.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryIcqPendingCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the querying chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "blocks",
+        value_name = "BLOCKS",
+        default_value = "100",
+        help = "Number of latest blocks of the chain to look for query requests in"
+    )]
+    blocks: u64,
+}
+
+/// An interchain query which has not been answered yet
+#[derive(Debug, Serialize)]
+struct PendingQuery {
+    /// The height at which the querying chain requested the query
+    requested_at: Height,
+    query: CrossChainQueryPacket,
+}
+
+impl Runnable for QueryIcqPendingCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let events =
+            match chain.query_pending_cross_chain_queries(QueryPendingCrossChainQueriesRequest {
+                blocks: self.blocks,
+            }) {
+                Ok(events) => events,
+                Err(e) => Output::error(format!(
+                    "there was a problem querying the pending interchain queries: {e}"
+                ))
+                .exit(),
+            };
+
+        let pending: Vec<PendingQuery> = events
+            .into_iter()
+            .filter_map(|event| {
+                event
+                    .event
+                    .cross_chain_query_packet()
+                    .map(|query| PendingQuery {
+                        requested_at: event.height,
+                        query: query.clone(),
+                    })
+            })
+            .collect();
+
+        if json() {
+            Output::success(pending).exit()
+        }
+
+        if pending.is_empty() {
+            Output::success_msg(format!(
+                "No pending interchain queries in the latest {} blocks of chain {}",
+                self.blocks, self.chain_id
+            ))
+            .exit()
+        }
+
+        let lines = pending
+            .iter()
+            .map(|pending| {
+                format!(
+                    "{} (requested at {}): {} on chain {} at height {} over {}",
+                    pending.query.query_id,
+                    pending.requested_at,
+                    pending.query.query_type,
+                    pending.query.chain_id,
+                    pending.query.height,
+                    pending.query.connection_id
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Output::success_msg(lines.join("\n")).exit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryIcqPendingCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_query_icq_pending() {
+        assert_eq!(
+            QueryIcqPendingCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                blocks: 100,
+            },
+            QueryIcqPendingCmd::parse_from(["test", "--chain", "chain_id"])
+        )
+    }
+
+    #[test]
+    fn test_query_icq_pending_blocks() {
+        assert_eq!(
+            QueryIcqPendingCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                blocks: 500,
+            },
+            QueryIcqPendingCmd::parse_from(["test", "--chain", "chain_id", "--blocks", "500"])
+        )
+    }
+
+    #[test]
+    fn test_query_icq_pending_no_chain() {
+        assert!(QueryIcqPendingCmd::try_parse_from(["test"]).is_err())
+    }
+}
@@ -0,0 +1,885 @@
+use core::fmt::{Display, Error as FmtError, Formatter};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::chain::requests::{
+    PageRequest, Qualified, QueryClientStatesRequest, QueryHeight, QueryHostConsensusStateRequest,
+    QueryPacketAcknowledgementsRequest, QueryPacketCommitmentsRequest, QueryPacketEventDataRequest,
+};
+use ibc_relayer::config::PacketFilter;
+use ibc_relayer::registry::Registry;
+use ibc_relayer::supervisor::client_state_filter::FilterPolicy;
+use ibc_relayer::supervisor::scan::{ChainScanner, ChannelScan, ConnectionScan, ScanMode};
+use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics03_connection::connection::State as ConnectionState;
+use ibc_relayer_types::core::ics04_channel::channel::{
+    IdentifiedChannelEnd, State as ChannelState,
+};
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+};
+use ibc_relayer_types::events::WithBlockDataType;
+use ibc_relayer_types::timestamp::Timestamp;
+
+use crate::conclude::{exit_with_unrecoverable_error, json, Output};
+use crate::prelude::*;
+
+use super::client::{client_staleness, format_secs, Status};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// The data structure that represents the arguments when invoking the `query stale-objects` CLI command.
+///
+/// The command has the following format:
+///
+/// `query stale-objects --chain <CHAIN_ID> [--older-than <DURATION>] [--idle-days <DAYS>]`
+///
+/// If successful, the expired or frozen clients of the chain, its connections and channels stuck
+/// in a handshake, and its open channels without any recent packet are displayed, along with the
+/// commands which may be used to clean them up.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryStaleObjectsCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain to query"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "older-than",
+        value_name = "DURATION",
+        default_value = "7days",
+        help = "How long a connection or channel handshake must have been stuck to be reported, \
+                measured from the last update of its client"
+    )]
+    older_than: humantime::Duration,
+
+    #[clap(
+        long = "idle-days",
+        value_name = "DAYS",
+        default_value = "30",
+        help = "Number of days without any packet sent over an open channel, in either direction, \
+                for the channel to be reported"
+    )]
+    idle_days: u64,
+}
+
+/// The objects of a chain which are likely abandoned
+#[derive(Debug, Serialize)]
+struct StaleObjectsReport {
+    chain_id: ChainId,
+    clients: Vec<StaleClient>,
+    connections: Vec<StuckConnection>,
+    channels: Vec<StaleChannel>,
+}
+
+/// A client which is expired or frozen
+#[derive(Debug, Serialize)]
+struct StaleClient {
+    client_id: ClientId,
+    counterparty_chain_id: ChainId,
+    status: Status,
+    last_update_secs_ago: u64,
+    suggested_actions: Vec<String>,
+}
+
+/// A connection whose handshake was not completed
+#[derive(Debug, Serialize)]
+struct StuckConnection {
+    connection_id: ConnectionId,
+    client_id: ClientId,
+    counterparty_chain_id: ChainId,
+    state: ConnectionState,
+    counterparty_state: Option<ConnectionState>,
+    stuck_for_secs: u64,
+    suggested_actions: Vec<String>,
+}
+
+/// A channel whose handshake was not completed, or which is not used anymore
+#[derive(Debug, Serialize)]
+struct StaleChannel {
+    port_id: PortId,
+    channel_id: ChannelId,
+    connection_id: ConnectionId,
+    counterparty_chain_id: ChainId,
+    state: ChannelState,
+    reason: StaleChannelReason,
+    suggested_actions: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum StaleChannelReason {
+    /// The handshake has been stuck for the given duration
+    StuckHandshake { stuck_for_secs: u64 },
+
+    /// No packet was sent over the open channel for the given duration, if ever
+    Idle { last_packet_secs_ago: Option<u64> },
+}
+
+/// The time at which the latest packet was sent over a channel end
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum LastPacket {
+    Never,
+    At(Timestamp),
+    Unknown,
+}
+
+impl Runnable for QueryStaleObjectsCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        if config.find_chain(&self.chain_id).is_none() {
+            Output::error(format!(
+                "chain '{}' not found in configuration file",
+                self.chain_id
+            ))
+            .exit()
+        }
+
+        // Scan all the connections and channels of the chain, whatever their state and the
+        // packet filter of the chain, since the abandoned ones are usually not relayed on.
+        let mut scan_config = (*config).clone();
+        scan_config.mode.connections.enabled = true;
+
+        let chain_config = scan_config
+            .chains
+            .iter_mut()
+            .find(|chain| chain.id == self.chain_id)
+            .map(|chain| {
+                chain.packet_filter = PacketFilter::default();
+                chain.clone()
+            })
+            .expect("the chain is in the configuration");
+
+        let mut registry = Registry::<BaseChainHandle>::new(scan_config.clone());
+        let mut client_state_filter = FilterPolicy::default();
+
+        let scan = ChainScanner::new(
+            &scan_config,
+            &mut registry,
+            &mut client_state_filter,
+            ScanMode::Full,
+        )
+        .scan_chain(&chain_config)
+        .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let chain = registry
+            .get_or_spawn(&self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let now = chain
+            .query_application_status()
+            .unwrap_or_else(exit_with_unrecoverable_error)
+            .timestamp;
+
+        let clients = chain
+            .query_clients(QueryClientStatesRequest {
+                pagination: Some(PageRequest::all()),
+            })
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let mut report = StaleObjectsReport {
+            chain_id: self.chain_id.clone(),
+            clients: Vec::new(),
+            connections: Vec::new(),
+            channels: Vec::new(),
+        };
+
+        // The status of each client, and how long ago it was last updated
+        let mut client_statuses = BTreeMap::new();
+
+        for client in clients {
+            let (status, since_update) = match client_staleness(&chain, &client, now) {
+                Ok(staleness) => staleness,
+                Err(e) => {
+                    warn!("skipping client {}: {}", client.client_id, e);
+                    continue;
+                }
+            };
+
+            if status != Status::Active {
+                let counterparty_chain_id = client.client_state.chain_id();
+
+                report.clients.push(StaleClient {
+                    suggested_actions: client_actions(&self.chain_id, &client.client_id),
+                    client_id: client.client_id.clone(),
+                    counterparty_chain_id,
+                    status,
+                    last_update_secs_ago: since_update.as_secs(),
+                });
+            }
+
+            client_statuses.insert(client.client_id, (status, since_update));
+        }
+
+        let older_than = *self.older_than;
+        let idle_for = Duration::from_secs(self.idle_days * SECONDS_PER_DAY);
+
+        for client in scan.clients.values() {
+            let Some(&(status, since_update)) = client_statuses.get(client.id()) else {
+                continue;
+            };
+
+            let counterparty_chain_id = client.counterparty_chain_id();
+
+            for connection in client.connections.values() {
+                if !connection.is_open() && since_update >= older_than {
+                    report.connections.push(StuckConnection {
+                        connection_id: connection.id().clone(),
+                        client_id: client.id().clone(),
+                        counterparty_chain_id: counterparty_chain_id.clone(),
+                        state: connection.state(),
+                        counterparty_state: connection.counterparty_state,
+                        stuck_for_secs: since_update.as_secs(),
+                        suggested_actions: connection_actions(
+                            &self.chain_id,
+                            &counterparty_chain_id,
+                            connection,
+                            status,
+                        ),
+                    });
+                }
+
+                for channel in connection.channels.values() {
+                    let state = *channel.channel.channel_end.state();
+
+                    let reason = match state {
+                        ChannelState::Init | ChannelState::TryOpen
+                            if since_update >= older_than =>
+                        {
+                            StaleChannelReason::StuckHandshake {
+                                stuck_for_secs: since_update.as_secs(),
+                            }
+                        }
+                        ChannelState::Open => {
+                            let counterparty_chain =
+                                match registry.get_or_spawn(&counterparty_chain_id) {
+                                    Ok(chain) => chain,
+                                    Err(e) => {
+                                        warn!(
+                                            "skipping channel {}/{}: {}",
+                                            channel.port(),
+                                            channel.id(),
+                                            e
+                                        );
+                                        continue;
+                                    }
+                                };
+
+                            match channel_idleness(&chain, &counterparty_chain, channel, now) {
+                                Some(last_packet_ago)
+                                    if last_packet_ago.map_or(true, |ago| ago >= idle_for) =>
+                                {
+                                    StaleChannelReason::Idle {
+                                        last_packet_secs_ago: last_packet_ago
+                                            .map(|ago| ago.as_secs()),
+                                    }
+                                }
+                                _ => continue,
+                            }
+                        }
+                        _ => continue,
+                    };
+
+                    report.channels.push(StaleChannel {
+                        port_id: channel.port().clone(),
+                        channel_id: channel.id().clone(),
+                        connection_id: connection.id().clone(),
+                        counterparty_chain_id: counterparty_chain_id.clone(),
+                        state,
+                        suggested_actions: channel_actions(
+                            &self.chain_id,
+                            &counterparty_chain_id,
+                            connection,
+                            &channel.channel,
+                            status,
+                        ),
+                        reason,
+                    });
+                }
+            }
+        }
+
+        if json() {
+            Output::success(report).exit()
+        } else {
+            Output::success_msg(report.to_string()).exit()
+        }
+    }
+}
+
+/// How long ago the latest packet was sent over the given open channel, in either direction.
+/// `Some(None)` means that no packet was ever sent, and `None` that it could not be determined.
+fn channel_idleness(
+    chain: &impl ChainHandle,
+    counterparty_chain: &impl ChainHandle,
+    channel: &ChannelScan,
+    now: Timestamp,
+) -> Option<Option<Duration>> {
+    let counterparty = channel.counterparty.as_ref()?;
+
+    let sent = last_packet(chain, counterparty_chain, &channel.channel, counterparty);
+    let received = last_packet(counterparty_chain, chain, counterparty, &channel.channel);
+
+    packet_idleness(sent, received, now)
+}
+
+/// How long ago the latest of the given packets was sent, if known,
+/// with `Some(None)` meaning that no packet was ever sent.
+fn packet_idleness(
+    sent: LastPacket,
+    received: LastPacket,
+    now: Timestamp,
+) -> Option<Option<Duration>> {
+    match (sent, received) {
+        (LastPacket::Unknown, _) | (_, LastPacket::Unknown) => None,
+        (LastPacket::Never, LastPacket::Never) => Some(None),
+        (LastPacket::At(at), LastPacket::Never) | (LastPacket::Never, LastPacket::At(at)) => {
+            Some(Some(now.duration_since(&at).unwrap_or_default()))
+        }
+        (LastPacket::At(sent), LastPacket::At(received)) => {
+            let latest = if sent > received { sent } else { received };
+            Some(Some(now.duration_since(&latest).unwrap_or_default()))
+        }
+    }
+}
+
+/// The time at which the latest packet was sent by `chain` over the given channel end, found by
+/// looking up the highest sequence among the packets awaiting an acknowledgment and the packets
+/// acknowledged on the counterparty chain.
+fn last_packet(
+    chain: &impl ChainHandle,
+    counterparty_chain: &impl ChainHandle,
+    channel: &IdentifiedChannelEnd,
+    counterparty: &IdentifiedChannelEnd,
+) -> LastPacket {
+    let commitments = chain.query_packet_commitments(QueryPacketCommitmentsRequest {
+        port_id: channel.port_id.clone(),
+        channel_id: channel.channel_id.clone(),
+        pagination: Some(PageRequest::all()),
+    });
+
+    let acknowledgements =
+        counterparty_chain.query_packet_acknowledgements(QueryPacketAcknowledgementsRequest {
+            port_id: counterparty.port_id.clone(),
+            channel_id: counterparty.channel_id.clone(),
+            pagination: Some(PageRequest::all()),
+            packet_commitment_sequences: Vec::new(),
+        });
+
+    let (Ok((commitments, _)), Ok((acknowledgements, _))) = (commitments, acknowledgements) else {
+        return LastPacket::Unknown;
+    };
+
+    let Some(sequence) = commitments.into_iter().chain(acknowledgements).max() else {
+        return LastPacket::Never;
+    };
+
+    let events = chain.query_packet_events(QueryPacketEventDataRequest {
+        event_id: WithBlockDataType::SendPacket,
+        source_channel_id: channel.channel_id.clone(),
+        source_port_id: channel.port_id.clone(),
+        destination_channel_id: counterparty.channel_id.clone(),
+        destination_port_id: counterparty.port_id.clone(),
+        sequences: vec![sequence],
+        height: Qualified::SmallerEqual(QueryHeight::Latest),
+    });
+
+    let Some(event) = events.ok().and_then(|events| events.into_iter().next()) else {
+        return LastPacket::Unknown;
+    };
+
+    chain
+        .query_host_consensus_state(QueryHostConsensusStateRequest {
+            height: QueryHeight::Specific(event.height),
+        })
+        .map_or(LastPacket::Unknown, |state| {
+            LastPacket::At(state.timestamp())
+        })
+}
+
+/// The command recovering an expired or frozen client through a governance proposal
+fn client_actions(chain_id: &ChainId, client_id: &ClientId) -> Vec<String> {
+    vec![format!(
+        "hermes tx client-recover --chain {chain_id} --subject-client {client_id} --amount <DEPOSIT>"
+    )]
+}
+
+/// The commands completing the handshake of a stuck connection, since connections cannot be closed
+fn connection_actions(
+    chain_id: &ChainId,
+    counterparty_chain_id: &ChainId,
+    connection: &ConnectionScan,
+    client_status: Status,
+) -> Vec<String> {
+    if client_status != Status::Active {
+        return vec![format!(
+            "none: the client of the connection is {client_status:?}, the handshake cannot be completed"
+        )];
+    }
+
+    let end = &connection.connection.connection_end;
+    let connection_id = connection.id();
+    let client_id = end.client_id();
+    let counterparty_client_id = end.counterparty().client_id();
+
+    match (
+        end.state,
+        connection.counterparty_state,
+        end.counterparty().connection_id(),
+    ) {
+        (ConnectionState::Init, Some(ConnectionState::Uninitialized) | None, _) => vec![format!(
+            "hermes tx conn-try --dst-chain {counterparty_chain_id} --src-chain {chain_id} \
+             --dst-client {counterparty_client_id} --src-client {client_id} \
+             --src-connection {connection_id}"
+        )],
+        (ConnectionState::Init, Some(ConnectionState::TryOpen), Some(counterparty_id)) => {
+            vec![format!(
+                "hermes tx conn-ack --dst-chain {chain_id} --src-chain {counterparty_chain_id} \
+                 --dst-client {client_id} --src-client {counterparty_client_id} \
+                 --dst-connection {connection_id} --src-connection {counterparty_id}"
+            )]
+        }
+        (ConnectionState::TryOpen, _, Some(counterparty_id)) => vec![format!(
+            "hermes tx conn-ack --dst-chain {counterparty_chain_id} --src-chain {chain_id} \
+             --dst-client {counterparty_client_id} --src-client {client_id} \
+             --dst-connection {counterparty_id} --src-connection {connection_id}"
+        )],
+        _ => vec![],
+    }
+}
+
+/// The commands closing a stale channel, or completing its handshake if its
+/// counterparty is unknown and the channel therefore cannot be closed
+fn channel_actions(
+    chain_id: &ChainId,
+    counterparty_chain_id: &ChainId,
+    connection: &ConnectionScan,
+    channel: &IdentifiedChannelEnd,
+    client_status: Status,
+) -> Vec<String> {
+    if client_status != Status::Active {
+        return vec![format!(
+            "none: the client of the channel is {client_status:?}, the channel cannot be closed"
+        )];
+    }
+
+    let connection_id = connection.id();
+    let port_id = &channel.port_id;
+    let channel_id = &channel.channel_id;
+    let counterparty = channel.channel_end.counterparty();
+    let counterparty_port_id = counterparty.port_id();
+
+    if let Some(counterparty_channel_id) = counterparty.channel_id() {
+        if rejects_close_init(port_id) {
+            return vec![format!(
+                "none: the application bound to port {port_id} rejects closing its channels"
+            )];
+        }
+
+        return vec![format!(
+            "hermes tx chan-close-init --dst-chain {chain_id} --src-chain {counterparty_chain_id} \
+             --dst-connection {connection_id} --dst-port {port_id} --src-port {counterparty_port_id} \
+             --dst-channel {channel_id} --src-channel {counterparty_channel_id}"
+        )];
+    }
+
+    match connection
+        .connection
+        .connection_end
+        .counterparty()
+        .connection_id()
+    {
+        Some(counterparty_connection_id) => vec![format!(
+            "hermes tx chan-open-try --dst-chain {counterparty_chain_id} --src-chain {chain_id} \
+             --dst-connection {counterparty_connection_id} --dst-port {counterparty_port_id} \
+             --src-port {port_id} --src-channel {channel_id}"
+        )],
+        None => vec![],
+    }
+}
+
+/// Whether the application bound to the given port rejects `ChanCloseInit`,
+/// as the ibc-go transfer and interchain accounts applications do.
+fn rejects_close_init(port_id: &PortId) -> bool {
+    let port_id = port_id.as_str();
+
+    port_id == PortId::transfer().as_str()
+        || port_id == "icahost"
+        || port_id.starts_with("icacontroller-")
+}
+
+impl Display for StaleObjectsReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        writeln!(f, "Stale objects on chain {}", self.chain_id)?;
+
+        writeln!(f, "\nClients:")?;
+        if self.clients.is_empty() {
+            writeln!(f, "  none")?;
+        }
+        for client in &self.clients {
+            writeln!(
+                f,
+                "  - {} (counterparty: {}): {:?}, last updated {} ago",
+                client.client_id,
+                client.counterparty_chain_id,
+                client.status,
+                format_secs(client.last_update_secs_ago)
+            )?;
+            write_actions(f, &client.suggested_actions)?;
+        }
+
+        writeln!(f, "\nConnections:")?;
+        if self.connections.is_empty() {
+            writeln!(f, "  none")?;
+        }
+        for connection in &self.connections {
+            let counterparty_state = connection
+                .counterparty_state
+                .map(|state| state.to_string())
+                .unwrap_or_else(|| "<unknown>".to_string());
+
+            writeln!(
+                f,
+                "  - {} (client: {}, counterparty: {}): {} (counterparty: {}), stuck for {}",
+                connection.connection_id,
+                connection.client_id,
+                connection.counterparty_chain_id,
+                connection.state,
+                counterparty_state,
+                format_secs(connection.stuck_for_secs)
+            )?;
+            write_actions(f, &connection.suggested_actions)?;
+        }
+
+        writeln!(f, "\nChannels:")?;
+        if self.channels.is_empty() {
+            writeln!(f, "  none")?;
+        }
+        for channel in &self.channels {
+            let reason = match channel.reason {
+                StaleChannelReason::StuckHandshake { stuck_for_secs } => {
+                    format!("stuck for {}", format_secs(stuck_for_secs))
+                }
+                StaleChannelReason::Idle {
+                    last_packet_secs_ago: Some(secs),
+                } => format!("last packet {} ago", format_secs(secs)),
+                StaleChannelReason::Idle {
+                    last_packet_secs_ago: None,
+                } => "no packet ever sent".to_string(),
+            };
+
+            writeln!(
+                f,
+                "  - {}/{} (connection: {}, counterparty: {}): {}, {}",
+                channel.port_id,
+                channel.channel_id,
+                channel.connection_id,
+                channel.counterparty_chain_id,
+                channel.state,
+                reason
+            )?;
+            write_actions(f, &channel.suggested_actions)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_actions(f: &mut Formatter<'_>, actions: &[String]) -> Result<(), FmtError> {
+    for action in actions {
+        writeln!(f, "      > {action}")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        channel_actions, connection_actions, packet_idleness, LastPacket, QueryStaleObjectsCmd,
+        Status,
+    };
+
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer::supervisor::scan::ConnectionScan;
+    use ibc_relayer_types::core::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnectionCounterparty, IdentifiedConnectionEnd,
+        State as ConnectionState,
+    };
+    use ibc_relayer_types::core::ics04_channel::channel::{
+        ChannelEnd, Counterparty as ChannelCounterparty, IdentifiedChannelEnd, Ordering,
+        State as ChannelState,
+    };
+    use ibc_relayer_types::core::ics04_channel::version::Version;
+    use ibc_relayer_types::core::ics24_host::identifier::{
+        ChainId, ChannelId, ClientId, ConnectionId, PortId,
+    };
+    use ibc_relayer_types::timestamp::Timestamp;
+
+    fn connection(
+        state: ConnectionState,
+        counterparty_state: Option<ConnectionState>,
+        counterparty_connection_id: Option<ConnectionId>,
+    ) -> ConnectionScan {
+        let end = ConnectionEnd::new(
+            state,
+            ClientId::from_str("07-tendermint-0").unwrap(),
+            ConnectionCounterparty::new(
+                ClientId::from_str("07-tendermint-1").unwrap(),
+                counterparty_connection_id,
+                Default::default(),
+            ),
+            Vec::new(),
+            Duration::ZERO,
+        );
+
+        ConnectionScan::new(
+            IdentifiedConnectionEnd::new(ConnectionId::new(0), end),
+            counterparty_state,
+        )
+    }
+
+    fn channel(
+        port_id: &str,
+        state: ChannelState,
+        counterparty_channel_id: Option<ChannelId>,
+    ) -> IdentifiedChannelEnd {
+        let port_id = PortId::from_str(port_id).unwrap();
+
+        IdentifiedChannelEnd::new(
+            port_id.clone(),
+            ChannelId::new(0),
+            ChannelEnd::new(
+                state,
+                Ordering::Unordered,
+                ChannelCounterparty::new(port_id, counterparty_channel_id),
+                vec![ConnectionId::new(0)],
+                Version::empty(),
+            ),
+        )
+    }
+
+    fn chain_ids() -> (ChainId, ChainId) {
+        (
+            ChainId::from_string("chain-a"),
+            ChainId::from_string("chain-b"),
+        )
+    }
+
+    fn timestamp(secs: u64) -> Timestamp {
+        Timestamp::from_nanoseconds(secs * 1_000_000_000).unwrap()
+    }
+
+    #[test]
+    fn test_query_stale_objects() {
+        assert_eq!(
+            QueryStaleObjectsCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                older_than: humantime::Duration::from_str("7days").unwrap(),
+                idle_days: 30,
+            },
+            QueryStaleObjectsCmd::parse_from(["test", "--chain", "chain_id"])
+        )
+    }
+
+    #[test]
+    fn test_query_stale_objects_thresholds() {
+        assert_eq!(
+            QueryStaleObjectsCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                older_than: humantime::Duration::from_str("12h").unwrap(),
+                idle_days: 90,
+            },
+            QueryStaleObjectsCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--older-than",
+                "12h",
+                "--idle-days",
+                "90"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_stale_objects_no_chain() {
+        assert!(QueryStaleObjectsCmd::try_parse_from(["test"]).is_err())
+    }
+
+    #[test]
+    fn connection_actions_complete_the_handshake() {
+        let (chain_id, counterparty_chain_id) = chain_ids();
+
+        let init = connection(ConnectionState::Init, None, None);
+        let actions = connection_actions(&chain_id, &counterparty_chain_id, &init, Status::Active);
+        assert_eq!(actions.len(), 1);
+        assert!(
+            actions[0].starts_with("hermes tx conn-try --dst-chain chain-b --src-chain chain-a")
+        );
+
+        let init = connection(
+            ConnectionState::Init,
+            Some(ConnectionState::TryOpen),
+            Some(ConnectionId::new(1)),
+        );
+        let actions = connection_actions(&chain_id, &counterparty_chain_id, &init, Status::Active);
+        assert_eq!(actions.len(), 1);
+        assert!(
+            actions[0].starts_with("hermes tx conn-ack --dst-chain chain-a --src-chain chain-b")
+        );
+        assert!(actions[0].ends_with("--src-connection connection-1"));
+
+        let try_open = connection(ConnectionState::TryOpen, None, Some(ConnectionId::new(1)));
+        let actions =
+            connection_actions(&chain_id, &counterparty_chain_id, &try_open, Status::Active);
+        assert_eq!(actions.len(), 1);
+        assert!(
+            actions[0].starts_with("hermes tx conn-ack --dst-chain chain-b --src-chain chain-a")
+        );
+        assert!(actions[0].contains("--dst-connection connection-1"));
+    }
+
+    #[test]
+    fn connection_actions_without_active_client() {
+        let (chain_id, counterparty_chain_id) = chain_ids();
+
+        let init = connection(ConnectionState::Init, None, None);
+        let actions = connection_actions(&chain_id, &counterparty_chain_id, &init, Status::Expired);
+        assert_eq!(actions.len(), 1);
+        assert!(actions[0].starts_with("none: the client of the connection is Expired"));
+
+        let open = connection(ConnectionState::Open, None, Some(ConnectionId::new(1)));
+        assert!(
+            connection_actions(&chain_id, &counterparty_chain_id, &open, Status::Active).is_empty()
+        );
+    }
+
+    #[test]
+    fn channel_actions_close_the_channel() {
+        let (chain_id, counterparty_chain_id) = chain_ids();
+        let connection = connection(ConnectionState::Open, None, Some(ConnectionId::new(1)));
+
+        let channel = channel("oracle", ChannelState::Open, Some(ChannelId::new(1)));
+        let actions = channel_actions(
+            &chain_id,
+            &counterparty_chain_id,
+            &connection,
+            &channel,
+            Status::Active,
+        );
+        assert_eq!(actions.len(), 1);
+        assert!(actions[0].starts_with("hermes tx chan-close-init --dst-chain chain-a"));
+        assert!(actions[0].ends_with("--dst-channel channel-0 --src-channel channel-1"));
+
+        let actions = channel_actions(
+            &chain_id,
+            &counterparty_chain_id,
+            &connection,
+            &channel,
+            Status::Frozen,
+        );
+        assert_eq!(actions.len(), 1);
+        assert!(actions[0].starts_with("none: the client of the channel is Frozen"));
+    }
+
+    #[test]
+    fn channel_actions_do_not_close_transfer_channels() {
+        let (chain_id, counterparty_chain_id) = chain_ids();
+        let connection = connection(ConnectionState::Open, None, Some(ConnectionId::new(1)));
+
+        for port_id in ["transfer", "icahost", "icacontroller-cosmos1owner"] {
+            let channel = channel(port_id, ChannelState::Open, Some(ChannelId::new(1)));
+            let actions = channel_actions(
+                &chain_id,
+                &counterparty_chain_id,
+                &connection,
+                &channel,
+                Status::Active,
+            );
+
+            assert_eq!(
+                actions,
+                vec![format!(
+                    "none: the application bound to port {port_id} rejects closing its channels"
+                )]
+            );
+        }
+    }
+
+    #[test]
+    fn channel_actions_complete_the_handshake_of_unknown_counterparty() {
+        let (chain_id, counterparty_chain_id) = chain_ids();
+        let channel = channel("transfer", ChannelState::Init, None);
+
+        let connection_with_counterparty =
+            connection(ConnectionState::Open, None, Some(ConnectionId::new(1)));
+        let actions = channel_actions(
+            &chain_id,
+            &counterparty_chain_id,
+            &connection_with_counterparty,
+            &channel,
+            Status::Active,
+        );
+        assert_eq!(actions.len(), 1);
+        assert!(actions[0].starts_with("hermes tx chan-open-try --dst-chain chain-b"));
+        assert!(actions[0].contains("--dst-connection connection-1"));
+
+        let connection_without_counterparty = connection(ConnectionState::Init, None, None);
+        assert!(channel_actions(
+            &chain_id,
+            &counterparty_chain_id,
+            &connection_without_counterparty,
+            &channel,
+            Status::Active,
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn channel_idleness_of_latest_packet() {
+        let now = timestamp(1000);
+
+        assert_eq!(
+            packet_idleness(LastPacket::Never, LastPacket::Never, now),
+            Some(None)
+        );
+        assert_eq!(
+            packet_idleness(LastPacket::At(timestamp(400)), LastPacket::Never, now),
+            Some(Some(Duration::from_secs(600)))
+        );
+        assert_eq!(
+            packet_idleness(LastPacket::Never, LastPacket::At(timestamp(900)), now),
+            Some(Some(Duration::from_secs(100)))
+        );
+        assert_eq!(
+            packet_idleness(
+                LastPacket::At(timestamp(400)),
+                LastPacket::At(timestamp(700)),
+                now
+            ),
+            Some(Some(Duration::from_secs(300)))
+        );
+        assert_eq!(
+            packet_idleness(LastPacket::Unknown, LastPacket::At(timestamp(700)), now),
+            None
+        );
+        assert_eq!(
+            packet_idleness(LastPacket::Never, LastPacket::Unknown, now),
+            None
+        );
+    }
+}
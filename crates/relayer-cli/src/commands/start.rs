@@ -24,6 +24,12 @@ pub struct StartCmd {
         help = "Force a full scan of the chains for clients, connections and channels"
     )]
     full_scan: bool,
+
+    #[clap(
+        long = "dry-run",
+        help = "Simulate the transactions instead of submitting them, logging their estimated gas and fees"
+    )]
+    dry_run: bool,
 }
 
 impl Runnable for StartCmd {
@@ -53,7 +59,15 @@ impl Runnable for StartCmd {
             open_or_create_profile_file(Path::new(&path_str));
         }
 
-        let config = (*app_config()).clone();
+        let mut config = (*app_config()).clone();
+
+        if self.dry_run {
+            warn!("running in dry-run mode, no transaction will be submitted");
+
+            for chain_config in config.chains.iter_mut() {
+                chain_config.dry_run = true;
+            }
+        }
 
         let options = SupervisorOptions {
             force_full_scan: self.full_scan,
@@ -253,7 +267,10 @@ mod tests {
     #[test]
     fn test_start_required_only() {
         assert_eq!(
-            StartCmd { full_scan: false },
+            StartCmd {
+                full_scan: false,
+                dry_run: false
+            },
             StartCmd::parse_from(["test"])
         )
     }
@@ -261,8 +278,22 @@ mod tests {
     #[test]
     fn test_start_full_scan() {
         assert_eq!(
-            StartCmd { full_scan: true },
+            StartCmd {
+                full_scan: true,
+                dry_run: false
+            },
             StartCmd::parse_from(["test", "--full-scan"])
         )
     }
+
+    #[test]
+    fn test_start_dry_run() {
+        assert_eq!(
+            StartCmd {
+                full_scan: false,
+                dry_run: true
+            },
+            StartCmd::parse_from(["test", "--dry-run"])
+        )
+    }
 }
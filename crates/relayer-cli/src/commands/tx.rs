@@ -6,8 +6,12 @@ use ibc_relayer::config::Config;
 mod channel;
 pub(crate) mod client;
 mod connection;
+mod icq;
+mod migrate;
 mod packet;
+mod recover;
 mod transfer;
+mod transfer_bench;
 mod upgrade;
 
 /// `tx` subcommand
@@ -47,6 +51,9 @@ pub enum TxCmd {
     /// Send a fungible token transfer test transaction (ICS20 MsgTransfer)
     FtTransfer(transfer::TxIcs20MsgTransferCmd),
 
+    /// Send fungible token transfers at a given rate and measure how long they take to be relayed
+    FtTransferBench(transfer_bench::TxIcs20TransferBenchCmd),
+
     /// Relay receive or timeout packets
     PacketRecv(packet::TxPacketRecvCmd),
 
@@ -55,6 +62,16 @@ pub enum TxCmd {
 
     /// Send an IBC upgrade plan
     UpgradeChain(upgrade::TxIbcUpgradeChainCmd),
+
+    /// Submit a governance proposal recovering an expired or frozen client with a substitute client
+    ClientRecover(recover::TxClientRecoverCmd),
+
+    /// Create a client with new parameters to replace an existing client, and print the setup moving traffic to it
+    ClientMigrate(migrate::TxClientMigrateCmd),
+
+    /// Answer interchain queries (ICS-31)
+    #[clap(subcommand)]
+    Icq(icq::TxIcqCmd),
 }
 
 impl Override<Config> for TxCmd {
@@ -504,7 +504,7 @@ impl TxUpgradeClientsCmd {
     }
 }
 
-fn parse_trust_threshold(input: &str) -> Result<TrustThreshold, Error> {
+pub(crate) fn parse_trust_threshold(input: &str) -> Result<TrustThreshold, Error> {
     let (num_part, denom_part) = input.split_once('/').ok_or_else(|| {
         Error::cli_arg("expected a fractional argument, two numbers separated by '/'".into())
     })?;
@@ -0,0 +1,164 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::chain::requests::QueryPendingCrossChainQueriesRequest;
+use ibc_relayer::chain::tracking::TrackedMsgs;
+use ibc_relayer::worker::cross_chain_query::build_cross_chain_query_responses;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::events::IbcEvent;
+
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+use crate::prelude::*;
+
+/// `tx icq` subcommand
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum TxIcqCmd {
+    /// Answer a pending interchain query (ICS-31) of a chain
+    Respond(TxIcqRespondCmd),
+}
+
+/// The data structure that represents the arguments when invoking the `tx icq respond` CLI command.
+///
+/// `tx icq respond --chain <CHAIN_ID> --id <QUERY_ID> [--blocks <BLOCKS>]`
+///
+/// Looks up the query in the latest blocks of the querying chain, queries the queried chain,
+/// and submits the response to the querying chain, whatever the `icq` configuration of the chain.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxIcqRespondCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the querying chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "id",
+        required = true,
+        value_name = "QUERY_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the query to answer"
+    )]
+    query_id: String,
+
+    #[clap(
+        long = "blocks",
+        value_name = "BLOCKS",
+        default_value = "100",
+        help = "Number of latest blocks of the querying chain to look for the query request in"
+    )]
+    blocks: u64,
+}
+
+impl Runnable for TxIcqRespondCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let querying_chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let pending = querying_chain
+            .query_pending_cross_chain_queries(QueryPendingCrossChainQueriesRequest {
+                blocks: self.blocks,
+            })
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let query = match pending
+            .iter()
+            .filter_map(|event| event.event.cross_chain_query_packet())
+            .find(|query| query.query_id == self.query_id)
+        {
+            Some(query) => query.clone(),
+            None => Output::error(format!(
+                "query '{}' is not pending in the latest {} blocks of chain {}",
+                self.query_id, self.blocks, self.chain_id
+            ))
+            .exit(),
+        };
+
+        let queried_chain = spawn_chain_runtime(&config, &query.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let responses = queried_chain
+            .cross_chain_query(vec![(&query).into()])
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        if responses.is_empty() {
+            Output::error(format!(
+                "chain {} could not answer query '{}' of type '{}' at height {}",
+                query.chain_id, query.query_id, query.query_type, query.height
+            ))
+            .exit()
+        }
+
+        let msgs = build_cross_chain_query_responses(
+            &querying_chain,
+            &queried_chain,
+            &query.connection_id,
+            responses,
+        )
+        .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let res = querying_chain
+            .send_messages_and_wait_commit(TrackedMsgs::new_static(msgs, "icq-respond"));
+
+        match res {
+            Ok(events) => Output::success(
+                events
+                    .into_iter()
+                    .map(|event| event.event)
+                    .collect::<Vec<IbcEvent>>(),
+            )
+            .exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TxIcqRespondCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_icq_respond() {
+        assert_eq!(
+            TxIcqRespondCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                query_id: "query_id".to_owned(),
+                blocks: 100,
+            },
+            TxIcqRespondCmd::parse_from(["test", "--chain", "chain_id", "--id", "query_id"])
+        )
+    }
+
+    #[test]
+    fn test_icq_respond_blocks() {
+        assert_eq!(
+            TxIcqRespondCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                query_id: "query_id".to_owned(),
+                blocks: 20,
+            },
+            TxIcqRespondCmd::parse_from([
+                "test", "--chain", "chain_id", "--id", "query_id", "--blocks", "20"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_icq_respond_no_id() {
+        assert!(TxIcqRespondCmd::try_parse_from(["test", "--chain", "chain_id"]).is_err())
+    }
+
+    #[test]
+    fn test_icq_respond_no_chain() {
+        assert!(TxIcqRespondCmd::try_parse_from(["test", "--id", "query_id"]).is_err())
+    }
+}
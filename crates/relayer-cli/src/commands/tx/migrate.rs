@@ -0,0 +1,572 @@
+use core::fmt::{Display, Error as FmtError, Formatter};
+use core::time::Duration;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::chain::requests::{
+    IncludeProof, PageRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
+    QueryConnectionChannelsRequest, QueryConnectionRequest, QueryHeight,
+};
+use ibc_relayer::chain::tracking::TrackedMsgs;
+use ibc_relayer::client_state::AnyClientState;
+use ibc_relayer::foreign_client::{extract_client_id, CreateOptions, ForeignClient};
+use ibc_relayer::supervisor::client_state_filter::{LOWER_BOUND, UPPER_BOUND};
+use ibc_relayer_types::clients::ics07_tendermint::client_state::{
+    AllowUpdate, ClientState as TmClientState,
+};
+use ibc_relayer_types::core::ics04_channel::channel::{IdentifiedChannelEnd, Ordering};
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId, ConnectionId};
+use ibc_relayer_types::downcast;
+use ibc_relayer_types::tx_msg::Msg;
+use tendermint_light_client_verifier::types::TrustThreshold;
+
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{exit_with_unrecoverable_error, json, Output};
+use crate::error::Error;
+use crate::prelude::*;
+
+use super::client::parse_trust_threshold;
+
+/// The data structure that represents the arguments when invoking the `tx client-migrate` CLI command.
+///
+/// `tx client-migrate --host-chain <HOST_CHAIN_ID> --client <CLIENT_ID> [--clock-drift <CLOCK_DRIFT>]
+///     [--trusting-period <TRUSTING_PERIOD>] [--trust-threshold <TRUST_THRESHOLD>] [--dry-run]`
+///
+/// A new client of the chain targeted by the given client is created on the host chain, with the
+/// given parameters and the remaining ones of the existing client. The differences between the
+/// two clients are then displayed, along with the commands creating the connections and channels
+/// moving the traffic of the existing client over to the new one.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxClientMigrateCmd {
+    #[clap(
+        long = "host-chain",
+        required = true,
+        value_name = "HOST_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain that hosts the client"
+    )]
+    host_chain_id: ChainId,
+
+    #[clap(
+        long = "client",
+        required = true,
+        value_name = "CLIENT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the client to migrate away from"
+    )]
+    client_id: ClientId,
+
+    /// The maximum allowed clock drift for the new client.
+    ///
+    /// If this option is not specified, the clock drift of the existing client is used.
+    #[clap(long = "clock-drift", value_name = "CLOCK_DRIFT")]
+    clock_drift: Option<humantime::Duration>,
+
+    /// The trusting period of the new client.
+    ///
+    /// If this option is not specified, the trusting period of the existing client is used.
+    #[clap(long = "trusting-period", value_name = "TRUSTING_PERIOD")]
+    trusting_period: Option<humantime::Duration>,
+
+    /// The trust threshold of the new client.
+    ///
+    /// If this option is not specified, the trust threshold of the existing client is used.
+    #[clap(long = "trust-threshold", value_name = "TRUST_THRESHOLD", parse(try_from_str = parse_trust_threshold))]
+    trust_threshold: Option<TrustThreshold>,
+
+    #[clap(
+        long = "dry-run",
+        help = "Only display the differences between the clients and the setup moving the traffic, \
+                without creating the new client"
+    )]
+    dry_run: bool,
+}
+
+/// The outcome of a client migration
+#[derive(Debug, Serialize)]
+struct ClientMigrationReport {
+    host_chain_id: ChainId,
+    reference_chain_id: ChainId,
+    old_client_id: ClientId,
+    new_client_id: Option<ClientId>,
+    differences: Vec<ClientStateDifference>,
+    setup: Vec<ConnectionSetup>,
+}
+
+/// A parameter which differs between the old and the new client states
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct ClientStateDifference {
+    field: &'static str,
+    old: String,
+    new: String,
+}
+
+/// The commands recreating an open connection of the old client, and its open channels,
+/// on top of the new client
+#[derive(Debug, Serialize)]
+struct ConnectionSetup {
+    old_connection_id: ConnectionId,
+    connection: String,
+    channels: Vec<String>,
+}
+
+impl Runnable for TxClientMigrateCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let host_chain = spawn_chain_runtime(&config, &self.host_chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let (client_state, _) = host_chain
+            .query_client_state(
+                QueryClientStateRequest {
+                    client_id: self.client_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(Error::relayer)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let Some(old_state) = downcast!(client_state => AnyClientState::Tendermint) else {
+            Output::error(format!(
+                "client '{}' is not a Tendermint client, only Tendermint clients can be migrated",
+                self.client_id
+            ))
+            .exit()
+        };
+
+        let reference_chain = spawn_chain_runtime(&config, &old_state.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let options = CreateOptions {
+            max_clock_drift: Some(
+                self.clock_drift
+                    .map_or(old_state.max_clock_drift, Into::into),
+            ),
+            trusting_period: Some(
+                self.trusting_period
+                    .map_or(old_state.trusting_period, Into::into),
+            ),
+            trust_threshold: Some(
+                self.trust_threshold
+                    .map_or(old_state.trust_threshold, Into::into),
+            ),
+        };
+
+        let client = ForeignClient::restore(
+            ClientId::default(),
+            host_chain.clone(),
+            reference_chain.clone(),
+        );
+
+        let msg = client
+            .build_create_client(options)
+            .map_err(Error::foreign_client)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let Some(new_state) = AnyClientState::try_from(msg.client_state.clone())
+            .ok()
+            .and_then(|state| downcast!(state => AnyClientState::Tendermint))
+        else {
+            Output::error("the new client state is not a Tendermint client state".to_string())
+                .exit()
+        };
+
+        // The supervisor does not relay on clients whose trust threshold is out of these bounds
+        if new_state.trust_threshold < LOWER_BOUND || new_state.trust_threshold > UPPER_BOUND {
+            Output::error(format!(
+                "the trust threshold of the new client ({}) must be between {} and {}, \
+                 otherwise Hermes would not relay on it",
+                new_state.trust_threshold, LOWER_BOUND, UPPER_BOUND
+            ))
+            .exit()
+        }
+
+        let setup = connection_setups(&host_chain, &self.client_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let new_client_id = if self.dry_run {
+            None
+        } else {
+            let events = host_chain
+                .send_messages_and_wait_commit(TrackedMsgs::new_single(
+                    msg.to_any(),
+                    "create client",
+                ))
+                .map_err(Error::relayer)
+                .unwrap_or_else(exit_with_unrecoverable_error);
+
+            let Some(event) = events.first() else {
+                Output::error("no event was emitted by the client creation".to_string()).exit()
+            };
+
+            let new_client_id = extract_client_id(&event.event)
+                .map_err(Error::foreign_client)
+                .unwrap_or_else(exit_with_unrecoverable_error);
+
+            Some(new_client_id.clone())
+        };
+
+        let mut report = ClientMigrationReport {
+            host_chain_id: self.host_chain_id.clone(),
+            reference_chain_id: old_state.chain_id.clone(),
+            old_client_id: self.client_id.clone(),
+            new_client_id,
+            differences: client_state_differences(&old_state, &new_state),
+            setup,
+        };
+
+        if let Some(new_client_id) = &report.new_client_id {
+            for connection in report.setup.iter_mut() {
+                connection.connection = connection
+                    .connection
+                    .replace(NEW_CLIENT_ID, new_client_id.as_str());
+            }
+        }
+
+        if json() {
+            Output::success(report).exit()
+        } else {
+            Output::success_msg(report.to_string()).exit()
+        }
+    }
+}
+
+/// Placeholders for the identifiers which are only known once the new objects are created
+const NEW_CLIENT_ID: &str = "<NEW_CLIENT_ID>";
+const NEW_CONNECTION_ID: &str = "<NEW_CONNECTION_ID>";
+
+/// The commands recreating every open connection of the given client, along with their open
+/// channels, on top of the new client
+fn connection_setups(
+    host_chain: &impl ChainHandle,
+    client_id: &ClientId,
+) -> Result<Vec<ConnectionSetup>, Error> {
+    let host_chain_id = host_chain.id();
+
+    let connection_ids = host_chain
+        .query_client_connections(QueryClientConnectionsRequest {
+            client_id: client_id.clone(),
+        })
+        .map_err(Error::relayer)?;
+
+    let mut setups = Vec::new();
+
+    for connection_id in connection_ids {
+        let (connection_end, _) = host_chain
+            .query_connection(
+                QueryConnectionRequest {
+                    connection_id: connection_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(Error::relayer)?;
+
+        if !connection_end.is_open() {
+            continue;
+        }
+
+        let counterparty_client_id = connection_end.counterparty().client_id();
+        let delay = connection_end.delay_period().as_secs();
+
+        let mut connection = format!(
+            "hermes create connection --a-chain {host_chain_id} --a-client {NEW_CLIENT_ID} \
+             --b-client {counterparty_client_id}"
+        );
+        if delay > 0 {
+            connection.push_str(&format!(" --delay {delay}"));
+        }
+
+        let channels = host_chain
+            .query_connection_channels(QueryConnectionChannelsRequest {
+                connection_id: connection_id.clone(),
+                pagination: Some(PageRequest::all()),
+            })
+            .map_err(Error::relayer)?;
+
+        let channels = channels
+            .iter()
+            .filter(|channel| channel.channel_end.is_open())
+            .map(|channel| channel_setup(&host_chain_id, channel))
+            .collect();
+
+        setups.push(ConnectionSetup {
+            old_connection_id: connection_id,
+            connection,
+            channels,
+        });
+    }
+
+    Ok(setups)
+}
+
+/// The command recreating the given open channel on top of the new connection
+fn channel_setup(host_chain_id: &ChainId, channel: &IdentifiedChannelEnd) -> String {
+    let order = match channel.channel_end.ordering() {
+        Ordering::Ordered => "ordered",
+        _ => "unordered",
+    };
+
+    format!(
+        "hermes create channel --a-chain {host_chain_id} --a-connection {NEW_CONNECTION_ID} \
+         --a-port {} --b-port {} --order {order} --channel-version {}",
+        channel.port_id,
+        channel.channel_end.counterparty().port_id(),
+        channel.channel_end.version(),
+    )
+}
+
+/// The parameters which differ between the old and the new client states,
+/// ignoring the latest and frozen heights
+fn client_state_differences(
+    old: &TmClientState,
+    new: &TmClientState,
+) -> Vec<ClientStateDifference> {
+    fn duration(d: Duration) -> String {
+        humantime::format_duration(d).to_string()
+    }
+
+    fn allow_update(allow: AllowUpdate) -> String {
+        format!(
+            "after_expiry: {}, after_misbehaviour: {}",
+            allow.after_expiry, allow.after_misbehaviour
+        )
+    }
+
+    let fields = [
+        (
+            "chain_id",
+            old.chain_id.to_string(),
+            new.chain_id.to_string(),
+        ),
+        (
+            "trust_threshold",
+            old.trust_threshold.to_string(),
+            new.trust_threshold.to_string(),
+        ),
+        (
+            "trusting_period",
+            duration(old.trusting_period),
+            duration(new.trusting_period),
+        ),
+        (
+            "unbonding_period",
+            duration(old.unbonding_period),
+            duration(new.unbonding_period),
+        ),
+        (
+            "max_clock_drift",
+            duration(old.max_clock_drift),
+            duration(new.max_clock_drift),
+        ),
+        (
+            "upgrade_path",
+            old.upgrade_path.join("/"),
+            new.upgrade_path.join("/"),
+        ),
+        (
+            "allow_update",
+            allow_update(old.allow_update),
+            allow_update(new.allow_update),
+        ),
+        (
+            "proof_specs",
+            serde_json::to_string(&old.proof_specs).unwrap_or_default(),
+            serde_json::to_string(&new.proof_specs).unwrap_or_default(),
+        ),
+    ];
+
+    fields
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(field, old, new)| ClientStateDifference { field, old, new })
+        .collect()
+}
+
+impl Display for ClientMigrationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match &self.new_client_id {
+            Some(new_client_id) => writeln!(
+                f,
+                "Created client {} on chain {} to replace client {} of chain {}",
+                new_client_id, self.host_chain_id, self.old_client_id, self.reference_chain_id
+            )?,
+            None => writeln!(
+                f,
+                "Dry run: a new client on chain {} would replace client {} of chain {}",
+                self.host_chain_id, self.old_client_id, self.reference_chain_id
+            )?,
+        }
+
+        writeln!(f, "\nDifferences:")?;
+        if self.differences.is_empty() {
+            writeln!(f, "  none")?;
+        }
+        for difference in &self.differences {
+            writeln!(
+                f,
+                "  - {}: {} -> {}",
+                difference.field, difference.old, difference.new
+            )?;
+        }
+
+        writeln!(f, "\nSetup moving the traffic to the new client:")?;
+        if self.setup.is_empty() {
+            writeln!(f, "  none: the client has no open connection")?;
+        }
+        for connection in &self.setup {
+            writeln!(
+                f,
+                "  - replacing connection {}:",
+                connection.old_connection_id
+            )?;
+            writeln!(f, "      {}", connection.connection)?;
+            for channel in &connection.channels {
+                writeln!(f, "      {channel}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{client_state_differences, ClientStateDifference, TxClientMigrateCmd};
+
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::clients::ics07_tendermint::client_state::{
+        AllowUpdate, ClientState as TmClientState,
+    };
+    use ibc_relayer_types::core::ics02_client::trust_threshold::TrustThreshold;
+    use ibc_relayer_types::core::ics23_commitment::specs::ProofSpecs;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+    use ibc_relayer_types::Height;
+    use tendermint_light_client_verifier::types::TrustThreshold as TmTrustThreshold;
+
+    #[test]
+    fn test_client_migrate_required_only() {
+        assert_eq!(
+            TxClientMigrateCmd {
+                host_chain_id: ChainId::from_string("chain_host_id"),
+                client_id: ClientId::from_str("07-tendermint-0").unwrap(),
+                clock_drift: None,
+                trusting_period: None,
+                trust_threshold: None,
+                dry_run: false,
+            },
+            TxClientMigrateCmd::parse_from([
+                "test",
+                "--host-chain",
+                "chain_host_id",
+                "--client",
+                "07-tendermint-0"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_client_migrate_all_options() {
+        assert_eq!(
+            TxClientMigrateCmd {
+                host_chain_id: ChainId::from_string("chain_host_id"),
+                client_id: ClientId::from_str("07-tendermint-0").unwrap(),
+                clock_drift: Some("5s".parse::<humantime::Duration>().unwrap()),
+                trusting_period: Some("14days".parse::<humantime::Duration>().unwrap()),
+                trust_threshold: Some(TmTrustThreshold::new(2, 3).unwrap()),
+                dry_run: true,
+            },
+            TxClientMigrateCmd::parse_from([
+                "test",
+                "--host-chain",
+                "chain_host_id",
+                "--client",
+                "07-tendermint-0",
+                "--clock-drift",
+                "5s",
+                "--trusting-period",
+                "14days",
+                "--trust-threshold",
+                "2/3",
+                "--dry-run"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_client_migrate_no_client() {
+        assert!(
+            TxClientMigrateCmd::try_parse_from(["test", "--host-chain", "chain_host_id"]).is_err()
+        )
+    }
+
+    #[test]
+    fn test_client_migrate_invalid_trust_threshold() {
+        assert!(TxClientMigrateCmd::try_parse_from([
+            "test",
+            "--host-chain",
+            "chain_host_id",
+            "--client",
+            "07-tendermint-0",
+            "--trust-threshold",
+            "2"
+        ])
+        .is_err())
+    }
+
+    fn client_state(trusting_period: Duration, trust_threshold: TrustThreshold) -> TmClientState {
+        TmClientState::new(
+            ChainId::from_string("chain_reference_id"),
+            trust_threshold,
+            trusting_period,
+            Duration::from_secs(21 * 24 * 3600),
+            Duration::from_secs(5),
+            Height::new(1, 10).unwrap(),
+            ProofSpecs::default(),
+            vec!["upgrade".to_string(), "upgradedIBCState".to_string()],
+            AllowUpdate {
+                after_expiry: true,
+                after_misbehaviour: true,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_client_state_differences() {
+        let old = client_state(
+            Duration::from_secs(14 * 24 * 3600),
+            TrustThreshold::ONE_THIRD,
+        );
+
+        let mut new = old.clone();
+        new.latest_height = Height::new(1, 20).unwrap();
+        assert!(client_state_differences(&old, &new).is_empty());
+
+        new.trusting_period = Duration::from_secs(7 * 24 * 3600);
+        new.trust_threshold = TrustThreshold::TWO_THIRDS;
+        assert_eq!(
+            client_state_differences(&old, &new),
+            vec![
+                ClientStateDifference {
+                    field: "trust_threshold",
+                    old: "1/3".to_string(),
+                    new: "2/3".to_string(),
+                },
+                ClientStateDifference {
+                    field: "trusting_period",
+                    old: "14days".to_string(),
+                    new: "7days".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde_json::json;
+
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::chain::requests::{IncludeProof, QueryClientStateRequest, QueryHeight};
+use ibc_relayer::recover_client::{build_client_recovery_proposal, ClientRecoveryOptions};
+use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+use crate::error::Error;
+use crate::prelude::*;
+
+/// The data structure that represents the arguments when invoking the `tx client-recover` CLI command.
+///
+/// `tx client-recover --chain <CHAIN_ID> --subject-client <SUBJECT_CLIENT_ID> --amount <AMOUNT>
+///     [--substitute-client <SUBSTITUTE_CLIENT_ID>] [--denom <DENOM>] [--generate-only]`
+///
+/// If no substitute client is given, a new client with the parameters of the subject client is
+/// created on the chain. The proposal recovering the subject client with the substitute client
+/// is then submitted to the chain, or printed as JSON with `--generate-only`.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxClientRecoverCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain hosting the client to recover"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "subject-client",
+        required = true,
+        value_name = "SUBJECT_CLIENT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the expired or frozen client to recover"
+    )]
+    subject_client_id: ClientId,
+
+    #[clap(
+        long = "amount",
+        required = true,
+        value_name = "AMOUNT",
+        help_heading = "REQUIRED",
+        help = "Amount of the deposit of the proposal"
+    )]
+    amount: u64,
+
+    #[clap(
+        long = "substitute-client",
+        value_name = "SUBSTITUTE_CLIENT_ID",
+        help = "Identifier of the active client replacing the subject client. \
+                If not given, a client with the parameters of the subject client is created"
+    )]
+    substitute_client_id: Option<ClientId>,
+
+    #[clap(
+        long = "denom",
+        value_name = "DENOM",
+        default_value = "stake",
+        help = "Denomination of the deposit of the proposal"
+    )]
+    denom: String,
+
+    #[clap(
+        long = "generate-only",
+        help = "Print the proposal as JSON, to be submitted with the `tx gov submit-proposal` command of the chain binary, instead of submitting it"
+    )]
+    generate_only: bool,
+}
+
+impl Runnable for TxClientRecoverCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let host_chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let (subject_state, _) = host_chain
+            .query_client_state(
+                QueryClientStateRequest {
+                    client_id: self.subject_client_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let counterparty_chain = spawn_chain_runtime(&config, &subject_state.chain_id())
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let opts = ClientRecoveryOptions {
+            subject_client_id: self.subject_client_id.clone(),
+            substitute_client_id: self.substitute_client_id.clone(),
+            amount: self.amount,
+            denom: self.denom.clone(),
+        };
+
+        let proposal = build_client_recovery_proposal(&host_chain, &counterparty_chain, &opts)
+            .map_err(Error::recover_client)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        if self.generate_only {
+            Output::success(proposal.to_json()).exit()
+        }
+
+        match proposal.send(&host_chain).map_err(Error::recover_client) {
+            Ok(tx_hash) => Output::success(json!({
+                "substitute_client_id": proposal.substitute_client_id,
+                "tx_hash": tx_hash,
+            }))
+            .exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TxClientRecoverCmd;
+
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+
+    #[test]
+    fn test_client_recover_required_only() {
+        assert_eq!(
+            TxClientRecoverCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                subject_client_id: ClientId::from_str("07-tendermint-0").unwrap(),
+                amount: 42,
+                substitute_client_id: None,
+                denom: "stake".to_owned(),
+                generate_only: false,
+            },
+            TxClientRecoverCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--subject-client",
+                "07-tendermint-0",
+                "--amount",
+                "42"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_client_recover_all_options() {
+        assert_eq!(
+            TxClientRecoverCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                subject_client_id: ClientId::from_str("07-tendermint-0").unwrap(),
+                amount: 42,
+                substitute_client_id: Some(ClientId::from_str("07-tendermint-1").unwrap()),
+                denom: "uatom".to_owned(),
+                generate_only: true,
+            },
+            TxClientRecoverCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--subject-client",
+                "07-tendermint-0",
+                "--amount",
+                "42",
+                "--substitute-client",
+                "07-tendermint-1",
+                "--denom",
+                "uatom",
+                "--generate-only"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_client_recover_no_subject() {
+        assert!(TxClientRecoverCmd::try_parse_from([
+            "test", "--chain", "chain_id", "--amount", "42"
+        ])
+        .is_err())
+    }
+
+    #[test]
+    fn test_client_recover_no_amount() {
+        assert!(TxClientRecoverCmd::try_parse_from([
+            "test",
+            "--chain",
+            "chain_id",
+            "--subject-client",
+            "07-tendermint-0"
+        ])
+        .is_err())
+    }
+}
@@ -8,14 +8,17 @@ use ibc_relayer::{
     chain::handle::ChainHandle,
     config::Config,
     event::IbcEventWithHeight,
-    transfer::{build_and_send_transfer_messages, TransferOptions},
+    transfer::{
+        build_and_send_transfer_messages, build_forward_route, ForwardHop, ForwardOptions,
+        TransferOptions,
+    },
 };
 use ibc_relayer_types::{
     applications::transfer::Amount,
     core::ics24_host::identifier::{ChainId, ChannelId, PortId},
 };
 
-use crate::cli_utils::{check_can_send_on_channel, ChainHandlePair};
+use crate::cli_utils::{check_can_send_on_channel, spawn_chain_runtime, ChainHandlePair};
 use crate::conclude::{exit_with_unrecoverable_error, Output};
 use crate::error::Error;
 use crate::prelude::*;
@@ -119,6 +122,31 @@ pub struct TxIcs20MsgTransferCmd {
         help = "Optional memo included in the transfer"
     )]
     memo: Option<String>,
+
+    #[clap(
+        long = "hop",
+        multiple_occurrences = true,
+        conflicts_with = "memo",
+        value_name = "CHAIN_ID:PORT_ID/CHANNEL_ID",
+        help = "Forward the transfer through the given chain and its channel, with the packet-forward-middleware, before the destination chain. Can be repeated, in the order of the hops"
+    )]
+    hops: Vec<ForwardHop>,
+
+    #[clap(
+        long = "hop-timeout",
+        default_value = "10m",
+        value_name = "HOP_TIMEOUT",
+        help = "Timeout of the packets forwarded by each hop"
+    )]
+    hop_timeout: humantime::Duration,
+
+    #[clap(
+        long = "hop-retries",
+        default_value = "1",
+        value_name = "HOP_RETRIES",
+        help = "Number of times each hop retries to forward the packet"
+    )]
+    hop_retries: u8,
 }
 
 impl Override<Config> for TxIcs20MsgTransferCmd {
@@ -131,7 +159,7 @@ impl Override<Config> for TxIcs20MsgTransferCmd {
         })?;
 
         if let Some(ref key_name) = self.key_name {
-            src_chain_config.key_name = key_name.to_string();
+            src_chain_config.key_name = key_name.as_str().into();
         }
 
         Ok(config)
@@ -177,6 +205,54 @@ impl TxIcs20MsgTransferCmd {
     }
 }
 
+impl TxIcs20MsgTransferCmd {
+    /// Send the transfer to the first of the hops given with `--hop`,
+    /// along with the memo instructing each hop to forward it to the next one,
+    /// and the last one to the destination chain.
+    fn forward(&self, config: &Config, chains: &ChainHandlePair, opts: TransferOptions) -> ! {
+        let hop_chains = self
+            .hops
+            .iter()
+            .map(|hop| {
+                spawn_chain_runtime(config, &hop.chain_id)
+                    .unwrap_or_else(exit_with_unrecoverable_error)
+            })
+            .collect::<Vec<_>>();
+
+        let forward = ForwardOptions {
+            hops: self.hops.clone(),
+            timeout: *self.hop_timeout,
+            retries: self.hop_retries,
+        };
+
+        let route = build_forward_route(&chains.src, &hop_chains, &chains.dst, &opts, &forward)
+            .map_err(Error::transfer)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        info!(
+            "the tokens are expected to be received as `{}` (`{}`) on chain {}",
+            route.denom,
+            route.denom_trace,
+            chains.dst.id()
+        );
+
+        let opts = TransferOptions {
+            receiver: Some(route.receiver),
+            memo: Some(route.memo),
+            ..opts
+        };
+
+        let res: Result<Vec<IbcEventWithHeight>, Error> =
+            build_and_send_transfer_messages(&chains.src, &hop_chains[0], &opts)
+                .map_err(Error::transfer);
+
+        match res {
+            Ok(ev) => Output::success(ev).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
 impl Runnable for TxIcs20MsgTransferCmd {
     fn run(&self) {
         let config = app_config();
@@ -189,6 +265,10 @@ impl Runnable for TxIcs20MsgTransferCmd {
         let chains = ChainHandlePair::spawn(&config, &self.src_chain_id, &self.dst_chain_id)
             .unwrap_or_else(exit_with_unrecoverable_error);
 
+        if !self.hops.is_empty() {
+            self.forward(&config, &chains, opts)
+        }
+
         check_can_send_on_channel(
             &chains.src,
             &opts.src_channel_id,
@@ -237,6 +317,9 @@ mod tests {
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                hops: vec![],
+                hop_timeout: "10m".parse().unwrap(),
+                hop_retries: 1,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -270,6 +353,9 @@ mod tests {
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                hops: vec![],
+                hop_timeout: "10m".parse().unwrap(),
+                hop_retries: 1,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -303,6 +389,9 @@ mod tests {
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                hops: vec![],
+                hop_timeout: "10m".parse().unwrap(),
+                hop_retries: 1,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -338,6 +427,9 @@ mod tests {
                 number_msgs: None,
                 key_name: Some("key_name".to_owned()),
                 memo: None,
+                hops: vec![],
+                hop_timeout: "10m".parse().unwrap(),
+                hop_retries: 1,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -373,6 +465,9 @@ mod tests {
                 number_msgs: Some(21),
                 key_name: None,
                 memo: None,
+                hops: vec![],
+                hop_timeout: "10m".parse().unwrap(),
+                hop_retries: 1,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -408,6 +503,9 @@ mod tests {
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                hops: vec![],
+                hop_timeout: "10m".parse().unwrap(),
+                hop_retries: 1,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -443,6 +541,9 @@ mod tests {
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                hops: vec![],
+                hop_timeout: "10m".parse().unwrap(),
+                hop_retries: 1,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -478,6 +579,9 @@ mod tests {
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                hops: vec![],
+                hop_timeout: "10m".parse().unwrap(),
+                hop_retries: 1,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -513,6 +617,9 @@ mod tests {
                 number_msgs: None,
                 key_name: None,
                 memo: Some("test memo".to_owned()),
+                hops: vec![],
+                hop_timeout: "10m".parse().unwrap(),
+                hop_retries: 1,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -532,6 +639,83 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_ft_transfer_hops() {
+        let cmd = TxIcs20MsgTransferCmd::parse_from([
+            "test",
+            "--dst-chain",
+            "chain_receiver",
+            "--src-chain",
+            "chain_sender",
+            "--src-port",
+            "port_sender",
+            "--src-channel",
+            "channel_sender",
+            "--amount",
+            "42",
+            "--hop",
+            "hub:transfer/channel-1",
+            "--hop",
+            "dex:transfer/channel-2",
+            "--hop-timeout",
+            "5m",
+            "--hop-retries",
+            "3",
+        ]);
+
+        assert_eq!(
+            cmd.hops,
+            vec![
+                "hub:transfer/channel-1".parse().unwrap(),
+                "dex:transfer/channel-2".parse().unwrap()
+            ]
+        );
+        assert_eq!(*cmd.hop_timeout, core::time::Duration::from_secs(300));
+        assert_eq!(cmd.hop_retries, 3);
+    }
+
+    #[test]
+    fn test_ft_transfer_hops_conflict_with_memo() {
+        assert!(TxIcs20MsgTransferCmd::try_parse_from([
+            "test",
+            "--dst-chain",
+            "chain_receiver",
+            "--src-chain",
+            "chain_sender",
+            "--src-port",
+            "port_sender",
+            "--src-channel",
+            "channel_sender",
+            "--amount",
+            "42",
+            "--hop",
+            "hub:transfer/channel-1",
+            "--memo",
+            "test memo"
+        ])
+        .is_err())
+    }
+
+    #[test]
+    fn test_ft_transfer_invalid_hop() {
+        assert!(TxIcs20MsgTransferCmd::try_parse_from([
+            "test",
+            "--dst-chain",
+            "chain_receiver",
+            "--src-chain",
+            "chain_sender",
+            "--src-port",
+            "port_sender",
+            "--src-channel",
+            "channel_sender",
+            "--amount",
+            "42",
+            "--hop",
+            "hub/channel-1"
+        ])
+        .is_err())
+    }
+
     #[test]
     fn test_ft_transfer_no_amount() {
         assert!(TxIcs20MsgTransferCmd::try_parse_from([
@@ -0,0 +1,967 @@
+use core::fmt;
+use core::time::Duration;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Instant;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use eyre::eyre;
+use serde::Serialize;
+use tracing::{info, warn};
+
+use ibc_relayer::{
+    chain::handle::{BaseChainHandle, ChainHandle},
+    config::Config,
+    transfer::{build_transfer_message, send_messages, TransferError, TransferTimeout},
+};
+use ibc_relayer_types::{
+    applications::transfer::Amount,
+    core::{
+        ics04_channel::packet::Sequence,
+        ics24_host::identifier::{ChainId, ChannelId, PortId},
+    },
+    events::IbcEvent,
+    signer::Signer,
+};
+
+use crate::cli_utils::{check_can_send_on_channel, spawn_chain_runtime_generic};
+use crate::conclude::{exit_with_unrecoverable_error, json, Output};
+use crate::prelude::*;
+
+/// How often the event listeners check whether the benchmark is over,
+/// and how often the settling phase checks whether all packets were acknowledged
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The data structure that represents the arguments when invoking the `tx ft-transfer-bench` CLI command.
+///
+/// `tx ft-transfer-bench [OPTIONS] --dst-chain <DST_CHAIN_ID> --src-chain <SRC_CHAIN_ID> --src-channel <SRC_CHANNEL_ID>... --amount <AMOUNT>`
+///
+/// Sends ICS-20 transfers at the given rate for the given duration, spread across
+/// the given channels and sender keys, while the packets are relayed by a running
+/// relayer. The end-to-end relay latency of every packet is then measured from the
+/// events emitted when the packet is received and when it is acknowledged.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxIcs20TransferBenchCmd {
+    #[clap(
+        long = "dst-chain",
+        required = true,
+        value_name = "DST_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination chain"
+    )]
+    dst_chain_id: ChainId,
+
+    #[clap(
+        long = "src-chain",
+        required = true,
+        value_name = "SRC_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source chain"
+    )]
+    src_chain_id: ChainId,
+
+    #[clap(
+        long = "src-port",
+        value_name = "SRC_PORT_ID",
+        default_value = "transfer",
+        help = "Identifier of the source port"
+    )]
+    src_port_id: PortId,
+
+    #[clap(
+        long = "src-channel",
+        visible_alias = "src-chan",
+        required = true,
+        multiple_values = true,
+        value_name = "SRC_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifiers of the source channels to send the transfers on, in turn"
+    )]
+    src_channel_ids: Vec<ChannelId>,
+
+    #[clap(
+        long = "amount",
+        required = true,
+        value_name = "AMOUNT",
+        help_heading = "REQUIRED",
+        help = "Amount of coins (samoleans, by default) to send in each transfer (e.g. `100`)"
+    )]
+    amount: Amount,
+
+    #[clap(
+        long = "denom",
+        value_name = "DENOM",
+        help = "Denomination of the coins to send",
+        default_value = "samoleans"
+    )]
+    denom: String,
+
+    #[clap(
+        long = "receiver",
+        value_name = "RECEIVER",
+        help = "The account address on the destination chain which will receive the tokens. If omitted, the relayer's wallet on the destination chain will be used"
+    )]
+    receiver: Option<String>,
+
+    #[clap(
+        long = "key-name",
+        multiple_values = true,
+        value_name = "KEY_NAME",
+        help = "Names of the keys sending the transfers, in turn (default: the primary `key_name` of the config)"
+    )]
+    key_names: Vec<String>,
+
+    #[clap(
+        long = "rate",
+        value_name = "RATE",
+        default_value = "1",
+        help = "Number of transfers to send per second"
+    )]
+    rate: u64,
+
+    #[clap(
+        long = "duration",
+        value_name = "DURATION",
+        default_value = "1m",
+        help = "How long to send transfers for (e.g. `30s`, `5m`)"
+    )]
+    duration: humantime::Duration,
+
+    #[clap(
+        long = "settle-timeout",
+        value_name = "SETTLE_TIMEOUT",
+        default_value = "2m",
+        help = "How long to wait for the packets to be acknowledged once all transfers were sent"
+    )]
+    settle_timeout: humantime::Duration,
+
+    #[clap(
+        long = "timeout-height-offset",
+        default_value = "0",
+        value_name = "TIMEOUT_HEIGHT_OFFSET",
+        help = "Timeout in number of blocks since current"
+    )]
+    timeout_height_offset: u64,
+
+    #[clap(
+        long = "timeout-seconds",
+        default_value = "0",
+        value_name = "TIMEOUT_SECONDS",
+        help = "Timeout in seconds since current"
+    )]
+    timeout_seconds: u64,
+
+    #[clap(
+        long = "packets-file",
+        value_name = "PACKETS_FILE",
+        help = "Write the send height and latencies of every packet, as JSON, to the given file"
+    )]
+    packets_file: Option<PathBuf>,
+}
+
+impl TxIcs20TransferBenchCmd {
+    fn validate_options(&self, config: &Config) -> eyre::Result<BenchPlan> {
+        let src_chain_config = config.find_chain(&self.src_chain_id).ok_or_else(|| {
+            eyre!(
+                "missing configuration for source chain '{}'",
+                self.src_chain_id
+            )
+        })?;
+
+        config.find_chain(&self.dst_chain_id).ok_or_else(|| {
+            eyre!(
+                "missing configuration for destination chain '{}'",
+                self.dst_chain_id
+            )
+        })?;
+
+        if self.rate == 0 {
+            return Err(eyre!("rate should be greater than zero"));
+        }
+
+        let duration = *self.duration;
+        let total =
+            u64::try_from(u128::from(self.rate) * duration.as_millis() / 1000).map_err(|_| {
+                eyre!(
+                    "too many transfers to send in {} at {} transfers per second",
+                    self.duration,
+                    self.rate
+                )
+            })?;
+        if total == 0 {
+            return Err(eyre!(
+                "no transfer would be sent in {} at {} transfers per second",
+                self.duration,
+                self.rate
+            ));
+        }
+
+        let key_names = if self.key_names.is_empty() {
+            vec![src_chain_config.key_name.primary().to_string()]
+        } else {
+            self.key_names.clone()
+        };
+
+        Ok(BenchPlan {
+            start: Instant::now(),
+            rate: self.rate,
+            total,
+            senders: key_names.len() as u64,
+            key_names,
+            channels: self.src_channel_ids.clone(),
+        })
+    }
+
+    /// Send the transfers due to the sender with the given index, batching
+    /// all the transfers which became due while the previous batch was sent.
+    fn run_sender(
+        &self,
+        plan: &BenchPlan,
+        index: u64,
+        src_chain: &BaseChainHandle,
+        dst_chain: &BaseChainHandle,
+        state: &Mutex<BenchState>,
+    ) {
+        let key_name = &plan.key_names[index as usize];
+        let mut next = index;
+
+        while next < plan.total {
+            let due = plan.due(next);
+            let now = Instant::now();
+            if due > now {
+                thread::sleep(due - now);
+            }
+
+            let mut batch = Vec::new();
+            while next < plan.total && plan.due(next) <= Instant::now() {
+                batch.push(plan.channel(next).clone());
+                next += plan.senders;
+            }
+
+            let count = batch.len() as u64;
+
+            match self.send_batch(src_chain, dst_chain, batch) {
+                Ok(sent) => {
+                    let mut state = state.lock().unwrap();
+                    state.txs += 1;
+                    state.failed += count.saturating_sub(sent.len() as u64);
+
+                    let sent_at = Instant::now();
+                    for (key, height) in sent {
+                        state.sent.insert(
+                            key,
+                            SentPacket {
+                                sender: key_name.clone(),
+                                height,
+                                at: sent_at,
+                            },
+                        );
+                    }
+                }
+                Err(e) => {
+                    warn!(
+                        "failed to send {} transfers with key '{}': {}",
+                        count, key_name, e
+                    );
+                    state.lock().unwrap().failed += count;
+                }
+            }
+        }
+    }
+
+    /// Send one transfer on each of the given channels, in a single transaction,
+    /// and return the packets which were sent along with their send heights.
+    fn send_batch(
+        &self,
+        src_chain: &BaseChainHandle,
+        dst_chain: &BaseChainHandle,
+        channels: Vec<ChannelId>,
+    ) -> Result<Vec<(PacketKey, u64)>, TransferError> {
+        let receiver = match &self.receiver {
+            Some(receiver) => receiver.parse().map_err(TransferError::receiver_address)?,
+            None => dst_chain.get_signer().map_err(TransferError::key)?,
+        };
+        let sender: Signer = src_chain.get_signer().map_err(TransferError::key)?;
+
+        let destination_chain_status = dst_chain
+            .query_application_status()
+            .map_err(TransferError::relayer)?;
+
+        let timeout = TransferTimeout::new(
+            self.timeout_height_offset,
+            Duration::from_secs(self.timeout_seconds),
+            &destination_chain_status,
+        )?;
+
+        let msgs = channels
+            .into_iter()
+            .map(|channel_id| {
+                build_transfer_message(
+                    self.src_port_id.clone(),
+                    channel_id,
+                    self.amount,
+                    self.denom.clone(),
+                    sender.clone(),
+                    receiver.clone(),
+                    timeout.timeout_height,
+                    timeout.timeout_timestamp,
+                    None,
+                )
+            })
+            .collect();
+
+        let events = send_messages(src_chain, msgs)?;
+
+        let sent = events
+            .into_iter()
+            .filter_map(|event| match event.event {
+                IbcEvent::SendPacket(ev) => Some((
+                    (ev.packet.source_channel, ev.packet.sequence),
+                    event.height.revision_height(),
+                )),
+                _ => None,
+            })
+            .collect();
+
+        Ok(sent)
+    }
+
+    /// Record when the packets sent on the benchmarked channels are received,
+    /// acknowledged or timed out, as reported by the events of the given chain,
+    /// until told to stop.
+    fn listen<Chain: ChainHandle>(
+        &self,
+        chain: &Chain,
+        state: &Mutex<BenchState>,
+        stop: &AtomicBool,
+    ) {
+        let subscription = match chain.subscribe() {
+            Ok(subscription) => subscription,
+            Err(e) => {
+                warn!(
+                    "failed to subscribe to the events of chain {}: {}",
+                    chain.id(),
+                    e
+                );
+                return;
+            }
+        };
+
+        while !stop.load(Ordering::SeqCst) {
+            let batch = match subscription.recv_timeout(POLL_INTERVAL) {
+                Ok(batch) => batch,
+                Err(_) => continue,
+            };
+
+            let batch = match batch.as_ref() {
+                Ok(batch) => batch,
+                Err(e) => {
+                    warn!("error in the events of chain {}: {}", chain.id(), e);
+                    continue;
+                }
+            };
+
+            let at = Instant::now();
+            let mut state = state.lock().unwrap();
+
+            for event in &batch.events {
+                let (outcome, packet) = match &event.event {
+                    IbcEvent::WriteAcknowledgement(ev) => (&mut state.received, &ev.packet),
+                    IbcEvent::AcknowledgePacket(ev) => (&mut state.acknowledged, &ev.packet),
+                    IbcEvent::TimeoutPacket(ev) => (&mut state.timed_out, &ev.packet),
+                    _ => continue,
+                };
+
+                if packet.source_port != self.src_port_id
+                    || !self.src_channel_ids.contains(&packet.source_channel)
+                {
+                    continue;
+                }
+
+                outcome.insert(
+                    (packet.source_channel.clone(), packet.sequence),
+                    Observed {
+                        height: event.height.revision_height(),
+                        at,
+                    },
+                );
+            }
+        }
+    }
+}
+
+impl Runnable for TxIcs20TransferBenchCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let plan = match self.validate_options(&config) {
+            Err(err) => Output::error(err).exit(),
+            Ok(result) => result,
+        };
+
+        // Spawn one runtime per sender key, so that each key signs its own transfers
+        let src_chains = plan
+            .key_names
+            .iter()
+            .map(|key_name| {
+                let mut config = (*config).clone();
+                if let Some(chain_config) = config.find_chain_mut(&self.src_chain_id) {
+                    chain_config.key_name = key_name.as_str().into();
+                }
+
+                spawn_chain_runtime_generic::<BaseChainHandle>(&config, &self.src_chain_id)
+                    .unwrap_or_else(exit_with_unrecoverable_error)
+            })
+            .collect::<Vec<_>>();
+
+        let dst_chain = spawn_chain_runtime_generic::<BaseChainHandle>(&config, &self.dst_chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        for channel_id in &self.src_channel_ids {
+            check_can_send_on_channel(
+                &src_chains[0],
+                channel_id,
+                &self.src_port_id,
+                &dst_chain.id(),
+            )
+            .unwrap_or_else(exit_with_unrecoverable_error);
+        }
+
+        info!(
+            "sending {} transfers over {} from {} keys on {} channels",
+            plan.total,
+            self.duration,
+            plan.senders,
+            plan.channels.len()
+        );
+
+        let state = Mutex::new(BenchState::default());
+        let stop = AtomicBool::new(false);
+
+        // The schedule starts once the chain runtimes are up and the channels checked
+        let plan = BenchPlan {
+            start: Instant::now(),
+            ..plan
+        };
+
+        let send_duration = thread::scope(|s| {
+            s.spawn(|| self.listen(&src_chains[0], &state, &stop));
+            s.spawn(|| self.listen(&dst_chain, &state, &stop));
+
+            let senders = src_chains
+                .iter()
+                .enumerate()
+                .map(|(index, src_chain)| {
+                    let (plan, state, dst_chain) = (&plan, &state, &dst_chain);
+                    s.spawn(move || {
+                        self.run_sender(plan, index as u64, src_chain, dst_chain, state)
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            for sender in senders {
+                let _ = sender.join();
+            }
+
+            let send_duration = plan.start.elapsed();
+
+            info!("all transfers sent, waiting for the packets to be acknowledged");
+
+            let settle_start = Instant::now();
+            while !state.lock().unwrap().is_settled()
+                && settle_start.elapsed() < *self.settle_timeout
+            {
+                thread::sleep(POLL_INTERVAL);
+            }
+
+            stop.store(true, Ordering::SeqCst);
+
+            send_duration
+        });
+
+        let state = state.lock().unwrap();
+
+        if let Some(file) = &self.packets_file {
+            let written = serde_json::to_string_pretty(&state.packet_records())
+                .map_err(|e| eyre!("failed to encode the packet records: {}", e))
+                .and_then(|records| {
+                    std::fs::write(file, records)
+                        .map_err(|e| eyre!("failed to write file {}: {}", file.display(), e))
+                });
+
+            if let Err(e) = written {
+                warn!("{}", e);
+            }
+        }
+
+        let report = state.report(send_duration, plan.senders, plan.channels.len());
+
+        if json() {
+            Output::success(report).exit()
+        }
+
+        Output::success_msg(report).exit()
+    }
+}
+
+/// The schedule of the transfers of a benchmark: the transfer with index `i`
+/// is due `i / rate` seconds after the start, and is sent by sender `i % senders`
+/// on the channels in turn.
+struct BenchPlan {
+    start: Instant,
+    rate: u64,
+    total: u64,
+    senders: u64,
+    key_names: Vec<String>,
+    channels: Vec<ChannelId>,
+}
+
+impl BenchPlan {
+    fn due(&self, index: u64) -> Instant {
+        let nanos = u128::from(index) * 1_000_000_000 / u128::from(self.rate);
+        self.start + Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+    }
+
+    fn channel(&self, index: u64) -> &ChannelId {
+        let round = index / self.senders;
+        &self.channels[(round % self.channels.len() as u64) as usize]
+    }
+}
+
+type PacketKey = (ChannelId, Sequence);
+
+struct SentPacket {
+    sender: String,
+    height: u64,
+    at: Instant,
+}
+
+struct Observed {
+    height: u64,
+    at: Instant,
+}
+
+/// What was observed about the packets of a benchmark
+#[derive(Default)]
+struct BenchState {
+    txs: u64,
+    failed: u64,
+    sent: HashMap<PacketKey, SentPacket>,
+    received: HashMap<PacketKey, Observed>,
+    acknowledged: HashMap<PacketKey, Observed>,
+    timed_out: HashMap<PacketKey, Observed>,
+}
+
+impl BenchState {
+    /// Whether all the packets which were sent are either acknowledged or timed out
+    fn is_settled(&self) -> bool {
+        self.sent
+            .keys()
+            .all(|key| self.acknowledged.contains_key(key) || self.timed_out.contains_key(key))
+    }
+
+    fn packet_records(&self) -> Vec<PacketRecord> {
+        let mut records = self
+            .sent
+            .iter()
+            .map(|(key, sent)| {
+                let received = self.received.get(key);
+                let acknowledged = self.acknowledged.get(key);
+
+                PacketRecord {
+                    channel_id: key.0.clone(),
+                    sequence: key.1,
+                    sender: sent.sender.clone(),
+                    send_height: sent.height,
+                    recv_height: received.map(|r| r.height),
+                    ack_height: acknowledged.map(|a| a.height),
+                    recv_latency_ms: received.map(|r| latency_ms(sent, r)),
+                    ack_latency_ms: acknowledged.map(|a| latency_ms(sent, a)),
+                    timed_out: self.timed_out.contains_key(key),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        records.sort_by(|a, b| (&a.channel_id, a.sequence).cmp(&(&b.channel_id, b.sequence)));
+        records
+    }
+
+    fn report(&self, send_duration: Duration, senders: u64, channels: usize) -> BenchReport {
+        let records = self.packet_records();
+
+        let sent = records.len() as u64;
+        let count = |f: fn(&PacketRecord) -> bool| records.iter().filter(|r| f(r)).count() as u64;
+
+        let acknowledged = count(|r| r.ack_height.is_some());
+        let timed_out = count(|r| r.timed_out);
+
+        BenchReport {
+            senders,
+            channels,
+            send_duration_secs: send_duration.as_secs_f64(),
+            txs: self.txs,
+            transfers_sent: sent,
+            transfers_failed: self.failed,
+            send_rate: sent as f64 / send_duration.as_secs_f64().max(f64::EPSILON),
+            received: count(|r| r.recv_height.is_some()),
+            acknowledged,
+            timed_out,
+            pending: sent - acknowledged - timed_out,
+            recv_latency_ms: Stats::new(records.iter().filter_map(|r| r.recv_latency_ms)),
+            ack_latency_ms: Stats::new(records.iter().filter_map(|r| r.ack_latency_ms)),
+            ack_latency_blocks: Stats::new(
+                records
+                    .iter()
+                    .filter_map(|r| r.ack_height.map(|h| h.saturating_sub(r.send_height))),
+            ),
+        }
+    }
+}
+
+fn latency_ms(sent: &SentPacket, observed: &Observed) -> u64 {
+    observed.at.saturating_duration_since(sent.at).as_millis() as u64
+}
+
+/// The send height and relay latencies of a packet of a benchmark
+#[derive(Debug, Serialize)]
+struct PacketRecord {
+    channel_id: ChannelId,
+    sequence: Sequence,
+    sender: String,
+    send_height: u64,
+    recv_height: Option<u64>,
+    ack_height: Option<u64>,
+    recv_latency_ms: Option<u64>,
+    ack_latency_ms: Option<u64>,
+    timed_out: bool,
+}
+
+/// The summary of a benchmark
+#[derive(Debug, Serialize)]
+struct BenchReport {
+    senders: u64,
+    channels: usize,
+    send_duration_secs: f64,
+    txs: u64,
+    transfers_sent: u64,
+    transfers_failed: u64,
+    send_rate: f64,
+    received: u64,
+    acknowledged: u64,
+    timed_out: u64,
+    pending: u64,
+    /// From the commit of a transfer to the packet being received on the destination chain
+    recv_latency_ms: Option<Stats>,
+    /// From the commit of a transfer to the packet being acknowledged on the source chain
+    ack_latency_ms: Option<Stats>,
+    /// Number of blocks of the source chain between a transfer and its acknowledgement
+    ack_latency_blocks: Option<Stats>,
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Sent {} transfers in {} txs over {:.1}s ({:.2} transfers/s) from {} keys on {} channels, {} failed to be sent",
+            self.transfers_sent,
+            self.txs,
+            self.send_duration_secs,
+            self.send_rate,
+            self.senders,
+            self.channels,
+            self.transfers_failed
+        )?;
+        writeln!(
+            f,
+            "Received: {}, acknowledged: {}, timed out: {}, pending: {}",
+            self.received, self.acknowledged, self.timed_out, self.pending
+        )?;
+
+        let stats = [
+            ("Recv latency (ms)", &self.recv_latency_ms),
+            ("Ack latency (ms)", &self.ack_latency_ms),
+            ("Ack latency (blocks)", &self.ack_latency_blocks),
+        ];
+
+        for (name, stats) in stats {
+            match stats {
+                Some(stats) => writeln!(f, "{name}: {stats}")?,
+                None => writeln!(f, "{name}: n/a")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Summary statistics of a set of measurements
+#[derive(Debug, PartialEq, Serialize)]
+struct Stats {
+    min: u64,
+    mean: f64,
+    p50: u64,
+    p90: u64,
+    p99: u64,
+    max: u64,
+}
+
+impl Stats {
+    fn new(values: impl Iterator<Item = u64>) -> Option<Self> {
+        let mut values = values.collect::<Vec<_>>();
+        if values.is_empty() {
+            return None;
+        }
+
+        values.sort_unstable();
+
+        // Nearest-rank percentile
+        let percentile = |p: usize| values[((values.len() * p + 99) / 100).max(1) - 1];
+
+        Some(Self {
+            min: values[0],
+            mean: values.iter().sum::<u64>() as f64 / values.len() as f64,
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: values[values.len() - 1],
+        })
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {}, mean {:.1}, p50 {}, p90 {}, p99 {}, max {}",
+            self.min, self.mean, self.p50, self.p90, self.p99, self.max
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ft_transfer_bench_required_only() {
+        let cmd = TxIcs20TransferBenchCmd::parse_from([
+            "test",
+            "--dst-chain",
+            "chain_receiver",
+            "--src-chain",
+            "chain_sender",
+            "--src-channel",
+            "channel-0",
+            "channel-1",
+            "--amount",
+            "42",
+        ]);
+
+        assert_eq!(
+            cmd,
+            TxIcs20TransferBenchCmd {
+                dst_chain_id: ChainId::from_string("chain_receiver"),
+                src_chain_id: ChainId::from_string("chain_sender"),
+                src_port_id: PortId::transfer(),
+                src_channel_ids: vec![ChannelId::new(0), ChannelId::new(1)],
+                amount: Amount::from(42u64),
+                denom: "samoleans".to_owned(),
+                receiver: None,
+                key_names: vec![],
+                rate: 1,
+                duration: "1m".parse().unwrap(),
+                settle_timeout: "2m".parse().unwrap(),
+                timeout_height_offset: 0,
+                timeout_seconds: 0,
+                packets_file: None,
+            }
+        )
+    }
+
+    #[test]
+    fn test_ft_transfer_bench_senders_and_rate() {
+        let cmd = TxIcs20TransferBenchCmd::parse_from([
+            "test",
+            "--dst-chain",
+            "chain_receiver",
+            "--src-chain",
+            "chain_sender",
+            "--src-chan",
+            "channel-0",
+            "--amount",
+            "42",
+            "--key-name",
+            "wallet-0",
+            "wallet-1",
+            "--rate",
+            "20",
+            "--duration",
+            "30s",
+            "--packets-file",
+            "packets.json",
+        ]);
+
+        assert_eq!(cmd.key_names, vec!["wallet-0", "wallet-1"]);
+        assert_eq!(cmd.rate, 20);
+        assert_eq!(*cmd.duration, Duration::from_secs(30));
+        assert_eq!(cmd.packets_file, Some(PathBuf::from("packets.json")));
+    }
+
+    #[test]
+    fn test_ft_transfer_bench_no_channel() {
+        assert!(TxIcs20TransferBenchCmd::try_parse_from([
+            "test",
+            "--dst-chain",
+            "chain_receiver",
+            "--src-chain",
+            "chain_sender",
+            "--amount",
+            "42"
+        ])
+        .is_err())
+    }
+
+    #[test]
+    fn plan_spreads_transfers_across_senders_and_channels() {
+        let plan = BenchPlan {
+            start: Instant::now(),
+            rate: 10,
+            total: 8,
+            senders: 2,
+            key_names: vec!["wallet-0".to_string(), "wallet-1".to_string()],
+            channels: vec![ChannelId::new(0), ChannelId::new(1)],
+        };
+
+        // Sender 0 sends transfers 0, 2, 4, 6 and sender 1 transfers 1, 3, 5, 7,
+        // each of them on both channels in turn
+        let channels = (0..plan.total)
+            .map(|i| plan.channel(i).clone())
+            .collect::<Vec<_>>();
+        let (channel_0, channel_1) = (ChannelId::new(0), ChannelId::new(1));
+        assert_eq!(
+            channels,
+            vec![
+                channel_0.clone(),
+                channel_0.clone(),
+                channel_1.clone(),
+                channel_1.clone(),
+                channel_0.clone(),
+                channel_0,
+                channel_1.clone(),
+                channel_1,
+            ]
+        );
+
+        assert_eq!(plan.due(3) - plan.start, Duration::from_millis(300));
+    }
+
+    #[test]
+    fn plan_schedules_transfers_without_truncation() {
+        let plan = |rate| BenchPlan {
+            start: Instant::now(),
+            rate,
+            total: u64::MAX,
+            senders: 1,
+            key_names: vec!["wallet-0".to_string()],
+            channels: vec![ChannelId::new(0)],
+        };
+
+        // Beyond `u32::MAX` transfers per second and transfers
+        let plan_high = plan(5_000_000_000);
+        assert_eq!(
+            plan_high.due(5_000_000_000) - plan_high.start,
+            Duration::from_secs(1)
+        );
+
+        let plan_low = plan(3);
+        assert_eq!(
+            plan_low.due(1) - plan_low.start,
+            Duration::from_nanos(333_333_333)
+        );
+        assert_eq!(
+            plan_low.due(5_000_000_000) - plan_low.start,
+            Duration::from_nanos(1_666_666_666_666_666_666)
+        );
+    }
+
+    #[test]
+    fn stats_use_nearest_rank_percentiles() {
+        assert_eq!(Stats::new(std::iter::empty()), None);
+
+        let stats = Stats::new((1..=100).rev()).unwrap();
+        assert_eq!(
+            stats,
+            Stats {
+                min: 1,
+                mean: 50.5,
+                p50: 50,
+                p90: 90,
+                p99: 99,
+                max: 100,
+            }
+        );
+
+        let stats = Stats::new([7].into_iter()).unwrap();
+        assert_eq!((stats.p50, stats.p99), (7, 7));
+    }
+
+    #[test]
+    fn report_joins_observations_with_sent_packets() {
+        let start = Instant::now();
+        let key = |seq: u64| (ChannelId::new(0), Sequence::from(seq));
+        let observed = |height: u64, ms: u64| Observed {
+            height,
+            at: start + Duration::from_millis(ms),
+        };
+
+        let mut state = BenchState {
+            txs: 2,
+            failed: 1,
+            ..BenchState::default()
+        };
+
+        for seq in 1..=3 {
+            state.sent.insert(
+                key(seq),
+                SentPacket {
+                    sender: "wallet".to_string(),
+                    height: 10,
+                    at: start,
+                },
+            );
+        }
+
+        state.received.insert(key(1), observed(20, 1000));
+        state.acknowledged.insert(key(1), observed(13, 3000));
+        state.timed_out.insert(key(2), observed(15, 5000));
+
+        // Packets sent by others on the same channel are ignored
+        state.acknowledged.insert(key(42), observed(13, 3000));
+
+        assert!(!state.is_settled());
+
+        let report = state.report(Duration::from_secs(2), 1, 1);
+        assert_eq!(report.transfers_sent, 3);
+        assert_eq!(report.transfers_failed, 1);
+        assert_eq!(report.received, 1);
+        assert_eq!(report.acknowledged, 1);
+        assert_eq!(report.timed_out, 1);
+        assert_eq!(report.pending, 1);
+        assert_eq!(report.recv_latency_ms.unwrap().max, 1000);
+        assert_eq!(report.ack_latency_ms.unwrap().max, 3000);
+        assert_eq!(report.ack_latency_blocks.unwrap().max, 3);
+
+        state.acknowledged.insert(key(3), observed(14, 4000));
+        assert!(state.is_settled());
+
+        let records = state.packet_records();
+        assert_eq!(
+            records.iter().map(|r| r.sequence).collect::<Vec<_>>(),
+            vec![Sequence::from(1), Sequence::from(2), Sequence::from(3)]
+        );
+    }
+}
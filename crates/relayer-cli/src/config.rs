@@ -8,7 +8,7 @@ use alloc::collections::BTreeSet;
 use std::path::PathBuf;
 
 use flex_error::{define_error, TraceError};
-use ibc_relayer::config::{ChainConfig, Config, ModeConfig};
+use ibc_relayer::config::{ChainConfig, Config, ModeConfig, RetryPolicy, ShardingConfig};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use tendermint_light_client_verifier::types::TrustThreshold;
 use tracing_subscriber::filter::ParseError;
@@ -60,6 +60,27 @@ define_error! {
                     e.threshold, e.chain_id, e.reason)
             },
 
+        DuplicateKeyNames
+            { chain_id: ChainId, key_name: String }
+            |e| {
+                format!("config file lists the key '{0}' more than once in `key_name` for the chain '{1}'",
+                    e.key_name, e.chain_id)
+            },
+
+        InvalidRetryJitter
+            { section: String, jitter: f64 }
+            |e| {
+                format!("config file specifies an invalid `jitter` ({0}) in the `{1}` retry policy, it must be between 0 and 1",
+                    e.jitter, e.section)
+            },
+
+        InvalidSharding
+            { reason: String, }
+            |e| {
+                format!("config file specifies invalid sharding config, caused by: {0}",
+                    e.reason)
+            },
+
         DeprecatedGasAdjustment
             {
                 gas_adjustment: f64,
@@ -96,11 +117,98 @@ pub fn validate_config(config: &Config) -> Result<(), Diagnostic<Error>> {
 
         // Validate gas-related settings
         validate_gas_settings(&c.id, c)?;
+
+        // Check that each signing key is listed only once
+        validate_key_names(c)?;
+
+        // Check the retry policies overriding the global ones
+        let retry = &c.retry;
+        validate_retry_policies(
+            &format!("chains.{}.retry", c.id),
+            [
+                retry.client.as_ref(),
+                retry.connection.as_ref(),
+                retry.channel.as_ref(),
+                retry.packet.as_ref(),
+                retry.tx.as_ref(),
+            ],
+        )?;
     }
 
+    // Check the global retry policies
+    let retry = &config.global.retry;
+    validate_retry_policies(
+        "global.retry",
+        [
+            Some(&retry.client),
+            Some(&retry.connection),
+            retry.channel.as_ref(),
+            Some(&retry.packet),
+            Some(&retry.tx),
+        ],
+    )?;
+
     // Check for invalid mode config
     validate_mode(&config.mode)?;
 
+    // Check for invalid sharding config
+    validate_sharding(&config.sharding)?;
+
+    Ok(())
+}
+
+fn validate_key_names(config: &ChainConfig) -> Result<(), Diagnostic<Error>> {
+    let mut unique_key_names = BTreeSet::new();
+    for key_name in config.key_name.iter() {
+        if !unique_key_names.insert(key_name) {
+            return Err(Diagnostic::Error(Error::duplicate_key_names(
+                config.id.clone(),
+                key_name.to_string(),
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that the jitter of the given client, connection, channel, packet and tx
+/// retry policies, if set, is a number between 0 and 1.
+fn validate_retry_policies(
+    section: &str,
+    policies: [Option<&RetryPolicy>; 5],
+) -> Result<(), Diagnostic<Error>> {
+    let kinds = ["client", "connection", "channel", "packet", "tx"];
+
+    for (kind, policy) in kinds.iter().zip(policies) {
+        let Some(policy) = policy else {
+            continue;
+        };
+
+        if !(0.0..=1.0).contains(&policy.jitter) {
+            return Err(Diagnostic::Error(Error::invalid_retry_jitter(
+                format!("{section}.{kind}"),
+                policy.jitter,
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_sharding(sharding: &ShardingConfig) -> Result<(), Diagnostic<Error>> {
+    if sharding.total_instances == 0 {
+        return Err(Diagnostic::Error(Error::invalid_sharding(
+            "`total_instances` must be at least 1".to_string(),
+        )));
+    }
+
+    if sharding.instance >= sharding.total_instances {
+        return Err(Diagnostic::Error(Error::invalid_sharding(format!(
+            "`instance` must be strictly less than `total_instances` ({}), found {}",
+            sharding.total_instances, sharding.instance
+        ))));
+    }
+
     Ok(())
 }
 
@@ -16,6 +16,7 @@ use ibc_relayer::error::Error as RelayerError;
 use ibc_relayer::foreign_client::ForeignClientError;
 use ibc_relayer::keyring::errors::Error as KeyRingError;
 use ibc_relayer::link::error::LinkError;
+use ibc_relayer::recover_client::RecoverClientError;
 use ibc_relayer::spawn::SpawnError;
 use ibc_relayer::supervisor::Error as SupervisorError;
 use ibc_relayer::transfer::TransferError;
@@ -112,6 +113,10 @@ define_error! {
             [ UpgradeChainError ]
             |_| { "upgrade chain error" },
 
+        RecoverClient
+            [ RecoverClientError ]
+            |_| { "recover client error" },
+
         Signer
             [ SignerError ]
             |_| { "signer error" },
@@ -4,7 +4,11 @@ use tracing::error;
 
 use crossbeam_channel as channel;
 
-use ibc_relayer::supervisor::dump_state::SupervisorState;
+use ibc_relayer::supervisor::{
+    clear_packets::{ClearPacketsOptions, ClearedPackets},
+    dump_state::SupervisorState,
+    rotate_key::{KeyRotation, RotateKeyOptions},
+};
 use ibc_relayer::{
     config::ChainConfig,
     rest::{
@@ -64,6 +68,30 @@ pub fn supervisor_state(
     submit_request(sender, |reply_to| Request::State { reply_to })
 }
 
+pub fn clear_packets(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+    options: ClearPacketsOptions,
+) -> Result<ClearedPackets, RestApiError> {
+    submit_request(sender, |reply_to| Request::ClearPackets {
+        chain_id: ChainId::from_string(chain_id),
+        options,
+        reply_to,
+    })
+}
+
+pub fn rotate_key(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+    options: RotateKeyOptions,
+) -> Result<KeyRotation, RestApiError> {
+    submit_request(sender, |reply_to| Request::RotateKey {
+        chain_id: ChainId::from_string(chain_id),
+        options,
+        reply_to,
+    })
+}
+
 pub fn assemble_version_info(sender: &channel::Sender<Request>) -> Vec<VersionInfo> {
     // Fetch the relayer library version
     let lib_version = submit_request(sender, |reply_to| Request::Version { reply_to })
@@ -3,17 +3,28 @@ use std::{
     net::{SocketAddr, ToSocketAddrs},
 };
 
-use axum::{extract::Path, response::IntoResponse, routing::get, Extension, Json, Router, Server};
+use axum::{
+    extract::Path,
+    response::IntoResponse,
+    routing::{get, post},
+    Extension, Json, Router, Server,
+};
 use crossbeam_channel as channel;
 use serde::{Deserialize, Serialize};
 use tokio::task::JoinHandle;
 
 use ibc_relayer::{
     rest::{request::Request, RestApiError},
-    supervisor::dump_state::SupervisorState,
+    supervisor::{
+        clear_packets::{ClearPacketsOptions, ClearedPackets},
+        dump_state::SupervisorState,
+        rotate_key::{KeyRotation, RotateKeyOptions},
+    },
 };
 
-use crate::handle::{all_chain_ids, assemble_version_info, chain_config, supervisor_state};
+use crate::handle::{
+    all_chain_ids, assemble_version_info, chain_config, clear_packets, rotate_key, supervisor_state,
+};
 
 pub type BoxError = Box<dyn Error + Send + Sync>;
 
@@ -68,6 +79,34 @@ async fn get_state(
     Json(JsonResult::from(state))
 }
 
+async fn post_clear_packets(
+    Path(id): Path<String>,
+    Extension(sender): Extension<Sender>,
+    Json(options): Json<ClearPacketsOptions>,
+) -> Json<JsonResult<ClearedPackets, RestApiError>> {
+    // The events of each height of the range are queried in turn,
+    // so the threads of the runtime must not be blocked in the meantime
+    let cleared = tokio::task::spawn_blocking(move || clear_packets(&sender, &id, options))
+        .await
+        .unwrap_or_else(|e| Err(RestApiError::ChannelRecv(e.to_string())));
+
+    Json(JsonResult::from(cleared))
+}
+
+async fn post_rotate_key(
+    Path(id): Path<String>,
+    Extension(sender): Extension<Sender>,
+    Json(options): Json<RotateKeyOptions>,
+) -> Json<JsonResult<KeyRotation, RestApiError>> {
+    // The rotation waits for the transactions in flight to be confirmed,
+    // so it must not block the threads of the runtime in the meantime
+    let rotation = tokio::task::spawn_blocking(move || rotate_key(&sender, &id, options))
+        .await
+        .unwrap_or_else(|e| Err(RestApiError::ChannelRecv(e.to_string())));
+
+    Json(JsonResult::from(rotation))
+}
+
 type Sender = channel::Sender<Request>;
 
 async fn run(addr: SocketAddr, sender: Sender) {
@@ -76,6 +115,8 @@ async fn run(addr: SocketAddr, sender: Sender) {
         .route("/chains", get(get_chains))
         .route("/chain/:id", get(get_chain))
         .route("/state", get(get_state))
+        .route("/chain/:id/clear-packets", post(post_clear_packets))
+        .route("/chain/:id/rotate-key", post(post_rotate_key))
         .layer(Extension(sender));
 
     Server::bind(&addr)
@@ -5,9 +5,13 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use ibc_relayer::{
     config::ChainConfig,
     rest::request::{Request, VersionInfo},
-    supervisor::dump_state::SupervisorState,
+    supervisor::{
+        clear_packets::{ClearPacketsOptions, ClearedPackets},
+        dump_state::SupervisorState,
+        rotate_key::{KeyRotation, RotateKeyOptions},
+    },
 };
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 
 use ibc_relayer_rest::spawn;
 
@@ -142,3 +146,107 @@ async fn state() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn rotate_key() {
+    let rotation = KeyRotation {
+        chain_id: "mock-0".parse().unwrap(),
+        old_key_name: "testkey".to_string(),
+        old_address: "cosmos1old".to_string(),
+        new_key_name: "newkey".to_string(),
+        new_address: "cosmos1new".to_string(),
+        drained: true,
+        swept: None,
+        registered_payees: vec![],
+    };
+    let expected: JsonResult<_, ()> = JsonResult::Success(rotation.clone());
+
+    let (tx, rx) = crossbeam_channel::unbounded();
+    let handle = spawn(("127.0.0.1", 19105), tx).unwrap();
+
+    std::thread::spawn(move || match rx.recv() {
+        Ok(Request::RotateKey {
+            chain_id,
+            options,
+            reply_to,
+        }) if chain_id.as_str() == "mock-0"
+            && options
+                == RotateKeyOptions {
+                    new_key: "newkey".to_string(),
+                    sweep: false,
+                } =>
+        {
+            reply_to.send(Ok(rotation)).unwrap()
+        }
+        Ok(req) => panic!("got the wrong request: {req:?}"),
+        Err(e) => panic!("got an error: {e}"),
+    });
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let response = reqwest::Client::new()
+        .post("http://127.0.0.1:19105/chain/mock-0/rotate-key")
+        .json(&RotateKeyOptions {
+            new_key: "newkey".to_string(),
+            sweep: false,
+        })
+        .send()
+        .await
+        .unwrap()
+        .json::<JsonResult<KeyRotation, ()>>()
+        .await
+        .unwrap();
+
+    assert_eq!(response, expected);
+
+    drop(handle);
+}
+
+#[tokio::test]
+async fn clear_packets() {
+    let options = ClearPacketsOptions {
+        port_id: PortId::transfer(),
+        channel_id: ChannelId::new(0),
+        from_height: 10,
+        to_height: 20,
+    };
+    let cleared = ClearedPackets {
+        chain_id: "mock-0".parse().unwrap(),
+        port_id: PortId::transfer(),
+        channel_id: ChannelId::new(0),
+        heights: 3,
+    };
+    let expected: JsonResult<_, ()> = JsonResult::Success(cleared.clone());
+
+    let (tx, rx) = crossbeam_channel::unbounded();
+    let handle = spawn(("127.0.0.1", 19106), tx).unwrap();
+
+    let expected_options = options.clone();
+    std::thread::spawn(move || match rx.recv() {
+        Ok(Request::ClearPackets {
+            chain_id,
+            options,
+            reply_to,
+        }) if chain_id.as_str() == "mock-0" && options == expected_options => {
+            reply_to.send(Ok(cleared)).unwrap()
+        }
+        Ok(req) => panic!("got the wrong request: {req:?}"),
+        Err(e) => panic!("got an error: {e}"),
+    });
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let response = reqwest::Client::new()
+        .post("http://127.0.0.1:19106/chain/mock-0/clear-packets")
+        .json(&options)
+        .send()
+        .await
+        .unwrap()
+        .json::<JsonResult<ClearedPackets, ()>>()
+        .await
+        .unwrap();
+
+    assert_eq!(response, expected);
+
+    drop(handle);
+}
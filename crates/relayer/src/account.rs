@@ -1,12 +1,165 @@
 //! Data structures related to the accounts used by the relayer.
 
+use core::fmt::{Display, Error as FmtError, Formatter};
+
 use serde::{Deserialize, Serialize};
+use tendermint::Time;
 
 /// The balance for a specific denom
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Balance {
     /// The amount of coins in the account, as a string to allow for large amounts
     pub amount: String,
     /// The denomination for that coin
     pub denom: String,
 }
+
+/// A fee allowance granted to one of the relayer's keys, through the `x/feegrant` module
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FeeAllowance {
+    /// The address of the account paying the fees
+    pub granter: String,
+    /// The address of the relayer's key allowed to use the grant
+    pub grantee: String,
+    /// The remaining amount the grantee may spend, `None` if unlimited
+    pub spend_limit: Option<Vec<Balance>>,
+    /// The remaining amount the grantee may spend in the current period,
+    /// `None` if the allowance is not periodic
+    pub period_can_spend: Option<Vec<Balance>>,
+    /// The time at which the allowance expires, `None` if it never expires
+    pub expiration: Option<Time>,
+    /// The messages the allowance is restricted to, empty if it applies to all messages
+    pub allowed_messages: Vec<String>,
+}
+
+/// Whether a [`FeeAllowance`] can still be used to pay for transactions
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeAllowanceStatus {
+    Active,
+    Expired,
+    Exhausted,
+}
+
+impl Display for FeeAllowanceStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::Active => write!(f, "active"),
+            Self::Expired => write!(f, "expired"),
+            Self::Exhausted => write!(f, "exhausted"),
+        }
+    }
+}
+
+impl FeeAllowance {
+    /// The status of the allowance at the given time.
+    pub fn status(&self, now: Time) -> FeeAllowanceStatus {
+        if matches!(self.expiration, Some(expiration) if expiration <= now) {
+            return FeeAllowanceStatus::Expired;
+        }
+
+        let is_exhausted = |limit: &Option<Vec<Balance>>| {
+            limit.as_ref().map_or(false, |coins| {
+                coins
+                    .iter()
+                    .all(|coin| coin.amount.trim_start_matches('0').is_empty())
+            })
+        };
+
+        if is_exhausted(&self.spend_limit) || is_exhausted(&self.period_can_spend) {
+            return FeeAllowanceStatus::Exhausted;
+        }
+
+        FeeAllowanceStatus::Active
+    }
+
+    /// The remaining amount the grantee may currently spend in the given denom,
+    /// `None` if the amount is unlimited.
+    pub fn remaining(&self, denom: &str) -> Option<f64> {
+        let amount_in = |coins: &Vec<Balance>| {
+            coins
+                .iter()
+                .find(|coin| coin.denom == denom)
+                .and_then(|coin| coin.amount.parse::<f64>().ok())
+                .unwrap_or(0.0)
+        };
+
+        match (self.spend_limit.as_ref(), self.period_can_spend.as_ref()) {
+            (None, None) => None,
+            (Some(limit), None) => Some(amount_in(limit)),
+            (None, Some(period)) => Some(amount_in(period)),
+            (Some(limit), Some(period)) => Some(amount_in(limit).min(amount_in(period))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coins(amount: &str) -> Option<Vec<Balance>> {
+        Some(vec![Balance {
+            amount: amount.to_string(),
+            denom: "stake".to_string(),
+        }])
+    }
+
+    fn allowance() -> FeeAllowance {
+        FeeAllowance {
+            granter: "cosmos1granter".to_string(),
+            grantee: "cosmos1grantee".to_string(),
+            spend_limit: None,
+            period_can_spend: None,
+            expiration: None,
+            allowed_messages: vec![],
+        }
+    }
+
+    fn time(secs: i64) -> Time {
+        Time::from_unix_timestamp(secs, 0).unwrap()
+    }
+
+    #[test]
+    fn unlimited_allowance_is_active() {
+        let allowance = allowance();
+
+        assert_eq!(allowance.status(time(1000)), FeeAllowanceStatus::Active);
+        assert_eq!(allowance.remaining("stake"), None);
+    }
+
+    #[test]
+    fn allowance_expires() {
+        let allowance = FeeAllowance {
+            expiration: Some(time(1000)),
+            ..allowance()
+        };
+
+        assert_eq!(allowance.status(time(999)), FeeAllowanceStatus::Active);
+        assert_eq!(allowance.status(time(1000)), FeeAllowanceStatus::Expired);
+    }
+
+    #[test]
+    fn allowance_is_exhausted() {
+        let allowance = FeeAllowance {
+            spend_limit: coins("500"),
+            period_can_spend: coins("0"),
+            ..allowance()
+        };
+
+        assert_eq!(allowance.status(time(0)), FeeAllowanceStatus::Exhausted);
+        assert_eq!(allowance.remaining("stake"), Some(0.0));
+        assert_eq!(allowance.remaining("uatom"), Some(0.0));
+    }
+
+    #[test]
+    fn remaining_is_the_smallest_limit() {
+        let allowance = FeeAllowance {
+            spend_limit: coins("500"),
+            period_can_spend: coins("200"),
+            ..allowance()
+        };
+
+        assert_eq!(allowance.status(time(0)), FeeAllowanceStatus::Active);
+        assert_eq!(allowance.remaining("stake"), Some(200.0));
+    }
+}
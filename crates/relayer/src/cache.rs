@@ -4,25 +4,21 @@
 //! concurrency of retrievals and a high expected concurrency for updates.
 use core::fmt::Formatter;
 use std::fmt;
-use std::time::Duration;
 
 use moka::sync::Cache as MokaCache;
 
 use ibc_relayer_types::core::ics02_client::height::Height;
 use ibc_relayer_types::core::ics03_connection::connection::ConnectionEnd;
-use ibc_relayer_types::core::ics04_channel::channel::ChannelEnd;
-use ibc_relayer_types::core::ics24_host::identifier::{ClientId, ConnectionId, PortChannelId};
+use ibc_relayer_types::core::ics04_channel::channel::{ChannelEnd, State};
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ClientId, ConnectionId, PortChannelId,
+};
+use ibc_relayer_types::events::IbcEvent;
 
 use crate::client_state::AnyClientState;
-
-const CHANNEL_CACHE_TTL: Duration = Duration::from_secs(60);
-const CONNECTION_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
-const CLIENT_STATE_CACHE_TTL: Duration = Duration::from_millis(500);
-const LATEST_HEIGHT_CACHE_TTL: Duration = Duration::from_millis(200);
-
-const CHANNEL_CACHE_CAPACITY: u64 = 10_000;
-const CONNECTION_CACHE_CAPACITY: u64 = 10_000;
-const CLIENT_STATE_CACHE_CAPACITY: u64 = 10_000;
+use crate::config::cache::{CacheConfig, CacheSettings};
+use crate::event::IbcEventWithHeight;
+use crate::telemetry;
 
 /// Whether or not a result was in cache (ie. a cache hit)
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -40,6 +36,8 @@ pub type CacheResult<A, E> = Result<(A, CacheStatus), E>;
 /// There should be one `Cache` instantiated per every chain runtime.
 #[derive(Clone)]
 pub struct Cache {
+    /// The chain whose query results are cached, for telemetry.
+    chain_id: ChainId,
     /// Cache storing [`ChannelEnd`]s keyed by their [`PortChannelId`]s.
     channels: MokaCache<PortChannelId, ChannelEnd>,
     /// Cache storing [`ConnectionEnd`]s keyed by their [`ConnectionId`]s.
@@ -50,36 +48,21 @@ pub struct Cache {
     latest_height: MokaCache<(), Height>,
 }
 
-impl Default for Cache {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 impl Cache {
-    /// Initializes a new empty [`Cache`] with default time-to-live values.
-    pub fn new() -> Cache {
-        let channels = MokaCache::builder()
-            .time_to_live(CHANNEL_CACHE_TTL)
-            .max_capacity(CHANNEL_CACHE_CAPACITY)
-            .build();
-
-        let connections = MokaCache::builder()
-            .time_to_live(CONNECTION_CACHE_TTL)
-            .max_capacity(CONNECTION_CACHE_CAPACITY)
-            .build();
-
-        let client_states = MokaCache::builder()
-            .time_to_live(CLIENT_STATE_CACHE_TTL)
-            .max_capacity(CLIENT_STATE_CACHE_CAPACITY)
-            .build();
-
-        let latest_height = MokaCache::builder()
-            .time_to_live(LATEST_HEIGHT_CACHE_TTL)
-            .max_capacity(1)
-            .build();
+    /// Initializes a new empty [`Cache`] with the given time-to-live values and capacities.
+    pub fn new(chain_id: ChainId, config: &CacheConfig) -> Cache {
+        let channels = build_cache(&chain_id, "query_channel", config.channel);
+        let connections = build_cache(&chain_id, "query_connection", config.connection);
+        let client_states = build_cache(&chain_id, "query_client_state", config.client_state);
+
+        let latest_height = build_cache(
+            &chain_id,
+            "query_latest_height",
+            CacheSettings::new(config.latest_height_ttl, 1),
+        );
 
         Cache {
+            chain_id,
             channels,
             connections,
             client_states,
@@ -87,6 +70,110 @@ impl Cache {
         }
     }
 
+    /// Updates or evicts the entries modified by the given events, so that the
+    /// queries do not return an entry which is older than the events seen for it.
+    ///
+    /// The cached latest height is also evicted if it is lower than the height of the events.
+    pub fn apply_events(&self, events: &[IbcEventWithHeight]) {
+        for event in events {
+            if let Some(latest_height) = self.latest_height.get(&()) {
+                if latest_height < event.height {
+                    self.latest_height.invalidate(&());
+                }
+            }
+
+            self.apply_event(&event.event);
+        }
+    }
+
+    /// Updates or evicts the entries modified by the given event.
+    ///
+    /// The channel ends are updated in place by the events which close them, since
+    /// `Closed` is the final state of a channel. The channel and connection ends are
+    /// evicted by the other handshake events, and the client states by the events which
+    /// update, upgrade or freeze the client.
+    pub fn apply_event(&self, event: &IbcEvent) {
+        match event {
+            IbcEvent::UpdateClient(e) => self.invalidate_client_state(e.client_id()),
+            IbcEvent::UpgradeClient(e) => self.invalidate_client_state(e.client_id()),
+            IbcEvent::ClientMisbehaviour(e) => self.invalidate_client_state(e.client_id()),
+
+            IbcEvent::OpenInitConnection(_)
+            | IbcEvent::OpenTryConnection(_)
+            | IbcEvent::OpenAckConnection(_)
+            | IbcEvent::OpenConfirmConnection(_) => {
+                if let Some(connection_id) = event
+                    .connection_attributes()
+                    .and_then(|attributes| attributes.connection_id.as_ref())
+                {
+                    self.invalidate_connection(connection_id);
+                }
+            }
+
+            IbcEvent::OpenInitChannel(_)
+            | IbcEvent::OpenTryChannel(_)
+            | IbcEvent::OpenAckChannel(_)
+            | IbcEvent::OpenConfirmChannel(_) => {
+                if let Some(attributes) = event.clone().channel_attributes() {
+                    if let Some(channel_id) = attributes.channel_id {
+                        self.invalidate_channel(&PortChannelId::new(
+                            channel_id,
+                            attributes.port_id,
+                        ));
+                    }
+                }
+            }
+            IbcEvent::CloseInitChannel(e) => self.close_channel(&PortChannelId::new(
+                e.channel_id().clone(),
+                e.port_id().clone(),
+            )),
+            IbcEvent::CloseConfirmChannel(e) => {
+                if let Some(channel_id) = e.channel_id() {
+                    self.close_channel(&PortChannelId::new(channel_id.clone(), e.port_id.clone()));
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    fn close_channel(&self, id: &PortChannelId) {
+        if let Some(mut channel) = self.channels.get(id) {
+            channel.set_state(State::Closed);
+            self.channels.insert(id.clone(), channel);
+        }
+    }
+
+    fn invalidate_channel(&self, id: &PortChannelId) {
+        self.channels.invalidate(id);
+        telemetry!(
+            queries_cache_size,
+            &self.chain_id,
+            "query_channel",
+            self.channels.entry_count()
+        );
+    }
+
+    fn invalidate_connection(&self, id: &ConnectionId) {
+        self.connections.invalidate(id);
+        telemetry!(
+            queries_cache_size,
+            &self.chain_id,
+            "query_connection",
+            self.connections.entry_count()
+        );
+    }
+
+    fn invalidate_client_state(&self, id: &ClientId) {
+        self.client_states.invalidate(id);
+        telemetry!(
+            queries_cache_size,
+            &self.chain_id,
+            "query_client_state",
+            self.client_states.entry_count()
+        );
+    }
+
     /// Return a cached [`ChannelEnd`] via its [`PortChannelId`] if it exists in the cache.
     /// Otherwise, attempts to fetch it via the supplied fetcher function `F`. If `F`
     /// returns successfully with the channel end in an open state, a copy of it is stored in
@@ -107,6 +194,12 @@ impl Cache {
             let chan = f()?;
             if chan.state().is_open() {
                 self.channels.insert(id.clone(), chan.clone());
+                telemetry!(
+                    queries_cache_size,
+                    &self.chain_id,
+                    "query_channel",
+                    self.channels.entry_count()
+                );
             }
             Ok((chan, CacheStatus::Miss))
         }
@@ -130,6 +223,12 @@ impl Cache {
             let conn = f()?;
             if conn.state().is_open() {
                 self.connections.insert(id.clone(), conn.clone());
+                telemetry!(
+                    queries_cache_size,
+                    &self.chain_id,
+                    "query_connection",
+                    self.connections.entry_count()
+                );
             }
             Ok((conn, CacheStatus::Miss))
         }
@@ -152,6 +251,12 @@ impl Cache {
         } else {
             let state = f()?;
             self.client_states.insert(id.clone(), state.clone());
+            telemetry!(
+                queries_cache_size,
+                &self.chain_id,
+                "query_client_state",
+                self.client_states.entry_count()
+            );
             Ok((state, CacheStatus::Miss))
         }
     }
@@ -177,8 +282,130 @@ impl Cache {
     }
 }
 
+/// Builds a sub-cache whose evictions are counted in the telemetry under the given query type.
+fn build_cache<K, V>(
+    chain_id: &ChainId,
+    query_type: &'static str,
+    settings: CacheSettings,
+) -> MokaCache<K, V>
+where
+    K: core::hash::Hash + Eq + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    let builder = MokaCache::builder()
+        .time_to_live(settings.ttl)
+        .max_capacity(settings.capacity);
+
+    #[cfg(feature = "telemetry")]
+    let builder = {
+        let chain_id = chain_id.clone();
+
+        builder.eviction_listener(move |_key, _value, cause| {
+            // Entries replaced by a newer value are not evicted
+            if cause != moka::notification::RemovalCause::Replaced {
+                telemetry!(queries_cache_evictions, &chain_id, query_type);
+            }
+        })
+    };
+
+    #[cfg(not(feature = "telemetry"))]
+    let _ = (chain_id, query_type);
+
+    builder.build()
+}
+
 impl fmt::Debug for Cache {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("Cache").finish_non_exhaustive()
+        f.debug_struct("Cache")
+            .field("chain_id", &self.chain_id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::convert::Infallible;
+    use core::time::Duration;
+
+    use ibc_relayer_types::core::ics04_channel::channel::{Counterparty, Ordering};
+    use ibc_relayer_types::core::ics04_channel::events::{CloseInit, SendPacket};
+    use ibc_relayer_types::core::ics04_channel::packet::Packet;
+    use ibc_relayer_types::core::ics04_channel::version::Version;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
+
+    fn channel_end(state: State) -> ChannelEnd {
+        ChannelEnd::new(
+            state,
+            Ordering::Unordered,
+            Counterparty::new(PortId::transfer(), Some(ChannelId::new(1))),
+            vec![ConnectionId::default()],
+            Version::ics20(),
+        )
+    }
+
+    fn query_channel(cache: &Cache, id: &PortChannelId, on_chain: State) -> (State, CacheStatus) {
+        let (channel, status) = cache
+            .get_or_try_insert_channel_with(id, || Ok::<_, Infallible>(channel_end(on_chain)))
+            .unwrap();
+
+        (*channel.state(), status)
+    }
+
+    #[test]
+    fn cache_settings_are_applied() {
+        let config = CacheConfig {
+            latest_height_ttl: Duration::ZERO,
+            ..CacheConfig::default()
+        };
+
+        let cache = Cache::new(ChainId::from_string("ibc-0"), &config);
+        let fetch = || Ok::<_, Infallible>(Height::new(0, 1).unwrap());
+
+        let (_, status) = cache.get_or_try_update_latest_height_with(fetch).unwrap();
+        assert_eq!(status, CacheStatus::Miss);
+
+        // Nothing is kept in cache with a zero time-to-live
+        let (_, status) = cache.get_or_try_update_latest_height_with(fetch).unwrap();
+        assert_eq!(status, CacheStatus::Miss);
+    }
+
+    #[test]
+    fn channel_is_closed_by_close_init() {
+        let cache = Cache::new(ChainId::from_string("ibc-0"), &CacheConfig::default());
+        let id = PortChannelId::new(ChannelId::new(0), PortId::transfer());
+
+        assert_eq!(
+            query_channel(&cache, &id, State::Open),
+            (State::Open, CacheStatus::Miss)
+        );
+        assert_eq!(
+            query_channel(&cache, &id, State::Open),
+            (State::Open, CacheStatus::Hit)
+        );
+
+        // Packet events do not change the channel end
+        cache.apply_event(&IbcEvent::SendPacket(SendPacket {
+            packet: Packet::default(),
+        }));
+        assert_eq!(
+            query_channel(&cache, &id, State::Closed),
+            (State::Open, CacheStatus::Hit)
+        );
+
+        cache.apply_event(&IbcEvent::CloseInitChannel(CloseInit {
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::new(0),
+            connection_id: ConnectionId::default(),
+            counterparty_port_id: PortId::transfer(),
+            counterparty_channel_id: Some(ChannelId::new(1)),
+        }));
+
+        // The cached channel end is closed without querying the chain again
+        assert_eq!(
+            query_channel(&cache, &id, State::Open),
+            (State::Closed, CacheStatus::Hit)
+        );
     }
 }
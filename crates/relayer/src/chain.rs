@@ -3,6 +3,7 @@ pub mod cosmos;
 pub mod counterparty;
 pub mod endpoint;
 pub mod handle;
+pub mod node_lag;
 pub mod requests;
 pub mod runtime;
 pub mod tracking;
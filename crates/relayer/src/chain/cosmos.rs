@@ -8,12 +8,12 @@ use core::{
 };
 use futures::future::join_all;
 use num_bigint::BigInt;
-use std::{cmp::Ordering, thread};
+use std::{cmp::Ordering, thread, time::Instant};
 
 use tokio::runtime::Runtime as TokioRuntime;
 use tonic::codegen::http::Uri;
 use tonic::metadata::AsciiMetadataValue;
-use tracing::{error, info, instrument, trace, warn};
+use tracing::{debug, error, info, instrument, trace, warn};
 
 use ibc_proto::cosmos::{
     base::node::v1beta1::ConfigResponse, staking::v1beta1::Params as StakingParams,
@@ -21,6 +21,7 @@ use ibc_proto::cosmos::{
 
 use ibc_proto::interchain_security::ccv::consumer::v1::Params as CcvConsumerParams;
 
+use ibc_proto::google::protobuf::Any;
 use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
@@ -58,67 +59,88 @@ use tendermint::block::Height as TmHeight;
 use tendermint::node::{self, info::TxIndexStatus};
 use tendermint::time::Time as TmTime;
 use tendermint_light_client::verifier::types::LightBlock as TmLightBlock;
-use tendermint_rpc::client::CompatMode;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use tendermint_rpc::endpoint::status;
-use tendermint_rpc::{Client, HttpClient, Order};
+use tendermint_rpc::{Client, HttpClient, Order, Url, WebSocketClientUrl};
 
-use crate::account::Balance;
+use crate::account::{Balance, FeeAllowance, FeeAllowanceStatus};
 use crate::chain::client::ClientSettings;
 use crate::chain::cosmos::batch::{
     send_batched_messages_and_wait_check_tx, send_batched_messages_and_wait_commit,
-    sequential_send_batched_messages_and_wait_commit,
+    sequential_send_batched_messages_and_wait_commit, simulate_batched_messages,
 };
 use crate::chain::cosmos::encode::key_pair_to_signer;
+use crate::chain::cosmos::failover::{
+    is_grpc_endpoint_failure, is_rpc_endpoint_failure, EndpointKind, EndpointPool, EndpointProbe,
+    SharedEndpointPool,
+};
 use crate::chain::cosmos::fee::maybe_register_counterparty_payee;
 use crate::chain::cosmos::gas::{calculate_fee, mul_ceil};
 use crate::chain::cosmos::query::account::get_or_fetch_account;
+use crate::chain::cosmos::query::authz::query_authz_grants;
 use crate::chain::cosmos::query::balance::{query_all_balances, query_balance};
+use crate::chain::cosmos::query::block_results::query_block_results;
 use crate::chain::cosmos::query::consensus_state::query_consensus_state_heights;
-use crate::chain::cosmos::query::custom::cross_chain_query_via_rpc;
+use crate::chain::cosmos::query::custom::{
+    cross_chain_query_via_rpc, query_pending_cross_chain_queries,
+};
 use crate::chain::cosmos::query::denom_trace::query_denom_trace;
 use crate::chain::cosmos::query::fee::query_incentivized_packet;
+use crate::chain::cosmos::query::feegrant::query_fee_allowance;
 use crate::chain::cosmos::query::status::query_status;
 use crate::chain::cosmos::query::tx::{
-    filter_matching_event, query_packets_from_block, query_packets_from_txs, query_txs,
+    filter_matching_event, query_block_packet_events, query_packets_from_block,
+    query_packets_from_txs, query_txs,
 };
 use crate::chain::cosmos::query::{abci_query, fetch_version_specs, packet_query, QueryResponse};
+use crate::chain::cosmos::sweep::{sweep_coins, sweepable_coins};
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
+use crate::chain::cosmos::types::dry_run::{
+    DryRunReport, DryRunReporter, SharedDryRunReport, DRY_RUN_REPORT_INTERVAL,
+};
 use crate::chain::cosmos::types::gas::{
     default_gas_from_config, gas_multiplier_from_config, max_gas_from_config,
 };
+use crate::chain::cosmos::types::wallet::{
+    with_resignable_signer, with_signer, Wallet, WalletPool, IBC_MSG_TYPE_URLS,
+};
 use crate::chain::endpoint::{ChainEndpoint, ChainStatus, HealthCheck};
 use crate::chain::handle::Subscription;
 use crate::chain::requests::*;
 use crate::chain::tracking::TrackedMsgs;
 use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
-use crate::config::{parse_gas_prices, ChainConfig, GasPrice};
+use crate::config::types::Memo;
+use crate::config::{parse_gas_prices, ChainConfig, CompatMode, EventSourceMode, GasPrice};
 use crate::consensus_state::AnyConsensusState;
 use crate::denom::DenomTrace;
-use crate::error::Error;
+use crate::error::{Error, ErrorDetail};
 use crate::event::source::{EventSource, TxEventSourceCmd};
 use crate::event::IbcEventWithHeight;
-use crate::keyring::{KeyRing, Secp256k1KeyPair, SigningKeyPair};
+use crate::keyring::{CosmosKeyPair, KeyRing, SigningKeyPair};
 use crate::light_client::tendermint::LightClient as TmLightClient;
 use crate::light_client::{LightClient, Verified};
 use crate::misbehaviour::MisbehaviourEvidence;
+use crate::util::lock::LockExt;
 use crate::util::pretty::{
     PrettyIdentifiedChannel, PrettyIdentifiedClientState, PrettyIdentifiedConnection,
 };
 
 use self::types::app_state::GenesisAppState;
 
+pub mod amino;
 pub mod batch;
 pub mod client;
 pub mod compatibility;
 pub mod encode;
 pub mod estimate;
+pub mod failover;
 pub mod fee;
 pub mod gas;
 pub mod query;
 pub mod retry;
 pub mod simulate;
+pub mod sweep;
 pub mod tx;
 pub mod types;
 pub mod version;
@@ -142,15 +164,25 @@ pub const BLOCK_MAX_BYTES_MAX_FRACTION: f64 = 0.9;
 pub struct CosmosSdkChain {
     config: ChainConfig,
     tx_config: TxConfig,
-    rpc_client: HttpClient,
+    /// The RPC endpoints, with a client for each of them, in the order of the pool
+    rpc_endpoints: SharedEndpointPool<Url>,
+    rpc_clients: Vec<HttpClient>,
     compat_mode: CompatMode,
-    grpc_addr: Uri,
+    grpc_endpoints: SharedEndpointPool<Uri>,
+    /// The WebSocket endpoints of the `push` event source, if any
+    websocket_endpoints: Option<SharedEndpointPool<WebSocketClientUrl>>,
+    /// Stops the probe of the endpoints when dropped, if there are backup endpoints
+    _endpoint_probe: Option<crossbeam_channel::Sender<()>>,
     light_client: TmLightClient,
     rt: Arc<TokioRuntime>,
-    keybase: KeyRing<Secp256k1KeyPair>,
+    keybase: KeyRing<CosmosKeyPair>,
+
+    /// The wallets used to sign transactions, loaded on first use
+    wallets: Option<WalletPool>,
 
-    /// A cached copy of the account information
-    account: Option<Account>,
+    /// The projected spend of the simulated transactions, in dry-run mode
+    dry_run_report: Option<SharedDryRunReport>,
+    _dry_run_reporter: Option<crossbeam_channel::Sender<()>>,
 
     tx_monitor_cmd: Option<TxEventSourceCmd>,
 }
@@ -161,17 +193,244 @@ impl CosmosSdkChain {
         &self.config
     }
 
+    /// The client of the RPC endpoint in use
+    fn rpc_client(&self) -> &HttpClient {
+        &self.rpc_clients[self.rpc_endpoints.acquire_read().current_index()]
+    }
+
+    /// The address of the RPC endpoint in use
+    fn rpc_addr(&self) -> Url {
+        self.rpc_endpoints.acquire_read().current().clone()
+    }
+
+    /// The address of the gRPC endpoint in use
+    fn grpc_addr(&self) -> Uri {
+        self.grpc_endpoints.acquire_read().current().clone()
+    }
+
+    /// Update the state which depends on the endpoints in use, ie. the addresses
+    /// in the transaction configurations and the light client, after a fail over.
+    fn sync_endpoints(&mut self) -> Result<(), Error> {
+        let rpc_addr = self.rpc_addr();
+        let grpc_addr = self.grpc_addr();
+
+        if self.tx_config.rpc_address != rpc_addr {
+            let config = ChainConfig {
+                rpc_addr: rpc_addr.clone(),
+                ..self.config.clone()
+            };
+
+            let node_info =
+                self.block_on(fetch_node_info(self.rpc_client(), &config.id, &rpc_addr))?;
+
+            self.light_client = TmLightClient::from_config(&config, node_info.id)?;
+        } else if self.tx_config.grpc_address == grpc_addr {
+            return Ok(());
+        }
+
+        self.tx_config.rpc_address = rpc_addr.clone();
+        self.tx_config.grpc_address = grpc_addr.clone();
+
+        if let Some(wallets) = self.wallets.as_mut() {
+            for index in 0..wallets.len() {
+                if let Some(tx_config) = wallets.wallet_mut(index).tx_config.as_mut() {
+                    tx_config.rpc_address = rpc_addr.clone();
+                    tx_config.grpc_address = grpc_addr.clone();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// The maximum size of any transaction sent by the relayer to this chain
     fn max_tx_size(&self) -> usize {
         self.config.max_tx_size.into()
     }
 
-    fn key(&self) -> Result<Secp256k1KeyPair, Error> {
+    fn key(&self) -> Result<CosmosKeyPair, Error> {
         self.keybase()
-            .get_key(&self.config.key_name)
+            .get_key(self.config.key_name.primary())
             .map_err(Error::key_base)
     }
 
+    /// Load the keys listed in `key_name` from the keyring, unless already loaded.
+    fn load_wallets(&mut self) -> Result<(), Error> {
+        if self.wallets.is_some() {
+            return Ok(());
+        }
+
+        let wallets = self
+            .config
+            .key_name
+            .iter()
+            .map(|key_name| {
+                let key_pair = self.keybase.get_key(key_name).map_err(Error::key_base)?;
+                let mut wallet = Wallet::new(key_name.to_string(), key_pair);
+
+                // Use the key's own fee granter, if it differs from the chain's one
+                wallet.tx_config = self
+                    .config
+                    .fee_granter_for(key_name)
+                    .filter(|granter| *granter != self.tx_config.gas_config.fee_granter)
+                    .map(|granter| self.tx_config.with_fee_granter(granter));
+
+                Ok(wallet)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        self.wallets = Some(WalletPool::new(
+            wallets,
+            self.config.wallet_selection,
+            self.config.max_block_time,
+        ));
+
+        Ok(())
+    }
+
+    /// Pick the wallet which should sign the given messages, and replace
+    /// the signer of the messages if it is not the primary wallet.
+    fn select_wallet(&mut self, messages: Vec<Any>) -> Result<(usize, Vec<Any>), Error> {
+        self.load_wallets()?;
+
+        let wallets = self.wallets.as_mut().expect("wallets were just loaded");
+
+        let index = wallets.select(&messages);
+
+        // With authz, the IBC messages are executed by the wallet on behalf of the granter,
+        // while the other messages are signed by the primary wallet, which built them.
+        if let Some(granter) = &self.config.authz_granter {
+            let messages = with_resignable_signer(messages, granter)?;
+            return Ok((index, messages));
+        }
+
+        if index == 0 {
+            return Ok((index, messages));
+        }
+
+        let wallet = wallets.wallet_mut(index);
+
+        debug!(
+            key_name = %wallet.key_name,
+            "signing {} messages with secondary wallet",
+            messages.len()
+        );
+
+        let messages = with_signer(messages, &wallet.address())?;
+
+        Ok((index, messages))
+    }
+
+    /// Checks that the fee grants configured for the relayer's keys, if any,
+    /// can be used to pay for transactions, ie. that they exist, and are neither
+    /// expired nor exhausted.
+    ///
+    /// The grants which cannot be queried are only reported with a warning,
+    /// as the failure of the query says nothing about the grants themselves.
+    fn validate_fee_grants(&self) -> Result<(), Error> {
+        if !self
+            .config
+            .key_name
+            .iter()
+            .any(|key_name| self.config.fee_granter_for(key_name).is_some())
+        {
+            return Ok(());
+        }
+
+        let now = match self.chain_status() {
+            Ok(status) => status.sync_info.latest_block_time,
+            Err(e) => {
+                warn!(
+                    "Fee grant check for chain '{}' skipped, failed to query the chain status: {}",
+                    self.id(),
+                    e
+                );
+                return Ok(());
+            }
+        };
+
+        for key_name in self.config.key_name.iter() {
+            let granter = match self.config.fee_granter_for(key_name) {
+                Some(granter) => granter,
+                None => continue,
+            };
+
+            let unusable = |reason: String| {
+                Error::unusable_fee_grant(
+                    self.id().clone(),
+                    key_name.to_string(),
+                    granter.to_string(),
+                    reason,
+                )
+            };
+
+            let allowance = match self.query_fee_allowance(Some(key_name)) {
+                Ok(allowance) => allowance,
+                Err(e) => {
+                    warn!(
+                        "Fee grant check for key '{}' of chain '{}' skipped, failed to query its allowance: {}",
+                        key_name,
+                        self.id(),
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            match allowance {
+                None => return Err(unusable("no allowance found".to_string())),
+                Some(allowance) => match allowance.status(now) {
+                    FeeAllowanceStatus::Active => {}
+                    status => return Err(unusable(format!("allowance is {status}"))),
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that, if an authz granter is configured, each of the relayer's keys
+    /// has been granted an unexpired `GenericAuthorization` by the granter
+    /// for every core IBC message.
+    fn validate_authz_grants(&self) -> Result<(), Error> {
+        let granter = match &self.config.authz_granter {
+            Some(granter) => granter,
+            None => return Ok(()),
+        };
+
+        let now = self.chain_status()?.sync_info.latest_block_time;
+
+        for key_name in self.config.key_name.iter() {
+            let key = self.keybase().get_key(key_name).map_err(Error::key_base)?;
+
+            let grants = self.block_on(query_authz_grants(
+                &self.grpc_addr(),
+                granter,
+                &key.account(),
+            ))?;
+
+            let missing = IBC_MSG_TYPE_URLS
+                .iter()
+                .filter(|type_url| !grants.iter().any(|grant| grant.allows(type_url, now)))
+                .copied()
+                .collect::<Vec<_>>();
+
+            if !missing.is_empty() {
+                return Err(Error::unusable_authz_grant(
+                    self.id().clone(),
+                    key_name.to_string(),
+                    granter.clone(),
+                    format!(
+                        "missing or expired generic authorization for: {}",
+                        missing.join(", ")
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Fetches the trusting period as a `Duration` from the chain config.
     /// If no trusting period exists in the config, the trusting period is calculated
     /// as two-thirds of the `unbonding_period`.
@@ -234,11 +493,11 @@ impl CosmosSdkChain {
 
         // Check on the configured max_tx_size against the consensus parameters at latest height
         let result = self
-            .block_on(self.rpc_client.consensus_params(latest_height))
+            .block_on(self.rpc_client().consensus_params(latest_height))
             .map_err(|e| {
                 Error::config_validation_json_rpc(
                     self.id().clone(),
-                    self.config.rpc_addr.to_string(),
+                    self.rpc_addr().to_string(),
                     "/consensus_params".to_string(),
                     e,
                 )
@@ -288,7 +547,7 @@ impl CosmosSdkChain {
         // Query /genesis RPC endpoint to retrieve the `max_expected_time_per_block` value
         // to use as `max_block_time`.
         // If it is not found, keep the configured `max_block_time`.
-        match self.block_on(self.rpc_client.genesis::<GenesisAppState>()) {
+        match self.block_on(self.rpc_client().genesis::<GenesisAppState>()) {
             Ok(genesis_reponse) => {
                 let old_max_block_time = self.config.max_block_time;
                 self.config.max_block_time =
@@ -318,22 +577,29 @@ impl CosmosSdkChain {
             }
         );
 
-        use crate::config::EventSourceMode as Mode;
-
         let (event_source, monitor_tx) = match &self.config.event_source {
-            Mode::Push { url, batch_delay } => EventSource::websocket(
+            EventSourceMode::Push { batch_delay, .. } => EventSource::websocket(
                 self.config.id.clone(),
-                url.clone(),
+                self.websocket_endpoints
+                    .clone()
+                    .expect("the WebSocket endpoints are set up for a push event source"),
                 self.compat_mode,
                 *batch_delay,
                 self.rt.clone(),
             ),
-            Mode::Pull { interval } => EventSource::rpc(
+            EventSourceMode::Pull { interval } => EventSource::rpc(
                 self.config.id.clone(),
-                self.rpc_client.clone(),
+                self.rpc_endpoints.clone(),
+                self.compat_mode,
                 *interval,
                 self.rt.clone(),
             ),
+            EventSourceMode::Stream { url } => {
+                let url = Uri::from_str(&url.to_string())
+                    .map_err(|e| Error::invalid_uri(url.to_string(), e))?;
+
+                EventSource::stream(self.config.id.clone(), url, self.rt.clone())
+            }
         }
         .map_err(Error::event_source)?;
 
@@ -355,7 +621,7 @@ impl CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::interchain_security::ccv::consumer::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone()
+                    self.grpc_addr()
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -392,7 +658,7 @@ impl CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::cosmos::staking::v1beta1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -447,7 +713,7 @@ impl CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::cosmos::base::node::v1beta1::service_client::ServiceClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -560,8 +826,8 @@ impl CosmosSdkChain {
         }
 
         let response = self.block_on(abci_query(
-            &self.rpc_client,
-            &self.config.rpc_addr,
+            self.rpc_client(),
+            &self.rpc_addr(),
             IBC_QUERY_PATH.to_string(),
             data.to_string(),
             height_query.into(),
@@ -599,8 +865,8 @@ impl CosmosSdkChain {
         let path = SDK_UPGRADE_QUERY_PATH.into();
 
         let response: QueryResponse = self.block_on(abci_query(
-            &self.rpc_client,
-            &self.config.rpc_addr,
+            self.rpc_client(),
+            &self.rpc_addr(),
             path,
             Path::Upgrade(query_data).to_string(),
             query_height.into(),
@@ -626,12 +892,12 @@ impl CosmosSdkChain {
         crate::telemetry!(query, self.id(), "status");
 
         let status = self
-            .block_on(self.rpc_client.status())
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .block_on(self.rpc_client().status())
+            .map_err(|e| Error::rpc(self.rpc_addr(), e))?;
 
         if status.sync_info.catching_up {
             return Err(Error::chain_not_caught_up(
-                self.config.rpc_addr.to_string(),
+                self.rpc_addr().to_string(),
                 self.config().id.clone(),
             ));
         }
@@ -649,11 +915,9 @@ impl CosmosSdkChain {
         );
         crate::telemetry!(query, self.id(), "query_latest_height");
 
-        let status = self.rt.block_on(query_status(
-            self.id(),
-            &self.rpc_client,
-            &self.config.rpc_addr,
-        ))?;
+        let status =
+            self.rt
+                .block_on(query_status(self.id(), self.rpc_client(), &self.rpc_addr()))?;
 
         Ok(status.height)
     }
@@ -678,19 +942,48 @@ impl CosmosSdkChain {
             }
         );
 
-        let proto_msgs = tracked_msgs.msgs;
+        let (index, proto_msgs) = self.select_wallet(tracked_msgs.msgs)?;
+
+        let rpc_client = self.rpc_client().clone();
+        let grpc_addr = self.grpc_addr();
+
+        let wallets = self.wallets.as_mut().expect("wallets were just loaded");
+        let Wallet {
+            key_pair,
+            account: m_account,
+            tx_config,
+            ..
+        } = wallets.wallet_mut(index);
+
+        let tx_config = tx_config.as_ref().unwrap_or(&self.tx_config);
 
-        let key_pair = self.key()?;
         let key_account = key_pair.account();
 
-        let account =
-            get_or_fetch_account(&self.grpc_addr, &key_account, &mut self.account).await?;
+        let account = get_or_fetch_account(&grpc_addr, &key_account, m_account).await?;
+
+        if let Some(report) = &self.dry_run_report {
+            let result = simulate_messages(
+                report,
+                tx_config,
+                key_pair,
+                account,
+                &self.config.memo_prefix,
+                proto_msgs,
+            )
+            .await;
 
-        if self.config.sequential_batch_tx {
+            if result.is_ok() {
+                wallets.record_submission(index);
+            }
+
+            return result.map(|()| Vec::new());
+        }
+
+        let result = if self.config.sequential_batch_tx {
             sequential_send_batched_messages_and_wait_commit(
-                &self.rpc_client,
-                &self.tx_config,
-                &key_pair,
+                &rpc_client,
+                tx_config,
+                key_pair,
                 account,
                 &self.config.memo_prefix,
                 proto_msgs,
@@ -698,15 +991,21 @@ impl CosmosSdkChain {
             .await
         } else {
             send_batched_messages_and_wait_commit(
-                &self.rpc_client,
-                &self.tx_config,
-                &key_pair,
+                &rpc_client,
+                tx_config,
+                key_pair,
                 account,
                 &self.config.memo_prefix,
                 proto_msgs,
             )
             .await
+        };
+
+        if result.is_ok() {
+            wallets.record_submission(index);
         }
+
+        result
     }
 
     #[instrument(
@@ -729,23 +1028,58 @@ impl CosmosSdkChain {
             }
         );
 
-        let proto_msgs = tracked_msgs.msgs;
+        let (index, proto_msgs) = self.select_wallet(tracked_msgs.msgs)?;
+
+        let rpc_client = self.rpc_client().clone();
+        let grpc_addr = self.grpc_addr();
+
+        let wallets = self.wallets.as_mut().expect("wallets were just loaded");
+        let Wallet {
+            key_pair,
+            account: m_account,
+            tx_config,
+            ..
+        } = wallets.wallet_mut(index);
+
+        let tx_config = tx_config.as_ref().unwrap_or(&self.tx_config);
 
-        let key_pair = self.key()?;
         let key_account = key_pair.account();
 
-        let account =
-            get_or_fetch_account(&self.grpc_addr, &key_account, &mut self.account).await?;
+        let account = get_or_fetch_account(&grpc_addr, &key_account, m_account).await?;
 
-        send_batched_messages_and_wait_check_tx(
-            &self.rpc_client,
-            &self.tx_config,
-            &key_pair,
+        if let Some(report) = &self.dry_run_report {
+            let result = simulate_messages(
+                report,
+                tx_config,
+                key_pair,
+                account,
+                &self.config.memo_prefix,
+                proto_msgs,
+            )
+            .await;
+
+            if result.is_ok() {
+                wallets.record_submission(index);
+            }
+
+            return result.map(|()| Vec::new());
+        }
+
+        let result = send_batched_messages_and_wait_check_tx(
+            &rpc_client,
+            tx_config,
+            key_pair,
             account,
             &self.config.memo_prefix,
             proto_msgs,
         )
-        .await
+        .await;
+
+        if result.is_ok() {
+            wallets.record_submission(index);
+        }
+
+        result
     }
 
     fn query_packet_from_block(
@@ -769,8 +1103,12 @@ impl CosmosSdkChain {
             tendermint::block::Height::try_from(block_height.revision_height()).unwrap();
 
         let response = self
-            .block_on(self.rpc_client.block_results(tm_height))
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .block_on(query_block_results(
+                self.rpc_client(),
+                self.compat_mode,
+                tm_height,
+            ))
+            .map_err(|e| Error::rpc(self.rpc_addr(), e))?;
 
         let response_height = ICSHeight::new(self.id().version(), u64::from(response.height))
             .map_err(|_| Error::invalid_height_no_source())?;
@@ -815,7 +1153,7 @@ impl CosmosSdkChain {
 
         for seq in request.sequences.iter().copied() {
             let response = self
-                .block_on(self.rpc_client.block_search(
+                .block_on(self.rpc_client().block_search(
                     packet_query(request, seq),
                     // We only need the first page
                     1,
@@ -833,7 +1171,7 @@ impl CosmosSdkChain {
                     // blocks first.
                     Order::Descending,
                 ))
-                .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+                .map_err(|e| Error::rpc(self.rpc_addr(), e))?;
 
             for block in response.blocks.into_iter().map(|response| response.block) {
                 let response_height =
@@ -866,24 +1204,68 @@ impl ChainEndpoint for CosmosSdkChain {
     type ConsensusState = TmConsensusState;
     type ClientState = TmClientState;
     type Time = TmTime;
-    type SigningKeyPair = Secp256k1KeyPair;
+    type SigningKeyPair = CosmosKeyPair;
 
     fn bootstrap(config: ChainConfig, rt: Arc<TokioRuntime>) -> Result<Self, Error> {
-        let mut rpc_client = HttpClient::new(config.rpc_addr.clone())
-            .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
+        let mut rpc_endpoints = EndpointPool::new(
+            config.id.clone(),
+            EndpointKind::Rpc,
+            config.rpc_addr.clone(),
+            config.failover.rpc_addrs.iter().cloned(),
+        );
 
-        let node_info = rt.block_on(fetch_node_info(&rpc_client, &config))?;
+        let mut rpc_clients = rpc_endpoints
+            .addrs()
+            .into_iter()
+            .map(|addr| HttpClient::new(addr.clone()).map_err(|e| Error::rpc(addr, e)))
+            .collect::<Result<Vec<_>, _>>()?;
 
-        let compat_mode = CompatMode::from_version(node_info.version).unwrap_or_else(|e| {
-            warn!("Unsupported tendermint version, will use v0.37 compatibility mode but relaying might not work as desired: {e}");
-            CompatMode::V0_37
-        });
-        rpc_client.set_compat_mode(compat_mode);
+        // Start with the first RPC endpoint which answers
+        let mut node_info = None;
+        let mut last_error = None;
 
-        let light_client = TmLightClient::from_config(&config, node_info.id)?;
+        for (index, (rpc_client, addr)) in rpc_clients.iter().zip(rpc_endpoints.addrs()).enumerate()
+        {
+            match rt.block_on(fetch_node_info(rpc_client, &config.id, &addr)) {
+                Ok(info) => {
+                    node_info = Some(info);
+                    rpc_endpoints.set_current(index);
+                    break;
+                }
+                Err(e) => {
+                    warn!(
+                        "failed to reach RPC endpoint {addr} of chain {}: {e}",
+                        config.id
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        let node_info = match (node_info, last_error) {
+            (Some(node_info), _) => node_info,
+            (None, Some(e)) => return Err(e),
+            (None, None) => unreachable!("the pool has at least one RPC endpoint"),
+        };
+
+        let compat_mode = CompatMode::select(config.compat_mode, &node_info.version.to_string());
+
+        for rpc_client in rpc_clients.iter_mut() {
+            rpc_client.set_compat_mode(compat_mode.rpc_compat());
+        }
+
+        let rpc_addr = rpc_endpoints.current().clone();
+
+        let light_client = TmLightClient::from_config(
+            &ChainConfig {
+                rpc_addr: rpc_addr.clone(),
+                ..config.clone()
+            },
+            node_info.id,
+        )?;
 
         // Initialize key store and load key
-        let keybase = KeyRing::new_secp256k1(
+        let keybase = KeyRing::new_cosmos(
             config.key_store_type,
             &config.account_prefix,
             &config.id,
@@ -891,23 +1273,83 @@ impl ChainEndpoint for CosmosSdkChain {
         )
         .map_err(Error::key_base)?;
 
-        let grpc_addr = Uri::from_str(&config.grpc_addr.to_string())
-            .map_err(|e| Error::invalid_uri(config.grpc_addr.to_string(), e))?;
+        let parse_grpc_addr = |addr: &Url| {
+            Uri::from_str(&addr.to_string()).map_err(|e| Error::invalid_uri(addr.to_string(), e))
+        };
+
+        let grpc_endpoints = EndpointPool::new(
+            config.id.clone(),
+            EndpointKind::Grpc,
+            parse_grpc_addr(&config.grpc_addr)?,
+            config
+                .failover
+                .grpc_addrs
+                .iter()
+                .map(parse_grpc_addr)
+                .collect::<Result<Vec<_>, _>>()?,
+        )
+        .shared();
+
+        let websocket_endpoints = match &config.event_source {
+            EventSourceMode::Push { url, .. } => Some(
+                EndpointPool::new(
+                    config.id.clone(),
+                    EndpointKind::WebSocket,
+                    url.clone(),
+                    config.failover.websocket_addrs.iter().cloned(),
+                )
+                .shared(),
+            ),
+            EventSourceMode::Pull { .. } | EventSourceMode::Stream { .. } => None,
+        };
+
+        let rpc_endpoints = rpc_endpoints.shared();
+
+        let endpoint_probe = config.failover.has_backups().then(|| {
+            EndpointProbe {
+                chain_id: config.id.clone(),
+                rt: rt.clone(),
+                timeout: config.rpc_timeout,
+                max_height_lag: config.failover.max_height_lag,
+                rpc: rpc_endpoints.clone(),
+                grpc: grpc_endpoints.clone(),
+                websocket: websocket_endpoints.clone(),
+            }
+            .spawn(config.failover.probe_interval)
+        });
+
+        let mut tx_config = TxConfig::try_from(&config)?;
+        tx_config.rpc_address = rpc_addr;
 
-        let tx_config = TxConfig::try_from(&config)?;
+        let dry_run_report = config
+            .dry_run
+            .then(|| SharedDryRunReport::new_lock(DryRunReport::new(Instant::now())));
+
+        let dry_run_reporter = dry_run_report.as_ref().map(|report| {
+            DryRunReporter {
+                chain_id: config.id.clone(),
+                report: report.clone(),
+            }
+            .spawn(DRY_RUN_REPORT_INTERVAL)
+        });
 
         // Retrieve the version specification of this chain
 
         let chain = Self {
             config,
-            rpc_client,
+            rpc_endpoints,
+            rpc_clients,
             compat_mode,
-            grpc_addr,
+            grpc_endpoints,
+            websocket_endpoints,
+            _endpoint_probe: endpoint_probe,
             light_client,
             rt,
             keybase,
             tx_config,
-            account: None,
+            wallets: None,
+            dry_run_report,
+            _dry_run_reporter: dry_run_reporter,
             tx_monitor_cmd: None,
         };
 
@@ -972,9 +1414,37 @@ impl ChainEndpoint for CosmosSdkChain {
             return Ok(HealthCheck::Unhealthy(Box::new(e)));
         }
 
+        if let Err(e) = self.validate_fee_grants() {
+            warn!("Fee grant check for chain '{}' failed", self.id());
+            warn!("    Reason: {}", e.detail());
+            warn!("    Transactions paid for by the fee granter will be rejected!");
+
+            return Ok(HealthCheck::Unhealthy(Box::new(e)));
+        }
+
+        if let Err(e) = self.validate_authz_grants() {
+            warn!("Authz grant check for chain '{}' failed", self.id());
+            warn!("    Reason: {}", e.detail());
+            warn!("    Messages executed on behalf of the authz granter will be rejected!");
+
+            return Ok(HealthCheck::Unhealthy(Box::new(e)));
+        }
+
         Ok(HealthCheck::Healthy)
     }
 
+    fn report_error(&self, error: &Error) {
+        match error.detail() {
+            ErrorDetail::Rpc(e) if is_rpc_endpoint_failure(&e.source) => {
+                self.rpc_endpoints.acquire_write().report_failure(&e.url);
+            }
+            detail if is_grpc_endpoint_failure(detail) => {
+                self.grpc_endpoints.acquire_write().report_current_failure();
+            }
+            _ => {}
+        }
+    }
+
     /// Fetch a header from the chain at the given height and verify it.
     fn verify_header(
         &mut self,
@@ -989,6 +1459,8 @@ impl ChainEndpoint for CosmosSdkChain {
             }
         );
 
+        self.sync_endpoints()?;
+
         let now = self.chain_status()?.sync_info.latest_block_time;
 
         self.light_client
@@ -1009,6 +1481,8 @@ impl ChainEndpoint for CosmosSdkChain {
             }
         );
 
+        self.sync_endpoints()?;
+
         let now = self.chain_status()?.sync_info.latest_block_time;
 
         self.light_client
@@ -1029,6 +1503,8 @@ impl ChainEndpoint for CosmosSdkChain {
         &mut self,
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.sync_endpoints()?;
+
         let runtime = self.rt.clone();
 
         runtime.block_on(self.do_send_messages_and_wait_commit(tracked_msgs))
@@ -1038,6 +1514,8 @@ impl ChainEndpoint for CosmosSdkChain {
         &mut self,
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<Response>, Error> {
+        self.sync_endpoints()?;
+
         let runtime = self.rt.clone();
 
         runtime.block_on(self.do_send_messages_and_wait_check_tx(tracked_msgs))
@@ -1053,13 +1531,103 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(signer)
     }
 
+    fn rotate_key(&mut self, key_name: &str) -> Result<(), Error> {
+        if self.config.key_name.primary() == key_name {
+            return Err(Error::key_already_primary(key_name.to_string()));
+        }
+
+        let key_pair = self
+            .keybase
+            .get_key(key_name)
+            .map_err(|e| Error::key_not_found(key_name.to_string(), e))?;
+
+        if key_pair.is_ledger() {
+            return Err(Error::ledger_key_not_relayable(key_name.to_string()));
+        }
+
+        info!(
+            chain = %self.id(),
+            old_key_name = %self.config.key_name.primary(),
+            new_key_name = %key_name,
+            address = %key_pair.account(),
+            "rotating the primary key of the chain"
+        );
+
+        self.config.key_name = self.config.key_name.with_primary(key_name);
+
+        // The wallets are loaded again, with the new key, before sending the next transaction
+        self.wallets = None;
+
+        Ok(())
+    }
+
+    fn sweep_balances(&mut self, key_name: &str) -> Result<Vec<Balance>, Error> {
+        let key_pair = self.keybase.get_key(key_name).map_err(Error::key_base)?;
+        let to_address = self.key()?.account();
+
+        if key_pair.account() == to_address {
+            return Err(Error::key_already_primary(key_name.to_string()));
+        }
+
+        let tx_config = match self.config.fee_granter_for(key_name) {
+            Some(granter) if granter != self.tx_config.gas_config.fee_granter => {
+                self.tx_config.with_fee_granter(granter)
+            }
+            _ => self.tx_config.clone(),
+        };
+
+        // Unless a fee granter pays for the transfer, the key keeps enough to pay for it
+        let kept_fee = if tx_config.gas_config.fee_granter.is_empty() {
+            tx_config.gas_config.max_fee.clone()
+        } else {
+            Default::default()
+        };
+
+        let balances = self.query_all_balances(Some(key_name))?;
+        let coins = sweepable_coins(&balances, &kept_fee);
+
+        if coins.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        if self.config.dry_run {
+            info!(
+                chain = %self.id(),
+                "[dry-run] skipping sweep of the balances of key `{}` to {}",
+                key_name,
+                to_address,
+            );
+
+            return Ok(Vec::new());
+        }
+
+        self.sync_endpoints()?;
+
+        self.rt.block_on(sweep_coins(
+            self.rpc_client(),
+            &tx_config,
+            &key_pair,
+            &self.config.memo_prefix,
+            &to_address,
+            coins.clone(),
+        ))?;
+
+        Ok(coins
+            .into_iter()
+            .map(|coin| Balance {
+                amount: coin.amount.to_string(),
+                denom: coin.denom,
+            })
+            .collect())
+    }
+
     /// Get the chain configuration
     fn config(&self) -> &ChainConfig {
         &self.config
     }
 
     fn ibc_version(&self) -> Result<Option<semver::Version>, Error> {
-        let version_specs = self.block_on(fetch_version_specs(self.id(), &self.grpc_addr))?;
+        let version_specs = self.block_on(fetch_version_specs(self.id(), &self.grpc_addr()))?;
         Ok(version_specs.ibc_go)
     }
 
@@ -1073,7 +1641,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let account = key.account();
 
         let denom = denom.unwrap_or(&self.config.gas_price.denom);
-        let balance = self.block_on(query_balance(&self.grpc_addr, &account, denom))?;
+        let balance = self.block_on(query_balance(&self.grpc_addr(), &account, denom))?;
 
         Ok(balance)
     }
@@ -1087,13 +1655,29 @@ impl ChainEndpoint for CosmosSdkChain {
         };
         let account = key.account();
 
-        let balance = self.block_on(query_all_balances(&self.grpc_addr, &account))?;
+        let balance = self.block_on(query_all_balances(&self.grpc_addr(), &account))?;
 
         Ok(balance)
     }
 
+    fn query_fee_allowance(&self, key_name: Option<&str>) -> Result<Option<FeeAllowance>, Error> {
+        let key_name = key_name.unwrap_or_else(|| self.config.key_name.primary());
+
+        let granter = self.config.fee_granter_for(key_name).ok_or_else(|| {
+            Error::fee_granter_not_configured(self.id().clone(), key_name.to_string())
+        })?;
+
+        let key = self.keybase().get_key(key_name).map_err(Error::key_base)?;
+
+        self.block_on(query_fee_allowance(
+            &self.grpc_addr(),
+            granter,
+            &key.account(),
+        ))
+    }
+
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
-        let denom_trace = self.block_on(query_denom_trace(&self.grpc_addr, &hash))?;
+        let denom_trace = self.block_on(query_denom_trace(&self.grpc_addr(), &hash))?;
 
         Ok(denom_trace)
     }
@@ -1126,13 +1710,13 @@ impl ChainEndpoint for CosmosSdkChain {
         // Instead, we need to pull block height via `/abci_info` and then fetch block
         // metadata at the given height via `/blockchain` endpoint.
         let abci_info = self
-            .block_on(self.rpc_client.abci_info())
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .block_on(self.rpc_client().abci_info())
+            .map_err(|e| Error::rpc(self.rpc_addr(), e))?;
 
         // Query `/header` endpoint to pull the latest block that the application committed.
         let response = self
-            .block_on(self.rpc_client.header(abci_info.last_block_height))
-            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+            .block_on(self.rpc_client().header(abci_info.last_block_height))
+            .map_err(|e| Error::rpc(self.rpc_addr(), e))?;
 
         let height = ICSHeight::new(
             ChainId::chain_version(response.header.chain_id.as_str()),
@@ -1144,6 +1728,21 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(ChainStatus { height, timestamp })
     }
 
+    /// The latest heights of the healthy RPC endpoints not in use, as last recorded by the
+    /// endpoint probe, so that no request is made to the possibly unresponsive backups here
+    fn query_alternate_heights(&self) -> Vec<u64> {
+        let rpc_endpoints = self.rpc_endpoints.acquire_read();
+        let current = rpc_endpoints.current_index();
+
+        rpc_endpoints
+            .endpoints()
+            .iter()
+            .enumerate()
+            .filter(|(index, endpoint)| *index != current && endpoint.health.healthy)
+            .filter_map(|(_, endpoint)| endpoint.health.latest_height)
+            .collect()
+    }
+
     fn query_clients(
         &self,
         request: QueryClientStatesRequest,
@@ -1159,7 +1758,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::client::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1289,7 +1888,7 @@ impl ChainEndpoint for CosmosSdkChain {
     ) -> Result<Vec<ICSHeight>, Error> {
         self.block_on(query_consensus_state_heights(
             self.id(),
-            &self.grpc_addr,
+            &self.grpc_addr(),
             request,
         ))
     }
@@ -1350,7 +1949,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::connection::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1394,7 +1993,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::connection::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1449,10 +2048,9 @@ impl ChainEndpoint for CosmosSdkChain {
             use ibc_proto::ibc::core::connection::v1 as connection;
             use tonic::IntoRequest;
 
-            let mut client =
-                connection::query_client::QueryClient::connect(chain.grpc_addr.clone())
-                    .await
-                    .map_err(Error::grpc_transport)?;
+            let mut client = connection::query_client::QueryClient::connect(chain.grpc_addr())
+                .await
+                .map_err(Error::grpc_transport)?;
 
             client = client.max_decoding_message_size(
                 chain.config().max_grpc_decoding_size.get_bytes() as usize,
@@ -1531,7 +2129,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1579,7 +2177,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1658,7 +2256,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1721,7 +2319,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1792,7 +2390,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1860,7 +2458,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1905,7 +2503,7 @@ impl ChainEndpoint for CosmosSdkChain {
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
+                    self.grpc_addr(),
                 ),
             )
             .map_err(Error::grpc_transport)?;
@@ -1964,7 +2562,7 @@ impl ChainEndpoint for CosmosSdkChain {
                 let mut client = self
                     .block_on(
                         ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                            self.grpc_addr.clone(),
+                            self.grpc_addr(),
                         ),
                     )
                     .map_err(Error::grpc_transport)?;
@@ -1997,8 +2595,8 @@ impl ChainEndpoint for CosmosSdkChain {
 
         self.block_on(query_txs(
             self.id(),
-            &self.rpc_client,
-            &self.config.rpc_addr,
+            self.rpc_client(),
+            &self.rpc_addr(),
             request,
         ))
     }
@@ -2030,15 +2628,16 @@ impl ChainEndpoint for CosmosSdkChain {
             // user passes the flag `packet-data-query-height`.
             Qualified::Equal(_) => self.block_on(query_packets_from_block(
                 self.id(),
-                &self.rpc_client,
-                &self.config.rpc_addr,
+                self.rpc_client(),
+                self.compat_mode,
+                &self.rpc_addr(),
                 &request,
             )),
             Qualified::SmallerEqual(_) => {
                 let tx_events = self.block_on(query_packets_from_txs(
                     self.id(),
-                    &self.rpc_client,
-                    &self.config.rpc_addr,
+                    self.rpc_client(),
+                    &self.rpc_addr(),
                     &request,
                 ))?;
 
@@ -2084,6 +2683,26 @@ impl ChainEndpoint for CosmosSdkChain {
         }
     }
 
+    fn query_block_packet_events(
+        &self,
+        request: QueryBlockPacketEventsRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        crate::time!(
+            "query_block_packet_events",
+            {
+                "src_chain": self.config().id.to_string(),
+            }
+        );
+
+        self.block_on(query_block_packet_events(
+            self.id(),
+            self.rpc_client(),
+            self.compat_mode,
+            &self.rpc_addr(),
+            &request,
+        ))
+    }
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -2095,21 +2714,21 @@ impl ChainEndpoint for CosmosSdkChain {
 
         let header = if height.value() == 0 {
             self.block_on(async {
-                self.rpc_client
+                self.rpc_client()
                     .latest_block()
                     .await
                     .map(|response| response.block.header)
             })
         } else {
             self.block_on(async {
-                self.rpc_client
+                self.rpc_client()
                     .header(height)
                     .await
                     .map(|response| response.header)
             })
         };
 
-        let header = header.map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+        let header = header.map_err(|e| Error::rpc(self.rpc_addr(), e))?;
         Ok(header.into())
     }
 
@@ -2171,6 +2790,8 @@ impl ChainEndpoint for CosmosSdkChain {
             }
         );
 
+        self.sync_endpoints()?;
+
         let now = self.chain_status()?.sync_info.latest_block_time;
 
         // Get the light block at target_height from chain.
@@ -2190,14 +2811,40 @@ impl ChainEndpoint for CosmosSdkChain {
         port_id: &PortId,
         counterparty_payee: &Signer,
     ) -> Result<(), Error> {
+        if self.config.dry_run {
+            info!(
+                chain = %self.id(),
+                "[dry-run] skipping registration of counterparty payee `{}` on channel {}/{}",
+                counterparty_payee,
+                port_id,
+                channel_id,
+            );
+
+            return Ok(());
+        }
+
         let address = self.get_signer()?;
         let key_pair = self.key()?;
 
+        self.sync_endpoints()?;
+        self.load_wallets()?;
+
+        let rpc_client = self.rpc_client().clone();
+
+        // The payee is registered for the relayer's address, hence with the primary wallet
+        let primary = self
+            .wallets
+            .as_mut()
+            .expect("wallets were just loaded")
+            .wallet_mut(0);
+
+        let tx_config = primary.tx_config.as_ref().unwrap_or(&self.tx_config);
+
         self.rt.block_on(maybe_register_counterparty_payee(
-            &self.rpc_client,
-            &self.tx_config,
+            &rpc_client,
+            tx_config,
             &key_pair,
-            &mut self.account,
+            &mut primary.account,
             &self.config.memo_prefix,
             channel_id,
             port_id,
@@ -2212,7 +2859,7 @@ impl ChainEndpoint for CosmosSdkChain {
     ) -> Result<Vec<CrossChainQueryResponse>, Error> {
         let tasks = requests
             .into_iter()
-            .map(|req| cross_chain_query_via_rpc(&self.rpc_client, req))
+            .map(|req| cross_chain_query_via_rpc(self.rpc_client(), req))
             .collect::<Vec<_>>();
 
         let joined_tasks = join_all(tasks);
@@ -2225,16 +2872,53 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(responses)
     }
 
+    fn query_pending_cross_chain_queries(
+        &self,
+        request: QueryPendingCrossChainQueriesRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        crate::telemetry!(query, self.id(), "query_pending_cross_chain_queries");
+
+        self.block_on(query_pending_cross_chain_queries(
+            self.rpc_client(),
+            &self.rpc_addr(),
+            self.compat_mode,
+            self.id(),
+            request.blocks,
+        ))
+    }
+
     fn query_incentivized_packet(
         &self,
         request: QueryIncentivizedPacketRequest,
     ) -> Result<QueryIncentivizedPacketResponse, Error> {
         let incentivized_response =
-            self.block_on(query_incentivized_packet(&self.grpc_addr, request))?;
+            self.block_on(query_incentivized_packet(&self.grpc_addr(), request))?;
         Ok(incentivized_response)
     }
 }
 
+/// Simulate the given messages instead of submitting them, and
+/// add the projected spend of the transactions to the dry-run report.
+async fn simulate_messages(
+    report: &SharedDryRunReport,
+    tx_config: &TxConfig,
+    key_pair: &CosmosKeyPair,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: Vec<Any>,
+) -> Result<(), Error> {
+    let simulated_txs =
+        simulate_batched_messages(tx_config, key_pair, account, tx_memo, messages).await?;
+
+    let mut report = report.acquire_write();
+
+    for tx in &simulated_txs {
+        report.record(tx);
+    }
+
+    Ok(())
+}
+
 fn sort_events_by_sequence(events: &mut [IbcEventWithHeight]) {
     events.sort_by(|a, b| {
         a.event
@@ -2247,18 +2931,19 @@ fn sort_events_by_sequence(events: &mut [IbcEventWithHeight]) {
 
 async fn fetch_node_info(
     rpc_client: &HttpClient,
-    config: &ChainConfig,
+    chain_id: &ChainId,
+    rpc_addr: &Url,
 ) -> Result<node::Info, Error> {
     crate::time!("fetch_node_info",
     {
-        "src_chain": config.id.to_string(),
+        "src_chain": chain_id.to_string(),
     });
 
     rpc_client
         .status()
         .await
         .map(|s| s.node_info)
-        .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))
+        .map_err(|e| Error::rpc(rpc_addr.clone(), e))
 }
 
 /// Returns the suffix counter for a CosmosSDK client id.
@@ -2286,10 +2971,10 @@ fn client_id_suffix(client_id: &ClientId) -> Option<u64> {
 ///    advertised by the node Hermes is connected to.
 fn do_health_check(chain: &CosmosSdkChain) -> Result<(), Error> {
     let chain_id = chain.id();
-    let grpc_address = chain.grpc_addr.to_string();
-    let rpc_address = chain.config.rpc_addr.to_string();
+    let grpc_address = chain.grpc_addr().to_string();
+    let rpc_address = chain.rpc_addr().to_string();
 
-    chain.block_on(chain.rpc_client.health()).map_err(|e| {
+    chain.block_on(chain.rpc_client().health()).map_err(|e| {
         Error::health_check_json_rpc(
             chain_id.clone(),
             rpc_address.clone(),
@@ -2348,7 +3033,8 @@ fn do_health_check(chain: &CosmosSdkChain) -> Result<(), Error> {
         );
     }
 
-    let version_specs = chain.block_on(fetch_version_specs(&chain.config.id, &chain.grpc_addr))?;
+    let version_specs =
+        chain.block_on(fetch_version_specs(&chain.config.id, &chain.grpc_addr()))?;
 
     if let Err(diagnostic) = compatibility::run_diagnostic(&version_specs) {
         return Err(Error::sdk_module_version(
@@ -0,0 +1,1161 @@
+//! Encoding of transactions for the amino-JSON sign mode (`SIGN_MODE_LEGACY_AMINO_JSON`),
+//! the only sign mode supported by the Cosmos application of Ledger devices.
+//!
+//! In this sign mode, the signer signs a `StdSignDoc`, i.e. a JSON document with sorted keys
+//! and without whitespace which holds the amino-JSON encoding of the messages of the transaction.
+//! As protobuf messages cannot be encoded in amino-JSON without reflection, only the messages
+//! which are worth signing on a hardware wallet are supported: token transfers, the creation,
+//! upgrade and update of Tendermint clients, and connection and channel handshakes.
+//! Any other message is rejected before anything is sent to the device.
+//!
+//! The encoding follows the rules of the Cosmos SDK: 64-bit integers are encoded as strings,
+//! bytes in base64, empty scalar fields are omitted and messages without a registered amino
+//! name are identified by their type URL.
+
+use ibc_proto::cosmos::bank::v1beta1::MsgSend;
+use ibc_proto::cosmos::base::v1beta1::Coin;
+use ibc_proto::cosmos::tx::v1beta1::Fee;
+use ibc_proto::google::protobuf::{Any, Duration as ProtoDuration, Timestamp as ProtoTimestamp};
+use ibc_proto::ibc::applications::transfer::v1::MsgTransfer;
+use ibc_proto::ibc::core::channel::v1::{
+    Channel, MsgChannelCloseConfirm, MsgChannelCloseInit, MsgChannelOpenAck, MsgChannelOpenConfirm,
+    MsgChannelOpenInit, MsgChannelOpenTry,
+};
+use ibc_proto::ibc::core::client::v1::{
+    Height, MsgCreateClient, MsgUpdateClient, MsgUpgradeClient,
+};
+use ibc_proto::ibc::core::connection::v1::{
+    Counterparty as ConnectionCounterparty, MsgConnectionOpenAck, MsgConnectionOpenConfirm,
+    MsgConnectionOpenInit, MsgConnectionOpenTry, Version as ConnectionVersion,
+};
+use ibc_proto::ibc::lightclients::tendermint::v1::{
+    ClientState as TmClientState, ConsensusState as TmConsensusState, Header,
+};
+use ibc_proto::ics23::ProofSpec;
+use ibc_relayer_types::applications::transfer::msgs::{send, transfer};
+use ibc_relayer_types::clients::ics07_tendermint::client_state::TENDERMINT_CLIENT_STATE_TYPE_URL;
+use ibc_relayer_types::clients::ics07_tendermint::consensus_state::TENDERMINT_CONSENSUS_STATE_TYPE_URL;
+use ibc_relayer_types::clients::ics07_tendermint::header::TENDERMINT_HEADER_TYPE_URL;
+use ibc_relayer_types::core::ics02_client::msgs::{create_client, update_client, upgrade_client};
+use ibc_relayer_types::core::ics03_connection::msgs::{
+    conn_open_ack, conn_open_confirm, conn_open_init, conn_open_try,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::{
+    chan_close_confirm, chan_close_init, chan_open_ack, chan_open_confirm, chan_open_init,
+    chan_open_try,
+};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use prost::Message;
+use serde_json::{json, Map, Value};
+use subtle_encoding::base64;
+use tendermint_proto::crypto::{public_key, PublicKey};
+use tendermint_proto::google::protobuf::Timestamp;
+use tendermint_proto::types::{
+    BlockId, Commit, CommitSig, Header as BlockHeader, SignedHeader, Validator, ValidatorSet,
+};
+
+use crate::chain::cosmos::types::account::{AccountNumber, AccountSequence};
+use crate::config::types::Memo;
+use crate::error::Error;
+
+/// Encode the `StdSignDoc` of a transaction, to be signed in the amino-JSON sign mode
+pub fn encode_sign_doc(
+    chain_id: &ChainId,
+    account_number: AccountNumber,
+    sequence: AccountSequence,
+    fee: &Fee,
+    memo: &Memo,
+    messages: &[Any],
+) -> Result<Vec<u8>, Error> {
+    let msgs = messages
+        .iter()
+        .map(encode_message)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // `serde_json` sorts the keys of the objects it serializes
+    let sign_doc = json!({
+        "account_number": account_number.to_u64().to_string(),
+        "chain_id": chain_id.to_string(),
+        "fee": encode_fee(fee),
+        "memo": memo.to_string(),
+        "msgs": msgs,
+        "sequence": sequence.to_u64().to_string(),
+    });
+
+    Ok(escape_html(&sign_doc.to_string()).into_bytes())
+}
+
+/// Escape the characters which the Go JSON encoder escapes
+/// when producing the sign bytes of the Cosmos SDK
+fn escape_html(json: &str) -> String {
+    json.replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+}
+
+fn encode_fee(fee: &Fee) -> Value {
+    Fields::default()
+        .value(
+            "amount",
+            Value::Array(fee.amount.iter().map(encode_coin).collect()),
+        )
+        .value("gas", fee.gas_limit.to_string().into())
+        .string("granter", &fee.granter)
+        .string("payer", &fee.payer)
+        .build()
+}
+
+fn encode_message(message: &Any) -> Result<Value, Error> {
+    let (name, value) = match message.type_url.as_str() {
+        transfer::TYPE_URL => ("cosmos-sdk/MsgTransfer", encode_transfer(decode(message)?)),
+        send::TYPE_URL => ("cosmos-sdk/MsgSend", encode_send(decode(message)?)),
+        create_client::TYPE_URL => (
+            create_client::TYPE_URL,
+            encode_create_client(decode(message)?)?,
+        ),
+        update_client::TYPE_URL => (
+            update_client::TYPE_URL,
+            encode_update_client(decode(message)?)?,
+        ),
+        upgrade_client::TYPE_URL => (
+            upgrade_client::TYPE_URL,
+            encode_upgrade_client(decode(message)?)?,
+        ),
+        conn_open_init::TYPE_URL => (
+            conn_open_init::TYPE_URL,
+            encode_conn_open_init(decode(message)?),
+        ),
+        conn_open_try::TYPE_URL => (
+            conn_open_try::TYPE_URL,
+            encode_conn_open_try(decode(message)?)?,
+        ),
+        conn_open_ack::TYPE_URL => (
+            conn_open_ack::TYPE_URL,
+            encode_conn_open_ack(decode(message)?)?,
+        ),
+        conn_open_confirm::TYPE_URL => (
+            conn_open_confirm::TYPE_URL,
+            encode_conn_open_confirm(decode(message)?),
+        ),
+        chan_open_init::TYPE_URL => (
+            chan_open_init::TYPE_URL,
+            encode_chan_open_init(decode(message)?),
+        ),
+        chan_open_try::TYPE_URL => (
+            chan_open_try::TYPE_URL,
+            encode_chan_open_try(decode(message)?),
+        ),
+        chan_open_ack::TYPE_URL => (
+            chan_open_ack::TYPE_URL,
+            encode_chan_open_ack(decode(message)?),
+        ),
+        chan_open_confirm::TYPE_URL => (
+            chan_open_confirm::TYPE_URL,
+            encode_chan_open_confirm(decode(message)?),
+        ),
+        chan_close_init::TYPE_URL => (
+            chan_close_init::TYPE_URL,
+            encode_chan_close_init(decode(message)?),
+        ),
+        chan_close_confirm::TYPE_URL => (
+            chan_close_confirm::TYPE_URL,
+            encode_chan_close_confirm(decode(message)?),
+        ),
+        type_url => return Err(Error::amino_json_unsupported_message(type_url.to_string())),
+    };
+
+    Ok(json!({ "type": name, "value": value }))
+}
+
+fn decode<M: Message + Default>(message: &Any) -> Result<M, Error> {
+    M::decode(message.value.as_slice())
+        .map_err(|e| Error::protobuf_decode(message.type_url.clone(), e))
+}
+
+fn encode_transfer(msg: MsgTransfer) -> Value {
+    // The token and the timeout height are encoded even when empty
+    Fields::default()
+        .string("source_port", &msg.source_port)
+        .string("source_channel", &msg.source_channel)
+        .value(
+            "token",
+            msg.token.as_ref().map(encode_coin).unwrap_or_else(empty),
+        )
+        .string("sender", &msg.sender)
+        .string("receiver", &msg.receiver)
+        .value(
+            "timeout_height",
+            msg.timeout_height
+                .as_ref()
+                .map(encode_height)
+                .unwrap_or_else(empty),
+        )
+        .uint64("timeout_timestamp", msg.timeout_timestamp)
+        .string("memo", &msg.memo)
+        .build()
+}
+
+fn encode_send(msg: MsgSend) -> Value {
+    Fields::default()
+        .string("from_address", &msg.from_address)
+        .string("to_address", &msg.to_address)
+        .value(
+            "amount",
+            Value::Array(msg.amount.iter().map(encode_coin).collect()),
+        )
+        .build()
+}
+
+fn encode_create_client(msg: MsgCreateClient) -> Result<Value, Error> {
+    Ok(Fields::default()
+        .message(
+            "client_state",
+            msg.client_state
+                .as_ref()
+                .map(encode_client_state)
+                .transpose()?,
+        )
+        .message(
+            "consensus_state",
+            msg.consensus_state
+                .as_ref()
+                .map(encode_consensus_state)
+                .transpose()?,
+        )
+        .string("signer", &msg.signer)
+        .build())
+}
+
+fn encode_upgrade_client(msg: MsgUpgradeClient) -> Result<Value, Error> {
+    Ok(Fields::default()
+        .string("client_id", &msg.client_id)
+        .message(
+            "client_state",
+            msg.client_state
+                .as_ref()
+                .map(encode_client_state)
+                .transpose()?,
+        )
+        .message(
+            "consensus_state",
+            msg.consensus_state
+                .as_ref()
+                .map(encode_consensus_state)
+                .transpose()?,
+        )
+        .bytes("proof_upgrade_client", &msg.proof_upgrade_client)
+        .bytes(
+            "proof_upgrade_consensus_state",
+            &msg.proof_upgrade_consensus_state,
+        )
+        .string("signer", &msg.signer)
+        .build())
+}
+
+fn encode_update_client(msg: MsgUpdateClient) -> Result<Value, Error> {
+    let client_message = match msg.header {
+        None => None,
+        Some(header) if header.type_url == TENDERMINT_HEADER_TYPE_URL => Some(json!({
+            "type": TENDERMINT_HEADER_TYPE_URL,
+            "value": encode_tendermint_header(decode(&header)?),
+        })),
+        Some(header) => {
+            return Err(Error::amino_json_unsupported_message(header.type_url));
+        }
+    };
+
+    Ok(Fields::default()
+        .string("client_id", &msg.client_id)
+        .message("client_message", client_message)
+        .string("signer", &msg.signer)
+        .build())
+}
+
+fn encode_conn_open_init(msg: MsgConnectionOpenInit) -> Value {
+    Fields::default()
+        .string("client_id", &msg.client_id)
+        .message(
+            "counterparty",
+            msg.counterparty
+                .as_ref()
+                .map(encode_connection_counterparty),
+        )
+        .message(
+            "version",
+            msg.version.as_ref().map(encode_connection_version),
+        )
+        .uint64("delay_period", msg.delay_period)
+        .string("signer", &msg.signer)
+        .build()
+}
+
+#[allow(deprecated)]
+fn encode_conn_open_try(msg: MsgConnectionOpenTry) -> Result<Value, Error> {
+    Ok(Fields::default()
+        .string("client_id", &msg.client_id)
+        .string("previous_connection_id", &msg.previous_connection_id)
+        .message(
+            "client_state",
+            msg.client_state
+                .as_ref()
+                .map(encode_client_state)
+                .transpose()?,
+        )
+        .message(
+            "counterparty",
+            msg.counterparty
+                .as_ref()
+                .map(encode_connection_counterparty),
+        )
+        .uint64("delay_period", msg.delay_period)
+        .values(
+            "counterparty_versions",
+            msg.counterparty_versions
+                .iter()
+                .map(encode_connection_version)
+                .collect(),
+        )
+        .message("proof_height", msg.proof_height.as_ref().map(encode_height))
+        .bytes("proof_init", &msg.proof_init)
+        .bytes("proof_client", &msg.proof_client)
+        .bytes("proof_consensus", &msg.proof_consensus)
+        .message(
+            "consensus_height",
+            msg.consensus_height.as_ref().map(encode_height),
+        )
+        .string("signer", &msg.signer)
+        .build())
+}
+
+fn encode_conn_open_ack(msg: MsgConnectionOpenAck) -> Result<Value, Error> {
+    Ok(Fields::default()
+        .string("connection_id", &msg.connection_id)
+        .string(
+            "counterparty_connection_id",
+            &msg.counterparty_connection_id,
+        )
+        .message(
+            "version",
+            msg.version.as_ref().map(encode_connection_version),
+        )
+        .message(
+            "client_state",
+            msg.client_state
+                .as_ref()
+                .map(encode_client_state)
+                .transpose()?,
+        )
+        .message("proof_height", msg.proof_height.as_ref().map(encode_height))
+        .bytes("proof_try", &msg.proof_try)
+        .bytes("proof_client", &msg.proof_client)
+        .bytes("proof_consensus", &msg.proof_consensus)
+        .message(
+            "consensus_height",
+            msg.consensus_height.as_ref().map(encode_height),
+        )
+        .string("signer", &msg.signer)
+        .build())
+}
+
+fn encode_conn_open_confirm(msg: MsgConnectionOpenConfirm) -> Value {
+    Fields::default()
+        .string("connection_id", &msg.connection_id)
+        .bytes("proof_ack", &msg.proof_ack)
+        .message("proof_height", msg.proof_height.as_ref().map(encode_height))
+        .string("signer", &msg.signer)
+        .build()
+}
+
+fn encode_connection_counterparty(counterparty: &ConnectionCounterparty) -> Value {
+    Fields::default()
+        .string("client_id", &counterparty.client_id)
+        .string("connection_id", &counterparty.connection_id)
+        .message(
+            "prefix",
+            counterparty.prefix.as_ref().map(|prefix| {
+                Fields::default()
+                    .bytes("key_prefix", &prefix.key_prefix)
+                    .build()
+            }),
+        )
+        .build()
+}
+
+fn encode_connection_version(version: &ConnectionVersion) -> Value {
+    Fields::default()
+        .string("identifier", &version.identifier)
+        .strings("features", &version.features)
+        .build()
+}
+
+fn encode_chan_open_init(msg: MsgChannelOpenInit) -> Value {
+    Fields::default()
+        .string("port_id", &msg.port_id)
+        .message("channel", msg.channel.as_ref().map(encode_channel))
+        .string("signer", &msg.signer)
+        .build()
+}
+
+#[allow(deprecated)]
+fn encode_chan_open_try(msg: MsgChannelOpenTry) -> Value {
+    Fields::default()
+        .string("port_id", &msg.port_id)
+        .string("previous_channel_id", &msg.previous_channel_id)
+        .message("channel", msg.channel.as_ref().map(encode_channel))
+        .string("counterparty_version", &msg.counterparty_version)
+        .bytes("proof_init", &msg.proof_init)
+        .message("proof_height", msg.proof_height.as_ref().map(encode_height))
+        .string("signer", &msg.signer)
+        .build()
+}
+
+fn encode_chan_open_ack(msg: MsgChannelOpenAck) -> Value {
+    Fields::default()
+        .string("port_id", &msg.port_id)
+        .string("channel_id", &msg.channel_id)
+        .string("counterparty_channel_id", &msg.counterparty_channel_id)
+        .string("counterparty_version", &msg.counterparty_version)
+        .bytes("proof_try", &msg.proof_try)
+        .message("proof_height", msg.proof_height.as_ref().map(encode_height))
+        .string("signer", &msg.signer)
+        .build()
+}
+
+fn encode_chan_open_confirm(msg: MsgChannelOpenConfirm) -> Value {
+    Fields::default()
+        .string("port_id", &msg.port_id)
+        .string("channel_id", &msg.channel_id)
+        .bytes("proof_ack", &msg.proof_ack)
+        .message("proof_height", msg.proof_height.as_ref().map(encode_height))
+        .string("signer", &msg.signer)
+        .build()
+}
+
+fn encode_chan_close_init(msg: MsgChannelCloseInit) -> Value {
+    Fields::default()
+        .string("port_id", &msg.port_id)
+        .string("channel_id", &msg.channel_id)
+        .string("signer", &msg.signer)
+        .build()
+}
+
+fn encode_chan_close_confirm(msg: MsgChannelCloseConfirm) -> Value {
+    Fields::default()
+        .string("port_id", &msg.port_id)
+        .string("channel_id", &msg.channel_id)
+        .bytes("proof_init", &msg.proof_init)
+        .message("proof_height", msg.proof_height.as_ref().map(encode_height))
+        .string("signer", &msg.signer)
+        .build()
+}
+
+fn encode_channel(channel: &Channel) -> Value {
+    Fields::default()
+        .int("state", channel.state.into())
+        .int("ordering", channel.ordering.into())
+        .message(
+            "counterparty",
+            channel.counterparty.as_ref().map(|counterparty| {
+                Fields::default()
+                    .string("port_id", &counterparty.port_id)
+                    .string("channel_id", &counterparty.channel_id)
+                    .build()
+            }),
+        )
+        .strings("connection_hops", &channel.connection_hops)
+        .string("version", &channel.version)
+        .build()
+}
+
+/// Encode a client state, which must be the one of a Tendermint client
+fn encode_client_state(client_state: &Any) -> Result<Value, Error> {
+    if client_state.type_url != TENDERMINT_CLIENT_STATE_TYPE_URL {
+        return Err(Error::amino_json_unsupported_message(
+            client_state.type_url.clone(),
+        ));
+    }
+
+    Ok(json!({
+        "type": TENDERMINT_CLIENT_STATE_TYPE_URL,
+        "value": encode_tendermint_client_state(decode(client_state)?),
+    }))
+}
+
+/// Encode a consensus state, which must be the one of a Tendermint client
+fn encode_consensus_state(consensus_state: &Any) -> Result<Value, Error> {
+    if consensus_state.type_url != TENDERMINT_CONSENSUS_STATE_TYPE_URL {
+        return Err(Error::amino_json_unsupported_message(
+            consensus_state.type_url.clone(),
+        ));
+    }
+
+    Ok(json!({
+        "type": TENDERMINT_CONSENSUS_STATE_TYPE_URL,
+        "value": encode_tendermint_consensus_state(decode(consensus_state)?),
+    }))
+}
+
+#[allow(deprecated)]
+fn encode_tendermint_client_state(client_state: TmClientState) -> Value {
+    Fields::default()
+        .string("chain_id", &client_state.chain_id)
+        .message(
+            "trust_level",
+            client_state.trust_level.as_ref().map(|trust_level| {
+                Fields::default()
+                    .uint64("numerator", trust_level.numerator)
+                    .uint64("denominator", trust_level.denominator)
+                    .build()
+            }),
+        )
+        .message(
+            "trusting_period",
+            client_state.trusting_period.as_ref().map(encode_duration),
+        )
+        .message(
+            "unbonding_period",
+            client_state.unbonding_period.as_ref().map(encode_duration),
+        )
+        .message(
+            "max_clock_drift",
+            client_state.max_clock_drift.as_ref().map(encode_duration),
+        )
+        .message(
+            "frozen_height",
+            client_state.frozen_height.as_ref().map(encode_height),
+        )
+        .message(
+            "latest_height",
+            client_state.latest_height.as_ref().map(encode_height),
+        )
+        .values(
+            "proof_specs",
+            client_state
+                .proof_specs
+                .iter()
+                .map(encode_proof_spec)
+                .collect(),
+        )
+        .strings("upgrade_path", &client_state.upgrade_path)
+        .bool(
+            "allow_update_after_expiry",
+            client_state.allow_update_after_expiry,
+        )
+        .bool(
+            "allow_update_after_misbehaviour",
+            client_state.allow_update_after_misbehaviour,
+        )
+        .build()
+}
+
+fn encode_tendermint_consensus_state(consensus_state: TmConsensusState) -> Value {
+    Fields::default()
+        .message(
+            "timestamp",
+            consensus_state
+                .timestamp
+                .as_ref()
+                .map(encode_proto_timestamp),
+        )
+        .message(
+            "root",
+            consensus_state
+                .root
+                .as_ref()
+                .map(|root| Fields::default().bytes("hash", &root.hash).build()),
+        )
+        .bytes(
+            "next_validators_hash",
+            &consensus_state.next_validators_hash,
+        )
+        .build()
+}
+
+fn encode_proof_spec(proof_spec: &ProofSpec) -> Value {
+    Fields::default()
+        .message(
+            "leaf_spec",
+            proof_spec.leaf_spec.as_ref().map(|leaf_spec| {
+                Fields::default()
+                    .int("hash", leaf_spec.hash.into())
+                    .int("prehash_key", leaf_spec.prehash_key.into())
+                    .int("prehash_value", leaf_spec.prehash_value.into())
+                    .int("length", leaf_spec.length.into())
+                    .bytes("prefix", &leaf_spec.prefix)
+                    .build()
+            }),
+        )
+        .message(
+            "inner_spec",
+            proof_spec.inner_spec.as_ref().map(|inner_spec| {
+                Fields::default()
+                    .values(
+                        "child_order",
+                        inner_spec
+                            .child_order
+                            .iter()
+                            .map(|&child| child.into())
+                            .collect(),
+                    )
+                    .int("child_size", inner_spec.child_size.into())
+                    .int("min_prefix_length", inner_spec.min_prefix_length.into())
+                    .int("max_prefix_length", inner_spec.max_prefix_length.into())
+                    .bytes("empty_child", &inner_spec.empty_child)
+                    .int("hash", inner_spec.hash.into())
+                    .build()
+            }),
+        )
+        .int("max_depth", proof_spec.max_depth.into())
+        .int("min_depth", proof_spec.min_depth.into())
+        .bool(
+            "prehash_key_before_comparison",
+            proof_spec.prehash_key_before_comparison,
+        )
+        .build()
+}
+
+fn encode_tendermint_header(header: Header) -> Value {
+    Fields::default()
+        .message(
+            "signed_header",
+            header.signed_header.as_ref().map(encode_signed_header),
+        )
+        .message(
+            "validator_set",
+            header.validator_set.as_ref().map(encode_validator_set),
+        )
+        .message(
+            "trusted_height",
+            header.trusted_height.as_ref().map(encode_height),
+        )
+        .message(
+            "trusted_validators",
+            header.trusted_validators.as_ref().map(encode_validator_set),
+        )
+        .build()
+}
+
+fn encode_signed_header(signed_header: &SignedHeader) -> Value {
+    Fields::default()
+        .message(
+            "header",
+            signed_header.header.as_ref().map(encode_block_header),
+        )
+        .message("commit", signed_header.commit.as_ref().map(encode_commit))
+        .build()
+}
+
+fn encode_block_header(header: &BlockHeader) -> Value {
+    Fields::default()
+        .message(
+            "version",
+            header.version.as_ref().map(|version| {
+                Fields::default()
+                    .uint64("block", version.block)
+                    .uint64("app", version.app)
+                    .build()
+            }),
+        )
+        .string("chain_id", &header.chain_id)
+        .int64("height", header.height)
+        .message("time", header.time.as_ref().map(encode_timestamp))
+        .message(
+            "last_block_id",
+            header.last_block_id.as_ref().map(encode_block_id),
+        )
+        .bytes("last_commit_hash", &header.last_commit_hash)
+        .bytes("data_hash", &header.data_hash)
+        .bytes("validators_hash", &header.validators_hash)
+        .bytes("next_validators_hash", &header.next_validators_hash)
+        .bytes("consensus_hash", &header.consensus_hash)
+        .bytes("app_hash", &header.app_hash)
+        .bytes("last_results_hash", &header.last_results_hash)
+        .bytes("evidence_hash", &header.evidence_hash)
+        .bytes("proposer_address", &header.proposer_address)
+        .build()
+}
+
+fn encode_commit(commit: &Commit) -> Value {
+    Fields::default()
+        .int64("height", commit.height)
+        .int("round", commit.round.into())
+        .message("block_id", commit.block_id.as_ref().map(encode_block_id))
+        .values(
+            "signatures",
+            commit.signatures.iter().map(encode_commit_sig).collect(),
+        )
+        .build()
+}
+
+fn encode_commit_sig(commit_sig: &CommitSig) -> Value {
+    Fields::default()
+        .int("block_id_flag", commit_sig.block_id_flag.into())
+        .bytes("validator_address", &commit_sig.validator_address)
+        .message(
+            "timestamp",
+            commit_sig.timestamp.as_ref().map(encode_timestamp),
+        )
+        .bytes("signature", &commit_sig.signature)
+        .build()
+}
+
+fn encode_block_id(block_id: &BlockId) -> Value {
+    Fields::default()
+        .bytes("hash", &block_id.hash)
+        .message(
+            "part_set_header",
+            block_id.part_set_header.as_ref().map(|part_set_header| {
+                Fields::default()
+                    .int("total", part_set_header.total.into())
+                    .bytes("hash", &part_set_header.hash)
+                    .build()
+            }),
+        )
+        .build()
+}
+
+fn encode_validator_set(validator_set: &ValidatorSet) -> Value {
+    Fields::default()
+        .values(
+            "validators",
+            validator_set
+                .validators
+                .iter()
+                .map(encode_validator)
+                .collect(),
+        )
+        .message(
+            "proposer",
+            validator_set.proposer.as_ref().map(encode_validator),
+        )
+        .int64("total_voting_power", validator_set.total_voting_power)
+        .build()
+}
+
+fn encode_validator(validator: &Validator) -> Value {
+    Fields::default()
+        .bytes("address", &validator.address)
+        .message("pub_key", validator.pub_key.as_ref().map(encode_public_key))
+        .int64("voting_power", validator.voting_power)
+        .int64("proposer_priority", validator.proposer_priority)
+        .build()
+}
+
+fn encode_public_key(public_key: &PublicKey) -> Value {
+    match &public_key.sum {
+        Some(public_key::Sum::Ed25519(key)) => Fields::default().bytes("ed25519", key),
+        Some(public_key::Sum::Secp256k1(key)) => Fields::default().bytes("secp256k1", key),
+        None => Fields::default(),
+    }
+    .build()
+}
+
+fn encode_coin(coin: &Coin) -> Value {
+    json!({ "amount": coin.amount, "denom": coin.denom })
+}
+
+fn encode_height(height: &Height) -> Value {
+    Fields::default()
+        .uint64("revision_number", height.revision_number)
+        .uint64("revision_height", height.revision_height)
+        .build()
+}
+
+/// Encode a timestamp in the RFC 3339 format, with as many fractional digits as needed
+fn encode_timestamp(timestamp: &Timestamp) -> Value {
+    tendermint::Time::try_from(timestamp.clone())
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_default()
+        .into()
+}
+
+/// Encode a duration as its number of nanoseconds, as amino does
+fn encode_duration(duration: &ProtoDuration) -> Value {
+    (i128::from(duration.seconds) * 1_000_000_000 + i128::from(duration.nanos))
+        .to_string()
+        .into()
+}
+
+fn encode_proto_timestamp(timestamp: &ProtoTimestamp) -> Value {
+    encode_timestamp(&Timestamp {
+        seconds: timestamp.seconds,
+        nanos: timestamp.nanos,
+    })
+}
+
+fn empty() -> Value {
+    Value::Object(Map::new())
+}
+
+/// The fields of a message, from which the empty scalar fields are left out
+#[derive(Default)]
+struct Fields(Map<String, Value>);
+
+impl Fields {
+    fn value(mut self, key: &str, value: Value) -> Self {
+        self.0.insert(key.to_string(), value);
+        self
+    }
+
+    fn message(self, key: &str, value: Option<Value>) -> Self {
+        match value {
+            Some(value) => self.value(key, value),
+            None => self,
+        }
+    }
+
+    fn values(self, key: &str, values: Vec<Value>) -> Self {
+        if values.is_empty() {
+            self
+        } else {
+            self.value(key, Value::Array(values))
+        }
+    }
+
+    fn string(self, key: &str, value: &str) -> Self {
+        if value.is_empty() {
+            self
+        } else {
+            self.value(key, value.into())
+        }
+    }
+
+    fn strings(self, key: &str, values: &[String]) -> Self {
+        self.values(
+            key,
+            values.iter().map(|value| value.as_str().into()).collect(),
+        )
+    }
+
+    fn bytes(self, key: &str, value: &[u8]) -> Self {
+        if value.is_empty() {
+            self
+        } else {
+            let encoded = String::from_utf8(base64::encode(value)).unwrap_or_default();
+            self.value(key, encoded.into())
+        }
+    }
+
+    fn bool(self, key: &str, value: bool) -> Self {
+        if value {
+            self.value(key, value.into())
+        } else {
+            self
+        }
+    }
+
+    fn int(self, key: &str, value: i64) -> Self {
+        if value == 0 {
+            self
+        } else {
+            self.value(key, value.into())
+        }
+    }
+
+    fn int64(self, key: &str, value: i64) -> Self {
+        if value == 0 {
+            self
+        } else {
+            self.value(key, value.to_string().into())
+        }
+    }
+
+    fn uint64(self, key: &str, value: u64) -> Self {
+        if value == 0 {
+            self
+        } else {
+            self.value(key, value.to_string().into())
+        }
+    }
+
+    fn build(self) -> Value {
+        Value::Object(self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_transfer_sign_doc() {
+        let transfer = MsgTransfer {
+            source_port: "transfer".to_string(),
+            source_channel: "channel-0".to_string(),
+            token: Some(Coin {
+                denom: "uatom".to_string(),
+                amount: "1000".to_string(),
+            }),
+            sender: "cosmos1sender".to_string(),
+            receiver: "osmo1<receiver>".to_string(),
+            timeout_height: Some(Height {
+                revision_number: 1,
+                revision_height: 0,
+            }),
+            timeout_timestamp: 0,
+            memo: String::new(),
+        };
+
+        let fee = Fee {
+            amount: vec![Coin {
+                denom: "uatom".to_string(),
+                amount: "500".to_string(),
+            }],
+            gas_limit: 200_000,
+            payer: String::new(),
+            granter: String::new(),
+        };
+
+        let sign_doc = encode_sign_doc(
+            &ChainId::from_string("cosmoshub-4"),
+            AccountNumber::new(7),
+            AccountSequence::new(42),
+            &fee,
+            &Memo::new("ledger").unwrap(),
+            &[Any {
+                type_url: transfer::TYPE_URL.to_string(),
+                value: transfer.encode_to_vec(),
+            }],
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(sign_doc).unwrap(),
+            concat!(
+                r#"{"account_number":"7","chain_id":"cosmoshub-4","#,
+                r#""fee":{"amount":[{"amount":"500","denom":"uatom"}],"gas":"200000"},"#,
+                r#""memo":"ledger","msgs":[{"type":"cosmos-sdk/MsgTransfer","value":{"#,
+                r#""receiver":"osmo1\u003creceiver\u003e","sender":"cosmos1sender","#,
+                r#""source_channel":"channel-0","source_port":"transfer","#,
+                r#""timeout_height":{"revision_number":"1"},"#,
+                r#""token":{"amount":"1000","denom":"uatom"}}}],"sequence":"42"}"#,
+            )
+        );
+    }
+
+    fn any(type_url: &str, message: impl Message) -> Any {
+        Any {
+            type_url: type_url.to_string(),
+            value: message.encode_to_vec(),
+        }
+    }
+
+    #[allow(deprecated)]
+    fn tendermint_client_state() -> Any {
+        any(
+            TENDERMINT_CLIENT_STATE_TYPE_URL,
+            TmClientState {
+                chain_id: "ibc-1".to_string(),
+                trust_level: Some(ibc_proto::ibc::lightclients::tendermint::v1::Fraction {
+                    numerator: 1,
+                    denominator: 3,
+                }),
+                trusting_period: Some(ProtoDuration {
+                    seconds: 1_209_600,
+                    nanos: 0,
+                }),
+                unbonding_period: Some(ProtoDuration {
+                    seconds: 1_814_400,
+                    nanos: 0,
+                }),
+                max_clock_drift: Some(ProtoDuration {
+                    seconds: 5,
+                    nanos: 500_000_000,
+                }),
+                frozen_height: None,
+                latest_height: Some(Height {
+                    revision_number: 1,
+                    revision_height: 10,
+                }),
+                proof_specs: ibc_relayer_types::core::ics23_commitment::specs::ProofSpecs::cosmos()
+                    .into(),
+                upgrade_path: vec!["upgrade".to_string(), "upgradedIBCState".to_string()],
+                allow_update_after_expiry: true,
+                allow_update_after_misbehaviour: true,
+            },
+        )
+    }
+
+    #[test]
+    fn encodes_create_client() {
+        let consensus_state = any(
+            TENDERMINT_CONSENSUS_STATE_TYPE_URL,
+            TmConsensusState {
+                timestamp: Some(ProtoTimestamp {
+                    seconds: 1_700_000_000,
+                    nanos: 0,
+                }),
+                root: Some(ibc_proto::ibc::core::commitment::v1::MerkleRoot {
+                    hash: vec![1, 2, 3],
+                }),
+                next_validators_hash: vec![4, 5, 6],
+            },
+        );
+
+        let encoded = encode_message(&any(
+            create_client::TYPE_URL,
+            MsgCreateClient {
+                client_state: Some(tendermint_client_state()),
+                consensus_state: Some(consensus_state),
+                signer: "cosmos1signer".to_string(),
+            },
+        ))
+        .unwrap();
+
+        assert_eq!(encoded["type"], create_client::TYPE_URL);
+
+        let client_state = &encoded["value"]["client_state"];
+        assert_eq!(client_state["type"], TENDERMINT_CLIENT_STATE_TYPE_URL);
+        assert_eq!(
+            client_state["value"]["trust_level"],
+            json!({ "denominator": "3", "numerator": "1" })
+        );
+        assert_eq!(client_state["value"]["trusting_period"], "1209600000000000");
+        assert_eq!(client_state["value"]["max_clock_drift"], "5500000000");
+        assert_eq!(client_state["value"]["allow_update_after_expiry"], true);
+        assert_eq!(client_state["value"].get("frozen_height"), None);
+
+        // The IAVL and Tendermint proof specs
+        let proof_specs = client_state["value"]["proof_specs"].as_array().unwrap();
+        assert_eq!(proof_specs.len(), 2);
+        assert_eq!(proof_specs[0]["inner_spec"]["child_order"], json!([0, 1]));
+        assert_eq!(proof_specs[0]["leaf_spec"]["prefix"], "AA==");
+
+        assert_eq!(
+            encoded["value"]["consensus_state"],
+            json!({
+                "type": TENDERMINT_CONSENSUS_STATE_TYPE_URL,
+                "value": {
+                    "next_validators_hash": "BAUG",
+                    "root": { "hash": "AQID" },
+                    "timestamp": "2023-11-14T22:13:20Z",
+                },
+            })
+        );
+        assert_eq!(encoded["value"]["signer"], "cosmos1signer");
+
+        let encoded = encode_message(&any(
+            upgrade_client::TYPE_URL,
+            MsgUpgradeClient {
+                client_id: "07-tendermint-0".to_string(),
+                client_state: Some(tendermint_client_state()),
+                consensus_state: None,
+                proof_upgrade_client: vec![7],
+                proof_upgrade_consensus_state: vec![8],
+                signer: "cosmos1signer".to_string(),
+            },
+        ))
+        .unwrap();
+
+        assert_eq!(encoded["type"], upgrade_client::TYPE_URL);
+        assert_eq!(encoded["value"]["client_id"], "07-tendermint-0");
+        assert_eq!(
+            encoded["value"]["client_state"]["value"]["upgrade_path"],
+            json!(["upgrade", "upgradedIBCState"])
+        );
+        assert_eq!(encoded["value"]["proof_upgrade_client"], "Bw==");
+        assert_eq!(encoded["value"].get("consensus_state"), None);
+    }
+
+    #[test]
+    fn encodes_connection_handshake() {
+        let counterparty = ConnectionCounterparty {
+            client_id: "07-tendermint-1".to_string(),
+            connection_id: String::new(),
+            prefix: Some(ibc_proto::ibc::core::commitment::v1::MerklePrefix {
+                key_prefix: b"ibc".to_vec(),
+            }),
+        };
+
+        let version = ConnectionVersion {
+            identifier: "1".to_string(),
+            features: vec!["ORDER_ORDERED".to_string(), "ORDER_UNORDERED".to_string()],
+        };
+
+        let encoded = encode_message(&any(
+            conn_open_init::TYPE_URL,
+            MsgConnectionOpenInit {
+                client_id: "07-tendermint-0".to_string(),
+                counterparty: Some(counterparty.clone()),
+                version: Some(version.clone()),
+                delay_period: 0,
+                signer: "cosmos1signer".to_string(),
+            },
+        ))
+        .unwrap();
+
+        assert_eq!(
+            encoded.to_string(),
+            concat!(
+                r#"{"type":"/ibc.core.connection.v1.MsgConnectionOpenInit","value":{"#,
+                r#""client_id":"07-tendermint-0","#,
+                r#""counterparty":{"client_id":"07-tendermint-1","prefix":{"key_prefix":"aWJj"}},"#,
+                r#""signer":"cosmos1signer","#,
+                r#""version":{"features":["ORDER_ORDERED","ORDER_UNORDERED"],"identifier":"1"}}}"#,
+            )
+        );
+
+        #[allow(deprecated)]
+        let encoded = encode_message(&any(
+            conn_open_try::TYPE_URL,
+            MsgConnectionOpenTry {
+                client_id: "07-tendermint-0".to_string(),
+                previous_connection_id: String::new(),
+                client_state: Some(tendermint_client_state()),
+                counterparty: Some(counterparty),
+                delay_period: 10,
+                counterparty_versions: vec![version],
+                proof_height: Some(Height {
+                    revision_number: 0,
+                    revision_height: 42,
+                }),
+                proof_init: vec![1],
+                proof_client: vec![2],
+                proof_consensus: vec![3],
+                consensus_height: Some(Height {
+                    revision_number: 1,
+                    revision_height: 10,
+                }),
+                signer: "cosmos1signer".to_string(),
+            },
+        ))
+        .unwrap();
+
+        let value = &encoded["value"];
+        assert_eq!(value["client_state"]["value"]["chain_id"], "ibc-1");
+        assert_eq!(value["delay_period"], "10");
+        assert_eq!(value["counterparty_versions"][0]["identifier"], "1");
+        assert_eq!(value["proof_height"], json!({ "revision_height": "42" }));
+        assert_eq!(value["proof_init"], "AQ==");
+        assert_eq!(value.get("previous_connection_id"), None);
+    }
+
+    #[test]
+    fn rejects_non_tendermint_client_states() {
+        let err = encode_message(&any(
+            create_client::TYPE_URL,
+            MsgCreateClient {
+                client_state: Some(Any {
+                    type_url: "/ibc.lightclients.wasm.v1.ClientState".to_string(),
+                    value: Vec::new(),
+                }),
+                consensus_state: None,
+                signer: "cosmos1signer".to_string(),
+            },
+        ))
+        .unwrap_err();
+
+        assert!(err.to_string().contains("wasm"));
+    }
+
+    #[test]
+    fn rejects_unsupported_messages() {
+        let err = encode_message(&Any {
+            type_url: "/ibc.core.channel.v1.MsgRecvPacket".to_string(),
+            value: Vec::new(),
+        })
+        .unwrap_err();
+
+        assert!(err.to_string().contains("MsgRecvPacket"));
+    }
+}
@@ -7,19 +7,23 @@ use ibc_relayer_types::Height;
 use prost::Message;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use tendermint_rpc::HttpClient;
-use tracing::debug;
+use tracing::{debug, info};
 
-use crate::chain::cosmos::encode::encoded_tx_metrics;
+use crate::chain::cosmos::encode::{encoded_tx_metrics, wrap_in_msg_exec};
+use crate::chain::cosmos::estimate::estimate_tx_fees;
 use crate::chain::cosmos::gas::gas_amount_to_fee;
 use crate::chain::cosmos::retry::send_tx_with_account_sequence_retry;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
+use crate::chain::cosmos::types::dry_run::SimulatedTx;
 use crate::chain::cosmos::types::tx::{TxStatus, TxSyncResult};
+use crate::chain::cosmos::types::wallet::is_resignable;
 use crate::chain::cosmos::wait::wait_for_block_commits;
 use crate::config::types::Memo;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
-use crate::keyring::Secp256k1KeyPair;
+use crate::keyring::{CosmosKeyPair, SigningKeyPair};
+use crate::util::pretty::PrettyFee;
 
 /**
    Broadcast messages as multiple batched transactions to the chain all at once,
@@ -31,7 +35,7 @@ use crate::keyring::Secp256k1KeyPair;
 pub async fn send_batched_messages_and_wait_commit(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     account: &mut Account,
     tx_memo: &Memo,
     messages: Vec<Any>,
@@ -68,7 +72,7 @@ pub async fn send_batched_messages_and_wait_commit(
 pub async fn sequential_send_batched_messages_and_wait_commit(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     account: &mut Account,
     tx_memo: &Memo,
     messages: Vec<Any>,
@@ -93,7 +97,7 @@ pub async fn sequential_send_batched_messages_and_wait_commit(
 pub async fn send_batched_messages_and_wait_check_tx(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     account: &mut Account,
     tx_memo: &Memo,
     messages: Vec<Any>,
@@ -118,10 +122,57 @@ pub async fn send_batched_messages_and_wait_check_tx(
     Ok(responses)
 }
 
+/**
+   Build, sign and simulate the batched transactions, without broadcasting them.
+   This is used in dry-run mode, to find out which transactions would be submitted,
+   along with their estimated gas and fees.
+*/
+pub async fn simulate_batched_messages(
+    config: &TxConfig,
+    key_pair: &CosmosKeyPair,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: Vec<Any>,
+) -> Result<Vec<SimulatedTx>, Error> {
+    if messages.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let batches = batch_messages(config, key_pair, account, tx_memo, messages)?;
+
+    let mut simulated_txs = Vec::new();
+
+    for batch in batches {
+        // Nothing is broadcast, hence all the batches are simulated with the current sequence
+        let fee = estimate_tx_fees(config, key_pair, account, tx_memo, &batch).await?;
+
+        let message_type_urls = batch
+            .into_iter()
+            .map(|message| message.type_url)
+            .collect::<Vec<_>>();
+
+        info!(
+            id = %config.chain_id,
+            messages = ?message_type_urls,
+            "[dry-run] would have submitted a tx with {} messages, using {} gas, fee {}",
+            message_type_urls.len(),
+            fee.gas_limit,
+            PrettyFee(&fee),
+        );
+
+        simulated_txs.push(SimulatedTx {
+            message_type_urls,
+            fee,
+        });
+    }
+
+    Ok(simulated_txs)
+}
+
 async fn send_messages_as_batches(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     account: &mut Account,
     tx_memo: &Memo,
     messages: Vec<Any>,
@@ -162,7 +213,7 @@ async fn send_messages_as_batches(
 async fn sequential_send_messages_as_batches(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     account: &mut Account,
     tx_memo: &Memo,
     messages: Vec<Any>,
@@ -240,7 +291,7 @@ fn response_to_tx_sync_result(
 
 fn batch_messages(
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     account: &Account,
     tx_memo: &Memo,
     messages: Vec<Any>,
@@ -267,8 +318,31 @@ fn batch_messages(
         envelope_len + 1 + prost::length_delimiter_len(body_len) + body_len
     }
 
+    // With authz, the IBC messages are nested in a `MsgExec` within an `Any`,
+    // so the length delimiters of both grow along with the messages.
+    // The other messages are added to the body as they are.
+    let empty_msg_exec = config
+        .authz_granter
+        .as_ref()
+        .map(|_| wrap_in_msg_exec(&key_pair.account(), &[]));
+
+    let body_len = |ibc_messages_len: usize, other_messages_len: usize| match &empty_msg_exec {
+        Some(msg_exec) if ibc_messages_len != 0 => {
+            let empty_value_len = msg_exec.value.len();
+            let empty_any_len = msg_exec.encoded_len();
+
+            let any_len = empty_any_len - prost::length_delimiter_len(empty_value_len)
+                + prost::length_delimiter_len(empty_value_len + ibc_messages_len)
+                + ibc_messages_len;
+
+            empty_body_len + other_messages_len + 1 + prost::length_delimiter_len(any_len) + any_len
+        }
+        _ => empty_body_len + ibc_messages_len + other_messages_len,
+    };
+
     let mut current_count = 0;
-    let mut current_len = empty_body_len;
+    let mut current_ibc_len = 0;
+    let mut current_other_len = 0;
     let mut current_batch = vec![];
 
     for message in messages {
@@ -278,8 +352,14 @@ fn batch_messages(
         // field tag (small varint) and the length delimiter.
         let tagged_len = 1 + prost::length_delimiter_len(message_len) + message_len;
 
+        let (ibc_len, other_len) = if is_resignable(&message.type_url) {
+            (current_ibc_len + tagged_len, current_other_len)
+        } else {
+            (current_ibc_len, current_other_len + tagged_len)
+        };
+
         if current_count >= max_message_count
-            || tx_len(tx_envelope_len, current_len + tagged_len) > max_tx_size
+            || tx_len(tx_envelope_len, body_len(ibc_len, other_len)) > max_tx_size
         {
             let insert_batch = mem::take(&mut current_batch);
 
@@ -290,11 +370,16 @@ fn batch_messages(
 
             batches.push(insert_batch);
             current_count = 0;
-            current_len = empty_body_len;
+            current_ibc_len = 0;
+            current_other_len = 0;
         }
 
         current_count += 1;
-        current_len += tagged_len;
+        if is_resignable(&message.type_url) {
+            current_ibc_len += tagged_len;
+        } else {
+            current_other_len += tagged_len;
+        }
         current_batch.push(message);
     }
 
@@ -318,14 +403,14 @@ mod tests {
     use crate::chain::cosmos::types::config::TxConfig;
     use crate::config;
     use crate::config::types::{MaxMsgNum, MaxTxSize, Memo};
-    use crate::keyring::{self, KeyRing, Secp256k1KeyPair, SigningKeyPair};
+    use crate::keyring::{self, CosmosKeyPair, KeyRing, SigningKeyPair};
     use ibc_proto::google::protobuf::Any;
     use ibc_relayer_types::core::ics24_host::identifier::ChainId;
     use std::fs;
 
     const COSMOS_HD_PATH: &str = "m/44'/118'/0'/0/0";
 
-    fn test_fixture() -> (TxConfig, Secp256k1KeyPair, Account) {
+    fn test_fixture() -> (TxConfig, CosmosKeyPair, Account) {
         let path = concat!(
             env!("CARGO_MANIFEST_DIR"),
             "/tests/config/fixtures/relayer_conf_example.toml"
@@ -341,7 +426,7 @@ mod tests {
             "/tests/config/fixtures/relayer-seed.json"
         );
         let seed_file_content = fs::read_to_string(path).unwrap();
-        let _keyring = KeyRing::new_secp256k1(
+        let _keyring = KeyRing::new_cosmos(
             keyring::Store::Memory,
             "cosmos",
             &chain_id,
@@ -349,7 +434,7 @@ mod tests {
         )
         .unwrap();
         let hd_path = COSMOS_HD_PATH.parse().unwrap();
-        let key_pair = Secp256k1KeyPair::from_seed_file(&seed_file_content, &hd_path).unwrap();
+        let key_pair = CosmosKeyPair::from_seed_file(&seed_file_content, &hd_path).unwrap();
 
         let account = Account {
             address: AccountAddress::new("".to_owned()),
@@ -363,6 +448,30 @@ mod tests {
     #[test]
     fn batch_does_not_exceed_max_tx_size() {
         let (config, key_pair, account) = test_fixture();
+        check_batch_does_not_exceed_max_tx_size(config, key_pair, account, "/example.Foo");
+    }
+
+    #[test]
+    fn authz_batch_does_not_exceed_max_tx_size() {
+        let (mut config, key_pair, account) = test_fixture();
+        config.authz_granter = Some("cosmos1granter".to_string());
+
+        // The IBC messages are wrapped in a `MsgExec`, the other ones are not
+        check_batch_does_not_exceed_max_tx_size(
+            config.clone(),
+            key_pair.clone(),
+            account.clone(),
+            "/ibc.core.channel.v1.MsgRecvPacket",
+        );
+        check_batch_does_not_exceed_max_tx_size(config, key_pair, account, "/example.Foo");
+    }
+
+    fn check_batch_does_not_exceed_max_tx_size(
+        config: TxConfig,
+        key_pair: CosmosKeyPair,
+        account: Account,
+        type_url: &str,
+    ) {
         let max_fee = gas_amount_to_fee(&config.gas_config, config.gas_config.max_gas);
         let mut messages = vec![Any {
             type_url: "/example.Baz".into(),
@@ -373,7 +482,7 @@ mod tests {
             messages.insert(
                 messages.len() - 1,
                 Any {
-                    type_url: "/example.Foo".into(),
+                    type_url: type_url.into(),
                     value: vec![0; n],
                 },
             );
@@ -10,7 +10,7 @@ use super::version;
 /// # Note: Should be consistent with [features] guide page.
 ///
 /// [features]: https://hermes.informal.systems/advanced/features.html
-const SDK_MODULE_VERSION_REQ: &str = ">=0.44, <0.48";
+const SDK_MODULE_VERSION_REQ: &str = ">=0.44, <0.51";
 
 /// Specifies the IBC-go module version requirement.
 /// At the moment, we support both chains with and without
@@ -20,7 +20,7 @@ const SDK_MODULE_VERSION_REQ: &str = ">=0.44, <0.48";
 /// # Note: Should be consistent with [features] guide page.
 ///
 /// [features]: https://hermes.informal.systems/advanced/features.html
-const IBC_GO_MODULE_VERSION_REQ: &str = ">=1.1, <=7";
+const IBC_GO_MODULE_VERSION_REQ: &str = ">=1.1, <=8";
 
 #[derive(Error, Debug)]
 pub enum Diagnostic {
@@ -87,3 +87,29 @@ fn ibc_go_diagnostic(version_info: Option<&semver::Version>) -> Result<(), Diagn
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(v: &str) -> semver::Version {
+        semver::Version::parse(v).unwrap()
+    }
+
+    #[test]
+    fn sdk_versions() {
+        assert!(sdk_diagnostic(&version("0.44.0")).is_ok());
+        assert!(sdk_diagnostic(&version("0.47.5")).is_ok());
+        assert!(sdk_diagnostic(&version("0.50.1")).is_ok());
+        assert!(sdk_diagnostic(&version("0.43.0")).is_err());
+        assert!(sdk_diagnostic(&version("0.51.0")).is_err());
+    }
+
+    #[test]
+    fn ibc_go_versions() {
+        assert!(ibc_go_diagnostic(None).is_ok());
+        assert!(ibc_go_diagnostic(Some(&version("7.3.0"))).is_ok());
+        assert!(ibc_go_diagnostic(Some(&version("8.0.0"))).is_ok());
+        assert!(ibc_go_diagnostic(Some(&version("9.0.0"))).is_err());
+    }
+}
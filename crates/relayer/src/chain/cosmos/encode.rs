@@ -1,5 +1,6 @@
 use bech32::{ToBase32, Variant};
 use core::str::FromStr;
+use ibc_proto::cosmos::tx::signing::v1beta1::SignMode;
 use ibc_proto::cosmos::tx::v1beta1::mode_info::{Single, Sum};
 use ibc_proto::cosmos::tx::v1beta1::{AuthInfo, Fee, ModeInfo, SignDoc, SignerInfo, TxBody, TxRaw};
 use ibc_proto::google::protobuf::Any;
@@ -9,17 +10,65 @@ use ibc_relayer_types::signer::Signer;
 use prost::Message;
 use tendermint::account::Id as AccountId;
 
+use crate::chain::cosmos::amino;
 use crate::chain::cosmos::types::account::{Account, AccountNumber, AccountSequence};
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::tx::SignedTx;
+use crate::chain::cosmos::types::wallet::is_resignable;
 use crate::config::types::Memo;
 use crate::config::AddressType;
 use crate::error::Error;
-use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+use crate::keyring::{CosmosKeyPair, SigningKeyPair};
+
+pub const MSG_EXEC_TYPE_URL: &str = "/cosmos.authz.v1beta1.MsgExec";
+
+// The `x/authz` protobuf messages are not part of `ibc-proto`, the definition below
+// is taken from: https://github.com/cosmos/cosmos-sdk/blob/v0.47.0/proto/cosmos/authz/v1beta1/tx.proto
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgExec {
+    #[prost(string, tag = "1")]
+    pub grantee: String,
+    #[prost(message, repeated, tag = "2")]
+    pub msgs: Vec<Any>,
+}
+
+/// Wrap the given messages in a single `MsgExec`, to be signed by the given grantee.
+pub fn wrap_in_msg_exec(grantee: &str, messages: &[Any]) -> Any {
+    let msg_exec = MsgExec {
+        grantee: grantee.to_string(),
+        msgs: messages.to_vec(),
+    };
+
+    Any {
+        type_url: MSG_EXEC_TYPE_URL.to_string(),
+        value: msg_exec.encode_to_vec(),
+    }
+}
+
+/// Wrap the core IBC messages among the given ones in a single `MsgExec`, to be signed by
+/// the given grantee, in place of the first of them. The other messages, which are not
+/// covered by the authz grants, are left to be executed directly by the grantee.
+pub fn wrap_ibc_messages_in_msg_exec(grantee: &str, messages: &[Any]) -> Vec<Any> {
+    let (ibc_messages, other_messages): (Vec<_>, Vec<_>) = messages
+        .iter()
+        .cloned()
+        .partition(|msg| is_resignable(&msg.type_url));
+
+    let Some(position) = messages
+        .iter()
+        .position(|msg| is_resignable(&msg.type_url))
+    else {
+        return other_messages;
+    };
+
+    let mut messages = other_messages;
+    messages.insert(position, wrap_in_msg_exec(grantee, &ibc_messages));
+    messages
+}
 
 pub fn sign_and_encode_tx(
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     account: &Account,
     tx_memo: &Memo,
     messages: &[Any],
@@ -46,13 +95,13 @@ pub struct EncodedTxMetrics {
 
 pub fn encoded_tx_metrics(
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     account: &Account,
     tx_memo: &Memo,
     messages: &[Any],
     fee: &Fee,
 ) -> Result<EncodedTxMetrics, Error> {
-    let signed_tx = sign_tx(config, key_pair, account, tx_memo, messages, fee)?;
+    let signed_tx = sign_tx_for_simulation(config, key_pair, account, tx_memo, messages, fee)?;
 
     let tx_raw = TxRaw {
         body_bytes: signed_tx.body_bytes,
@@ -76,28 +125,82 @@ pub fn encoded_tx_metrics(
 
 pub fn sign_tx(
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: &[Any],
+    fee: &Fee,
+) -> Result<SignedTx, Error> {
+    build_tx(config, key_pair, account, tx_memo, messages, fee, true)
+}
+
+/// Build a transaction to be simulated or measured rather than submitted.
+///
+/// Since every signature of a Ledger key has to be approved by the user on the device,
+/// transactions signed by such a key are given a placeholder signature of the same length.
+pub fn sign_tx_for_simulation(
+    config: &TxConfig,
+    key_pair: &CosmosKeyPair,
     account: &Account,
     tx_memo: &Memo,
     messages: &[Any],
     fee: &Fee,
+) -> Result<SignedTx, Error> {
+    let sign = key_pair.sign_mode() == SignMode::Direct;
+    build_tx(config, key_pair, account, tx_memo, messages, fee, sign)
+}
+
+fn build_tx(
+    config: &TxConfig,
+    key_pair: &CosmosKeyPair,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: &[Any],
+    fee: &Fee,
+    sign: bool,
 ) -> Result<SignedTx, Error> {
     let key_bytes = encode_key_bytes(key_pair)?;
 
-    let signer = encode_signer_info(&config.address_type, account.sequence, key_bytes)?;
+    let signer = encode_signer_info(
+        &config.address_type,
+        account.sequence,
+        key_bytes,
+        key_pair.sign_mode(),
+    )?;
+
+    // With authz, the relayer's key executes the IBC messages on behalf of the granter
+    let messages = match &config.authz_granter {
+        Some(_) => wrap_ibc_messages_in_msg_exec(&key_pair.account(), messages),
+        None => messages.to_vec(),
+    };
 
     let (body, body_bytes) =
-        tx_body_and_bytes(messages, tx_memo, config.extension_options.clone())?;
+        tx_body_and_bytes(messages.clone(), tx_memo, config.extension_options.clone())?;
 
     let (auth_info, auth_info_bytes) = auth_info_and_bytes(signer, fee.clone())?;
 
-    let signed_doc = encode_sign_doc(
-        &config.chain_id,
-        key_pair,
-        account.number,
-        auth_info_bytes.clone(),
-        body_bytes.clone(),
-    )?;
+    let signed_doc = if !sign {
+        vec![0; 64]
+    } else if key_pair.sign_mode() == SignMode::LegacyAminoJson {
+        let sign_doc = amino::encode_sign_doc(
+            &config.chain_id,
+            account.number,
+            account.sequence,
+            fee,
+            tx_memo,
+            &messages,
+        )?;
+
+        key_pair.sign(&sign_doc).map_err(Error::key_base)?
+    } else {
+        encode_sign_doc(
+            &config.chain_id,
+            key_pair,
+            account.number,
+            auth_info_bytes.clone(),
+            body_bytes.clone(),
+        )?
+    };
 
     Ok(SignedTx {
         body,
@@ -108,10 +211,10 @@ pub fn sign_tx(
     })
 }
 
-fn encode_key_bytes(key_pair: &Secp256k1KeyPair) -> Result<Vec<u8>, Error> {
+fn encode_key_bytes(key_pair: &CosmosKeyPair) -> Result<Vec<u8>, Error> {
     let mut pk_buf = Vec::new();
 
-    prost::Message::encode(&key_pair.public_key.serialize().to_vec(), &mut pk_buf)
+    prost::Message::encode(&key_pair.public_key().serialize().to_vec(), &mut pk_buf)
         .map_err(|e| Error::protobuf_encode("PublicKey".into(), e))?;
 
     Ok(pk_buf)
@@ -119,7 +222,7 @@ fn encode_key_bytes(key_pair: &Secp256k1KeyPair) -> Result<Vec<u8>, Error> {
 
 fn encode_sign_doc(
     chain_id: &ChainId,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     account_number: AccountNumber,
     auth_info_bytes: Vec<u8>,
     body_bytes: Vec<u8>,
@@ -144,6 +247,7 @@ fn encode_signer_info(
     address_type: &AddressType,
     sequence: AccountSequence,
     key_bytes: Vec<u8>,
+    sign_mode: SignMode,
 ) -> Result<SignerInfo, Error> {
     let pk_type = match address_type {
         AddressType::Cosmos => "/cosmos.crypto.secp256k1.PubKey".to_string(),
@@ -155,7 +259,9 @@ fn encode_signer_info(
         value: key_bytes,
     };
 
-    let single = Single { mode: 1 };
+    let single = Single {
+        mode: sign_mode.into(),
+    };
     let sum_single = Some(Sum::Single(single));
     let mode = Some(ModeInfo { sum: sum_single });
     let signer_info = SignerInfo {
@@ -203,13 +309,13 @@ fn auth_info_and_bytes(signer_info: SignerInfo, fee: Fee) -> Result<(AuthInfo, V
 }
 
 fn tx_body_and_bytes(
-    proto_msgs: &[Any],
+    proto_msgs: Vec<Any>,
     memo: &Memo,
     extension_options: Vec<Any>,
 ) -> Result<(TxBody, Vec<u8>), Error> {
     // Create TxBody
     let body = TxBody {
-        messages: proto_msgs.to_vec(),
+        messages: proto_msgs,
         memo: memo.to_string(),
         timeout_height: 0_u64,
         extension_options,
@@ -225,7 +331,7 @@ fn tx_body_and_bytes(
     Ok((body, body_buf))
 }
 
-pub fn key_pair_to_signer(key_pair: &Secp256k1KeyPair) -> Result<Signer, Error> {
+pub fn key_pair_to_signer(key_pair: &CosmosKeyPair) -> Result<Signer, Error> {
     let signer = key_pair
         .account()
         .parse()
@@ -233,3 +339,67 @@ pub fn key_pair_to_signer(key_pair: &Secp256k1KeyPair) -> Result<Signer, Error>
 
     Ok(signer)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_messages_in_msg_exec() {
+        let messages = vec![
+            Any {
+                type_url: "/ibc.core.client.v1.MsgUpdateClient".to_string(),
+                value: vec![1, 2, 3],
+            },
+            Any {
+                type_url: "/ibc.core.channel.v1.MsgRecvPacket".to_string(),
+                value: vec![4, 5],
+            },
+        ];
+
+        let wrapped = wrap_in_msg_exec("cosmos1grantee", &messages);
+        assert_eq!(wrapped.type_url, MSG_EXEC_TYPE_URL);
+
+        let msg_exec = MsgExec::decode(wrapped.value.as_slice()).unwrap();
+        assert_eq!(msg_exec.grantee, "cosmos1grantee");
+        assert_eq!(msg_exec.msgs, messages);
+    }
+
+    #[test]
+    fn wrap_only_ibc_messages_in_msg_exec() {
+        let register_payee = Any {
+            type_url: "/ibc.applications.fee.v1.MsgRegisterCounterpartyPayee".to_string(),
+            value: vec![1],
+        };
+        let update_client = Any {
+            type_url: "/ibc.core.client.v1.MsgUpdateClient".to_string(),
+            value: vec![2],
+        };
+        let transfer = Any {
+            type_url: "/ibc.applications.transfer.v1.MsgTransfer".to_string(),
+            value: vec![3],
+        };
+        let recv_packet = Any {
+            type_url: "/ibc.core.channel.v1.MsgRecvPacket".to_string(),
+            value: vec![4],
+        };
+
+        let messages = vec![
+            register_payee.clone(),
+            update_client.clone(),
+            transfer.clone(),
+            recv_packet.clone(),
+        ];
+
+        let wrapped = wrap_ibc_messages_in_msg_exec("cosmos1grantee", &messages);
+        assert_eq!(wrapped.len(), 3);
+        assert_eq!(wrapped[0], register_payee);
+        assert_eq!(wrapped[2], transfer);
+
+        let msg_exec = MsgExec::decode(wrapped[1].value.as_slice()).unwrap();
+        assert_eq!(msg_exec.msgs, vec![update_client, recv_packet]);
+
+        let wrapped = wrap_ibc_messages_in_msg_exec("cosmos1grantee", &[transfer.clone()]);
+        assert_eq!(wrapped, vec![transfer]);
+    }
+}
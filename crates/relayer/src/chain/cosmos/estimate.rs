@@ -4,7 +4,7 @@ use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use tonic::codegen::http::Uri;
 use tracing::{debug, error, span, warn, Level};
 
-use crate::chain::cosmos::encode::sign_tx;
+use crate::chain::cosmos::encode::sign_tx_for_simulation;
 use crate::chain::cosmos::gas::gas_amount_to_fee;
 use crate::chain::cosmos::simulate::send_tx_simulate;
 use crate::chain::cosmos::types::account::Account;
@@ -12,12 +12,12 @@ use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::gas::GasConfig;
 use crate::config::types::Memo;
 use crate::error::Error;
-use crate::keyring::Secp256k1KeyPair;
+use crate::keyring::CosmosKeyPair;
 use crate::util::pretty::PrettyFee;
 
 pub async fn estimate_tx_fees(
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     account: &Account,
     tx_memo: &Memo,
     messages: &[Any],
@@ -29,7 +29,7 @@ pub async fn estimate_tx_fees(
         PrettyFee(&gas_config.max_fee)
     );
 
-    let signed_tx = sign_tx(
+    let signed_tx = sign_tx_for_simulation(
         config,
         key_pair,
         account,
@@ -0,0 +1,563 @@
+//! Failover between the endpoints of a chain.
+//!
+//! Each kind of endpoint of a chain (RPC, gRPC and WebSocket) has an [`EndpointPool`],
+//! made of the endpoint set in the chain configuration followed by the backup endpoints
+//! of its `failover` section. The pool designates the endpoint currently in use, and
+//! moves to the healthiest of the other endpoints when the current one fails, or is
+//! found to be unhealthy by the background [`EndpointProbe`].
+
+use alloc::sync::Arc;
+use core::fmt::{Display, Error as FmtError, Formatter};
+use core::str::FromStr;
+use core::time::Duration;
+use std::thread;
+use std::time::Instant;
+
+use crossbeam_channel as channel;
+use futures::future::join_all;
+use http::Uri;
+use ibc_proto::cosmos::base::tendermint::v1beta1::{
+    service_client::ServiceClient, GetSyncingRequest,
+};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use tendermint_rpc::error::ErrorDetail as RpcErrorDetail;
+use tendermint_rpc::{Client, HttpClient, Url, WebSocketClientUrl};
+use tokio::runtime::Runtime as TokioRuntime;
+use tracing::{debug, error_span, warn};
+
+use crate::error::ErrorDetail;
+use crate::telemetry;
+use crate::util::lock::{LockExt, RwArc};
+
+/// An [`EndpointPool`] shared between a chain, its event source and the endpoint probe.
+pub type SharedEndpointPool<A> = RwArc<EndpointPool<A>>;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EndpointKind {
+    Rpc,
+    Grpc,
+    WebSocket,
+}
+
+impl EndpointKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Rpc => "rpc",
+            Self::Grpc => "grpc",
+            Self::WebSocket => "websocket",
+        }
+    }
+}
+
+impl Display for EndpointKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The health of an endpoint, as observed by the requests made to it and by the probe.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EndpointHealth {
+    /// Whether the endpoint failed neither the last request nor the last probe
+    pub healthy: bool,
+
+    /// The number of consecutive failed requests and probes
+    pub failures: u32,
+
+    /// How long the last successful probe took
+    pub latency: Option<Duration>,
+
+    /// The latest height reported by the last successful probe, if the endpoint reports it
+    pub latest_height: Option<u64>,
+}
+
+/// The result of a successful probe of an endpoint
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ProbeResult {
+    pub latency: Duration,
+    pub latest_height: Option<u64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Endpoint<A> {
+    pub addr: A,
+    pub health: EndpointHealth,
+}
+
+/// The endpoints of a given kind of a chain, one of which is in use at any given time.
+#[derive(Clone, Debug)]
+pub struct EndpointPool<A> {
+    chain_id: ChainId,
+    kind: EndpointKind,
+    endpoints: Vec<Endpoint<A>>,
+    current: usize,
+}
+
+impl<A> EndpointPool<A>
+where
+    A: Clone + Display + PartialEq,
+{
+    /// A pool made of the given primary endpoint followed by the given backup endpoints,
+    /// all assumed to be healthy until proven otherwise.
+    pub fn new(
+        chain_id: ChainId,
+        kind: EndpointKind,
+        primary: A,
+        backups: impl IntoIterator<Item = A>,
+    ) -> Self {
+        let mut endpoints: Vec<Endpoint<A>> = Vec::new();
+
+        for addr in core::iter::once(primary).chain(backups) {
+            if endpoints.iter().all(|endpoint| endpoint.addr != addr) {
+                endpoints.push(Endpoint {
+                    addr,
+                    health: EndpointHealth {
+                        healthy: true,
+                        ..Default::default()
+                    },
+                });
+            }
+        }
+
+        Self {
+            chain_id,
+            kind,
+            endpoints,
+            current: 0,
+        }
+    }
+
+    pub fn shared(self) -> SharedEndpointPool<A> {
+        RwArc::new_lock(self)
+    }
+
+    pub fn kind(&self) -> EndpointKind {
+        self.kind
+    }
+
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    pub fn endpoints(&self) -> &[Endpoint<A>] {
+        &self.endpoints
+    }
+
+    pub fn addrs(&self) -> Vec<A> {
+        self.endpoints.iter().map(|e| e.addr.clone()).collect()
+    }
+
+    /// The endpoint currently in use
+    pub fn current(&self) -> &A {
+        &self.endpoints[self.current].addr
+    }
+
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    /// Use the endpoint at the given index from now on, eg. because it is the first one which answered.
+    pub fn set_current(&mut self, index: usize) {
+        if index < self.endpoints.len() {
+            self.current = index;
+        }
+    }
+
+    /// Record that a request to the given endpoint failed, failing over to
+    /// another endpoint if it is the one in use.
+    ///
+    /// Returns whether the endpoint in use changed.
+    pub fn report_failure(&mut self, addr: &A) -> bool {
+        let Some(index) = self.endpoints.iter().position(|e| &e.addr == addr) else {
+            return false;
+        };
+
+        let health = &mut self.endpoints[index].health;
+        health.healthy = false;
+        health.failures = health.failures.saturating_add(1);
+
+        index == self.current && self.fail_over()
+    }
+
+    /// Record that a request to the endpoint in use failed, and fail over to another endpoint.
+    ///
+    /// Returns whether the endpoint in use changed.
+    pub fn report_current_failure(&mut self) -> bool {
+        let current = self.current().clone();
+        self.report_failure(&current)
+    }
+
+    /// Record the results of a probe of all the endpoints, in the order of the endpoints of the pool.
+    ///
+    /// If `max_height_lag` is set, the endpoints which lag behind the most advanced
+    /// endpoint by more than that many blocks are deemed unhealthy.
+    /// Fails over to another endpoint if the one in use is unhealthy.
+    ///
+    /// Returns whether the endpoint in use changed.
+    pub fn record_probes(
+        &mut self,
+        results: Vec<Result<ProbeResult, String>>,
+        max_height_lag: Option<u64>,
+    ) -> bool {
+        let max_height = results
+            .iter()
+            .filter_map(|result| result.as_ref().ok()?.latest_height)
+            .max();
+
+        for (endpoint, result) in self.endpoints.iter_mut().zip(results) {
+            let was_healthy = endpoint.health.healthy;
+
+            let result = result.and_then(|probe| match (probe.latest_height, max_height) {
+                (Some(height), Some(max_height))
+                    if max_height_lag.map_or(false, |lag| height + lag < max_height) =>
+                {
+                    Err(format!(
+                        "endpoint is at height {height}, lagging behind height {max_height}"
+                    ))
+                }
+                _ => Ok(probe),
+            });
+
+            match result {
+                Ok(probe) => {
+                    endpoint.health = EndpointHealth {
+                        healthy: true,
+                        failures: 0,
+                        latency: Some(probe.latency),
+                        latest_height: probe.latest_height,
+                    };
+                }
+                Err(e) => {
+                    if was_healthy {
+                        warn!(
+                            chain = %self.chain_id,
+                            "{} endpoint {} is unhealthy: {e}",
+                            self.kind,
+                            endpoint.addr
+                        );
+                    } else {
+                        debug!(
+                            chain = %self.chain_id,
+                            "{} endpoint {} is still unhealthy: {e}",
+                            self.kind,
+                            endpoint.addr
+                        );
+                    }
+
+                    endpoint.health.healthy = false;
+                    endpoint.health.failures = endpoint.health.failures.saturating_add(1);
+                }
+            }
+
+            telemetry!(
+                endpoint_health,
+                &self.chain_id,
+                self.kind.as_str(),
+                &endpoint.addr.to_string(),
+                endpoint.health.healthy,
+                endpoint
+                    .health
+                    .latency
+                    .map_or(0, |latency| latency.as_millis() as u64)
+            );
+        }
+
+        !self.endpoints[self.current].health.healthy && self.fail_over()
+    }
+
+    /// Switch to the healthiest endpoint other than the one in use, ie. the one with
+    /// the fewest failures and the lowest latency, in the order of the pool for ties.
+    /// When none is healthy, switch to the next one in the pool.
+    fn fail_over(&mut self) -> bool {
+        let len = self.endpoints.len();
+
+        if len < 2 {
+            return false;
+        }
+
+        let current = self.current;
+
+        let next = (1..len)
+            .map(|offset| (current + offset) % len)
+            .min_by_key(|&index| {
+                let health = &self.endpoints[index].health;
+                (
+                    !health.healthy,
+                    health.failures,
+                    health.latency.unwrap_or(Duration::MAX),
+                )
+            })
+            .filter(|&index| self.endpoints[index].health.healthy)
+            .unwrap_or((current + 1) % len);
+
+        warn!(
+            chain = %self.chain_id,
+            "failing over from {} endpoint {} to {}",
+            self.kind,
+            self.endpoints[current].addr,
+            self.endpoints[next].addr,
+        );
+
+        telemetry!(endpoint_failovers, &self.chain_id, self.kind.as_str());
+
+        self.current = next;
+
+        true
+    }
+}
+
+/// Whether the given RPC error is due to the endpoint being unreachable or
+/// unresponsive, rather than to the request itself.
+pub fn is_rpc_endpoint_failure(e: &RpcErrorDetail) -> bool {
+    matches!(
+        e,
+        RpcErrorDetail::Io(_)
+            | RpcErrorDetail::Http(_)
+            | RpcErrorDetail::Hyper(_)
+            | RpcErrorDetail::Server(_)
+            | RpcErrorDetail::ClientInternal(_)
+            | RpcErrorDetail::Timeout(_)
+            | RpcErrorDetail::WebSocket(_)
+            | RpcErrorDetail::WebSocketTimeout(_)
+            | RpcErrorDetail::Tungstenite(_)
+            | RpcErrorDetail::ChannelSend(_)
+            | RpcErrorDetail::Join(_)
+    )
+}
+
+/// Whether the given error is due to the gRPC endpoint being unreachable or unresponsive.
+pub fn is_grpc_endpoint_failure(e: &ErrorDetail) -> bool {
+    match e {
+        ErrorDetail::GrpcTransport(_) => true,
+        ErrorDetail::GrpcStatus(e) => matches!(
+            e.status.code(),
+            tonic::Code::Unavailable | tonic::Code::DeadlineExceeded
+        ),
+        _ => false,
+    }
+}
+
+/// The RPC URL of the node serving the given WebSocket URL,
+/// eg. `http://127.0.0.1:26657` for `ws://127.0.0.1:26657/websocket`.
+pub fn websocket_rpc_url(url: &WebSocketClientUrl) -> Option<Url> {
+    let url = url.to_string();
+
+    let url = if let Some(rest) = url.strip_prefix("wss://") {
+        format!("https://{rest}")
+    } else {
+        format!("http://{}", url.strip_prefix("ws://")?)
+    };
+
+    let url = url.trim_end_matches('/');
+    Url::from_str(url.strip_suffix("/websocket").unwrap_or(url)).ok()
+}
+
+/// Periodically probes the health of the endpoints of a chain, failing over
+/// to another endpoint when the one in use is unhealthy.
+pub struct EndpointProbe {
+    pub chain_id: ChainId,
+    pub rt: Arc<TokioRuntime>,
+    pub timeout: Duration,
+    pub max_height_lag: u64,
+    pub rpc: SharedEndpointPool<Url>,
+    pub grpc: SharedEndpointPool<Uri>,
+    pub websocket: Option<SharedEndpointPool<WebSocketClientUrl>>,
+}
+
+impl EndpointProbe {
+    /// Probe the endpoints every `interval` in a background thread,
+    /// until the returned sender is dropped.
+    pub fn spawn(self, interval: Duration) -> channel::Sender<()> {
+        let (shutdown_tx, shutdown_rx) = channel::bounded::<()>(0);
+
+        thread::spawn(move || {
+            let _span = error_span!("endpoint_probe", chain = %self.chain_id).entered();
+
+            while let Err(channel::RecvTimeoutError::Timeout) = shutdown_rx.recv_timeout(interval) {
+                self.probe();
+            }
+
+            debug!("endpoint probe is shutting down");
+        });
+
+        shutdown_tx
+    }
+
+    /// Probe all the endpoints once
+    pub fn probe(&self) {
+        let timeout = self.timeout;
+        let max_height_lag = Some(self.max_height_lag);
+
+        let rpc_addrs = self.rpc.acquire_read().addrs();
+        let results = self.rt.block_on(join_all(
+            rpc_addrs.iter().map(|addr| probe_rpc(addr, timeout)),
+        ));
+        self.rpc
+            .acquire_write()
+            .record_probes(results, max_height_lag);
+
+        let grpc_addrs = self.grpc.acquire_read().addrs();
+        let results = self.rt.block_on(join_all(
+            grpc_addrs.iter().map(|addr| probe_grpc(addr, timeout)),
+        ));
+        self.grpc.acquire_write().record_probes(results, None);
+
+        if let Some(websocket) = &self.websocket {
+            let ws_addrs = websocket.acquire_read().addrs();
+            let results = self
+                .rt
+                .block_on(join_all(ws_addrs.iter().map(|addr| async move {
+                    let rpc_addr = websocket_rpc_url(addr)
+                        .ok_or_else(|| format!("cannot derive the RPC URL of {addr}"))?;
+                    probe_rpc(&rpc_addr, timeout).await
+                })));
+            websocket
+                .acquire_write()
+                .record_probes(results, max_height_lag);
+        }
+    }
+}
+
+/// Probe an RPC endpoint with a `/status` query, which fails if the node is catching up.
+pub async fn probe_rpc(addr: &Url, timeout: Duration) -> Result<ProbeResult, String> {
+    let start = Instant::now();
+
+    let client = HttpClient::new(addr.clone()).map_err(|e| e.to_string())?;
+
+    let status = tokio::time::timeout(timeout, client.status())
+        .await
+        .map_err(|_| format!("no response after {timeout:?}"))?
+        .map_err(|e| e.to_string())?;
+
+    if status.sync_info.catching_up {
+        return Err("node is catching up".to_string());
+    }
+
+    Ok(ProbeResult {
+        latency: start.elapsed(),
+        latest_height: Some(status.sync_info.latest_block_height.value()),
+    })
+}
+
+/// Probe a gRPC endpoint with a `GetSyncing` query, which fails if the node is syncing.
+pub async fn probe_grpc(addr: &Uri, timeout: Duration) -> Result<ProbeResult, String> {
+    let start = Instant::now();
+
+    let query = async {
+        let mut client = ServiceClient::connect(addr.clone())
+            .await
+            .map_err(|e| e.to_string())?;
+
+        client
+            .get_syncing(tonic::Request::new(GetSyncingRequest {}))
+            .await
+            .map(|response| response.into_inner().syncing)
+            .map_err(|e| e.to_string())
+    };
+
+    let syncing = tokio::time::timeout(timeout, query)
+        .await
+        .map_err(|_| format!("no response after {timeout:?}"))??;
+
+    if syncing {
+        return Err("node is syncing".to_string());
+    }
+
+    Ok(ProbeResult {
+        latency: start.elapsed(),
+        latest_height: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(addrs: &[&str]) -> EndpointPool<String> {
+        EndpointPool::new(
+            ChainId::from_string("chain-a"),
+            EndpointKind::Rpc,
+            addrs[0].to_string(),
+            addrs[1..].iter().map(|addr| addr.to_string()),
+        )
+    }
+
+    fn probed(latency_millis: u64, height: u64) -> Result<ProbeResult, String> {
+        Ok(ProbeResult {
+            latency: Duration::from_millis(latency_millis),
+            latest_height: Some(height),
+        })
+    }
+
+    #[test]
+    fn fails_over_to_next_endpoint_in_order() {
+        let mut pool = pool(&["a", "b", "c", "a"]);
+        assert_eq!(pool.len(), 3);
+        assert_eq!(pool.current(), "a");
+
+        assert!(pool.report_current_failure());
+        assert_eq!(pool.current(), "b");
+
+        // A failure of an endpoint which is not in use does not trigger a fail over
+        assert!(!pool.report_failure(&"a".to_string()));
+        assert_eq!(pool.current(), "b");
+
+        // When no endpoint is healthy, the endpoints are used in turn
+        assert!(pool.report_current_failure());
+        assert_eq!(pool.current(), "c");
+        assert!(pool.report_current_failure());
+        assert_eq!(pool.current(), "a");
+    }
+
+    #[test]
+    fn fails_over_to_healthiest_endpoint() {
+        let mut pool = pool(&["a", "b", "c"]);
+
+        assert!(!pool.record_probes(
+            vec![probed(10, 100), probed(50, 100), probed(5, 100)],
+            Some(5)
+        ));
+        assert_eq!(pool.current(), "a");
+
+        assert!(pool.report_current_failure());
+        assert_eq!(pool.current(), "c");
+    }
+
+    #[test]
+    fn lagging_endpoint_is_unhealthy() {
+        let mut pool = pool(&["a", "b"]);
+
+        assert!(pool.record_probes(vec![probed(10, 90), probed(50, 100)], Some(5)));
+        assert_eq!(pool.current(), "b");
+        assert!(!pool.endpoints()[0].health.healthy);
+
+        // The endpoint which caught up is healthy again, but the one in use is kept
+        assert!(!pool.record_probes(vec![probed(10, 100), probed(50, 100)], Some(5)));
+        assert_eq!(pool.current(), "b");
+        assert!(pool.endpoints()[0].health.healthy);
+
+        assert!(pool.record_probes(
+            vec![probed(10, 101), Err("connection refused".to_string())],
+            Some(5)
+        ));
+        assert_eq!(pool.current(), "a");
+    }
+
+    #[test]
+    fn websocket_rpc_urls() {
+        let rpc_url = |ws: &str| websocket_rpc_url(&ws.parse().unwrap()).map(|u| u.to_string());
+
+        assert_eq!(
+            rpc_url("ws://127.0.0.1:26657/websocket").as_deref(),
+            Some("http://127.0.0.1:26657/")
+        );
+        assert_eq!(
+            rpc_url("wss://rpc.example.com/websocket").as_deref(),
+            Some("https://rpc.example.com/")
+        );
+    }
+}
@@ -15,11 +15,14 @@ use crate::chain::requests::{QueryClientEventRequest, QueryPacketEventDataReques
 use crate::error::Error;
 
 pub mod account;
+pub mod authz;
 pub mod balance;
+pub mod block_results;
 pub mod consensus_state;
 pub mod custom;
 pub mod denom_trace;
 pub mod fee;
+pub mod feegrant;
 pub mod status;
 pub mod tx;
 
@@ -0,0 +1,206 @@
+use http::uri::{PathAndQuery, Uri};
+use ibc_proto::cosmos::base::query::v1beta1::{PageRequest, PageResponse};
+use ibc_proto::google::protobuf::{Any, Timestamp};
+use prost::Message;
+use tendermint::Time;
+use tonic::codec::ProstCodec;
+use tonic::transport::Channel;
+
+use crate::chain::requests::PageRequest as QueryPageRequest;
+use crate::config::default::max_grpc_decoding_size;
+use crate::error::Error;
+
+// The `x/authz` protobuf messages are not part of `ibc-proto`, the definitions below
+// are taken from: https://github.com/cosmos/cosmos-sdk/blob/v0.47.0/proto/cosmos/authz/v1beta1
+
+const GRANTS_PATH: &str = "/cosmos.authz.v1beta1.Query/Grants";
+
+pub const GENERIC_AUTHORIZATION_TYPE_URL: &str = "/cosmos.authz.v1beta1.GenericAuthorization";
+
+#[derive(Clone, PartialEq, Message)]
+pub struct QueryGrantsRequest {
+    #[prost(string, tag = "1")]
+    pub granter: String,
+    #[prost(string, tag = "2")]
+    pub grantee: String,
+    #[prost(string, tag = "3")]
+    pub msg_type_url: String,
+    #[prost(message, optional, tag = "4")]
+    pub pagination: Option<PageRequest>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct QueryGrantsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub grants: Vec<Grant>,
+    #[prost(message, optional, tag = "2")]
+    pub pagination: Option<PageResponse>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Grant {
+    #[prost(message, optional, tag = "1")]
+    pub authorization: Option<Any>,
+    #[prost(message, optional, tag = "2")]
+    pub expiration: Option<Timestamp>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct GenericAuthorization {
+    #[prost(string, tag = "1")]
+    pub msg: String,
+}
+
+/// An authorization given by a granter to a grantee.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuthzGrant {
+    /// The type URL of the authorization
+    pub authorization_type_url: String,
+
+    /// The type URL of the message which the grantee may execute,
+    /// if this is a `GenericAuthorization`
+    pub msg_type_url: Option<String>,
+
+    /// The time after which the grant cannot be used anymore, if any
+    pub expiration: Option<Time>,
+}
+
+impl AuthzGrant {
+    /// Whether this grant allows the grantee to execute messages
+    /// with the given type URL at the given time.
+    pub fn allows(&self, msg_type_url: &str, now: Time) -> bool {
+        self.msg_type_url.as_deref() == Some(msg_type_url)
+            && self.expiration.map_or(true, |expiration| expiration > now)
+    }
+}
+
+/// Uses the GRPC client to retrieve all the authorizations given by `granter` to `grantee`.
+pub async fn query_authz_grants(
+    grpc_address: &Uri,
+    granter: &str,
+    grantee: &str,
+) -> Result<Vec<AuthzGrant>, Error> {
+    let channel = Channel::builder(grpc_address.clone())
+        .connect()
+        .await
+        .map_err(Error::grpc_transport)?;
+
+    let mut client = tonic::client::Grpc::new(channel)
+        .max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
+
+    client.ready().await.map_err(Error::grpc_transport)?;
+
+    let request = tonic::Request::new(QueryGrantsRequest {
+        granter: granter.to_string(),
+        grantee: grantee.to_string(),
+        msg_type_url: String::new(),
+        pagination: Some(QueryPageRequest::all().into()),
+    });
+
+    let response = client
+        .unary::<_, QueryGrantsResponse, _>(
+            request,
+            PathAndQuery::from_static(GRANTS_PATH),
+            ProstCodec::default(),
+        )
+        .await
+        .map_err(|e| Error::grpc_status(e, "query_authz_grants".to_owned()))?;
+
+    response
+        .into_inner()
+        .grants
+        .into_iter()
+        .map(decode_grant)
+        .collect()
+}
+
+fn decode_grant(grant: Grant) -> Result<AuthzGrant, Error> {
+    let authorization = grant
+        .authorization
+        .ok_or_else(|| Error::grpc_response_param("authorization".to_string()))?;
+
+    // Only generic authorizations are relevant to the IBC messages,
+    // the other kinds are kept around without a message type.
+    let msg_type_url = if authorization.type_url == GENERIC_AUTHORIZATION_TYPE_URL {
+        let generic = GenericAuthorization::decode(authorization.value.as_slice())
+            .map_err(|e| Error::protobuf_decode(authorization.type_url.clone(), e))?;
+
+        Some(generic.msg)
+    } else {
+        None
+    };
+
+    let expiration = grant
+        .expiration
+        .map(|expiration| {
+            Time::from_unix_timestamp(expiration.seconds, expiration.nanos as u32)
+                .map_err(|_| Error::grpc_response_param("expiration".to_string()))
+        })
+        .transpose()?;
+
+    Ok(AuthzGrant {
+        authorization_type_url: authorization.type_url,
+        msg_type_url,
+        expiration,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECV_PACKET: &str = "/ibc.core.channel.v1.MsgRecvPacket";
+
+    fn time(seconds: i64) -> Time {
+        Time::from_unix_timestamp(seconds, 0).unwrap()
+    }
+
+    #[test]
+    fn decode_generic_authorization() {
+        let generic = GenericAuthorization {
+            msg: RECV_PACKET.to_string(),
+        };
+
+        let grant = decode_grant(Grant {
+            authorization: Some(Any {
+                type_url: GENERIC_AUTHORIZATION_TYPE_URL.to_string(),
+                value: generic.encode_to_vec(),
+            }),
+            expiration: Some(Timestamp {
+                seconds: 2000,
+                nanos: 0,
+            }),
+        })
+        .unwrap();
+
+        assert_eq!(grant.msg_type_url.as_deref(), Some(RECV_PACKET));
+        assert!(grant.allows(RECV_PACKET, time(1000)));
+        assert!(!grant.allows(RECV_PACKET, time(3000)));
+        assert!(!grant.allows("/ibc.core.channel.v1.MsgTimeout", time(1000)));
+    }
+
+    #[test]
+    fn other_authorizations_allow_nothing() {
+        let grant = decode_grant(Grant {
+            authorization: Some(Any {
+                type_url: "/cosmos.bank.v1beta1.SendAuthorization".to_string(),
+                value: vec![],
+            }),
+            expiration: None,
+        })
+        .unwrap();
+
+        assert_eq!(grant.msg_type_url, None);
+        assert!(!grant.allows(RECV_PACKET, time(1000)));
+    }
+
+    #[test]
+    fn reject_grant_without_authorization() {
+        let result = decode_grant(Grant {
+            authorization: None,
+            expiration: None,
+        });
+
+        assert!(result.is_err());
+    }
+}
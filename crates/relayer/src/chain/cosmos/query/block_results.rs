@@ -0,0 +1,172 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tendermint::{abci, block, consensus, validator};
+use tendermint_rpc::dialect::{DeliverTx, Dialect};
+use tendermint_rpc::endpoint::block_results::Response as BlockResults;
+use tendermint_rpc::request::RequestMessage;
+use tendermint_rpc::{Client, Error, HttpClient, Method};
+
+use crate::config::CompatMode;
+
+/// Query the results of the block at the given height.
+///
+/// With CometBFT 0.38, the begin and end block events are replaced with the
+/// `finalize_block_events`, which are returned as end block events.
+pub async fn query_block_results(
+    rpc_client: &HttpClient,
+    compat_mode: CompatMode,
+    height: block::Height,
+) -> Result<BlockResults, Error> {
+    match compat_mode {
+        CompatMode::V0_38 => rpc_client.perform(BlockResultsRequest::new(height)).await,
+        CompatMode::V0_34 | CompatMode::V0_37 => rpc_client.block_results(height).await,
+    }
+}
+
+/// The `/block_results` request, whose response includes
+/// the `finalize_block_events` introduced in CometBFT 0.38.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockResultsRequest {
+    pub height: Option<block::Height>,
+}
+
+impl BlockResultsRequest {
+    pub fn new(height: block::Height) -> Self {
+        Self {
+            height: Some(height),
+        }
+    }
+}
+
+impl RequestMessage for BlockResultsRequest {
+    fn method(&self) -> Method {
+        Method::BlockResults
+    }
+}
+
+impl<S: Dialect> tendermint_rpc::Request<S> for BlockResultsRequest {
+    type Response = BlockResultsResponse<S::Event>;
+}
+
+impl<S: Dialect> tendermint_rpc::SimpleRequest<S> for BlockResultsRequest {
+    type Output = BlockResults;
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlockResultsResponse<Ev> {
+    pub height: block::Height,
+
+    /// Txs results (might be explicit null)
+    pub txs_results: Option<Vec<DeliverTx<Ev>>>,
+
+    /// Begin block events, up to CometBFT 0.37 (might be explicit null)
+    pub begin_block_events: Option<Vec<Ev>>,
+
+    /// End block events, up to CometBFT 0.37 (might be explicit null)
+    pub end_block_events: Option<Vec<Ev>>,
+
+    /// Finalize block events, since CometBFT 0.38 (might be explicit null)
+    pub finalize_block_events: Option<Vec<Ev>>,
+
+    /// Validator updates (might be explicit null)
+    #[serde(
+        default,
+        deserialize_with = "tendermint_rpc::serializers::nullable::deserialize"
+    )]
+    pub validator_updates: Vec<validator::Update>,
+
+    /// New consensus params (might be explicit null)
+    pub consensus_param_updates: Option<consensus::Params>,
+}
+
+impl<Ev> tendermint_rpc::Response for BlockResultsResponse<Ev> where Ev: Serialize + DeserializeOwned
+{}
+
+impl<Ev> From<BlockResultsResponse<Ev>> for BlockResults
+where
+    Ev: Into<abci::Event>,
+{
+    fn from(response: BlockResultsResponse<Ev>) -> Self {
+        fn convert<Ev: Into<abci::Event>>(events: Option<Vec<Ev>>) -> Option<Vec<abci::Event>> {
+            events.map(|events| events.into_iter().map(Into::into).collect())
+        }
+
+        let end_block_events = match (
+            convert(response.end_block_events),
+            convert(response.finalize_block_events),
+        ) {
+            (None, None) => None,
+            (end_block_events, finalize_block_events) => Some(
+                end_block_events
+                    .into_iter()
+                    .chain(finalize_block_events)
+                    .flatten()
+                    .collect(),
+            ),
+        };
+
+        Self {
+            height: response.height,
+            txs_results: response
+                .txs_results
+                .map(|txs_results| txs_results.into_iter().map(Into::into).collect()),
+            begin_block_events: convert(response.begin_block_events),
+            end_block_events,
+            validator_updates: response.validator_updates,
+            consensus_param_updates: response.consensus_param_updates,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tendermint_rpc::dialect::v0_37::Event;
+    use tendermint_rpc::Response;
+
+    #[test]
+    fn finalize_block_events_are_end_block_events() {
+        let json = r#"{
+            "jsonrpc": "2.0",
+            "id": -1,
+            "result": {
+                "height": "10",
+                "txs_results": [{
+                    "code": 0,
+                    "data": null,
+                    "log": "",
+                    "info": "",
+                    "gas_wanted": "100",
+                    "gas_used": "50",
+                    "events": [{
+                        "type": "send_packet",
+                        "attributes": [{ "key": "packet_sequence", "value": "1", "index": true }]
+                    }],
+                    "codespace": ""
+                }],
+                "finalize_block_events": [{
+                    "type": "update_client",
+                    "attributes": [{ "key": "client_id", "value": "07-tendermint-0", "index": true }]
+                }],
+                "validator_updates": null,
+                "consensus_param_updates": null,
+                "app_hash": ""
+            }
+        }"#;
+
+        let response = BlockResultsResponse::<Event>::from_string(json).unwrap();
+        let block_results = BlockResults::from(response);
+
+        assert_eq!(block_results.height, block::Height::from(10_u32));
+        assert_eq!(
+            block_results.txs_results.unwrap()[0].events[0].kind,
+            "send_packet"
+        );
+        assert_eq!(block_results.begin_block_events, None);
+
+        let end_block_events = block_results.end_block_events.unwrap();
+        assert_eq!(end_block_events.len(), 1);
+        assert_eq!(end_block_events[0].kind, "update_client");
+    }
+}
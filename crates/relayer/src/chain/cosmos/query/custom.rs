@@ -1,10 +1,24 @@
+use std::collections::{BTreeMap, HashSet};
+
 use crate::chain::requests::CrossChainQueryRequest;
+use crate::config::CompatMode;
 use crate::error::Error;
+use crate::event::source::rpc::collect_events;
+use crate::event::IbcEventWithHeight;
 use hex;
+use ibc_proto::cosmos::tx::v1beta1::Tx;
+use ibc_proto::stride::interchainquery::v1::MsgSubmitQueryResponse;
 use ibc_relayer_types::applications::ics31_icq::{
-    error::Error as CrossChainQueryError, response::CrossChainQueryResponse,
+    error::Error as CrossChainQueryError,
+    response::{CrossChainQueryResponse, TYPE_URL as MSG_SUBMIT_QUERY_RESPONSE_TYPE_URL},
 };
-use tendermint_rpc::{Client, HttpClient};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use prost::Message;
+use tendermint::block::Height as BlockHeight;
+use tendermint_rpc::{query::Query, Client, HttpClient, Order, Url};
+
+/// How many transactions to fetch per page when looking for the answered queries
+const TX_SEARCH_PAGE_SIZE: u8 = 100;
 
 pub async fn cross_chain_query_via_rpc(
     client: &HttpClient,
@@ -43,3 +57,154 @@ pub async fn cross_chain_query_via_rpc(
         response.proof.unwrap(),
     ))
 }
+
+/// Collect the interchain queries requested by the chain in its latest `blocks` blocks
+/// which have not been answered by a `MsgSubmitQueryResponse` since, ordered by height.
+pub async fn query_pending_cross_chain_queries(
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    compat_mode: CompatMode,
+    chain_id: &ChainId,
+    blocks: u64,
+) -> Result<Vec<IbcEventWithHeight>, Error> {
+    let latest_height = rpc_client
+        .abci_info()
+        .await
+        .map_err(|e| Error::rpc(rpc_address.clone(), e))?
+        .last_block_height
+        .value();
+
+    let start_height = latest_height
+        .saturating_sub(blocks)
+        .saturating_add(1)
+        .max(1);
+
+    // The latest request of each query, by query identifier
+    let mut requests = BTreeMap::new();
+
+    for height in start_height..=latest_height {
+        let height = BlockHeight::try_from(height).map_err(Error::invalid_height)?;
+
+        let batch = collect_events(rpc_client, compat_mode, chain_id, height)
+            .await
+            .map_err(Error::event_source)?;
+
+        for event in batch.into_iter().flat_map(|batch| batch.events) {
+            if let Some(packet) = event.event.cross_chain_query_packet() {
+                requests.insert(packet.query_id.clone(), event);
+            }
+        }
+    }
+
+    if requests.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let answered =
+        query_answered_cross_chain_queries(rpc_client, rpc_address, start_height).await?;
+
+    let mut pending = requests
+        .into_iter()
+        .filter(|(query_id, _)| !answered.contains(query_id))
+        .map(|(_, event)| event)
+        .collect::<Vec<_>>();
+
+    pending.sort_by_key(|event| event.height);
+
+    Ok(pending)
+}
+
+/// Collect the identifiers of the queries answered by the transactions
+/// included in the chain from the given height onwards.
+async fn query_answered_cross_chain_queries(
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    start_height: u64,
+) -> Result<HashSet<String>, Error> {
+    let query = Query::eq("message.action", MSG_SUBMIT_QUERY_RESPONSE_TYPE_URL)
+        .and_gte("tx.height", start_height);
+
+    let mut answered = HashSet::new();
+    let mut page = 1;
+
+    loop {
+        let response = rpc_client
+            .tx_search(
+                query.clone(),
+                false,
+                page,
+                TX_SEARCH_PAGE_SIZE,
+                Order::Ascending,
+            )
+            .await
+            .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+        for tx in &response.txs {
+            answered.extend(answered_query_ids(&tx.tx));
+        }
+
+        let fetched = u64::from(page) * u64::from(TX_SEARCH_PAGE_SIZE);
+        if response.txs.is_empty() || fetched >= u64::from(response.total_count) {
+            return Ok(answered);
+        }
+
+        page += 1;
+    }
+}
+
+/// The identifiers of the queries answered by the `MsgSubmitQueryResponse` messages
+/// of the given encoded transaction
+fn answered_query_ids(tx_bytes: &[u8]) -> Vec<String> {
+    let Some(body) = Tx::decode(tx_bytes).ok().and_then(|tx| tx.body) else {
+        return vec![];
+    };
+
+    body.messages
+        .into_iter()
+        .filter(|msg| msg.type_url == MSG_SUBMIT_QUERY_RESPONSE_TYPE_URL)
+        .filter_map(|msg| MsgSubmitQueryResponse::decode(msg.value.as_slice()).ok())
+        .map(|msg| msg.query_id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_proto::cosmos::tx::v1beta1::TxBody;
+    use ibc_proto::google::protobuf::Any;
+
+    #[test]
+    fn answered_query_ids_of_tx() {
+        let response = |query_id: &str| Any {
+            type_url: MSG_SUBMIT_QUERY_RESPONSE_TYPE_URL.to_string(),
+            value: MsgSubmitQueryResponse {
+                query_id: query_id.to_string(),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        };
+
+        let tx = Tx {
+            body: Some(TxBody {
+                messages: vec![
+                    Any {
+                        type_url: "/ibc.core.client.v1.MsgUpdateClient".to_string(),
+                        value: vec![],
+                    },
+                    response("query-1"),
+                    response("query-2"),
+                ],
+                ..Default::default()
+            }),
+            auth_info: None,
+            signatures: vec![],
+        };
+
+        assert_eq!(
+            answered_query_ids(&tx.encode_to_vec()),
+            vec!["query-1".to_string(), "query-2".to_string()]
+        );
+        assert!(answered_query_ids(b"not a transaction").is_empty());
+    }
+}
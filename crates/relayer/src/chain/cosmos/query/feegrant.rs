@@ -0,0 +1,295 @@
+use http::uri::{PathAndQuery, Uri};
+use ibc_proto::cosmos::base::v1beta1::Coin;
+use ibc_proto::google::protobuf::{Any, Duration, Timestamp};
+use prost::Message;
+use tendermint::Time;
+use tonic::codec::ProstCodec;
+use tonic::transport::Channel;
+use tonic::Code;
+
+use crate::account::{Balance, FeeAllowance};
+use crate::config::default::max_grpc_decoding_size;
+use crate::error::Error;
+
+// The `x/feegrant` protobuf messages are not part of `ibc-proto`, the definitions below
+// are taken from: https://github.com/cosmos/cosmos-sdk/blob/v0.47.0/proto/cosmos/feegrant/v1beta1
+
+const ALLOWANCE_PATH: &str = "/cosmos.feegrant.v1beta1.Query/Allowance";
+
+const BASIC_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.BasicAllowance";
+const PERIODIC_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.PeriodicAllowance";
+const ALLOWED_MSG_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.AllowedMsgAllowance";
+
+#[derive(Clone, PartialEq, Message)]
+pub struct QueryAllowanceRequest {
+    #[prost(string, tag = "1")]
+    pub granter: String,
+    #[prost(string, tag = "2")]
+    pub grantee: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct QueryAllowanceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub allowance: Option<Grant>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Grant {
+    #[prost(string, tag = "1")]
+    pub granter: String,
+    #[prost(string, tag = "2")]
+    pub grantee: String,
+    #[prost(message, optional, tag = "3")]
+    pub allowance: Option<Any>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct BasicAllowance {
+    #[prost(message, repeated, tag = "1")]
+    pub spend_limit: Vec<Coin>,
+    #[prost(message, optional, tag = "2")]
+    pub expiration: Option<Timestamp>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct PeriodicAllowance {
+    #[prost(message, optional, tag = "1")]
+    pub basic: Option<BasicAllowance>,
+    #[prost(message, optional, tag = "2")]
+    pub period: Option<Duration>,
+    #[prost(message, repeated, tag = "3")]
+    pub period_spend_limit: Vec<Coin>,
+    #[prost(message, repeated, tag = "4")]
+    pub period_can_spend: Vec<Coin>,
+    #[prost(message, optional, tag = "5")]
+    pub period_reset: Option<Timestamp>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct AllowedMsgAllowance {
+    #[prost(message, optional, tag = "1")]
+    pub allowance: Option<Any>,
+    #[prost(string, repeated, tag = "2")]
+    pub allowed_messages: Vec<String>,
+}
+
+/// Uses the GRPC client to retrieve the fee allowance given by `granter` to `grantee`.
+/// Returns `None` if there is no such allowance, eg. because it was fully spent.
+pub async fn query_fee_allowance(
+    grpc_address: &Uri,
+    granter: &str,
+    grantee: &str,
+) -> Result<Option<FeeAllowance>, Error> {
+    let channel = Channel::builder(grpc_address.clone())
+        .connect()
+        .await
+        .map_err(Error::grpc_transport)?;
+
+    let mut client = tonic::client::Grpc::new(channel)
+        .max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
+
+    client.ready().await.map_err(Error::grpc_transport)?;
+
+    let request = tonic::Request::new(QueryAllowanceRequest {
+        granter: granter.to_string(),
+        grantee: grantee.to_string(),
+    });
+
+    let response = client
+        .unary::<_, QueryAllowanceResponse, _>(
+            request,
+            PathAndQuery::from_static(ALLOWANCE_PATH),
+            ProstCodec::default(),
+        )
+        .await;
+
+    let grant = match response {
+        Ok(response) => response.into_inner().allowance,
+        // The SDK returns a `NotFound` error when there is no grant
+        Err(status) if status.code() == Code::NotFound => None,
+        Err(status) if status.message().contains("fee-grant not found") => None,
+        Err(status) => {
+            return Err(Error::grpc_status(status, "query_fee_allowance".to_owned()));
+        }
+    };
+
+    grant.map(fee_allowance_from_grant).transpose()
+}
+
+fn fee_allowance_from_grant(grant: Grant) -> Result<FeeAllowance, Error> {
+    let mut fee_allowance = FeeAllowance {
+        granter: grant.granter,
+        grantee: grant.grantee,
+        spend_limit: None,
+        period_can_spend: None,
+        expiration: None,
+        allowed_messages: Vec::new(),
+    };
+
+    let allowance = grant
+        .allowance
+        .ok_or_else(|| Error::grpc_response_param("allowance".to_string()))?;
+
+    decode_allowance(allowance, &mut fee_allowance)?;
+
+    Ok(fee_allowance)
+}
+
+fn decode_allowance(allowance: Any, fee_allowance: &mut FeeAllowance) -> Result<(), Error> {
+    let decode_error = |e| Error::protobuf_decode(allowance.type_url.clone(), e);
+
+    match allowance.type_url.as_str() {
+        BASIC_ALLOWANCE_TYPE_URL => {
+            let basic = BasicAllowance::decode(allowance.value.as_slice()).map_err(decode_error)?;
+
+            apply_basic_allowance(basic, fee_allowance)
+        }
+        PERIODIC_ALLOWANCE_TYPE_URL => {
+            let periodic =
+                PeriodicAllowance::decode(allowance.value.as_slice()).map_err(decode_error)?;
+
+            if let Some(basic) = periodic.basic {
+                apply_basic_allowance(basic, fee_allowance)?;
+            }
+
+            fee_allowance.period_can_spend = Some(to_balances(periodic.period_can_spend));
+
+            Ok(())
+        }
+        ALLOWED_MSG_ALLOWANCE_TYPE_URL => {
+            let allowed =
+                AllowedMsgAllowance::decode(allowance.value.as_slice()).map_err(decode_error)?;
+
+            fee_allowance.allowed_messages = allowed.allowed_messages;
+
+            let inner = allowed
+                .allowance
+                .ok_or_else(|| Error::grpc_response_param("allowance".to_string()))?;
+
+            decode_allowance(inner, fee_allowance)
+        }
+        type_url => Err(Error::unknown_fee_allowance_type(type_url.to_string())),
+    }
+}
+
+fn apply_basic_allowance(
+    basic: BasicAllowance,
+    fee_allowance: &mut FeeAllowance,
+) -> Result<(), Error> {
+    // An empty spend limit means that the allowance is unlimited
+    if !basic.spend_limit.is_empty() {
+        fee_allowance.spend_limit = Some(to_balances(basic.spend_limit));
+    }
+
+    fee_allowance.expiration = basic
+        .expiration
+        .map(|expiration| {
+            Time::from_unix_timestamp(expiration.seconds, expiration.nanos as u32)
+                .map_err(|_| Error::grpc_response_param("expiration".to_string()))
+        })
+        .transpose()?;
+
+    Ok(())
+}
+
+fn to_balances(coins: Vec<Coin>) -> Vec<Balance> {
+    coins
+        .into_iter()
+        .map(|coin| Balance {
+            amount: coin.amount,
+            denom: coin.denom,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::account::FeeAllowanceStatus;
+
+    fn coin(amount: &str) -> Coin {
+        Coin {
+            denom: "stake".to_string(),
+            amount: amount.to_string(),
+        }
+    }
+
+    fn grant(allowance: Any) -> Grant {
+        Grant {
+            granter: "cosmos1granter".to_string(),
+            grantee: "cosmos1grantee".to_string(),
+            allowance: Some(allowance),
+        }
+    }
+
+    #[test]
+    fn decode_allowed_msg_periodic_allowance() {
+        let periodic = PeriodicAllowance {
+            basic: Some(BasicAllowance {
+                spend_limit: vec![coin("1000")],
+                expiration: Some(Timestamp {
+                    seconds: 2000,
+                    nanos: 0,
+                }),
+            }),
+            period: None,
+            period_spend_limit: vec![coin("100")],
+            period_can_spend: vec![coin("0")],
+            period_reset: None,
+        };
+
+        let allowed = AllowedMsgAllowance {
+            allowance: Some(Any {
+                type_url: PERIODIC_ALLOWANCE_TYPE_URL.to_string(),
+                value: periodic.encode_to_vec(),
+            }),
+            allowed_messages: vec!["/ibc.core.channel.v1.MsgRecvPacket".to_string()],
+        };
+
+        let allowance = fee_allowance_from_grant(grant(Any {
+            type_url: ALLOWED_MSG_ALLOWANCE_TYPE_URL.to_string(),
+            value: allowed.encode_to_vec(),
+        }))
+        .unwrap();
+
+        assert_eq!(allowance.allowed_messages.len(), 1);
+        assert_eq!(allowance.remaining("stake"), Some(0.0));
+        assert_eq!(
+            allowance.expiration,
+            Some(Time::from_unix_timestamp(2000, 0).unwrap())
+        );
+        assert_eq!(
+            allowance.status(Time::from_unix_timestamp(1000, 0).unwrap()),
+            FeeAllowanceStatus::Exhausted
+        );
+    }
+
+    #[test]
+    fn decode_unlimited_basic_allowance() {
+        let basic = BasicAllowance {
+            spend_limit: vec![],
+            expiration: None,
+        };
+
+        let allowance = fee_allowance_from_grant(grant(Any {
+            type_url: BASIC_ALLOWANCE_TYPE_URL.to_string(),
+            value: basic.encode_to_vec(),
+        }))
+        .unwrap();
+
+        assert!(allowance.spend_limit.is_none());
+        assert_eq!(allowance.expiration, None);
+    }
+
+    #[test]
+    fn reject_unknown_allowance() {
+        let result = fee_allowance_from_grant(grant(Any {
+            type_url: "/foo.Allowance".to_string(),
+            value: vec![],
+        }));
+
+        assert!(result.is_err());
+    }
+}
@@ -1,7 +1,7 @@
 use ibc_relayer_types::core::ics02_client::height::Height;
 use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
-use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::events::{IbcEvent, WithBlockDataType};
 use ibc_relayer_types::Height as ICSHeight;
 use tendermint::abci::Event;
 use tendermint::Hash as TxHash;
@@ -9,11 +9,14 @@ use tendermint_rpc::endpoint::tx::Response as TxResponse;
 use tendermint_rpc::{Client, HttpClient, Order, Url};
 use tracing::warn;
 
+use crate::chain::cosmos::query::block_results::query_block_results;
 use crate::chain::cosmos::query::{header_query, packet_query, tx_hash_query};
 use crate::chain::cosmos::types::events;
 use crate::chain::requests::{
-    QueryClientEventRequest, QueryHeight, QueryPacketEventDataRequest, QueryTxHash, QueryTxRequest,
+    Qualified, QueryBlockPacketEventsRequest, QueryClientEventRequest, QueryHeight,
+    QueryPacketEventDataRequest, QueryTxHash, QueryTxRequest,
 };
+use crate::config::CompatMode;
 use crate::error::Error;
 use crate::event::{ibc_event_try_from_abci_event, IbcEventWithHeight};
 
@@ -168,10 +171,12 @@ pub async fn query_packets_from_txs(
 
 /// This function queries packet events from a block at a specific height.
 /// It returns packet events that match certain criteria (see [`filter_matching_event`]).
-/// It returns at most one packet event for each sequence specified in the request.
+/// It returns at most one packet event for each sequence specified in the request,
+/// or all the packet events of the channel if no sequence is specified.
 pub async fn query_packets_from_block(
     chain_id: &ChainId,
     rpc_client: &HttpClient,
+    compat_mode: CompatMode,
     rpc_address: &Url,
     request: &QueryPacketEventDataRequest,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
@@ -190,55 +195,117 @@ pub async fn query_packets_from_block(
         }
     };
 
+    query_matching_events_from_block(
+        chain_id,
+        rpc_client,
+        compat_mode,
+        rpc_address,
+        tm_height,
+        core::slice::from_ref(request),
+    )
+    .await
+}
+
+/// This function queries the packets sent and acknowledged on a channel in the block
+/// at the requested height, pulling the results of the block only once for both kinds of events.
+pub async fn query_block_packet_events(
+    chain_id: &ChainId,
+    rpc_client: &HttpClient,
+    compat_mode: CompatMode,
+    rpc_address: &Url,
+    request: &QueryBlockPacketEventsRequest,
+) -> Result<Vec<IbcEventWithHeight>, Error> {
+    crate::time!(
+        "query_block_packet_events",
+        {
+            "src_chain": chain_id,
+        }
+    );
+    crate::telemetry!(query, chain_id, "query_block_packet_events");
+
+    let tm_height = tendermint::block::Height::try_from(request.height.revision_height())
+        .map_err(|_| Error::invalid_height_no_source())?;
+
+    let height = Qualified::Equal(QueryHeight::Specific(request.height));
+
+    // The packets sent on the channel have their source on the queried chain,
+    // while the packets acknowledged on the channel have their destination on it
+    let requests = [
+        QueryPacketEventDataRequest {
+            event_id: WithBlockDataType::SendPacket,
+            source_port_id: request.port_id.clone(),
+            source_channel_id: request.channel_id.clone(),
+            destination_port_id: request.counterparty_port_id.clone(),
+            destination_channel_id: request.counterparty_channel_id.clone(),
+            sequences: vec![],
+            height,
+        },
+        QueryPacketEventDataRequest {
+            event_id: WithBlockDataType::WriteAck,
+            source_port_id: request.counterparty_port_id.clone(),
+            source_channel_id: request.counterparty_channel_id.clone(),
+            destination_port_id: request.port_id.clone(),
+            destination_channel_id: request.channel_id.clone(),
+            sequences: vec![],
+            height,
+        },
+    ];
+
+    query_matching_events_from_block(
+        chain_id,
+        rpc_client,
+        compat_mode,
+        rpc_address,
+        tm_height,
+        &requests,
+    )
+    .await
+}
+
+/// Returns the events of the block at the given height which match any of the given requests:
+/// the begin-block events first, then the events of each transaction of the block in turn,
+/// and finally the end-block events.
+async fn query_matching_events_from_block(
+    chain_id: &ChainId,
+    rpc_client: &HttpClient,
+    compat_mode: CompatMode,
+    rpc_address: &Url,
+    tm_height: tendermint::block::Height,
+    requests: &[QueryPacketEventDataRequest],
+) -> Result<Vec<IbcEventWithHeight>, Error> {
     let height = Height::new(chain_id.version(), u64::from(tm_height))
         .map_err(|_| Error::invalid_height_no_source())?;
 
-    let block_results = rpc_client
-        .block_results(tm_height)
+    let block_results = query_block_results(rpc_client, compat_mode, tm_height)
         .await
         .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
 
-    let mut tx_events = vec![];
-    let mut begin_block_events = vec![];
-    let mut end_block_events = vec![];
-
-    if let Some(txs) = block_results.txs_results {
-        for tx in txs {
-            tx_events.append(
-                &mut tx
-                    .events
-                    .iter()
-                    .filter_map(|ev| filter_matching_event(ev, request, &request.sequences))
-                    .map(|ev| IbcEventWithHeight::new(ev, height))
-                    .collect(),
-            )
-        }
-    }
-
-    begin_block_events.append(
-        &mut block_results
-            .begin_block_events
-            .unwrap_or_default()
+    let matching_event = |ev: &Event| {
+        requests
             .iter()
-            .filter_map(|ev| filter_matching_event(ev, request, &request.sequences))
+            .find_map(|request| filter_matching_event(ev, request, &request.sequences))
             .map(|ev| IbcEventWithHeight::new(ev, height))
-            .collect(),
-    );
+    };
+
+    let mut events: Vec<_> = block_results
+        .begin_block_events
+        .unwrap_or_default()
+        .iter()
+        .filter_map(matching_event)
+        .collect();
 
-    end_block_events.append(
-        &mut block_results
+    for tx in block_results.txs_results.unwrap_or_default() {
+        events.extend(tx.events.iter().filter_map(matching_event));
+    }
+
+    events.extend(
+        block_results
             .end_block_events
             .unwrap_or_default()
             .iter()
-            .filter_map(|ev| filter_matching_event(ev, request, &request.sequences))
-            .map(|ev| IbcEventWithHeight::new(ev, height))
-            .collect(),
+            .filter_map(matching_event),
     );
 
-    let mut events = begin_block_events;
-    events.append(&mut tx_events);
-    events.append(&mut end_block_events);
-
     Ok(events)
 }
 
@@ -313,6 +380,7 @@ fn packet_from_tx_search_response(
 
 /// Returns the given event wrapped in `Some` if the event data
 /// is consistent with the request parameters.
+/// An empty list of sequences matches the packets with any sequence.
 /// Returns `None` otherwise.
 pub fn filter_matching_event(
     event: &Event,
@@ -328,7 +396,7 @@ pub fn filter_matching_event(
             && packet.source_channel == request.source_channel_id
             && packet.destination_port == request.destination_port_id
             && packet.destination_channel == request.destination_channel_id
-            && seqs.contains(&packet.sequence)
+            && (seqs.is_empty() || seqs.contains(&packet.sequence))
     }
 
     if event.kind != request.event_id.as_str() {
@@ -1,4 +1,3 @@
-use core::time::Duration;
 use std::thread;
 
 use tracing::{debug, error, instrument, warn};
@@ -14,13 +13,10 @@ use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::config::types::Memo;
 use crate::error::Error;
-use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+use crate::keyring::{CosmosKeyPair, SigningKeyPair};
 use crate::sdk_error::sdk_error_from_tx_sync_error_code;
 use crate::{telemetry, time};
 
-// Delay in milliseconds before retrying in the case of account sequence mismatch.
-const ACCOUNT_SEQUENCE_RETRY_DELAY: u64 = 300;
-
 // The error "incorrect account sequence" is defined as the unique error code 32 in cosmos-sdk:
 // https://github.com/cosmos/cosmos-sdk/blob/v0.44.0/types/errors/errors.go#L115-L117
 const INCORRECT_ACCOUNT_SEQUENCE_ERR: u32 = 32;
@@ -35,7 +31,8 @@ const INCORRECT_ACCOUNT_SEQUENCE_ERR: u32 = 32;
 ///     the `broadcast_tx_sync` step.
 ///
 /// We treat both cases by re-fetching the account sequence number
-/// from the full node and retrying once with the new account s.n.
+/// from the full node and retrying with the new account s.n.,
+/// as configured by the `tx` retry policy of the chain.
 #[instrument(
     name = "send_tx_with_account_sequence_retry",
     level = "error",
@@ -48,7 +45,7 @@ const INCORRECT_ACCOUNT_SEQUENCE_ERR: u32 = 32;
 pub async fn send_tx_with_account_sequence_retry(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     account: &mut Account,
     tx_memo: &Memo,
     messages: &[Any],
@@ -69,6 +66,11 @@ pub async fn send_tx_with_account_sequence_retry(
 
     if response.is_ok() {
         telemetry!(messages_submitted, &config.chain_id, _message_count);
+        telemetry!(
+            wallet_txs_submitted,
+            &config.chain_id,
+            account.address.as_str()
+        );
     }
 
     response
@@ -77,7 +79,7 @@ pub async fn send_tx_with_account_sequence_retry(
 async fn do_send_tx_with_account_sequence_retry(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     account: &mut Account,
     tx_memo: &Memo,
     messages: &[Any],
@@ -86,29 +88,41 @@ async fn do_send_tx_with_account_sequence_retry(
         // Gas estimation failed with account sequence mismatch during gas estimation.
         // It indicates that the account sequence cached by hermes is stale (got < expected).
         // This can happen when the same account is used by another agent.
-        Err(ref e) if mismatch_account_sequence_number_error_requires_refresh(e) => {
+        Err(e) if mismatch_account_sequence_number_error_requires_refresh(&e) => {
             warn!(
                 error = %e,
                 "failed to estimate gas because of a mismatched account sequence number, \
-                refreshing account sequence number and retrying once",
+                refreshing account sequence number and retrying",
             );
 
             refresh_account_and_retry_send_tx_with_account_sequence(
-                rpc_client, config, key_pair, account, tx_memo, messages,
+                rpc_client,
+                config,
+                key_pair,
+                account,
+                tx_memo,
+                messages,
+                Err(e),
             )
             .await
         }
 
         // Gas estimation succeeded but broadcast_tx_sync failed with a retry-able error.
-        Ok(ref response) if response.code == Code::from(INCORRECT_ACCOUNT_SEQUENCE_ERR) => {
+        Ok(response) if response.code == Code::from(INCORRECT_ACCOUNT_SEQUENCE_ERR) => {
             warn!(
                 ?response,
                 "failed to broadcast tx because of a mismatched account sequence number, \
-                refreshing account sequence number and retrying once"
+                refreshing account sequence number and retrying"
             );
 
             refresh_account_and_retry_send_tx_with_account_sequence(
-                rpc_client, config, key_pair, account, tx_memo, messages,
+                rpc_client,
+                config,
+                key_pair,
+                account,
+                tx_memo,
+                messages,
+                Ok(response),
             )
             .await
         }
@@ -161,22 +175,54 @@ async fn do_send_tx_with_account_sequence_retry(
     }
 }
 
+/// Refresh the account sequence number and retry sending the tx, after each of
+/// the delays of the `tx` retry policy, as long as the account sequence mismatches.
+/// Returns the `failed` result if the policy does not allow for any retry.
 async fn refresh_account_and_retry_send_tx_with_account_sequence(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     account: &mut Account,
     tx_memo: &Memo,
     messages: &[Any],
+    failed: Result<Response, Error>,
 ) -> Result<Response, Error> {
     let key_account = key_pair.account();
-    // Re-fetch the account sequence number
-    refresh_account(&config.grpc_address, &key_account, account).await?;
 
-    // Retry after delay
-    thread::sleep(Duration::from_millis(ACCOUNT_SEQUENCE_RETRY_DELAY));
+    let mut result = failed;
+
+    for (attempt, delay) in config.retry.delays().enumerate() {
+        if attempt > 0 {
+            warn!(
+                retry = attempt + 1,
+                "account sequence number still mismatched, refreshing it and retrying"
+            );
+        }
+
+        // Re-fetch the account sequence number
+        refresh_account(&config.grpc_address, &key_account, account).await?;
+
+        // Retry after delay
+        thread::sleep(delay);
 
-    estimate_fee_and_send_tx(rpc_client, config, key_pair, account, tx_memo, messages).await
+        result = estimate_fee_and_send_tx(rpc_client, config, key_pair, account, tx_memo, messages)
+            .await;
+
+        if !is_account_sequence_mismatch(&result) {
+            break;
+        }
+    }
+
+    result
+}
+
+/// Whether sending a tx failed because of a mismatched account sequence number,
+/// either during the gas estimation or when broadcasting the tx.
+fn is_account_sequence_mismatch(result: &Result<Response, Error>) -> bool {
+    match result {
+        Ok(response) => response.code == Code::from(INCORRECT_ACCOUNT_SEQUENCE_ERR),
+        Err(e) => mismatch_account_sequence_number_error_requires_refresh(e),
+    }
 }
 
 /// Determine whether the given error yielded by `tx_simulate`
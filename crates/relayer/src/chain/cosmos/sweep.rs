@@ -0,0 +1,154 @@
+use core::str::FromStr;
+
+use ibc_proto::cosmos::tx::v1beta1::Fee;
+use ibc_relayer_types::applications::transfer::msgs::send::MsgSend;
+use ibc_relayer_types::applications::transfer::{Amount, Coin};
+use ibc_relayer_types::tx_msg::Msg;
+use tendermint_rpc::HttpClient;
+use tracing::warn;
+
+use crate::account::Balance;
+use crate::chain::cosmos::query::account::get_or_fetch_account;
+use crate::chain::cosmos::retry::send_tx_with_account_sequence_retry;
+use crate::chain::cosmos::types::account::Account;
+use crate::chain::cosmos::types::config::TxConfig;
+use crate::chain::cosmos::wait::wait_tx_succeed;
+use crate::config::types::Memo;
+use crate::error::Error;
+use crate::keyring::{CosmosKeyPair, SigningKeyPair};
+
+/// Send the given coins from the account of the given key to the given address,
+/// in a single bank transfer signed and paid for by the key.
+pub async fn sweep_coins(
+    rpc_client: &HttpClient,
+    tx_config: &TxConfig,
+    key_pair: &CosmosKeyPair,
+    tx_memo: &Memo,
+    to_address: &str,
+    coins: Vec<Coin<String>>,
+) -> Result<(), Error> {
+    let from_address = key_pair.account();
+
+    let mut m_account: Option<Account> = None;
+    let account =
+        get_or_fetch_account(&tx_config.grpc_address, &from_address, &mut m_account).await?;
+
+    let message = MsgSend {
+        from_address: from_address.clone(),
+        to_address: to_address.to_string(),
+        amount: coins,
+    }
+    .to_any();
+
+    let response = send_tx_with_account_sequence_retry(
+        rpc_client,
+        tx_config,
+        key_pair,
+        account,
+        tx_memo,
+        &[message],
+    )
+    .await?;
+
+    wait_tx_succeed(
+        rpc_client,
+        &tx_config.rpc_address,
+        &tx_config.rpc_timeout,
+        &response.hash,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// The coins of the given balances which can be swept out of an account, ie.
+/// all of them except for the given fee, which is kept to pay for the transfer.
+/// The denominations with nothing left once the fee is kept are left out.
+pub fn sweepable_coins(balances: &[Balance], kept_fee: &Fee) -> Vec<Coin<String>> {
+    balances
+        .iter()
+        .filter_map(|balance| {
+            let amount = match Amount::from_str(&balance.amount) {
+                Ok(amount) => amount,
+                Err(e) => {
+                    warn!(
+                        denom = %balance.denom,
+                        "skipping balance with invalid amount `{}`: {}", balance.amount, e
+                    );
+                    return None;
+                }
+            };
+
+            let fee = kept_fee
+                .amount
+                .iter()
+                .filter(|coin| coin.denom == balance.denom)
+                .filter_map(|coin| Amount::from_str(&coin.amount).ok())
+                .sum::<Amount>();
+
+            amount
+                .checked_sub(fee)
+                .filter(|amount| *amount > Amount::from(0u64))
+                .map(|amount| Coin {
+                    denom: balance.denom.clone(),
+                    amount,
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+
+    fn balance(amount: &str, denom: &str) -> Balance {
+        Balance {
+            amount: amount.to_string(),
+            denom: denom.to_string(),
+        }
+    }
+
+    #[test]
+    fn keeps_max_fee_in_fee_denom() {
+        let max_fee = Fee {
+            amount: vec![ProtoCoin {
+                denom: "stake".to_string(),
+                amount: "1000".to_string(),
+            }],
+            gas_limit: 400000,
+            payer: String::new(),
+            granter: String::new(),
+        };
+
+        let coins = sweepable_coins(
+            &[
+                balance("5000", "stake"),
+                balance("1000", "uatom"),
+                balance(
+                    "0",
+                    "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2",
+                ),
+            ],
+            &max_fee,
+        );
+
+        assert_eq!(
+            coins,
+            vec![
+                Coin {
+                    denom: "stake".to_string(),
+                    amount: Amount::from(4000u64),
+                },
+                Coin {
+                    denom: "uatom".to_string(),
+                    amount: Amount::from(1000u64),
+                },
+            ]
+        );
+
+        // Nothing is left to sweep once the fee is kept
+        assert!(sweepable_coins(&[balance("800", "stake")], &max_fee).is_empty());
+    }
+}
@@ -14,14 +14,14 @@ use crate::chain::cosmos::wait::wait_tx_succeed;
 use crate::config::types::Memo;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
-use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+use crate::keyring::{CosmosKeyPair, SigningKeyPair};
 
 use super::batch::send_batched_messages_and_wait_commit;
 
 pub async fn estimate_fee_and_send_tx(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     account: &Account,
     tx_memo: &Memo,
     messages: &[Any],
@@ -37,7 +37,7 @@ pub async fn estimate_fee_and_send_tx(
 async fn send_tx_with_fee(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     account: &Account,
     tx_memo: &Memo,
     messages: &[Any],
@@ -67,7 +67,7 @@ pub async fn broadcast_tx_sync(
 /**
  A simplified version of send_tx that does not depend on `ChainHandle`.
 
- This allows different wallet ([`Secp256k1KeyPair`]) to be used for
+ This allows different wallet ([`CosmosKeyPair`]) to be used for
  submitting transactions. The simple behavior as follows:
 
  - Query the account information on the fly. This may introduce more
@@ -79,7 +79,7 @@ pub async fn broadcast_tx_sync(
 pub async fn simple_send_tx(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     messages: Vec<Any>,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
     let key_account = key_pair.account();
@@ -117,7 +117,7 @@ pub async fn simple_send_tx(
 pub async fn batched_send_tx(
     rpc_client: &HttpClient,
     config: &TxConfig,
-    key_pair: &Secp256k1KeyPair,
+    key_pair: &CosmosKeyPair,
     messages: Vec<Any>,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
     let key_account = key_pair.account();
@@ -7,7 +7,7 @@ use tendermint_rpc::Url;
 
 use crate::chain::cosmos::types::gas::GasConfig;
 use crate::config::types::{MaxMsgNum, MaxTxSize};
-use crate::config::{AddressType, ChainConfig};
+use crate::config::{AddressType, ChainConfig, RetryPolicy};
 use crate::error::Error;
 
 #[derive(Debug, Clone)]
@@ -21,6 +21,10 @@ pub struct TxConfig {
     pub max_msg_num: MaxMsgNum,
     pub max_tx_size: MaxTxSize,
     pub extension_options: Vec<Any>,
+    /// The account on behalf of which the messages are executed, via `MsgExec`
+    pub authz_granter: Option<String>,
+    /// The policy for retrying the txs which failed because of a mismatched account sequence
+    pub retry: RetryPolicy,
 }
 
 impl<'a> TryFrom<&'a ChainConfig> for TxConfig {
@@ -48,6 +52,20 @@ impl<'a> TryFrom<&'a ChainConfig> for TxConfig {
             max_msg_num: config.max_msg_num,
             max_tx_size: config.max_tx_size,
             extension_options,
+            authz_granter: config.authz_granter.clone(),
+            retry: config.retry.tx.clone().unwrap_or_else(RetryPolicy::tx),
         })
     }
 }
+
+impl TxConfig {
+    /// A copy of this configuration, with fees paid by the given fee granter.
+    pub fn with_fee_granter(&self, granter: &str) -> Self {
+        let mut config = self.clone();
+
+        config.gas_config.fee_granter = granter.to_string();
+        config.gas_config.max_fee.granter = granter.to_string();
+
+        config
+    }
+}
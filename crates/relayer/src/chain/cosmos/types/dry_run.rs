@@ -0,0 +1,186 @@
+//! Bookkeeping of the transactions which would have been submitted
+//! to a chain when running in dry-run mode.
+
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+use std::thread;
+use std::time::Instant;
+
+use crossbeam_channel as channel;
+use ibc_proto::cosmos::tx::v1beta1::Fee;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use itertools::Itertools;
+use tracing::{debug, error_span, info};
+
+use crate::util::lock::{LockExt, RwArc};
+
+/// How often the projected spend of a chain is reported.
+pub const DRY_RUN_REPORT_INTERVAL: Duration = Duration::from_secs(60);
+
+pub type SharedDryRunReport = RwArc<DryRunReport>;
+
+/// A transaction which was built, signed and simulated, but not broadcast.
+#[derive(Clone, Debug)]
+pub struct SimulatedTx {
+    pub message_type_urls: Vec<String>,
+    pub fee: Fee,
+}
+
+/// The aggregate projected spend of the simulated transactions
+/// since the last report.
+#[derive(Clone, Debug)]
+pub struct DryRunReport {
+    since: Instant,
+    txs: u64,
+    messages: u64,
+    gas: u64,
+    fees: BTreeMap<String, u128>,
+}
+
+impl DryRunReport {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            since: now,
+            txs: 0,
+            messages: 0,
+            gas: 0,
+            fees: BTreeMap::new(),
+        }
+    }
+
+    pub fn record(&mut self, tx: &SimulatedTx) {
+        self.txs += 1;
+        self.messages += tx.message_type_urls.len() as u64;
+        self.gas += tx.fee.gas_limit;
+
+        for coin in &tx.fee.amount {
+            // Coin amounts are integers, anything else is ignored
+            if let Ok(amount) = coin.amount.parse::<u128>() {
+                *self.fees.entry(coin.denom.clone()).or_default() += amount;
+            }
+        }
+    }
+
+    /// The projected fees, eg. `1200stake, 5uatom`.
+    pub fn fees(&self) -> String {
+        if self.fees.is_empty() {
+            return "none".to_string();
+        }
+
+        self.fees
+            .iter()
+            .map(|(denom, amount)| format!("{amount}{denom}"))
+            .join(", ")
+    }
+
+    /// Log the projected spend since the last report, and start a new interval.
+    pub fn report(&mut self, chain_id: &ChainId, now: Instant) {
+        info!(
+            chain = %chain_id,
+            txs = self.txs,
+            messages = self.messages,
+            gas = self.gas,
+            fees = %self.fees(),
+            "[dry-run] projected spend over the last {}s",
+            now.duration_since(self.since).as_secs(),
+        );
+
+        *self = Self::new(now);
+    }
+}
+
+/// Periodically reports the projected spend of the transactions
+/// simulated on a chain, whether or not any were simulated since.
+pub struct DryRunReporter {
+    pub chain_id: ChainId,
+    pub report: SharedDryRunReport,
+}
+
+impl DryRunReporter {
+    /// Report the projected spend every `interval` in a background thread,
+    /// until the returned sender is dropped.
+    pub fn spawn(self, interval: Duration) -> channel::Sender<()> {
+        let (shutdown_tx, shutdown_rx) = channel::bounded::<()>(0);
+
+        thread::spawn(move || {
+            let _span = error_span!("dry_run_reporter", chain = %self.chain_id).entered();
+
+            while let Err(channel::RecvTimeoutError::Timeout) = shutdown_rx.recv_timeout(interval) {
+                self.report
+                    .acquire_write()
+                    .report(&self.chain_id, Instant::now());
+            }
+
+            debug!("dry-run reporter is shutting down");
+        });
+
+        shutdown_tx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+
+    fn simulated_tx(messages: usize, gas: u64, amount: &str) -> SimulatedTx {
+        SimulatedTx {
+            message_type_urls: vec!["/ibc.core.channel.v1.MsgRecvPacket".to_string(); messages],
+            fee: Fee {
+                amount: vec![Coin {
+                    denom: "stake".to_string(),
+                    amount: amount.to_string(),
+                }],
+                gas_limit: gas,
+                payer: String::new(),
+                granter: String::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn aggregates_simulated_txs() {
+        let mut report = DryRunReport::new(Instant::now());
+
+        report.record(&simulated_tx(2, 1000, "10"));
+        report.record(&simulated_tx(3, 500, "5"));
+
+        assert_eq!(report.txs, 2);
+        assert_eq!(report.messages, 5);
+        assert_eq!(report.gas, 1500);
+        assert_eq!(report.fees(), "15stake");
+    }
+
+    #[test]
+    fn resets_after_each_report() {
+        let chain_id = ChainId::from_string("chain-a");
+        let start = Instant::now();
+        let mut report = DryRunReport::new(start);
+
+        report.record(&simulated_tx(1, 1000, "10"));
+        report.report(&chain_id, start + Duration::from_secs(60));
+
+        assert_eq!(report.txs, 0);
+        assert_eq!(report.fees(), "none");
+        assert_eq!(report.since, start + Duration::from_secs(60));
+    }
+
+    #[test]
+    fn reporter_reports_without_simulated_txs() {
+        let chain_id = ChainId::from_string("chain-a");
+        let start = Instant::now();
+        let report = SharedDryRunReport::new_lock(DryRunReport::new(start));
+
+        let reporter = DryRunReporter {
+            chain_id,
+            report: report.clone(),
+        }
+        .spawn(Duration::from_millis(10));
+
+        thread::sleep(Duration::from_millis(100));
+        drop(reporter);
+
+        assert!(report.acquire_read().since > start);
+    }
+}
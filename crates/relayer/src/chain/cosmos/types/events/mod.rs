@@ -11,3 +11,53 @@ pub fn from_tx_response_event(height: Height, event: &abci::Event) -> Option<Ibc
         .ok()
         .map(|ibc_event| IbcEventWithHeight::new(ibc_event, height))
 }
+
+/// Extract the IBC events from the events of a transaction result, including the events
+/// of the messages executed by a `MsgExec`, which are emitted along with its own events.
+pub fn from_tx_result_events(height: Height, events: &[abci::Event]) -> Vec<IbcEventWithHeight> {
+    events
+        .iter()
+        .flat_map(|event| from_tx_response_event(height, event))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::events::IbcEvent;
+
+    use ibc_relayer_types::core::ics03_connection::events::{Attributes, OpenInit};
+    use tendermint::abci::EventAttributeIndexExt;
+
+    #[test]
+    fn extract_ibc_events_from_msg_exec_tx() {
+        // A transaction wrapping an IBC message in a `MsgExec` emits the events of the
+        // inner message, along with the `message` events of the `MsgExec` itself.
+        let open_init = OpenInit::from(Attributes {
+            connection_id: Some("connection-0".parse().unwrap()),
+            client_id: "07-tendermint-0".parse().unwrap(),
+            counterparty_connection_id: None,
+            counterparty_client_id: "07-tendermint-1".parse().unwrap(),
+        });
+
+        let events = vec![
+            abci::Event::new(
+                "message",
+                [("action", "/cosmos.authz.v1beta1.MsgExec").index()],
+            ),
+            abci::Event::from(open_init.clone()),
+            abci::Event::new("message", [("module", "ibc_connection").index()]),
+        ];
+
+        let height = Height::new(0, 10).unwrap();
+
+        let ibc_events = from_tx_result_events(height, &events);
+
+        assert_eq!(ibc_events.len(), 1);
+        assert!(
+            matches!(&ibc_events[0].event, IbcEvent::OpenInitConnection(event) if *event == open_init)
+        );
+        assert_eq!(ibc_events[0].height, height);
+    }
+}
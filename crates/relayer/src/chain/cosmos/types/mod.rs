@@ -1,6 +1,8 @@
 pub mod account;
 pub mod app_state;
 pub mod config;
+pub mod dry_run;
 pub mod events;
 pub mod gas;
 pub mod tx;
+pub mod wallet;
@@ -0,0 +1,382 @@
+//! Management of the set of keys used to sign transactions on a chain.
+//!
+//! When several keys are configured in `key_name`, each one has its own
+//! account sequence, so that transactions signed by different keys
+//! never contend for the same sequence number.
+
+use alloc::collections::VecDeque;
+use core::time::Duration;
+use std::time::Instant;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::channel::v1::{
+    MsgAcknowledgement, MsgChannelCloseConfirm, MsgChannelCloseInit, MsgChannelOpenAck,
+    MsgChannelOpenConfirm, MsgChannelOpenInit, MsgChannelOpenTry, MsgRecvPacket, MsgTimeout,
+    MsgTimeoutOnClose,
+};
+use ibc_proto::ibc::core::client::v1::{MsgCreateClient, MsgSubmitMisbehaviour, MsgUpdateClient};
+use ibc_proto::ibc::core::connection::v1::{
+    MsgConnectionOpenAck, MsgConnectionOpenConfirm, MsgConnectionOpenInit, MsgConnectionOpenTry,
+};
+use ibc_relayer_types::core::ics02_client::msgs::{create_client, misbehaviour, update_client};
+use ibc_relayer_types::core::ics03_connection::msgs::{
+    conn_open_ack, conn_open_confirm, conn_open_init, conn_open_try,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::{
+    acknowledgement, chan_close_confirm, chan_close_init, chan_open_ack, chan_open_confirm,
+    chan_open_init, chan_open_try, recv_packet, timeout, timeout_on_close,
+};
+use prost::Message;
+
+use crate::chain::cosmos::types::account::Account;
+use crate::chain::cosmos::types::config::TxConfig;
+use crate::config::WalletSelection;
+use crate::error::Error;
+use crate::keyring::{CosmosKeyPair, SigningKeyPair};
+
+/// A key used to sign transactions, along with its cached account information.
+#[derive(Clone, Debug)]
+pub struct Wallet {
+    pub key_name: String,
+    pub key_pair: CosmosKeyPair,
+
+    /// A cached copy of the account information
+    pub account: Option<Account>,
+
+    /// The transaction configuration to use with this key,
+    /// if it differs from the chain's one, eg. because of a key-specific fee granter
+    pub tx_config: Option<TxConfig>,
+}
+
+impl Wallet {
+    pub fn new(key_name: String, key_pair: CosmosKeyPair) -> Self {
+        Self {
+            key_name,
+            key_pair,
+            account: None,
+            tx_config: None,
+        }
+    }
+
+    pub fn address(&self) -> String {
+        self.key_pair.account()
+    }
+}
+
+/// The set of wallets used to sign transactions on a chain.
+///
+/// The first wallet is the primary one, which is the one used for
+/// all the messages whose signer cannot be substituted.
+#[derive(Clone, Debug)]
+pub struct WalletPool {
+    wallets: Vec<Wallet>,
+    scheduler: WalletScheduler,
+}
+
+impl WalletPool {
+    /// Panics if the given list of wallets is empty.
+    pub fn new(wallets: Vec<Wallet>, selection: WalletSelection, busy_window: Duration) -> Self {
+        assert!(!wallets.is_empty(), "wallet pool must not be empty");
+
+        let scheduler = WalletScheduler::new(wallets.len(), selection, busy_window);
+
+        Self { wallets, scheduler }
+    }
+
+    pub fn len(&self) -> usize {
+        self.wallets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wallets.is_empty()
+    }
+
+    pub fn wallets(&self) -> &[Wallet] {
+        &self.wallets
+    }
+
+    /// The wallet at the given index, as returned by [`WalletPool::select`].
+    pub fn wallet_mut(&mut self, index: usize) -> &mut Wallet {
+        &mut self.wallets[index]
+    }
+
+    /// Pick the wallet which should sign the given messages.
+    ///
+    /// If any of the messages must be signed by the primary key,
+    /// as per [`requires_primary`], the primary wallet is picked.
+    pub fn select(&mut self, messages: &[Any]) -> usize {
+        if messages.iter().any(|msg| requires_primary(&msg.type_url)) {
+            return 0;
+        }
+
+        self.select_any()
+    }
+
+    /// Pick the next wallet, whatever the messages it will sign.
+    pub fn select_any(&mut self) -> usize {
+        if self.wallets.len() == 1 {
+            return 0;
+        }
+
+        self.scheduler.select(Instant::now())
+    }
+
+    /// Record that a transaction was just submitted by the wallet at the given index.
+    pub fn record_submission(&mut self, index: usize) {
+        self.scheduler.record(index, Instant::now());
+    }
+}
+
+/// Picks the index of the next wallet to use, according to the configured strategy.
+#[derive(Clone, Debug)]
+pub struct WalletScheduler {
+    selection: WalletSelection,
+    busy_window: Duration,
+    next: usize,
+    recent: Vec<VecDeque<Instant>>,
+}
+
+impl WalletScheduler {
+    pub fn new(count: usize, selection: WalletSelection, busy_window: Duration) -> Self {
+        Self {
+            selection,
+            busy_window,
+            next: 0,
+            recent: vec![VecDeque::new(); count],
+        }
+    }
+
+    pub fn select(&mut self, now: Instant) -> usize {
+        let count = self.recent.len();
+
+        let index = match self.selection {
+            WalletSelection::RoundRobin => self.next,
+            WalletSelection::LeastBusy => {
+                self.prune(now);
+
+                // Start from the next wallet in round-robin order,
+                // so that ties are broken by rotating over the wallets.
+                (0..count)
+                    .map(|offset| (self.next + offset) % count)
+                    .min_by_key(|&index| self.recent[index].len())
+                    .unwrap_or(0)
+            }
+        };
+
+        self.next = (index + 1) % count;
+
+        index
+    }
+
+    pub fn record(&mut self, index: usize, now: Instant) {
+        if let Some(recent) = self.recent.get_mut(index) {
+            recent.push_back(now);
+        }
+    }
+
+    /// Forget about the submissions which happened before the busy window.
+    fn prune(&mut self, now: Instant) {
+        for recent in self.recent.iter_mut() {
+            while let Some(instant) = recent.front() {
+                if now.duration_since(*instant) > self.busy_window {
+                    recent.pop_front();
+                } else {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Whether a message with the given type URL must be signed by the primary key.
+///
+/// This is the case of the messages whose signer cannot be substituted, and of
+/// `MsgRecvPacket`: ICS-29 pays the forward relay fees to the counterparty payee
+/// registered for the signer, which is only registered for the primary key.
+pub fn requires_primary(type_url: &str) -> bool {
+    !is_resignable(type_url) || type_url == recv_packet::TYPE_URL
+}
+
+/// Whether the signer of a message with the given type URL can be substituted.
+pub fn is_resignable(type_url: &str) -> bool {
+    IBC_MSG_TYPE_URLS.contains(&type_url)
+}
+
+/// The type URLs of the core IBC messages submitted by the relayer.
+pub const IBC_MSG_TYPE_URLS: &[&str] = &[
+    create_client::TYPE_URL,
+    update_client::TYPE_URL,
+    misbehaviour::TYPE_URL,
+    conn_open_init::TYPE_URL,
+    conn_open_try::TYPE_URL,
+    conn_open_ack::TYPE_URL,
+    conn_open_confirm::TYPE_URL,
+    chan_open_init::TYPE_URL,
+    chan_open_try::TYPE_URL,
+    chan_open_ack::TYPE_URL,
+    chan_open_confirm::TYPE_URL,
+    chan_close_init::TYPE_URL,
+    chan_close_confirm::TYPE_URL,
+    recv_packet::TYPE_URL,
+    acknowledgement::TYPE_URL,
+    timeout::TYPE_URL,
+    timeout_on_close::TYPE_URL,
+];
+
+/// Replace the signer of each of the given messages with the given address.
+///
+/// All the messages must be of a type for which [`is_resignable`] holds.
+pub fn with_signer(messages: Vec<Any>, signer: &str) -> Result<Vec<Any>, Error> {
+    messages
+        .into_iter()
+        .map(|msg| replace_signer(msg, signer))
+        .collect()
+}
+
+/// Replace the signer of the messages for which [`is_resignable`] holds with the
+/// given address, leaving the other messages as they are.
+pub fn with_resignable_signer(messages: Vec<Any>, signer: &str) -> Result<Vec<Any>, Error> {
+    messages
+        .into_iter()
+        .map(|msg| {
+            if is_resignable(&msg.type_url) {
+                replace_signer(msg, signer)
+            } else {
+                Ok(msg)
+            }
+        })
+        .collect()
+}
+
+fn replace_signer(msg: Any, signer: &str) -> Result<Any, Error> {
+    fn resign<M: Message + Default>(
+        msg: Any,
+        signer: &str,
+        set_signer: impl FnOnce(&mut M, String),
+    ) -> Result<Any, Error> {
+        let mut raw = M::decode(msg.value.as_slice())
+            .map_err(|e| Error::protobuf_decode(msg.type_url.clone(), e))?;
+
+        set_signer(&mut raw, signer.to_string());
+
+        Ok(Any {
+            type_url: msg.type_url,
+            value: raw.encode_to_vec(),
+        })
+    }
+
+    macro_rules! resign {
+        ($msg:expr, $($type_url:path => $raw:ty),* $(,)?) => {
+            match $msg.type_url.as_str() {
+                $(
+                    $type_url => resign::<$raw>($msg, signer, |raw, signer| raw.signer = signer),
+                )*
+                _ => Ok($msg),
+            }
+        };
+    }
+
+    resign!(msg,
+        create_client::TYPE_URL => MsgCreateClient,
+        update_client::TYPE_URL => MsgUpdateClient,
+        misbehaviour::TYPE_URL => MsgSubmitMisbehaviour,
+        conn_open_init::TYPE_URL => MsgConnectionOpenInit,
+        conn_open_try::TYPE_URL => MsgConnectionOpenTry,
+        conn_open_ack::TYPE_URL => MsgConnectionOpenAck,
+        conn_open_confirm::TYPE_URL => MsgConnectionOpenConfirm,
+        chan_open_init::TYPE_URL => MsgChannelOpenInit,
+        chan_open_try::TYPE_URL => MsgChannelOpenTry,
+        chan_open_ack::TYPE_URL => MsgChannelOpenAck,
+        chan_open_confirm::TYPE_URL => MsgChannelOpenConfirm,
+        chan_close_init::TYPE_URL => MsgChannelCloseInit,
+        chan_close_confirm::TYPE_URL => MsgChannelCloseConfirm,
+        recv_packet::TYPE_URL => MsgRecvPacket,
+        acknowledgement::TYPE_URL => MsgAcknowledgement,
+        timeout::TYPE_URL => MsgTimeout,
+        timeout_on_close::TYPE_URL => MsgTimeoutOnClose,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheduler(count: usize, selection: WalletSelection) -> WalletScheduler {
+        WalletScheduler::new(count, selection, Duration::from_secs(30))
+    }
+
+    #[test]
+    fn round_robin_rotates_over_all_wallets() {
+        let mut scheduler = scheduler(3, WalletSelection::RoundRobin);
+        let now = Instant::now();
+
+        let picks = (0..7).map(|_| scheduler.select(now)).collect::<Vec<_>>();
+
+        assert_eq!(picks, vec![0, 1, 2, 0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn least_busy_picks_wallet_with_fewest_recent_submissions() {
+        let mut scheduler = scheduler(3, WalletSelection::LeastBusy);
+        let now = Instant::now();
+
+        scheduler.record(0, now);
+        scheduler.record(0, now);
+        scheduler.record(1, now);
+
+        assert_eq!(scheduler.select(now), 2);
+
+        scheduler.record(2, now);
+        scheduler.record(2, now);
+
+        assert_eq!(scheduler.select(now), 1);
+    }
+
+    #[test]
+    fn least_busy_forgets_old_submissions() {
+        let mut scheduler = scheduler(2, WalletSelection::LeastBusy);
+        let start = Instant::now();
+
+        scheduler.record(1, start);
+        scheduler.record(0, start + Duration::from_secs(40));
+        scheduler.record(0, start + Duration::from_secs(40));
+
+        // The submission by wallet 1 is out of the busy window
+        assert_eq!(scheduler.select(start + Duration::from_secs(45)), 1);
+    }
+
+    #[test]
+    fn replaces_signer_of_ibc_messages() {
+        let msg = MsgRecvPacket {
+            signer: "cosmos1primary".to_string(),
+            ..Default::default()
+        };
+
+        let any = Any {
+            type_url: recv_packet::TYPE_URL.to_string(),
+            value: msg.encode_to_vec(),
+        };
+
+        assert!(is_resignable(&any.type_url));
+
+        let resigned = with_signer(vec![any], "cosmos1other").unwrap();
+        let decoded = MsgRecvPacket::decode(resigned[0].value.as_slice()).unwrap();
+
+        assert_eq!(decoded.signer, "cosmos1other");
+    }
+
+    #[test]
+    fn transfers_are_not_resignable() {
+        assert!(!is_resignable("/ibc.applications.transfer.v1.MsgTransfer"));
+    }
+
+    #[test]
+    fn recv_packets_require_primary_wallet() {
+        assert!(requires_primary(recv_packet::TYPE_URL));
+        assert!(requires_primary(
+            "/ibc.applications.transfer.v1.MsgTransfer"
+        ));
+        assert!(!requires_primary(acknowledgement::TYPE_URL));
+        assert!(!requires_primary(update_client::TYPE_URL));
+    }
+}
@@ -12,7 +12,7 @@ use tokio::time::sleep;
 use tracing::{debug, debug_span, trace};
 
 use crate::chain::cosmos::query::tx::query_tx_response;
-use crate::chain::cosmos::types::events::from_tx_response_event;
+use crate::chain::cosmos::types::events::from_tx_result_events;
 use crate::chain::cosmos::types::tx::{TxStatus, TxSyncResult};
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
@@ -104,12 +104,7 @@ async fn update_tx_sync_result(
                     message_count
                 ];
             } else {
-                tx_sync_result.events = response
-                    .tx_result
-                    .events
-                    .iter()
-                    .flat_map(|event| from_tx_response_event(height, event))
-                    .collect::<Vec<_>>();
+                tx_sync_result.events = from_tx_result_events(height, &response.tx_result.events);
             }
         }
     }
@@ -31,7 +31,7 @@ use ibc_relayer_types::Height as ICSHeight;
 
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response as TxResponse;
 
-use crate::account::Balance;
+use crate::account::{Balance, FeeAllowance};
 use crate::chain::client::ClientSettings;
 use crate::chain::handle::Subscription;
 use crate::chain::requests::*;
@@ -79,7 +79,9 @@ pub trait ChainEndpoint: Sized {
     type Time;
 
     /// Type of the key pair used for signatures of messages on chain
-    type SigningKeyPair: SigningKeyPairSized + Into<AnySigningKeyPair>;
+    type SigningKeyPair: SigningKeyPairSized
+        + Into<AnySigningKeyPair>
+        + TryFrom<AnySigningKeyPair, Error = AnySigningKeyPair>;
 
     /// Returns the chain's identifier
     fn id(&self) -> &ChainId {
@@ -100,6 +102,10 @@ pub trait ChainEndpoint: Sized {
     /// Perform a health check
     fn health_check(&mut self) -> Result<HealthCheck, Error>;
 
+    /// Notifies the chain that a request failed with the given error, so that it can
+    /// fail over to another node if the error shows that the node in use is unreachable.
+    fn report_error(&self, _error: &Error) {}
+
     // Events
     fn subscribe(&mut self) -> Result<Subscription, Error>;
 
@@ -118,8 +124,8 @@ pub trait ChainEndpoint: Sized {
         // Get the key from key seed file
         let key_pair = self
             .keybase()
-            .get_key(&self.config().key_name)
-            .map_err(|e| Error::key_not_found(self.config().key_name.clone(), e))?;
+            .get_key(self.config().key_name.primary())
+            .map_err(|e| Error::key_not_found(self.config().key_name.primary().to_string(), e))?;
 
         Ok(key_pair)
     }
@@ -132,6 +138,15 @@ pub trait ChainEndpoint: Sized {
         Ok(())
     }
 
+    /// Make the given key of the keyring the primary key of the chain, which
+    /// signs the transactions submitted from now on in place of the current one.
+    fn rotate_key(&mut self, key_name: &str) -> Result<(), Error>;
+
+    /// Send all the balances of the given key of the keyring to the address of
+    /// the primary key, keeping enough of the fee denomination to pay for the
+    /// transfer. Returns the balances which were sent.
+    fn sweep_balances(&mut self, key_name: &str) -> Result<Vec<Balance>, Error>;
+
     // Versioning
 
     /// Return the version of the IBC protocol that this chain is running, if known.
@@ -180,6 +195,11 @@ pub trait ChainEndpoint: Sized {
     /// If no account is given, behavior must be specified, e.g. retrieve it from configuration file.
     fn query_all_balances(&self, key_name: Option<&str>) -> Result<Vec<Balance>, Error>;
 
+    /// Query the fee allowance granted to the given key by its configured fee granter.
+    /// If no key is given, behavior must be specified, e.g. retrieve it from configuration file.
+    /// Returns `None` if there is no such allowance.
+    fn query_fee_allowance(&self, key_name: Option<&str>) -> Result<Option<FeeAllowance>, Error>;
+
     /// Query the denomination trace given a trace hash.
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error>;
 
@@ -193,6 +213,12 @@ pub trait ChainEndpoint: Sized {
     /// Query the latest height and timestamp the application is at
     fn query_application_status(&self) -> Result<ChainStatus, Error>;
 
+    /// The latest heights reported by the alternate endpoints of the chain, if any,
+    /// against which the lag of the endpoint in use is measured.
+    fn query_alternate_heights(&self) -> Vec<u64> {
+        Vec::new()
+    }
+
     /// Performs a query to retrieve the state of all clients that a chain hosts.
     fn query_clients(
         &self,
@@ -360,6 +386,12 @@ pub trait ChainEndpoint: Sized {
         request: QueryPacketEventDataRequest,
     ) -> Result<Vec<IbcEventWithHeight>, Error>;
 
+    /// Query the packets sent and acknowledged on a channel in a single block
+    fn query_block_packet_events(
+        &self,
+        request: QueryBlockPacketEventsRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error>;
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -682,6 +714,13 @@ pub trait ChainEndpoint: Sized {
         requests: Vec<CrossChainQueryRequest>,
     ) -> Result<Vec<CrossChainQueryResponse>, Error>;
 
+    /// Query the interchain queries requested by the chain in its latest blocks
+    /// which have not been answered yet.
+    fn query_pending_cross_chain_queries(
+        &self,
+        request: QueryPendingCrossChainQueriesRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error>;
+
     fn query_incentivized_packet(
         &self,
         request: QueryIncentivizedPacketRequest,
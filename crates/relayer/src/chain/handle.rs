@@ -28,7 +28,8 @@ use ibc_relayer_types::{
 };
 
 use crate::{
-    account::Balance,
+    account::{Balance, FeeAllowance},
+    cache::Cache,
     client_state::{AnyClientState, IdentifiedAnyClientState},
     config::ChainConfig,
     connection::ConnectionMsgType,
@@ -47,6 +48,7 @@ use crate::{
 use super::{
     client::ClientSettings,
     endpoint::{ChainStatus, HealthCheck},
+    node_lag::NodeLag,
     requests::*,
     tracking::TrackedMsgs,
 };
@@ -127,6 +129,14 @@ pub enum ChainRequest {
         reply_to: ReplyTo<ChainConfig>,
     },
 
+    GetCache {
+        reply_to: ReplyTo<Cache>,
+    },
+
+    GetNodeLag {
+        reply_to: ReplyTo<NodeLag>,
+    },
+
     Signer {
         reply_to: ReplyTo<Signer>,
     },
@@ -141,6 +151,16 @@ pub enum ChainRequest {
         reply_to: ReplyTo<()>,
     },
 
+    RotateKey {
+        key_name: String,
+        reply_to: ReplyTo<()>,
+    },
+
+    SweepBalances {
+        key_name: String,
+        reply_to: ReplyTo<Vec<Balance>>,
+    },
+
     IbcVersion {
         reply_to: ReplyTo<Option<semver::Version>>,
     },
@@ -156,6 +176,11 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Vec<Balance>>,
     },
 
+    QueryFeeAllowance {
+        key_name: Option<String>,
+        reply_to: ReplyTo<Option<FeeAllowance>>,
+    },
+
     QueryDenomTrace {
         hash: String,
         reply_to: ReplyTo<DenomTrace>,
@@ -346,6 +371,11 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
     },
 
+    QueryBlockPacketEvents {
+        request: QueryBlockPacketEventsRequest,
+        reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
+    },
+
     QueryHostConsensusState {
         request: QueryHostConsensusStateRequest,
         reply_to: ReplyTo<AnyConsensusState>,
@@ -363,6 +393,11 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Vec<CrossChainQueryResponse>>,
     },
 
+    QueryPendingCrossChainQueries {
+        request: QueryPendingCrossChainQueriesRequest,
+        reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
+    },
+
     QueryIncentivizedPacket {
         request: QueryIncentivizedPacketRequest,
         reply_to: ReplyTo<QueryIncentivizedPacketResponse>,
@@ -404,10 +439,27 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
 
     fn config(&self) -> Result<ChainConfig, Error>;
 
+    /// Return the query cache of the chain runtime, which is kept up to date
+    /// with the IBC events of the chain.
+    fn get_cache(&self) -> Result<Cache, Error>;
+
+    /// Return the lag of the node queried by the chain runtime, which is
+    /// checked periodically when `max_node_lag` is configured for the chain.
+    fn get_node_lag(&self) -> Result<NodeLag, Error>;
+
     fn get_key(&self) -> Result<AnySigningKeyPair, Error>;
 
     fn add_key(&self, key_name: String, key: AnySigningKeyPair) -> Result<(), Error>;
 
+    /// Make the given key of the keyring the primary key of the chain, which
+    /// signs the transactions submitted from now on in place of the current one.
+    fn rotate_key(&self, key_name: String) -> Result<(), Error>;
+
+    /// Send all the balances of the given key of the keyring to the address of
+    /// the primary key, keeping enough of the fee denomination to pay for the
+    /// transfer. Returns the balances which were sent.
+    fn sweep_balances(&self, key_name: String) -> Result<Vec<Balance>, Error>;
+
     /// Return the version of the IBC protocol that this chain is running, if known.
     fn ibc_version(&self) -> Result<Option<semver::Version>, Error>;
 
@@ -424,6 +476,10 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
     /// If no account is given, behavior must be specified, e.g. retrieve it from configuration file.
     fn query_all_balances(&self, key_name: Option<String>) -> Result<Vec<Balance>, Error>;
 
+    /// Query the fee allowance granted to the given key by its configured fee granter.
+    /// If no key is given, behavior must be specified, e.g. retrieve it from configuration file.
+    fn query_fee_allowance(&self, key_name: Option<String>) -> Result<Option<FeeAllowance>, Error>;
+
     /// Query the denomination trace given a trace hash.
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error>;
 
@@ -657,6 +713,12 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         request: QueryPacketEventDataRequest,
     ) -> Result<Vec<IbcEventWithHeight>, Error>;
 
+    /// Query the packets sent and acknowledged on a channel in a single block
+    fn query_block_packet_events(
+        &self,
+        request: QueryBlockPacketEventsRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error>;
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -674,6 +736,11 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         request: Vec<CrossChainQueryRequest>,
     ) -> Result<Vec<CrossChainQueryResponse>, Error>;
 
+    fn query_pending_cross_chain_queries(
+        &self,
+        request: QueryPendingCrossChainQueriesRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error>;
+
     fn query_incentivized_packet(
         &self,
         request: QueryIncentivizedPacketRequest,
@@ -25,8 +25,12 @@ use ibc_relayer_types::{
 };
 
 use crate::{
-    account::Balance,
-    chain::{client::ClientSettings, endpoint::ChainStatus, requests::*, tracking::TrackedMsgs},
+    account::{Balance, FeeAllowance},
+    cache::Cache,
+    chain::{
+        client::ClientSettings, endpoint::ChainStatus, node_lag::NodeLag, requests::*,
+        tracking::TrackedMsgs,
+    },
     client_state::{AnyClientState, IdentifiedAnyClientState},
     config::ChainConfig,
     connection::ConnectionMsgType,
@@ -133,6 +137,14 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::Config { reply_to })
     }
 
+    fn get_cache(&self) -> Result<Cache, Error> {
+        self.send(|reply_to| ChainRequest::GetCache { reply_to })
+    }
+
+    fn get_node_lag(&self) -> Result<NodeLag, Error> {
+        self.send(|reply_to| ChainRequest::GetNodeLag { reply_to })
+    }
+
     fn get_key(&self) -> Result<AnySigningKeyPair, Error> {
         self.send(|reply_to| ChainRequest::GetKey { reply_to })
     }
@@ -145,6 +157,14 @@ impl ChainHandle for BaseChainHandle {
         })
     }
 
+    fn rotate_key(&self, key_name: String) -> Result<(), Error> {
+        self.send(|reply_to| ChainRequest::RotateKey { key_name, reply_to })
+    }
+
+    fn sweep_balances(&self, key_name: String) -> Result<Vec<Balance>, Error> {
+        self.send(|reply_to| ChainRequest::SweepBalances { key_name, reply_to })
+    }
+
     fn ibc_version(&self) -> Result<Option<semver::Version>, Error> {
         self.send(|reply_to| ChainRequest::IbcVersion { reply_to })
     }
@@ -165,6 +185,10 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::QueryAllBalances { key_name, reply_to })
     }
 
+    fn query_fee_allowance(&self, key_name: Option<String>) -> Result<Option<FeeAllowance>, Error> {
+        self.send(|reply_to| ChainRequest::QueryFeeAllowance { key_name, reply_to })
+    }
+
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
         self.send(|reply_to| ChainRequest::QueryDenomTrace { hash, reply_to })
     }
@@ -481,6 +505,13 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::QueryPacketEventData { request, reply_to })
     }
 
+    fn query_block_packet_events(
+        &self,
+        request: QueryBlockPacketEventsRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.send(|reply_to| ChainRequest::QueryBlockPacketEvents { request, reply_to })
+    }
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -509,6 +540,13 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::CrossChainQuery { request, reply_to })
     }
 
+    fn query_pending_cross_chain_queries(
+        &self,
+        request: QueryPendingCrossChainQueriesRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.send(|reply_to| ChainRequest::QueryPendingCrossChainQueries { request, reply_to })
+    }
+
     fn query_incentivized_packet(
         &self,
         request: QueryIncentivizedPacketRequest,
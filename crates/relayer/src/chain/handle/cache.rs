@@ -1,6 +1,8 @@
+use alloc::sync::Arc;
 use core::fmt::{Display, Error as FmtError, Formatter};
 use crossbeam_channel as channel;
-use tracing::Span;
+use once_cell::sync::OnceCell;
+use tracing::{warn, Span};
 
 use ibc_proto::ibc::apps::fee::v1::QueryIncentivizedPacketRequest;
 use ibc_proto::ibc::apps::fee::v1::QueryIncentivizedPacketResponse;
@@ -21,11 +23,12 @@ use ibc_relayer_types::proofs::Proofs;
 use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::Height;
 
-use crate::account::Balance;
+use crate::account::{Balance, FeeAllowance};
 use crate::cache::{Cache, CacheStatus};
 use crate::chain::client::ClientSettings;
 use crate::chain::endpoint::{ChainStatus, HealthCheck};
 use crate::chain::handle::{ChainHandle, ChainRequest, Subscription};
+use crate::chain::node_lag::NodeLag;
 use crate::chain::requests::*;
 use crate::chain::tracking::TrackedMsgs;
 use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
@@ -42,17 +45,20 @@ use crate::telemetry;
 
 /// A chain handle with support for caching.
 /// To be used for the passive relaying mode (i.e., `start` CLI).
+///
+/// The cache is the one of the chain runtime, which keeps it up to date with the
+/// IBC events of the chain, and is shared by all the clones of the handle.
 #[derive(Debug, Clone)]
 pub struct CachingChainHandle<Handle> {
     inner: Handle,
-    cache: Cache,
+    cache: Arc<OnceCell<Cache>>,
 }
 
 impl<Handle> CachingChainHandle<Handle> {
     pub fn new(handle: Handle) -> Self {
         Self {
             inner: handle,
-            cache: Cache::new(),
+            cache: Arc::new(OnceCell::new()),
         }
     }
 
@@ -61,6 +67,27 @@ impl<Handle> CachingChainHandle<Handle> {
     }
 }
 
+impl<Handle: ChainHandle> CachingChainHandle<Handle> {
+    fn cache(&self) -> &Cache {
+        self.cache.get_or_init(|| {
+            self.inner().get_cache().unwrap_or_else(|e| {
+                warn!(
+                    chain = %self.id(),
+                    "failed to get the cache of the chain runtime, using a new cache: {e}"
+                );
+
+                let config = self
+                    .inner()
+                    .config()
+                    .map(|config| config.cache)
+                    .unwrap_or_default();
+
+                Cache::new(self.id(), &config)
+            })
+        })
+    }
+}
+
 impl<Handle: ChainHandle> Display for CachingChainHandle<Handle> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         write!(
@@ -114,6 +141,14 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().config()
     }
 
+    fn get_cache(&self) -> Result<Cache, Error> {
+        Ok(self.cache().clone())
+    }
+
+    fn get_node_lag(&self) -> Result<NodeLag, Error> {
+        self.inner().get_node_lag()
+    }
+
     fn get_key(&self) -> Result<AnySigningKeyPair, Error> {
         self.inner().get_key()
     }
@@ -122,6 +157,14 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().add_key(key_name, key)
     }
 
+    fn rotate_key(&self, key_name: String) -> Result<(), Error> {
+        self.inner().rotate_key(key_name)
+    }
+
+    fn sweep_balances(&self, key_name: String) -> Result<Vec<Balance>, Error> {
+        self.inner().sweep_balances(key_name)
+    }
+
     fn ibc_version(&self) -> Result<Option<semver::Version>, Error> {
         self.inner().ibc_version()
     }
@@ -138,6 +181,10 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().query_all_balances(key_name)
     }
 
+    fn query_fee_allowance(&self, key_name: Option<String>) -> Result<Option<FeeAllowance>, Error> {
+        self.inner().query_fee_allowance(key_name)
+    }
+
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
         self.inner().query_denom_trace(hash)
     }
@@ -149,11 +196,13 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
     fn query_latest_height(&self) -> Result<Height, Error> {
         let handle = self.inner();
         let (result, in_cache) = self
-            .cache
+            .cache()
             .get_or_try_update_latest_height_with(|| handle.query_latest_height())?;
 
         if in_cache == CacheStatus::Hit {
             telemetry!(queries_cache_hits, &self.id(), "query_latest_height");
+        } else {
+            telemetry!(queries_cache_misses, &self.id(), "query_latest_height");
         }
 
         Ok(result)
@@ -177,7 +226,7 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
             IncludeProof::Yes => handle.query_client_state(request, IncludeProof::Yes),
             IncludeProof::No => {
                 if matches!(request.height, QueryHeight::Latest) {
-                    let (result, in_cache) = self.cache.get_or_try_insert_client_state_with(
+                    let (result, in_cache) = self.cache().get_or_try_insert_client_state_with(
                         &request.client_id,
                         || {
                             handle
@@ -188,6 +237,8 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
 
                     if in_cache == CacheStatus::Hit {
                         telemetry!(queries_cache_hits, &self.id(), "query_client_state");
+                    } else {
+                        telemetry!(queries_cache_misses, &self.id(), "query_client_state");
                     }
 
                     Ok((result, None))
@@ -252,7 +303,7 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
             IncludeProof::Yes => handle.query_connection(request, IncludeProof::Yes),
             IncludeProof::No => {
                 if matches!(request.height, QueryHeight::Latest) {
-                    let (result, in_cache) = self.cache.get_or_try_insert_connection_with(
+                    let (result, in_cache) = self.cache().get_or_try_insert_connection_with(
                         &request.connection_id,
                         || {
                             handle
@@ -263,6 +314,8 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
 
                     if in_cache == CacheStatus::Hit {
                         telemetry!(queries_cache_hits, &self.id(), "query_connection");
+                    } else {
+                        telemetry!(queries_cache_misses, &self.id(), "query_connection");
                     }
 
                     Ok((result, None))
@@ -313,7 +366,7 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
             IncludeProof::Yes => handle.query_channel(request, IncludeProof::Yes),
             IncludeProof::No => {
                 if matches!(request.height, QueryHeight::Latest) {
-                    let (result, in_cache) = self.cache.get_or_try_insert_channel_with(
+                    let (result, in_cache) = self.cache().get_or_try_insert_channel_with(
                         &PortChannelId::new(request.channel_id.clone(), request.port_id.clone()),
                         || {
                             handle
@@ -324,6 +377,8 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
 
                     if in_cache == CacheStatus::Hit {
                         telemetry!(queries_cache_hits, &self.id(), "query_channel");
+                    } else {
+                        telemetry!(queries_cache_misses, &self.id(), "query_channel");
                     }
 
                     Ok((result, None))
@@ -480,6 +535,13 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().query_packet_events(request)
     }
 
+    fn query_block_packet_events(
+        &self,
+        request: QueryBlockPacketEventsRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.inner().query_block_packet_events(request)
+    }
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -504,6 +566,13 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner.cross_chain_query(request)
     }
 
+    fn query_pending_cross_chain_queries(
+        &self,
+        request: QueryPendingCrossChainQueriesRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.inner.query_pending_cross_chain_queries(request)
+    }
+
     fn query_incentivized_packet(
         &self,
         request: QueryIncentivizedPacketRequest,
@@ -511,3 +580,97 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner.query_incentivized_packet(request)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    use ibc_relayer_types::core::ics04_channel::channel::{
+        Counterparty, Ordering as ChannelOrdering, State,
+    };
+    use ibc_relayer_types::core::ics04_channel::events::CloseInit;
+    use ibc_relayer_types::core::ics04_channel::version::Version;
+    use ibc_relayer_types::events::IbcEvent;
+
+    use crate::chain::handle::BaseChainHandle;
+    use crate::config::CacheConfig;
+
+    fn open_channel() -> ChannelEnd {
+        ChannelEnd::new(
+            State::Open,
+            ChannelOrdering::Unordered,
+            Counterparty::new(PortId::transfer(), Some(ChannelId::new(1))),
+            vec![ConnectionId::default()],
+            Version::ics20(),
+        )
+    }
+
+    /// Spawns a runtime which shares the given cache, and whose chain always
+    /// returns an open channel end, counting the channel queries.
+    fn spawn_runtime(
+        cache: Cache,
+        queries: Arc<AtomicUsize>,
+    ) -> channel::Sender<(Span, ChainRequest)> {
+        let (sender, receiver) = channel::unbounded::<(Span, ChainRequest)>();
+
+        thread::spawn(move || {
+            for (_, request) in receiver {
+                match request {
+                    ChainRequest::GetCache { reply_to } => {
+                        reply_to.send(Ok(cache.clone())).unwrap();
+                    }
+                    ChainRequest::QueryChannel { reply_to, .. } => {
+                        queries.fetch_add(1, Ordering::SeqCst);
+                        reply_to.send(Ok((open_channel(), None))).unwrap();
+                    }
+                    request => panic!("unexpected request: {request:?}"),
+                }
+            }
+        });
+
+        sender
+    }
+
+    #[test]
+    fn close_init_is_observed_immediately_by_query_channel() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let cache = Cache::new(chain_id.clone(), &CacheConfig::default());
+        let queries = Arc::new(AtomicUsize::new(0));
+
+        let sender = spawn_runtime(cache.clone(), queries.clone());
+        let handle = CachingChainHandle::new(BaseChainHandle::new(chain_id, sender));
+
+        let query_channel = || {
+            let request = QueryChannelRequest {
+                port_id: PortId::transfer(),
+                channel_id: ChannelId::new(0),
+                height: QueryHeight::Latest,
+            };
+
+            let (channel, _) = handle.query_channel(request, IncludeProof::No).unwrap();
+            *channel.state()
+        };
+
+        assert_eq!(query_channel(), State::Open);
+        assert_eq!(query_channel(), State::Open);
+        assert_eq!(queries.load(Ordering::SeqCst), 1);
+
+        // The runtime receives the `CloseInit` event from the event source
+        cache.apply_events(&[IbcEventWithHeight::new(
+            IbcEvent::CloseInitChannel(CloseInit {
+                port_id: PortId::transfer(),
+                channel_id: ChannelId::new(0),
+                connection_id: ConnectionId::default(),
+                counterparty_port_id: PortId::transfer(),
+                counterparty_channel_id: Some(ChannelId::new(1)),
+            }),
+            Height::new(0, 10).unwrap(),
+        )]);
+
+        assert_eq!(query_channel(), State::Closed);
+        assert_eq!(queries.load(Ordering::SeqCst), 1);
+    }
+}
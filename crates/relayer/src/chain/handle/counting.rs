@@ -25,10 +25,12 @@ use ibc_relayer_types::proofs::Proofs;
 use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::Height;
 
-use crate::account::Balance;
+use crate::account::{Balance, FeeAllowance};
+use crate::cache::Cache;
 use crate::chain::client::ClientSettings;
 use crate::chain::endpoint::{ChainStatus, HealthCheck};
 use crate::chain::handle::{ChainHandle, ChainRequest, Subscription};
+use crate::chain::node_lag::NodeLag;
 use crate::chain::requests::*;
 use crate::chain::tracking::TrackedMsgs;
 use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
@@ -140,6 +142,16 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().config()
     }
 
+    fn get_cache(&self) -> Result<Cache, Error> {
+        self.inc_metric("get_cache");
+        self.inner().get_cache()
+    }
+
+    fn get_node_lag(&self) -> Result<NodeLag, Error> {
+        self.inc_metric("get_node_lag");
+        self.inner().get_node_lag()
+    }
+
     fn get_key(&self) -> Result<AnySigningKeyPair, Error> {
         self.inc_metric("get_key");
         self.inner().get_key()
@@ -150,6 +162,16 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().add_key(key_name, key)
     }
 
+    fn rotate_key(&self, key_name: String) -> Result<(), Error> {
+        self.inc_metric("rotate_key");
+        self.inner().rotate_key(key_name)
+    }
+
+    fn sweep_balances(&self, key_name: String) -> Result<Vec<Balance>, Error> {
+        self.inc_metric("sweep_balances");
+        self.inner().sweep_balances(key_name)
+    }
+
     fn ibc_version(&self) -> Result<Option<semver::Version>, Error> {
         self.inc_metric("ibc_version");
         self.inner().ibc_version()
@@ -169,6 +191,11 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().query_all_balances(key_name)
     }
 
+    fn query_fee_allowance(&self, key_name: Option<String>) -> Result<Option<FeeAllowance>, Error> {
+        self.inc_metric("query_fee_allowance");
+        self.inner().query_fee_allowance(key_name)
+    }
+
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
         self.inc_metric("query_denom_trace");
         self.inner().query_denom_trace(hash)
@@ -469,6 +496,14 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().query_packet_events(request)
     }
 
+    fn query_block_packet_events(
+        &self,
+        request: QueryBlockPacketEventsRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.inc_metric("query_block_packet_events");
+        self.inner().query_block_packet_events(request)
+    }
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -496,6 +531,14 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner.cross_chain_query(request)
     }
 
+    fn query_pending_cross_chain_queries(
+        &self,
+        request: QueryPendingCrossChainQueriesRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.inc_metric("query_pending_cross_chain_queries");
+        self.inner.query_pending_cross_chain_queries(request)
+    }
+
     fn query_incentivized_packet(
         &self,
         request: QueryIncentivizedPacketRequest,
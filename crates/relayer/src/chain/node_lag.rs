@@ -0,0 +1,136 @@
+//! Detection of a node lagging behind its chain, from which Hermes would
+//! otherwise build outdated proofs and submit transactions bound to fail.
+
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use core::time::Duration;
+
+use ibc_relayer_types::timestamp::Timestamp;
+
+use crate::chain::endpoint::ChainStatus;
+use crate::config::MaxNodeLag;
+
+/// How far behind its chain the node queried by a chain runtime is,
+/// as measured by the runtime and shared with the workers of the chain.
+#[derive(Clone, Debug, Default)]
+pub struct NodeLag {
+    inner: Arc<NodeLagState>,
+}
+
+#[derive(Debug, Default)]
+struct NodeLagState {
+    degraded: AtomicBool,
+    blocks: AtomicU64,
+}
+
+impl NodeLag {
+    /// Whether the node lags behind its chain by more than the configured
+    /// `max_node_lag`, in which case the packet workers of the chain are paused
+    pub fn is_degraded(&self) -> bool {
+        self.inner.degraded.load(Ordering::Acquire)
+    }
+
+    /// How many blocks the node lagged behind the alternate endpoints of
+    /// the chain at the last check
+    pub fn blocks(&self) -> u64 {
+        self.inner.blocks.load(Ordering::Acquire)
+    }
+
+    /// Record the outcome of a check of the lag of the node,
+    /// returning whether the chain became degraded or recovered.
+    pub fn update(&self, lag: &MeasuredLag, max: &MaxNodeLag) -> bool {
+        self.inner.blocks.store(lag.blocks, Ordering::Release);
+
+        let degraded = lag.exceeds(max);
+        self.inner.degraded.swap(degraded, Ordering::AcqRel) != degraded
+    }
+}
+
+/// The lag of a node measured at a given point in time
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MeasuredLag {
+    /// How many blocks the node lags behind the most advanced alternate endpoint
+    pub blocks: u64,
+
+    /// How old the latest block of the node is
+    pub time: Duration,
+}
+
+impl MeasuredLag {
+    /// Measure the lag of a node from its status, the latest heights of the
+    /// alternate endpoints of the chain and the current time.
+    pub fn measure(status: &ChainStatus, alternate_heights: &[u64], now: Timestamp) -> Self {
+        let height = status.height.revision_height();
+
+        let blocks = alternate_heights
+            .iter()
+            .map(|alternate| alternate.saturating_sub(height))
+            .max()
+            .unwrap_or_default();
+
+        let time = now.duration_since(&status.timestamp).unwrap_or_default();
+
+        Self { blocks, time }
+    }
+
+    pub fn exceeds(&self, max: &MaxNodeLag) -> bool {
+        self.blocks > max.blocks || self.time > max.time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::Height;
+
+    fn status(height: u64, secs_ago: u64, now: Timestamp) -> ChainStatus {
+        let nanos = now.nanoseconds() - secs_ago * 1_000_000_000;
+
+        ChainStatus {
+            height: Height::new(0, height).unwrap(),
+            timestamp: Timestamp::from_nanoseconds(nanos).unwrap(),
+        }
+    }
+
+    #[test]
+    fn measures_lag_against_alternate_endpoints_and_wall_clock() {
+        let now = Timestamp::now();
+
+        let lag = MeasuredLag::measure(&status(100, 12, now), &[95, 112, 108], now);
+        assert_eq!(lag.blocks, 12);
+        assert_eq!(lag.time, Duration::from_secs(12));
+
+        let lag = MeasuredLag::measure(&status(100, 0, now), &[], now);
+        assert_eq!(lag, MeasuredLag::default());
+    }
+
+    #[test]
+    fn degrades_and_recovers() {
+        let max = MaxNodeLag {
+            blocks: 10,
+            time: Duration::from_secs(60),
+        };
+
+        let node_lag = NodeLag::default();
+        let caught_up = MeasuredLag {
+            blocks: 2,
+            time: Duration::from_secs(5),
+        };
+        let behind = MeasuredLag {
+            blocks: 20,
+            time: Duration::from_secs(5),
+        };
+
+        assert!(!node_lag.update(&caught_up, &max));
+        assert!(!node_lag.is_degraded());
+
+        assert!(node_lag.update(&behind, &max));
+        assert!(node_lag.is_degraded());
+        assert_eq!(node_lag.blocks(), 20);
+
+        assert!(!node_lag.update(&behind, &max));
+        assert!(node_lag.update(&caught_up, &max));
+        assert!(!node_lag.is_degraded());
+    }
+}
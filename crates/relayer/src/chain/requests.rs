@@ -432,6 +432,19 @@ pub struct QueryPacketEventDataRequest {
     pub height: Qualified<QueryHeight>,
 }
 
+/// Used to query the packets sent and acknowledged on a specific channel
+/// in the block at `height`, from a single query of the results of the block.
+/// The channel is identified by its end on the queried chain, `port_id`/`channel_id`,
+/// and its end on the counterparty chain.
+#[derive(Clone, Debug)]
+pub struct QueryBlockPacketEventsRequest {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub counterparty_port_id: PortId,
+    pub counterparty_channel_id: ChannelId,
+    pub height: Height,
+}
+
 /// Refines an inner type by assigning it to refer to either a:
 ///     - range of values (when using variant `SmallerEqual`), or
 ///     - to a specific value (with variant `Equal`).
@@ -492,3 +505,11 @@ pub struct CrossChainQueryRequest {
     pub request: String,
     pub height: TMBlockHeight,
 }
+
+/// Used for querying the interchain queries requested by a chain
+/// which have not been answered yet.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct QueryPendingCrossChainQueriesRequest {
+    /// How many of the latest blocks of the chain to look for requests in
+    pub blocks: u64,
+}
@@ -1,9 +1,12 @@
+use alloc::collections::VecDeque;
 use alloc::sync::Arc;
+use core::cmp::Reverse;
+use core::time::Duration;
 use std::thread;
 
 use crossbeam_channel as channel;
 use tokio::runtime::Runtime as TokioRuntime;
-use tracing::{error, Span};
+use tracing::{error, info, warn, Span};
 
 use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
@@ -25,11 +28,13 @@ use ibc_relayer_types::{
     },
     proofs::Proofs,
     signer::Signer,
+    timestamp::Timestamp,
     Height,
 };
 
 use crate::{
-    account::Balance,
+    account::{Balance, FeeAllowance},
+    cache::Cache,
     chain::requests::QueryPacketEventDataRequest,
     client_state::{AnyClientState, IdentifiedAnyClientState},
     config::ChainConfig,
@@ -41,16 +46,22 @@ use crate::{
     keyring::AnySigningKeyPair,
     light_client::AnyHeader,
     misbehaviour::MisbehaviourEvidence,
+    telemetry,
 };
 
 use super::{
     client::ClientSettings,
     endpoint::{ChainEndpoint, ChainStatus, HealthCheck},
     handle::{ChainHandle, ChainRequest, ReplyTo, Subscription},
+    node_lag::{MeasuredLag, NodeLag},
     requests::*,
     tracking::TrackedMsgs,
 };
 
+/// How often the runtime checks the lag of the node of the chain,
+/// when `max_node_lag` is configured for the chain
+const NODE_LAG_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
 pub struct Threads {
     pub chain_runtime: thread::JoinHandle<()>,
     pub event_source: Option<thread::JoinHandle<()>>,
@@ -68,6 +79,21 @@ pub struct ChainRuntime<Endpoint: ChainEndpoint> {
     /// in through this channel.
     request_receiver: channel::Receiver<(Span, ChainRequest)>,
 
+    /// The query cache shared by the `CachingChainHandle`s of this runtime,
+    /// kept up to date with the IBC events of the chain
+    cache: Cache,
+
+    /// The subscription through which the runtime receives the IBC events
+    /// of the chain for the cache, once the event source is started
+    cache_subscription: Option<Subscription>,
+
+    /// How far behind the chain its node is, shared with the workers of
+    /// the chain, which are paused while the node lags too far behind
+    node_lag: NodeLag,
+
+    /// The requests received but not handled yet, see [`next_request`]
+    queued_requests: VecDeque<(Span, ChainRequest)>,
+
     #[allow(dead_code)]
     rt: Arc<TokioRuntime>, // Making this future-proof, so we keep the runtime around.
 }
@@ -114,12 +140,17 @@ where
     /// Basic constructor
     fn new(chain: Endpoint, rt: Arc<TokioRuntime>) -> Self {
         let (request_sender, request_receiver) = channel::unbounded();
+        let cache = Cache::new(chain.id().clone(), &chain.config().cache);
 
         Self {
             rt,
             chain,
             request_sender,
             request_receiver,
+            cache,
+            cache_subscription: None,
+            node_lag: NodeLag::default(),
+            queued_requests: VecDeque::new(),
         }
     }
 
@@ -131,240 +162,388 @@ where
     }
 
     fn run(mut self) -> Result<(), Error> {
+        let node_lag_checks = if self.chain.config().max_node_lag.is_some() {
+            channel::tick(NODE_LAG_CHECK_INTERVAL)
+        } else {
+            channel::never()
+        };
+
         loop {
+            let cache_events = self
+                .cache_subscription
+                .clone()
+                .unwrap_or_else(channel::never);
+
+            // Only wait for the next event if there is no request left to handle
+            let timeout = if self.queued_requests.is_empty() {
+                Duration::MAX
+            } else {
+                Duration::ZERO
+            };
+
             channel::select! {
-                recv(self.request_receiver) -> event => {
-                    let (span, event) = match event {
-                        Ok((span, event)) => (span, event),
+                recv(node_lag_checks) -> _ => {
+                    self.check_node_lag();
+                },
+
+                recv(cache_events) -> batch => {
+                    match batch {
+                        Ok(batch) => {
+                            if let Ok(batch) = batch.as_ref() {
+                                self.cache.apply_events(&batch.events);
+                            }
+                        }
+                        Err(_) => {
+                            // The event source has stopped, it is resubscribed to on the next `Subscribe` request
+                            self.cache_subscription = None;
+                        }
+                    }
+                },
+
+                recv(self.request_receiver) -> request => {
+                    match request {
+                        Ok(request) => self.queued_requests.push_back(request),
                         Err(e) => {
                             error!("received error via chain request channel: {}", e);
                             continue;
                         }
-                    };
+                    }
+                },
 
-                    let _span = span.entered();
+                default(timeout) => {},
+            }
 
-                    match event {
-                        ChainRequest::Shutdown { reply_to } => {
-                            let res = self.chain.shutdown();
+            // Queue the requests received in the meantime, so that the messages
+            // of the channels with the highest priority are submitted first
+            self.queued_requests
+                .extend(self.request_receiver.try_iter());
 
-                            reply_to.send(res)
-                                .map_err(Error::send)?;
+            if let Some((span, request)) = next_request(&mut self.queued_requests) {
+                let _span = span.entered();
 
-                            break;
-                        },
+                match request {
+                    ChainRequest::Shutdown { reply_to } => {
+                        let res = self.chain.shutdown();
 
-                        ChainRequest::HealthCheck { reply_to } => {
-                            self.health_check(reply_to)?
-                        },
+                        reply_to.send(res).map_err(Error::send)?;
 
-                        ChainRequest::Subscribe { reply_to } => {
-                            self.subscribe(reply_to)?
-                        },
+                        break;
+                    }
+                    request => self.handle_request(request)?,
+                }
+            }
+        }
 
-                        ChainRequest::SendMessagesAndWaitCommit { tracked_msgs, reply_to } => {
-                            self.send_messages_and_wait_commit(tracked_msgs, reply_to)?
-                        },
+        Ok(())
+    }
 
-                        ChainRequest::SendMessagesAndWaitCheckTx { tracked_msgs, reply_to } => {
-                            self.send_messages_and_wait_check_tx(tracked_msgs, reply_to)?
-                        },
+    /// Handle the given request, except for `Shutdown` which is handled by [`Self::run`].
+    fn handle_request(&mut self, request: ChainRequest) -> Result<(), Error> {
+        match request {
+            ChainRequest::Shutdown { .. } => {
+                unreachable!("the chain runtime shuts down in its event loop")
+            }
 
-                        ChainRequest::Signer { reply_to } => {
-                            self.get_signer(reply_to)?
-                        },
+            ChainRequest::HealthCheck { reply_to } => self.health_check(reply_to)?,
 
-                        ChainRequest::Config { reply_to } => {
-                            self.get_config(reply_to)?
-                        },
+            ChainRequest::Subscribe { reply_to } => self.subscribe(reply_to)?,
 
-                        ChainRequest::GetKey { reply_to } => {
-                            self.get_key(reply_to)?
-                        },
+            ChainRequest::SendMessagesAndWaitCommit {
+                tracked_msgs,
+                reply_to,
+            } => self.send_messages_and_wait_commit(tracked_msgs, reply_to)?,
 
-                        ChainRequest::AddKey { key_name, key, reply_to } => {
-                            self.add_key(key_name, key, reply_to)?
-                        },
+            ChainRequest::SendMessagesAndWaitCheckTx {
+                tracked_msgs,
+                reply_to,
+            } => self.send_messages_and_wait_check_tx(tracked_msgs, reply_to)?,
 
-                        ChainRequest::IbcVersion { reply_to } => {
-                            self.ibc_version(reply_to)?
-                        },
+            ChainRequest::Signer { reply_to } => self.get_signer(reply_to)?,
 
-                        ChainRequest::BuildHeader { trusted_height, target_height, client_state, reply_to } => {
-                            self.build_header(trusted_height, target_height, client_state, reply_to)?
-                        },
+            ChainRequest::Config { reply_to } => self.get_config(reply_to)?,
 
-                        ChainRequest::BuildClientState { height, settings, reply_to } => {
-                            self.build_client_state(height, settings, reply_to)?
-                        },
+            ChainRequest::GetCache { reply_to } => self.get_cache(reply_to)?,
 
-                        ChainRequest::BuildConsensusState { trusted, target, client_state, reply_to } => {
-                            self.build_consensus_state(trusted, target, client_state, reply_to)?
-                        },
+            ChainRequest::GetNodeLag { reply_to } => self.get_node_lag(reply_to)?,
 
-                        ChainRequest::BuildMisbehaviour { client_state, update_event, reply_to } => {
-                            self.check_misbehaviour(update_event, client_state, reply_to)?
-                        },
+            ChainRequest::GetKey { reply_to } => self.get_key(reply_to)?,
 
-                        ChainRequest::BuildConnectionProofsAndClientState { message_type, connection_id, client_id, height, reply_to } => {
-                            self.build_connection_proofs_and_client_state(message_type, connection_id, client_id, height, reply_to)?
-                        },
+            ChainRequest::AddKey {
+                key_name,
+                key,
+                reply_to,
+            } => self.add_key(key_name, key, reply_to)?,
 
-                        ChainRequest::BuildChannelProofs { port_id, channel_id, height, reply_to } => {
-                            self.build_channel_proofs(port_id, channel_id, height, reply_to)?
-                        },
+            ChainRequest::RotateKey { key_name, reply_to } => {
+                self.rotate_key(key_name, reply_to)?
+            }
 
-                        ChainRequest::QueryBalance { key_name, denom, reply_to } => {
-                            self.query_balance(key_name, denom, reply_to)?
-                        },
+            ChainRequest::SweepBalances { key_name, reply_to } => {
+                self.sweep_balances(key_name, reply_to)?
+            }
 
-                        ChainRequest::QueryAllBalances { key_name, reply_to } => {
-                            self.query_all_balances(key_name, reply_to)?
-                        },
+            ChainRequest::IbcVersion { reply_to } => self.ibc_version(reply_to)?,
+
+            ChainRequest::BuildHeader {
+                trusted_height,
+                target_height,
+                client_state,
+                reply_to,
+            } => self.build_header(trusted_height, target_height, client_state, reply_to)?,
+
+            ChainRequest::BuildClientState {
+                height,
+                settings,
+                reply_to,
+            } => self.build_client_state(height, settings, reply_to)?,
+
+            ChainRequest::BuildConsensusState {
+                trusted,
+                target,
+                client_state,
+                reply_to,
+            } => self.build_consensus_state(trusted, target, client_state, reply_to)?,
+
+            ChainRequest::BuildMisbehaviour {
+                client_state,
+                update_event,
+                reply_to,
+            } => self.check_misbehaviour(update_event, client_state, reply_to)?,
+
+            ChainRequest::BuildConnectionProofsAndClientState {
+                message_type,
+                connection_id,
+                client_id,
+                height,
+                reply_to,
+            } => self.build_connection_proofs_and_client_state(
+                message_type,
+                connection_id,
+                client_id,
+                height,
+                reply_to,
+            )?,
+
+            ChainRequest::BuildChannelProofs {
+                port_id,
+                channel_id,
+                height,
+                reply_to,
+            } => self.build_channel_proofs(port_id, channel_id, height, reply_to)?,
+
+            ChainRequest::QueryBalance {
+                key_name,
+                denom,
+                reply_to,
+            } => self.query_balance(key_name, denom, reply_to)?,
+
+            ChainRequest::QueryAllBalances { key_name, reply_to } => {
+                self.query_all_balances(key_name, reply_to)?
+            }
 
-                        ChainRequest::QueryDenomTrace { hash, reply_to } => {
-                            self.query_denom_trace(hash, reply_to)?
-                        },
+            ChainRequest::QueryFeeAllowance { key_name, reply_to } => {
+                self.query_fee_allowance(key_name, reply_to)?
+            }
 
-                        ChainRequest::QueryApplicationStatus { reply_to } => {
-                            self.query_application_status(reply_to)?
-                        },
+            ChainRequest::QueryDenomTrace { hash, reply_to } => {
+                self.query_denom_trace(hash, reply_to)?
+            }
 
-                        ChainRequest::QueryClients { request, reply_to } => {
-                            self.query_clients(request, reply_to)?
-                        },
+            ChainRequest::QueryApplicationStatus { reply_to } => {
+                self.query_application_status(reply_to)?
+            }
 
-                        ChainRequest::QueryClientConnections { request, reply_to } => {
-                            self.query_client_connections(request, reply_to)?
-                        },
+            ChainRequest::QueryClients { request, reply_to } => {
+                self.query_clients(request, reply_to)?
+            }
 
-                        ChainRequest::QueryClientState { request, include_proof, reply_to } => {
-                            self.query_client_state(request, include_proof, reply_to)?
-                        },
+            ChainRequest::QueryClientConnections { request, reply_to } => {
+                self.query_client_connections(request, reply_to)?
+            }
 
-                        ChainRequest::QueryConsensusStateHeights { request, reply_to } => {
-                            self.query_consensus_state_heights(request, reply_to)?
-                        },
+            ChainRequest::QueryClientState {
+                request,
+                include_proof,
+                reply_to,
+            } => self.query_client_state(request, include_proof, reply_to)?,
 
-                        ChainRequest::QueryConsensusState { request, include_proof, reply_to } => {
-                            self.query_consensus_state(request, include_proof, reply_to)?
-                        },
+            ChainRequest::QueryConsensusStateHeights { request, reply_to } => {
+                self.query_consensus_state_heights(request, reply_to)?
+            }
 
-                        ChainRequest::QueryUpgradedClientState { request, reply_to } => {
-                            self.query_upgraded_client_state(request, reply_to)?
-                        },
+            ChainRequest::QueryConsensusState {
+                request,
+                include_proof,
+                reply_to,
+            } => self.query_consensus_state(request, include_proof, reply_to)?,
 
-                        ChainRequest::QueryUpgradedConsensusState { request, reply_to } => {
-                            self.query_upgraded_consensus_state(request, reply_to)?
-                        },
+            ChainRequest::QueryUpgradedClientState { request, reply_to } => {
+                self.query_upgraded_client_state(request, reply_to)?
+            }
 
-                        ChainRequest::QueryCommitmentPrefix { reply_to } => {
-                            self.query_commitment_prefix(reply_to)?
-                        },
+            ChainRequest::QueryUpgradedConsensusState { request, reply_to } => {
+                self.query_upgraded_consensus_state(request, reply_to)?
+            }
 
-                        ChainRequest::QueryCompatibleVersions { reply_to } => {
-                            self.query_compatible_versions(reply_to)?
-                        },
+            ChainRequest::QueryCommitmentPrefix { reply_to } => {
+                self.query_commitment_prefix(reply_to)?
+            }
 
-                        ChainRequest::QueryConnection { request, include_proof, reply_to } => {
-                            self.query_connection(request, include_proof, reply_to)?
-                        },
+            ChainRequest::QueryCompatibleVersions { reply_to } => {
+                self.query_compatible_versions(reply_to)?
+            }
 
-                        ChainRequest::QueryConnections { request, reply_to } => {
-                            self.query_connections(request, reply_to)?
-                        },
+            ChainRequest::QueryConnection {
+                request,
+                include_proof,
+                reply_to,
+            } => self.query_connection(request, include_proof, reply_to)?,
 
-                        ChainRequest::QueryConnectionChannels { request, reply_to } => {
-                            self.query_connection_channels(request, reply_to)?
-                        },
+            ChainRequest::QueryConnections { request, reply_to } => {
+                self.query_connections(request, reply_to)?
+            }
 
-                        ChainRequest::QueryChannels { request, reply_to } => {
-                            self.query_channels(request, reply_to)?
-                        },
+            ChainRequest::QueryConnectionChannels { request, reply_to } => {
+                self.query_connection_channels(request, reply_to)?
+            }
 
-                        ChainRequest::QueryChannel { request, include_proof, reply_to } => {
-                            self.query_channel(request, include_proof, reply_to)?
-                        },
+            ChainRequest::QueryChannels { request, reply_to } => {
+                self.query_channels(request, reply_to)?
+            }
 
-                        ChainRequest::QueryChannelClientState { request, reply_to } => {
-                            self.query_channel_client_state(request, reply_to)?
-                        },
+            ChainRequest::QueryChannel {
+                request,
+                include_proof,
+                reply_to,
+            } => self.query_channel(request, include_proof, reply_to)?,
 
-                        ChainRequest::BuildPacketProofs { packet_type, port_id, channel_id, sequence, height, reply_to } => {
-                            self.build_packet_proofs(packet_type, port_id, channel_id, sequence, height, reply_to)?
-                        },
+            ChainRequest::QueryChannelClientState { request, reply_to } => {
+                self.query_channel_client_state(request, reply_to)?
+            }
 
-                        ChainRequest::QueryPacketCommitment { request, include_proof, reply_to } => {
-                            self.query_packet_commitment(request, include_proof, reply_to)?
-                        },
+            ChainRequest::BuildPacketProofs {
+                packet_type,
+                port_id,
+                channel_id,
+                sequence,
+                height,
+                reply_to,
+            } => self.build_packet_proofs(
+                packet_type,
+                port_id,
+                channel_id,
+                sequence,
+                height,
+                reply_to,
+            )?,
+
+            ChainRequest::QueryPacketCommitment {
+                request,
+                include_proof,
+                reply_to,
+            } => self.query_packet_commitment(request, include_proof, reply_to)?,
+
+            ChainRequest::QueryPacketCommitments { request, reply_to } => {
+                self.query_packet_commitments(request, reply_to)?
+            }
 
-                        ChainRequest::QueryPacketCommitments { request, reply_to } => {
-                            self.query_packet_commitments(request, reply_to)?
-                        },
+            ChainRequest::QueryPacketReceipt {
+                request,
+                include_proof,
+                reply_to,
+            } => self.query_packet_receipt(request, include_proof, reply_to)?,
 
-                        ChainRequest::QueryPacketReceipt { request, include_proof, reply_to } => {
-                            self.query_packet_receipt(request, include_proof, reply_to)?
-                        },
+            ChainRequest::QueryUnreceivedPackets { request, reply_to } => {
+                self.query_unreceived_packets(request, reply_to)?
+            }
 
-                        ChainRequest::QueryUnreceivedPackets { request, reply_to } => {
-                            self.query_unreceived_packets(request, reply_to)?
-                        },
+            ChainRequest::QueryPacketAcknowledgement {
+                request,
+                include_proof,
+                reply_to,
+            } => self.query_packet_acknowledgement(request, include_proof, reply_to)?,
 
-                        ChainRequest::QueryPacketAcknowledgement { request, include_proof, reply_to } => {
-                            self.query_packet_acknowledgement(request, include_proof, reply_to)?
-                        },
+            ChainRequest::QueryPacketAcknowledgements { request, reply_to } => {
+                self.query_packet_acknowledgements(request, reply_to)?
+            }
 
-                        ChainRequest::QueryPacketAcknowledgements { request, reply_to } => {
-                            self.query_packet_acknowledgements(request, reply_to)?
-                        },
+            ChainRequest::QueryUnreceivedAcknowledgement { request, reply_to } => {
+                self.query_unreceived_acknowledgement(request, reply_to)?
+            }
 
-                        ChainRequest::QueryUnreceivedAcknowledgement { request, reply_to } => {
-                            self.query_unreceived_acknowledgement(request, reply_to)?
-                        },
+            ChainRequest::QueryNextSequenceReceive {
+                request,
+                include_proof,
+                reply_to,
+            } => self.query_next_sequence_receive(request, include_proof, reply_to)?,
 
-                        ChainRequest::QueryNextSequenceReceive { request, include_proof, reply_to } => {
-                            self.query_next_sequence_receive(request, include_proof, reply_to)?
-                        },
+            ChainRequest::QueryPacketEventDataFromTxs { request, reply_to } => {
+                self.query_txs(request, reply_to)?
+            }
 
-                        ChainRequest::QueryPacketEventDataFromTxs { request, reply_to } => {
-                            self.query_txs(request, reply_to)?
-                        },
+            ChainRequest::QueryPacketEventData { request, reply_to } => {
+                self.query_packet_events(request, reply_to)?
+            }
 
-                        ChainRequest::QueryPacketEventData { request, reply_to } => {
-                            self.query_packet_events(request, reply_to)?
-                        },
+            ChainRequest::QueryBlockPacketEvents { request, reply_to } => {
+                self.query_block_packet_events(request, reply_to)?
+            }
 
-                        ChainRequest::QueryHostConsensusState { request, reply_to } => {
-                            self.query_host_consensus_state(request, reply_to)?
-                        },
+            ChainRequest::QueryHostConsensusState { request, reply_to } => {
+                self.query_host_consensus_state(request, reply_to)?
+            }
 
-                        ChainRequest::MaybeRegisterCounterpartyPayee { channel_id, port_id, counterparty_payee, reply_to } => {
-                            self.maybe_register_counterparty_payee(&channel_id, &port_id, &counterparty_payee, reply_to)?
-                        },
+            ChainRequest::MaybeRegisterCounterpartyPayee {
+                channel_id,
+                port_id,
+                counterparty_payee,
+                reply_to,
+            } => self.maybe_register_counterparty_payee(
+                &channel_id,
+                &port_id,
+                &counterparty_payee,
+                reply_to,
+            )?,
+
+            ChainRequest::CrossChainQuery { request, reply_to } => {
+                self.cross_chain_query(request, reply_to)?
+            }
 
-                        ChainRequest::CrossChainQuery { request, reply_to } => {
-                            self.cross_chain_query(request, reply_to)?
-                        },
+            ChainRequest::QueryPendingCrossChainQueries { request, reply_to } => {
+                self.query_pending_cross_chain_queries(request, reply_to)?
+            }
 
-                        ChainRequest::QueryIncentivizedPacket { request, reply_to } => {
-                            self.query_incentivized_packet(request, reply_to)?
-                        },
-                    }
-                },
+            ChainRequest::QueryIncentivizedPacket { request, reply_to } => {
+                self.query_incentivized_packet(request, reply_to)?
             }
         }
 
         Ok(())
     }
 
+    /// Send the result of a request back to its sender, reporting the error to the chain, if any.
+    fn reply<T>(&self, reply_to: ReplyTo<T>, result: Result<T, Error>) -> Result<(), Error> {
+        if let Err(e) = &result {
+            self.chain.report_error(e);
+        }
+
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn health_check(&mut self, reply_to: ReplyTo<HealthCheck>) -> Result<(), Error> {
         let result = self.chain.health_check();
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn subscribe(&mut self, reply_to: ReplyTo<Subscription>) -> Result<(), Error> {
         let subscription = self.chain.subscribe();
-        reply_to.send(subscription).map_err(Error::send)
+
+        // Keep the cache up to date with the events of the chain once the event source is started
+        if subscription.is_ok() && self.cache_subscription.is_none() {
+            self.cache_subscription = self.chain.subscribe().ok();
+        }
+
+        self.reply(reply_to, subscription)
     }
 
     fn send_messages_and_wait_commit(
@@ -373,7 +552,13 @@ where
         reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
     ) -> Result<(), Error> {
         let result = self.chain.send_messages_and_wait_commit(tracked_msgs);
-        reply_to.send(result).map_err(Error::send)
+
+        // Apply the events of the transactions right away, without waiting for the event source
+        if let Ok(events) = &result {
+            self.cache.apply_events(events);
+        }
+
+        self.reply(reply_to, result)
     }
 
     fn send_messages_and_wait_check_tx(
@@ -382,7 +567,7 @@ where
         reply_to: ReplyTo<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>>,
     ) -> Result<(), Error> {
         let result = self.chain.send_messages_and_wait_check_tx(tracked_msgs);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_balance(
@@ -395,7 +580,7 @@ where
             .chain
             .query_balance(key_name.as_deref(), denom.as_deref());
 
-        reply_to.send(balance).map_err(Error::send)
+        self.reply(reply_to, balance)
     }
 
     fn query_all_balances(
@@ -404,32 +589,88 @@ where
         reply_to: ReplyTo<Vec<Balance>>,
     ) -> Result<(), Error> {
         let balances = self.chain.query_all_balances(key_name.as_deref());
-        reply_to.send(balances).map_err(Error::send)
+        self.reply(reply_to, balances)
+    }
+
+    fn query_fee_allowance(
+        &self,
+        key_name: Option<String>,
+        reply_to: ReplyTo<Option<FeeAllowance>>,
+    ) -> Result<(), Error> {
+        let allowance = self.chain.query_fee_allowance(key_name.as_deref());
+        self.reply(reply_to, allowance)
     }
 
     fn query_denom_trace(&self, hash: String, reply_to: ReplyTo<DenomTrace>) -> Result<(), Error> {
         let denom_trace = self.chain.query_denom_trace(hash);
-        reply_to.send(denom_trace).map_err(Error::send)
+        self.reply(reply_to, denom_trace)
     }
 
     fn query_application_status(&self, reply_to: ReplyTo<ChainStatus>) -> Result<(), Error> {
         let latest_timestamp = self.chain.query_application_status();
-        reply_to.send(latest_timestamp).map_err(Error::send)
+        self.reply(reply_to, latest_timestamp)
     }
 
     fn get_signer(&mut self, reply_to: ReplyTo<Signer>) -> Result<(), Error> {
         let result = self.chain.get_signer();
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn get_config(&self, reply_to: ReplyTo<ChainConfig>) -> Result<(), Error> {
         let result = Ok(self.chain.config().clone());
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
+    }
+
+    fn get_cache(&self, reply_to: ReplyTo<Cache>) -> Result<(), Error> {
+        self.reply(reply_to, Ok(self.cache.clone()))
+    }
+
+    fn get_node_lag(&self, reply_to: ReplyTo<NodeLag>) -> Result<(), Error> {
+        self.reply(reply_to, Ok(self.node_lag.clone()))
+    }
+
+    /// Measure how far behind the chain its node is, against the wall clock
+    /// and the alternate endpoints of the chain, and mark the chain as degraded
+    /// while the lag exceeds the configured `max_node_lag`.
+    fn check_node_lag(&self) {
+        let Some(max_lag) = &self.chain.config().max_node_lag else {
+            return;
+        };
+
+        let status = match self.chain.query_application_status() {
+            Ok(status) => status,
+            Err(e) => {
+                warn!("failed to query the status of the node to check its lag: {e}");
+                self.chain.report_error(&e);
+                return;
+            }
+        };
+
+        let alternate_heights = self.chain.query_alternate_heights();
+        let lag = MeasuredLag::measure(&status, &alternate_heights, Timestamp::now());
+
+        telemetry!(node_lag_blocks, self.chain.id(), lag.blocks);
+
+        if self.node_lag.update(&lag, max_lag) {
+            if self.node_lag.is_degraded() {
+                warn!(
+                    blocks = lag.blocks,
+                    time = ?lag.time,
+                    "node lags behind the chain by more than `max_node_lag`, pausing the packet workers of the chain"
+                );
+            } else {
+                info!(
+                    blocks = lag.blocks,
+                    time = ?lag.time,
+                    "node caught up with the chain, resuming the packet workers of the chain"
+                );
+            }
+        }
     }
 
     fn get_key(&mut self, reply_to: ReplyTo<AnySigningKeyPair>) -> Result<(), Error> {
         let result = self.chain.get_key().map(Into::into);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn add_key(
@@ -438,16 +679,29 @@ where
         key: AnySigningKeyPair,
         reply_to: ReplyTo<()>,
     ) -> Result<(), Error> {
-        let key = key
-            .downcast()
-            .ok_or_else(|| Error::invalid_key_type(key.key_type()))?;
+        let key = Endpoint::SigningKeyPair::try_from(key)
+            .map_err(|key| Error::invalid_key_type(key.key_type()))?;
         let result = self.chain.add_key(&key_name, key);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
+    }
+
+    fn rotate_key(&mut self, key_name: String, reply_to: ReplyTo<()>) -> Result<(), Error> {
+        let result = self.chain.rotate_key(&key_name);
+        self.reply(reply_to, result)
+    }
+
+    fn sweep_balances(
+        &mut self,
+        key_name: String,
+        reply_to: ReplyTo<Vec<Balance>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.sweep_balances(&key_name);
+        self.reply(reply_to, result)
     }
 
     fn ibc_version(&mut self, reply_to: ReplyTo<Option<semver::Version>>) -> Result<(), Error> {
         let result = self.chain.ibc_version();
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn build_header(
@@ -466,7 +720,7 @@ where
                 (header, support)
             });
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     /// Constructs a client state for the given height
@@ -481,7 +735,7 @@ where
             .build_client_state(height, settings)
             .map(|cs| cs.into());
 
-        reply_to.send(client_state).map_err(Error::send)
+        self.reply(reply_to, client_state)
     }
 
     /// Constructs a consensus state for the given height
@@ -499,7 +753,7 @@ where
             .build_consensus_state(verified)
             .map(|cs| cs.into());
 
-        reply_to.send(consensus_state).map_err(Error::send)
+        self.reply(reply_to, consensus_state)
     }
 
     /// Constructs AnyMisbehaviour for the update event
@@ -511,7 +765,7 @@ where
     ) -> Result<(), Error> {
         let misbehaviour = self.chain.check_misbehaviour(&update_event, &client_state);
 
-        reply_to.send(misbehaviour).map_err(Error::send)
+        self.reply(reply_to, misbehaviour)
     }
 
     fn build_connection_proofs_and_client_state(
@@ -529,7 +783,7 @@ where
             height,
         );
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_clients(
@@ -538,7 +792,7 @@ where
         reply_to: ReplyTo<Vec<IdentifiedAnyClientState>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_clients(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_client_connections(
@@ -547,7 +801,7 @@ where
         reply_to: ReplyTo<Vec<ConnectionId>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_client_connections(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_client_state(
@@ -558,7 +812,7 @@ where
     ) -> Result<(), Error> {
         let res = self.chain.query_client_state(request, include_proof);
 
-        reply_to.send(res).map_err(Error::send)
+        self.reply(reply_to, res)
     }
 
     fn query_upgraded_client_state(
@@ -568,7 +822,7 @@ where
     ) -> Result<(), Error> {
         let result = self.chain.query_upgraded_client_state(request);
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_consensus_state_heights(
@@ -577,7 +831,7 @@ where
         reply_to: ReplyTo<Vec<Height>>,
     ) -> Result<(), Error> {
         let heights = self.chain.query_consensus_state_heights(request);
-        reply_to.send(heights).map_err(Error::send)
+        self.reply(reply_to, heights)
     }
 
     fn query_consensus_state(
@@ -588,7 +842,7 @@ where
     ) -> Result<(), Error> {
         let res = self.chain.query_consensus_state(request, include_proof);
 
-        reply_to.send(res).map_err(Error::send)
+        self.reply(reply_to, res)
     }
 
     fn query_upgraded_consensus_state(
@@ -598,17 +852,17 @@ where
     ) -> Result<(), Error> {
         let result = self.chain.query_upgraded_consensus_state(request);
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_commitment_prefix(&self, reply_to: ReplyTo<CommitmentPrefix>) -> Result<(), Error> {
         let prefix = self.chain.query_commitment_prefix();
-        reply_to.send(prefix).map_err(Error::send)
+        self.reply(reply_to, prefix)
     }
 
     fn query_compatible_versions(&self, reply_to: ReplyTo<Vec<Version>>) -> Result<(), Error> {
         let versions = self.chain.query_compatible_versions();
-        reply_to.send(versions).map_err(Error::send)
+        self.reply(reply_to, versions)
     }
 
     fn query_connection(
@@ -618,7 +872,7 @@ where
         reply_to: ReplyTo<(ConnectionEnd, Option<MerkleProof>)>,
     ) -> Result<(), Error> {
         let connection_end = self.chain.query_connection(request, include_proof);
-        reply_to.send(connection_end).map_err(Error::send)
+        self.reply(reply_to, connection_end)
     }
 
     fn query_connections(
@@ -627,7 +881,7 @@ where
         reply_to: ReplyTo<Vec<IdentifiedConnectionEnd>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_connections(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_connection_channels(
@@ -636,7 +890,7 @@ where
         reply_to: ReplyTo<Vec<IdentifiedChannelEnd>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_connection_channels(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_channels(
@@ -645,7 +899,7 @@ where
         reply_to: ReplyTo<Vec<IdentifiedChannelEnd>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_channels(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_channel(
@@ -655,7 +909,7 @@ where
         reply_to: ReplyTo<(ChannelEnd, Option<MerkleProof>)>,
     ) -> Result<(), Error> {
         let result = self.chain.query_channel(request, include_proof);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_channel_client_state(
@@ -664,7 +918,7 @@ where
         reply_to: ReplyTo<Option<IdentifiedAnyClientState>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_channel_client_state(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn build_channel_proofs(
@@ -678,7 +932,7 @@ where
             .chain
             .build_channel_proofs(&port_id, &channel_id, height);
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn build_packet_proofs(
@@ -694,7 +948,7 @@ where
             self.chain
                 .build_packet_proofs(packet_type, port_id, channel_id, sequence, height);
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_packet_commitment(
@@ -704,7 +958,7 @@ where
         reply_to: ReplyTo<(Vec<u8>, Option<MerkleProof>)>,
     ) -> Result<(), Error> {
         let result = self.chain.query_packet_commitment(request, include_proof);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_packet_commitments(
@@ -713,7 +967,7 @@ where
         reply_to: ReplyTo<(Vec<Sequence>, Height)>,
     ) -> Result<(), Error> {
         let result = self.chain.query_packet_commitments(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_packet_receipt(
@@ -723,7 +977,7 @@ where
         reply_to: ReplyTo<(Vec<u8>, Option<MerkleProof>)>,
     ) -> Result<(), Error> {
         let result = self.chain.query_packet_receipt(request, include_proof);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_unreceived_packets(
@@ -732,7 +986,7 @@ where
         reply_to: ReplyTo<Vec<Sequence>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_unreceived_packets(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_packet_acknowledgement(
@@ -744,7 +998,7 @@ where
         let result = self
             .chain
             .query_packet_acknowledgement(request, include_proof);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_packet_acknowledgements(
@@ -753,7 +1007,7 @@ where
         reply_to: ReplyTo<(Vec<Sequence>, Height)>,
     ) -> Result<(), Error> {
         let result = self.chain.query_packet_acknowledgements(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_unreceived_acknowledgement(
@@ -762,7 +1016,7 @@ where
         reply_to: ReplyTo<Vec<Sequence>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_unreceived_acknowledgements(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_next_sequence_receive(
@@ -774,7 +1028,7 @@ where
         let result = self
             .chain
             .query_next_sequence_receive(request, include_proof);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_txs(
@@ -783,7 +1037,7 @@ where
         reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_txs(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_packet_events(
@@ -793,11 +1047,20 @@ where
     ) -> Result<(), Error> {
         let result = self.chain.query_packet_events(request);
 
-        reply_to.send(result).map_err(Error::send)?;
+        self.reply(reply_to, result)?;
 
         Ok(())
     }
 
+    fn query_block_packet_events(
+        &self,
+        request: QueryBlockPacketEventsRequest,
+        reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_block_packet_events(request);
+        self.reply(reply_to, result)
+    }
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -808,7 +1071,7 @@ where
             .query_host_consensus_state(request)
             .map(|h| h.into());
 
-        reply_to.send(result).map_err(Error::send)?;
+        self.reply(reply_to, result)?;
 
         Ok(())
     }
@@ -824,7 +1087,7 @@ where
             self.chain
                 .maybe_register_counterparty_payee(channel_id, port_id, counterparty_payee);
 
-        reply_to.send(result).map_err(Error::send)?;
+        self.reply(reply_to, result)?;
 
         Ok(())
     }
@@ -835,7 +1098,18 @@ where
         reply_to: ReplyTo<Vec<CrossChainQueryResponse>>,
     ) -> Result<(), Error> {
         let result = self.chain.cross_chain_query(request);
-        reply_to.send(result).map_err(Error::send)?;
+        self.reply(reply_to, result)?;
+
+        Ok(())
+    }
+
+    fn query_pending_cross_chain_queries(
+        &self,
+        request: QueryPendingCrossChainQueriesRequest,
+        reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_pending_cross_chain_queries(request);
+        self.reply(reply_to, result)?;
 
         Ok(())
     }
@@ -846,8 +1120,93 @@ where
         reply_to: ReplyTo<QueryIncentivizedPacketResponse>,
     ) -> Result<(), Error> {
         let result = self.chain.query_incentivized_packet(request);
-        reply_to.send(result).map_err(Error::send)?;
+        self.reply(reply_to, result)?;
 
         Ok(())
     }
 }
+
+/// The next queued request to handle: the first one, unless it submits messages, in which
+/// case the first queued submission among those of the channels with the highest
+/// [`priority`](TrackedMsgs::priority) is handled instead.
+fn next_request(requests: &mut VecDeque<(Span, ChainRequest)>) -> Option<(Span, ChainRequest)> {
+    let (_, first) = requests.front()?;
+
+    if submission_priority(first).is_none() {
+        return requests.pop_front();
+    }
+
+    let index = requests
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (_, request))| {
+            submission_priority(request).map(|priority| (index, priority))
+        })
+        .min_by_key(|&(index, priority)| (Reverse(priority), index))
+        .map_or(0, |(index, _)| index);
+
+    requests.remove(index)
+}
+
+/// The priority of the messages submitted by the given request, if it submits messages.
+fn submission_priority(request: &ChainRequest) -> Option<u32> {
+    match request {
+        ChainRequest::SendMessagesAndWaitCommit { tracked_msgs, .. }
+        | ChainRequest::SendMessagesAndWaitCheckTx { tracked_msgs, .. } => {
+            Some(tracked_msgs.priority)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::chain::handle::reply_channel;
+
+    fn send(priority: u32) -> (Span, ChainRequest) {
+        let (reply_to, _) = reply_channel();
+        let tracked_msgs = TrackedMsgs::new_static(vec![], "test").with_priority(priority);
+
+        let request = ChainRequest::SendMessagesAndWaitCheckTx {
+            tracked_msgs,
+            reply_to,
+        };
+
+        (Span::none(), request)
+    }
+
+    fn health_check() -> (Span, ChainRequest) {
+        let (reply_to, _) = reply_channel();
+
+        (Span::none(), ChainRequest::HealthCheck { reply_to })
+    }
+
+    fn priorities(requests: &mut VecDeque<(Span, ChainRequest)>) -> Vec<Option<u32>> {
+        core::iter::from_fn(|| next_request(requests))
+            .map(|(_, request)| submission_priority(&request))
+            .collect()
+    }
+
+    #[test]
+    fn submissions_of_highest_priority_channels_come_first() {
+        let mut requests =
+            VecDeque::from([send(0), send(5), health_check(), send(10), send(5), send(0)]);
+
+        assert_eq!(
+            priorities(&mut requests),
+            vec![Some(10), Some(5), Some(5), Some(0), None, Some(0)]
+        );
+    }
+
+    #[test]
+    fn other_requests_are_handled_in_order() {
+        let mut requests = VecDeque::from([health_check(), send(0), send(10), health_check()]);
+
+        assert_eq!(
+            priorities(&mut requests),
+            vec![None, Some(10), Some(0), None]
+        );
+    }
+}
@@ -65,17 +65,26 @@ impl Display for TrackingId {
 pub struct TrackedMsgs {
     pub msgs: Vec<Any>,
     pub tracking_id: TrackingId,
+
+    /// The priority of the channel on which the messages are relayed, the chain runtime
+    /// submits the queued messages of the channels with the highest priority first.
+    pub priority: u32,
 }
 
 impl TrackedMsgs {
     pub fn new(msgs: Vec<Any>, tracking_id: TrackingId) -> Self {
-        Self { msgs, tracking_id }
+        Self {
+            msgs,
+            tracking_id,
+            priority: 0,
+        }
     }
 
     pub fn new_static(msgs: Vec<Any>, tracking_id: &'static str) -> Self {
         Self {
             msgs,
             tracking_id: TrackingId::Static(tracking_id),
+            priority: 0,
         }
     }
 
@@ -83,6 +92,7 @@ impl TrackedMsgs {
         Self {
             msgs,
             tracking_id: TrackingId::Uuid(tracking_id),
+            priority: 0,
         }
     }
 
@@ -90,6 +100,7 @@ impl TrackedMsgs {
         Self {
             msgs: vec![msg],
             tracking_id: TrackingId::Static(tracking_id),
+            priority: 0,
         }
     }
 
@@ -97,9 +108,15 @@ impl TrackedMsgs {
         Self {
             msgs: vec![msg],
             tracking_id: TrackingId::Uuid(tracking_id),
+            priority: 0,
         }
     }
 
+    /// Submit the messages with the given channel priority.
+    pub fn with_priority(self, priority: u32) -> Self {
+        Self { priority, ..self }
+    }
+
     pub fn messages(&self) -> &Vec<Any> {
         &self.msgs
     }
@@ -103,6 +103,15 @@ impl AnyClientState {
         }
     }
 
+    pub fn trusting_period(&self) -> Option<Duration> {
+        match self {
+            AnyClientState::Tendermint(state) => Some(state.trusting_period),
+
+            #[cfg(test)]
+            AnyClientState::Mock(_) => None,
+        }
+    }
+
     pub fn refresh_period(&self) -> Option<Duration> {
         match self {
             AnyClientState::Tendermint(tm_state) => tm_state.refresh_time(),
@@ -1,9 +1,16 @@
 //! Relayer configuration
 
+pub mod cache;
+pub mod compat_mode;
 pub mod error;
+pub mod failover;
 pub mod filter;
 pub mod gas_multiplier;
+pub mod icq;
 pub mod proof_specs;
+pub mod retry;
+pub mod scheduling;
+pub mod sharding;
 pub mod types;
 
 use alloc::collections::BTreeMap;
@@ -33,15 +40,22 @@ use ibc_relayer_types::timestamp::ZERO_DURATION;
 
 use crate::chain::ChainType;
 use crate::config::gas_multiplier::GasMultiplier;
-use crate::config::types::{MaxMsgNum, MaxTxSize, Memo};
+use crate::config::types::{KeyNames, MaxMsgNum, MaxTxSize, Memo};
 use crate::error::Error as RelayerError;
 use crate::extension_options::ExtensionOptionDynamicFeeTx;
 use crate::keyring::Store;
 
 pub use crate::config::Error as ConfigError;
+pub use cache::CacheConfig;
+pub use compat_mode::CompatMode;
 pub use error::Error;
+pub use failover::FailoverConfig;
+pub use icq::IcqConfig;
 
+pub use self::retry::{RetryConfig, RetryKind, RetryOverrides, RetryPolicy};
 pub use filter::PacketFilter;
+pub use scheduling::PacketScheduling;
+pub use sharding::ShardingConfig;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GasPrice {
@@ -150,6 +164,10 @@ impl Display for ExtensionOption {
 pub mod default {
     use super::*;
 
+    pub fn client_expiry_warning_threshold() -> Duration {
+        Duration::from_secs(24 * 60 * 60) // 1 day
+    }
+
     pub fn chain_type() -> ChainType {
         ChainType::CosmosSdk
     }
@@ -238,6 +256,8 @@ pub struct Config {
     pub rest: RestConfig,
     #[serde(default)]
     pub telemetry: TelemetryConfig,
+    #[serde(default)]
+    pub sharding: ShardingConfig,
     #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
     pub chains: Vec<ChainConfig>,
 }
@@ -255,6 +275,26 @@ impl Config {
         self.chains.iter_mut().find(|c| c.id == *id)
     }
 
+    /// The policy for retrying the operations of the given kind which target the given chain,
+    /// ie. the policy of the chain's `retry` section if any, or else the one of `[global.retry]`.
+    pub fn retry_policy(&self, chain_id: &ChainId, kind: RetryKind) -> RetryPolicy {
+        self.find_chain(chain_id)
+            .and_then(|chain_config| chain_config.retry.policy(kind))
+            .unwrap_or_else(|| self.global.retry.policy(kind))
+            .clone()
+    }
+
+    /// The policy for retrying the steps of the channel handshakes which target the given chain,
+    /// ie. the channel policy of the chain's `retry` section if any, or else the one of
+    /// `[global.retry]`. If neither is set, the channel workers derive the policy from the
+    /// `max_block_time` of the chains at both ends of the channel.
+    pub fn channel_retry_policy(&self, chain_id: &ChainId) -> Option<RetryPolicy> {
+        self.find_chain(chain_id)
+            .and_then(|chain_config| chain_config.retry.channel.as_ref())
+            .or(self.global.retry.channel.as_ref())
+            .cloned()
+    }
+
     /// Returns true if filtering is disabled or if packets are allowed on
     /// the channel [`PortId`] [`ChannelId`] on [`ChainId`].
     /// Returns false otherwise.
@@ -278,7 +318,7 @@ impl Config {
     }
 }
 
-#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ModeConfig {
     pub clients: Clients,
@@ -304,6 +344,7 @@ impl Default for ModeConfig {
                 enabled: true,
                 refresh: true,
                 misbehaviour: true,
+                expiry_warning_threshold: default::client_expiry_warning_threshold(),
             },
             connections: Connections { enabled: false },
             channels: Channels { enabled: false },
@@ -315,7 +356,7 @@ impl Default for ModeConfig {
     }
 }
 
-#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Clients {
     pub enabled: bool,
@@ -323,6 +364,22 @@ pub struct Clients {
     pub refresh: bool,
     #[serde(default)]
     pub misbehaviour: bool,
+    #[serde(
+        default = "default::client_expiry_warning_threshold",
+        with = "humantime_serde"
+    )]
+    pub expiry_warning_threshold: Duration,
+}
+
+impl Default for Clients {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            refresh: false,
+            misbehaviour: false,
+            expiry_warning_threshold: default::client_expiry_warning_threshold(),
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, Default, Deserialize, Serialize)]
@@ -337,7 +394,7 @@ pub struct Channels {
     pub enabled: bool,
 }
 
-#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Packets {
     pub enabled: bool,
@@ -349,6 +406,8 @@ pub struct Packets {
     pub tx_confirmation: bool,
     #[serde(default = "default::auto_register_counterparty_payee")]
     pub auto_register_counterparty_payee: bool,
+    #[serde(default)]
+    pub scheduling: PacketScheduling,
 }
 
 impl Default for Packets {
@@ -359,6 +418,7 @@ impl Default for Packets {
             clear_on_start: default::clear_on_start(),
             tx_confirmation: default::tx_confirmation(),
             auto_register_counterparty_payee: default::auto_register_counterparty_payee(),
+            scheduling: PacketScheduling::default(),
         }
     }
 }
@@ -398,6 +458,7 @@ impl Display for LogLevel {
 #[serde(default, deny_unknown_fields)]
 pub struct GlobalConfig {
     pub log_level: LogLevel,
+    pub retry: RetryConfig,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -482,6 +543,9 @@ pub struct RestConfig {
     pub enabled: bool,
     pub host: String,
     pub port: u16,
+    /// Whether the keys of the chains can be rotated through the REST API
+    #[serde(default)]
+    pub key_rotation: bool,
 }
 
 impl Default for RestConfig {
@@ -490,6 +554,7 @@ impl Default for RestConfig {
             enabled: false,
             host: "127.0.0.1".to_string(),
             port: 3000,
+            key_rotation: false,
         }
     }
 }
@@ -523,6 +588,28 @@ impl Display for AddressType {
     }
 }
 
+/// How the relayer picks which of the configured keys signs the next transaction,
+/// when more than one key is specified in `key_name`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WalletSelection {
+    /// Use each key in turn
+    #[default]
+    RoundRobin,
+
+    /// Use the key which submitted the fewest transactions recently
+    LeastBusy,
+}
+
+impl Display for WalletSelection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            WalletSelection::RoundRobin => write!(f, "round_robin"),
+            WalletSelection::LeastBusy => write!(f, "least_busy"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct GenesisRestart {
@@ -530,6 +617,30 @@ pub struct GenesisRestart {
     pub archive_addr: Url,
 }
 
+/// How far behind the chain the node queried by Hermes can fall before the chain
+/// is considered degraded, in which case its packet workers are paused until the
+/// node catches up.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MaxNodeLag {
+    /// How many blocks the node can lag behind the most advanced of the
+    /// alternate RPC endpoints configured in `[chains.failover]`
+    pub blocks: u64,
+
+    /// How old the latest block of the node can be, compared to the wall clock
+    #[serde(with = "humantime_serde")]
+    pub time: Duration,
+}
+
+impl Default for MaxNodeLag {
+    fn default() -> Self {
+        Self {
+            blocks: 10,
+            time: Duration::from_secs(60),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(tag = "mode", rename_all = "lowercase")]
 pub enum EventSourceMode {
@@ -550,6 +661,13 @@ pub enum EventSourceMode {
         #[serde(default = "default::poll_interval", with = "humantime_serde")]
         interval: Duration,
     },
+
+    /// Stream-based event source, via a gRPC endpoint streaming the events
+    /// of each block, eg. served by an event indexer
+    Stream {
+        /// The gRPC URL of the event streaming endpoint
+        url: Url,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
@@ -571,6 +689,11 @@ pub struct ChainConfig {
     /// The type of event source and associated settings
     pub event_source: EventSourceMode,
 
+    /// The version of the Tendermint/CometBFT RPC protocol spoken by the node,
+    /// detected from the version reported by the node if unspecified
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compat_mode: Option<CompatMode>,
+
     /// Timeout used when issuing RPC queries
     #[serde(default = "default::rpc_timeout", with = "humantime_serde")]
     pub rpc_timeout: Duration,
@@ -580,7 +703,9 @@ pub struct ChainConfig {
     pub trusted_node: bool,
 
     pub account_prefix: String,
-    pub key_name: String,
+    pub key_name: KeyNames,
+    #[serde(default)]
+    pub wallet_selection: WalletSelection,
     #[serde(default)]
     pub key_store_type: Store,
     pub key_store_folder: Option<PathBuf>,
@@ -598,6 +723,14 @@ pub struct ChainConfig {
     pub gas_multiplier: Option<GasMultiplier>,
 
     pub fee_granter: Option<String>,
+    /// The fee granter to use for specific keys listed in `key_name`, by key name,
+    /// overriding `fee_granter` for these keys
+    #[serde(default)]
+    pub fee_granters: BTreeMap<String, String>,
+    /// The account on behalf of which the IBC messages are submitted, using `x/authz`.
+    /// The keys listed in `key_name` must have been granted a `GenericAuthorization`
+    /// by this account for each of the IBC messages they submit.
+    pub authz_granter: Option<String>,
     #[serde(default)]
     pub max_msg_num: MaxMsgNum,
     #[serde(default)]
@@ -637,6 +770,11 @@ pub struct ChainConfig {
     #[serde(default)]
     pub sequential_batch_tx: bool,
 
+    // This field is only meant to be set via the `start --dry-run` command, in which
+    // case the transactions are simulated instead of being broadcast to the chain.
+    #[serde(skip)]
+    pub dry_run: bool,
+
     // Note: These last few need to be last otherwise we run into `ValueAfterTable` error when serializing to TOML.
     //       That's because these are all tables and have to come last when serializing.
     #[serde(
@@ -657,12 +795,43 @@ pub struct ChainConfig {
     #[serde(default)]
     pub packet_filter: PacketFilter,
 
+    /// The retry policies overriding the ones of the `[global.retry]` section for this chain
+    #[serde(default, skip_serializing_if = "RetryOverrides::is_empty")]
+    pub retry: RetryOverrides,
+
+    /// The time-to-live and capacity of the caches of query results kept by `hermes start`
+    #[serde(default, skip_serializing_if = "CacheConfig::is_default")]
+    pub cache: CacheConfig,
+
+    /// The backup endpoints to fail over to when the configured ones are unhealthy
+    #[serde(default, skip_serializing_if = "FailoverConfig::is_default")]
+    pub failover: FailoverConfig,
+
+    /// The interchain queries (ICS-31) requested by this chain which are served
+    #[serde(default, skip_serializing_if = "IcqConfig::is_default")]
+    pub icq: IcqConfig,
+
+    /// How far behind the chain its node can fall before the packet workers of the chain are paused
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_node_lag: Option<MaxNodeLag>,
+
     #[serde(default)]
     pub address_type: AddressType,
     #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
     pub extension_options: Vec<ExtensionOption>,
 }
 
+impl ChainConfig {
+    /// The address of the account paying the fees for transactions signed by the given key, if any.
+    pub fn fee_granter_for(&self, key_name: &str) -> Option<&str> {
+        self.fee_granters
+            .get(key_name)
+            .or(self.fee_granter.as_ref())
+            .map(String::as_str)
+            .filter(|granter| !granter.is_empty())
+    }
+}
+
 /// Attempt to load and parse the TOML config file as a `Config`.
 pub fn load(path: impl AsRef<Path>) -> Result<Config, Error> {
     let config_toml = std::fs::read_to_string(&path).map_err(Error::io)?;
@@ -697,8 +866,12 @@ pub(crate) fn store_writer(config: &Config, mut writer: impl Write) -> Result<()
 mod tests {
     use core::str::FromStr;
 
+    use core::time::Duration;
+
     use super::{load, parse_gas_prices, store_writer};
-    use crate::config::GasPrice;
+    use crate::config::retry::Backoff;
+    use crate::config::{default, Clients, GasPrice, RetryKind, RetryPolicy};
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
     use test_log::test;
 
     #[test]
@@ -737,6 +910,47 @@ mod tests {
         dbg!(config);
     }
 
+    #[test]
+    fn parse_valid_retry_config() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example_retry.toml"
+        );
+
+        let config = load(path).expect("could not parse config");
+
+        let chain_a = ChainId::from_string("chain_A");
+        let chain_b = ChainId::from_string("chain_B");
+
+        // The policy of chain A overrides the global one
+        let packet_a = config.retry_policy(&chain_a, RetryKind::Packet);
+        assert_eq!(packet_a.backoff, Backoff::Fibonacci);
+        assert_eq!(packet_a.max_total_delay, Some(Duration::from_secs(300)));
+
+        let packet_b = config.retry_policy(&chain_b, RetryKind::Packet);
+        assert_eq!(packet_b, config.global.retry.packet);
+        assert_eq!(packet_b.max_attempts, Some(3));
+
+        let tx_a = config.retry_policy(&chain_a, RetryKind::Tx);
+        assert_eq!(
+            tx_a.delays().collect::<Vec<_>>(),
+            vec![Duration::from_secs(1); 2]
+        );
+
+        // The policies which are not configured keep their default
+        assert_eq!(
+            config.retry_policy(&chain_a, RetryKind::Client),
+            RetryPolicy::client_refresh()
+        );
+        // The channel policy is only set for chain A, and is otherwise left to the workers
+        let channel_a = config.channel_retry_policy(&chain_a).unwrap();
+        assert_eq!(channel_a.max_total_delay, Some(Duration::from_secs(60)));
+        assert_eq!(config.channel_retry_policy(&chain_b), None);
+
+        let mut buffer = Vec::new();
+        store_writer(&config, &mut buffer).unwrap();
+    }
+
     #[test]
     fn parse_valid_telemetry() {
         let path = concat!(
@@ -772,6 +986,14 @@ mod tests {
         store_writer(&config, &mut buffer).unwrap();
     }
 
+    #[test]
+    fn default_clients_warn_before_expiry() {
+        assert_eq!(
+            Clients::default().expiry_warning_threshold,
+            default::client_expiry_warning_threshold()
+        );
+    }
+
     #[test]
     fn gas_price_from_str() {
         let gp_original = GasPrice::new(10.0, "atom".to_owned());
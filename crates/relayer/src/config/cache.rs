@@ -0,0 +1,87 @@
+//! Configuration of the caches of query results kept by the chain handles of `hermes start`.
+
+use core::time::Duration;
+
+use serde_derive::{Deserialize, Serialize};
+
+/// The time-to-live and the capacity of a cache.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CacheSettings {
+    /// How long an entry is kept in the cache after it was inserted
+    #[serde(with = "humantime_serde")]
+    pub ttl: Duration,
+
+    /// The maximum number of entries in the cache
+    pub capacity: u64,
+}
+
+impl CacheSettings {
+    pub const fn new(ttl: Duration, capacity: u64) -> Self {
+        Self { ttl, capacity }
+    }
+}
+
+/// The settings of each of the caches of a chain.
+///
+/// Only the channel and connection ends in the `Open` state are cached,
+/// together with the latest client states and the latest height of the chain.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    /// How long the latest height of the chain is cached for
+    #[serde(with = "humantime_serde")]
+    pub latest_height_ttl: Duration,
+
+    pub channel: CacheSettings,
+    pub connection: CacheSettings,
+    pub client_state: CacheSettings,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            latest_height_ttl: Duration::from_millis(200),
+            channel: CacheSettings::new(Duration::from_secs(60), 10_000),
+            connection: CacheSettings::new(Duration::from_secs(10 * 60), 10_000),
+            client_state: CacheSettings::new(Duration::from_millis(500), 10_000),
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_cache_config() {
+        let config: CacheConfig = toml::from_str(
+            r#"
+            latest_height_ttl = '1s'
+            client_state = { ttl = '6s', capacity = 500 }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.latest_height_ttl, Duration::from_secs(1));
+        assert_eq!(
+            config.client_state,
+            CacheSettings::new(Duration::from_secs(6), 500)
+        );
+
+        // The caches which are not specified keep their default settings
+        assert_eq!(config.channel, CacheConfig::default().channel);
+        assert_eq!(config.connection, CacheConfig::default().connection);
+    }
+
+    #[test]
+    fn reject_incomplete_cache_settings() {
+        assert!(toml::from_str::<CacheConfig>("channel = { ttl = '5m' }").is_err());
+    }
+}
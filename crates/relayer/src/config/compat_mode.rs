@@ -0,0 +1,133 @@
+//! The RPC dialect spoken by the full node of a chain.
+
+use core::fmt::{Display, Error as FmtError, Formatter};
+use core::str::FromStr;
+
+use serde_derive::{Deserialize, Serialize};
+use tendermint_rpc::client::CompatMode as RpcCompatMode;
+use tracing::warn;
+
+/// The version of the Tendermint/CometBFT RPC protocol used to talk to a full node.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum CompatMode {
+    /// Tendermint 0.34
+    #[serde(rename = "0.34")]
+    V0_34,
+
+    /// CometBFT 0.37
+    #[serde(rename = "0.37")]
+    V0_37,
+
+    /// CometBFT 0.38, where the begin and end block events
+    /// are replaced with the `FinalizeBlock` events
+    #[serde(rename = "0.38")]
+    V0_38,
+}
+
+impl CompatMode {
+    /// Determine the compatibility mode from the version reported by a node's `/status` endpoint.
+    /// Returns `None` if the version is not supported.
+    pub fn from_version(version: &str) -> Option<Self> {
+        let version = semver::Version::parse(version.trim_start_matches('v')).ok()?;
+
+        match (version.major, version.minor) {
+            (0, 34) => Some(Self::V0_34),
+            (0, 37) => Some(Self::V0_37),
+            (0, 38) => Some(Self::V0_38),
+            _ => None,
+        }
+    }
+
+    /// Use the configured compatibility mode if any, or else detect it from the
+    /// version reported by the node, defaulting to CometBFT 0.37.
+    pub fn select(configured: Option<Self>, node_version: &str) -> Self {
+        if let Some(compat_mode) = configured {
+            return compat_mode;
+        }
+
+        Self::from_version(node_version).unwrap_or_else(|| {
+            warn!(
+                "unsupported Tendermint version '{node_version}', will use v0.37 compatibility mode \
+                 but relaying might not work as desired, set `compat_mode` in the chain's configuration \
+                 to override this"
+            );
+
+            Self::V0_37
+        })
+    }
+
+    /// The RPC dialect to use for the requests to the node.
+    ///
+    /// CometBFT 0.38 uses the same JSON-RPC encoding as 0.37, except for
+    /// the `/block_results` endpoint which must be handled separately.
+    pub fn rpc_compat(&self) -> RpcCompatMode {
+        match self {
+            Self::V0_34 => RpcCompatMode::V0_34,
+            Self::V0_37 | Self::V0_38 => RpcCompatMode::V0_37,
+        }
+    }
+}
+
+impl Display for CompatMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::V0_34 => write!(f, "0.34"),
+            Self::V0_37 => write!(f, "0.37"),
+            Self::V0_38 => write!(f, "0.38"),
+        }
+    }
+}
+
+impl FromStr for CompatMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0.34" => Ok(Self::V0_34),
+            "0.37" => Ok(Self::V0_37),
+            "0.38" => Ok(Self::V0_38),
+            _ => Err(format!(
+                "invalid compatibility mode '{s}', expected one of: 0.34, 0.37, 0.38"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_from_node_version() {
+        assert_eq!(CompatMode::from_version("0.34.27"), Some(CompatMode::V0_34));
+        assert_eq!(CompatMode::from_version("v0.37.2"), Some(CompatMode::V0_37));
+        assert_eq!(CompatMode::from_version("0.38.0"), Some(CompatMode::V0_38));
+        assert_eq!(CompatMode::from_version("0.39.0"), None);
+        assert_eq!(CompatMode::from_version("foo"), None);
+    }
+
+    #[test]
+    fn configured_mode_overrides_node_version() {
+        assert_eq!(
+            CompatMode::select(Some(CompatMode::V0_34), "0.38.0"),
+            CompatMode::V0_34
+        );
+        assert_eq!(CompatMode::select(None, "0.38.0"), CompatMode::V0_38);
+        assert_eq!(CompatMode::select(None, "1.2.3"), CompatMode::V0_37);
+    }
+
+    #[test]
+    fn cometbft_0_38_uses_0_37_rpc_dialect() {
+        assert_eq!(CompatMode::V0_38.rpc_compat(), RpcCompatMode::V0_37);
+        assert_eq!(CompatMode::V0_34.rpc_compat(), RpcCompatMode::V0_34);
+    }
+
+    #[test]
+    fn parse_compat_mode() {
+        for mode in [CompatMode::V0_34, CompatMode::V0_37, CompatMode::V0_38] {
+            assert_eq!(mode.to_string().parse::<CompatMode>(), Ok(mode));
+        }
+
+        assert!("0.35".parse::<CompatMode>().is_err());
+    }
+}
@@ -0,0 +1,82 @@
+//! Configuration of the backup endpoints of a chain, and of the probe
+//! which checks the health of the endpoints to fail over between them.
+
+use core::time::Duration;
+
+use serde_derive::{Deserialize, Serialize};
+use tendermint_rpc::{Url, WebSocketClientUrl};
+
+/// The endpoints used when the ones configured by `rpc_addr`, `grpc_addr`
+/// and the `url` of a `push` event source are unhealthy.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FailoverConfig {
+    /// Backup RPC endpoints, in order of preference
+    pub rpc_addrs: Vec<Url>,
+
+    /// Backup gRPC endpoints, in order of preference
+    pub grpc_addrs: Vec<Url>,
+
+    /// Backup WebSocket endpoints of the `push` event source, in order of preference
+    pub websocket_addrs: Vec<WebSocketClientUrl>,
+
+    /// How often the health of each endpoint is probed
+    #[serde(with = "humantime_serde")]
+    pub probe_interval: Duration,
+
+    /// How many blocks an endpoint can lag behind the most advanced
+    /// endpoint of the chain before being considered unhealthy
+    pub max_height_lag: u64,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            rpc_addrs: Vec::new(),
+            grpc_addrs: Vec::new(),
+            websocket_addrs: Vec::new(),
+            probe_interval: Duration::from_secs(30),
+            max_height_lag: 5,
+        }
+    }
+}
+
+impl FailoverConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Whether any backup endpoint is configured
+    pub fn has_backups(&self) -> bool {
+        !self.rpc_addrs.is_empty()
+            || !self.grpc_addrs.is_empty()
+            || !self.websocket_addrs.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_failover_config() {
+        let config: FailoverConfig = toml::from_str(
+            r#"
+            rpc_addrs = ['http://127.0.0.1:26757', 'http://127.0.0.1:26857']
+            websocket_addrs = ['ws://127.0.0.1:26757/websocket']
+            probe_interval = '10s'
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.rpc_addrs.len(), 2);
+        assert!(config.grpc_addrs.is_empty());
+        assert_eq!(config.websocket_addrs.len(), 1);
+        assert_eq!(config.probe_interval, Duration::from_secs(10));
+        assert_eq!(
+            config.max_height_lag,
+            FailoverConfig::default().max_height_lag
+        );
+        assert!(config.has_backups());
+    }
+}
@@ -0,0 +1,118 @@
+//! Configuration of the interchain queries (ICS-31) served for a chain,
+//! ie. of the `query_request` events emitted by the chain which Hermes answers.
+
+use byte_unit::Byte;
+use serde_derive::{Deserialize, Serialize};
+
+/// Restricts the interchain queries requested by a chain which are served.
+///
+/// The filters apply to the querying chain, ie. the chain emitting the requests.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IcqConfig {
+    /// The modules whose store can be queried, eg. `bank` for queries of type `store/bank/key`.
+    /// All modules are allowed if empty.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_modules: Vec<String>,
+
+    /// The query paths which can be queried, eg. `store/bank/key`. A path ending with `*`
+    /// allows all the paths starting with the preceding prefix. All paths are allowed if empty.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_query_paths: Vec<String>,
+
+    /// The maximum size of the result of a query. Responses whose result is larger are dropped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_response_size: Option<Byte>,
+}
+
+impl IcqConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Whether a query on the given path, eg. `store/bank/key`, is allowed
+    pub fn allows_query(&self, query_path: &str) -> bool {
+        let module_allowed = self.allowed_modules.is_empty()
+            || query_module(query_path).map_or(false, |module| {
+                self.allowed_modules.iter().any(|m| m == module)
+            });
+
+        let path_allowed = self.allowed_query_paths.is_empty()
+            || self
+                .allowed_query_paths
+                .iter()
+                .any(|allowed| match allowed.strip_suffix('*') {
+                    Some(prefix) => query_path.starts_with(prefix),
+                    None => query_path == allowed,
+                });
+
+        module_allowed && path_allowed
+    }
+
+    /// Whether a query result of the given size, in bytes, can be relayed
+    pub fn allows_response_size(&self, size: usize) -> bool {
+        self.max_response_size
+            .map_or(true, |max| size as u64 <= max.get_bytes())
+    }
+}
+
+/// The module whose store is queried by the given query path, eg. `bank` for `store/bank/key`
+fn query_module(query_path: &str) -> Option<&str> {
+    let mut segments = query_path.trim_start_matches('/').split('/');
+
+    match (segments.next(), segments.next()) {
+        (Some("store"), Some(module)) if !module.is_empty() => Some(module),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_icq_config() {
+        let config: IcqConfig = toml::from_str(
+            r#"
+            allowed_modules = ['bank', 'staking']
+            allowed_query_paths = ['store/bank/key', 'store/staking/*']
+            max_response_size = '4KiB'
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.allowed_modules, vec!["bank", "staking"]);
+        assert_eq!(config.max_response_size, Some(Byte::from_bytes(4096)));
+
+        assert!(config.allows_query("store/bank/key"));
+        assert!(config.allows_query("store/staking/key"));
+        assert!(!config.allows_query("store/bank/subspace"));
+        assert!(!config.allows_query("store/acc/key"));
+
+        assert!(config.allows_response_size(4096));
+        assert!(!config.allows_response_size(4097));
+    }
+
+    #[test]
+    fn default_icq_config_allows_all() {
+        let config = IcqConfig::default();
+
+        assert!(config.is_default());
+        assert!(config.allows_query("store/bank/key"));
+        assert!(config.allows_query("custom/query"));
+        assert!(config.allows_response_size(usize::MAX));
+    }
+
+    #[test]
+    fn allowed_modules_only() {
+        let config = IcqConfig {
+            allowed_modules: vec!["bank".to_string()],
+            ..Default::default()
+        };
+
+        assert!(config.allows_query("store/bank/key"));
+        assert!(config.allows_query("/store/bank/subspace"));
+        assert!(!config.allows_query("store/staking/key"));
+        assert!(!config.allows_query("custom/bank"));
+    }
+}
@@ -0,0 +1,446 @@
+//! Configuration of the delays between the retries of the workers and of the transaction submission.
+
+use core::fmt::{Display, Error as FmtError, Formatter};
+use core::hash::{BuildHasher, Hasher};
+use core::time::Duration;
+use std::collections::hash_map::RandomState;
+
+use itertools::Either;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::util::retry::{clamp_total, ConstantGrowth, Fibonacci};
+
+/// How the delay between two attempts grows.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backoff {
+    /// Always wait for the `initial_delay`
+    Constant,
+
+    /// Start with the `initial_delay`, and add the `delay_increment` after each attempt
+    Linear,
+
+    /// Start with the `initial_delay`, and double it after each attempt
+    Exponential,
+
+    /// Start with the `initial_delay`, and follow the Fibonacci sequence
+    Fibonacci,
+}
+
+impl Display for Backoff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::Constant => write!(f, "constant"),
+            Self::Linear => write!(f, "linear"),
+            Self::Exponential => write!(f, "exponential"),
+            Self::Fibonacci => write!(f, "fibonacci"),
+        }
+    }
+}
+
+/// The delays to wait for before retrying a failed operation.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RetryPolicy {
+    pub backoff: Backoff,
+
+    #[serde(with = "humantime_serde")]
+    pub initial_delay: Duration,
+
+    /// The increment of the `linear` backoff, defaults to the `initial_delay`
+    #[serde(
+        default,
+        with = "humantime_serde",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub delay_increment: Option<Duration>,
+
+    /// The upper bound of each delay
+    #[serde(with = "humantime_serde")]
+    pub max_delay: Duration,
+
+    /// The upper bound of the sum of all the delays, if any
+    #[serde(
+        default,
+        with = "humantime_serde",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_total_delay: Option<Duration>,
+
+    /// Randomize each delay by up to this fraction of its value, between 0 and 1
+    #[serde(default)]
+    pub jitter: f64,
+
+    /// The maximum number of retries, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<usize>,
+}
+
+impl RetryPolicy {
+    /// The delays before each retry.
+    ///
+    /// Each delay is capped by `max_delay` both before and after the jitter is applied,
+    /// and the delays are then capped by `max_total_delay`, so that the bounds hold
+    /// regardless of the jitter.
+    pub fn delays(&self) -> impl Iterator<Item = Duration> {
+        let initial_delay = self.initial_delay;
+
+        let backoff: Box<dyn Iterator<Item = Duration> + Send> = match self.backoff {
+            Backoff::Constant => Box::new(core::iter::repeat(initial_delay)),
+            Backoff::Linear => Box::new(ConstantGrowth::new(
+                initial_delay,
+                self.delay_increment.unwrap_or(initial_delay),
+            )),
+            Backoff::Exponential => {
+                Box::new(core::iter::successors(Some(initial_delay), |delay| {
+                    Some(delay.saturating_mul(2))
+                }))
+            }
+            Backoff::Fibonacci => Box::new(Fibonacci::from(initial_delay)),
+        };
+
+        // The jitter is validated along with the configuration, an invalid one is ignored here
+        let jitter = if (0.0..=1.0).contains(&self.jitter) {
+            self.jitter
+        } else {
+            0.0
+        };
+        let max_delay = self.max_delay;
+
+        let delays = backoff
+            .map(move |delay| with_jitter(delay.min(max_delay), jitter).min(max_delay))
+            .take(self.max_attempts.unwrap_or(usize::MAX));
+
+        match self.max_total_delay {
+            Some(max_total_delay) => Either::Left(clamp_total(delays, max_delay, max_total_delay)),
+            None => Either::Right(delays),
+        }
+    }
+
+    /// The retry policy of the connection handshake workers.
+    ///
+    /// The delay is initially 200ms and grows by 100ms at each step, up to 500ms.
+    /// The overall amount of time spent backing off is capped to 2 seconds.
+    pub fn connection_handshake() -> Self {
+        Self {
+            backoff: Backoff::Linear,
+            initial_delay: Duration::from_millis(200),
+            delay_increment: Some(Duration::from_millis(100)),
+            max_delay: Duration::from_millis(500),
+            max_total_delay: Some(Duration::from_secs(2)),
+            jitter: 0.0,
+            max_attempts: None,
+        }
+    }
+
+    /// The retry policy of the client refresh workers.
+    ///
+    /// The delay is initially 1s and follows the Fibonacci sequence, up to 1 hour.
+    /// The overall amount of time spent backing off is capped to 1 day.
+    pub fn client_refresh() -> Self {
+        Self {
+            backoff: Backoff::Fibonacci,
+            initial_delay: Duration::from_secs(1),
+            delay_increment: None,
+            max_delay: Duration::from_secs(60 * 60),
+            max_total_delay: Some(Duration::from_secs(60 * 60 * 24)),
+            jitter: 0.0,
+            max_attempts: None,
+        }
+    }
+
+    /// The retry policy of the packet workers, when relaying or clearing packets.
+    ///
+    /// Up to 5 attempts are made, ie. 4 retries, without any delay between them.
+    pub fn packet() -> Self {
+        Self {
+            backoff: Backoff::Constant,
+            initial_delay: Duration::ZERO,
+            delay_increment: None,
+            max_delay: Duration::ZERO,
+            max_total_delay: None,
+            jitter: 0.0,
+            max_attempts: Some(4),
+        }
+    }
+
+    /// The retry policy of the transactions which failed because of
+    /// a mismatched account sequence number.
+    ///
+    /// The transaction is retried once after 300ms.
+    pub fn tx() -> Self {
+        Self {
+            backoff: Backoff::Constant,
+            initial_delay: Duration::from_millis(300),
+            delay_increment: None,
+            max_delay: Duration::from_millis(300),
+            max_total_delay: None,
+            jitter: 0.0,
+            max_attempts: Some(1),
+        }
+    }
+}
+
+/// Randomize the delay to a value between `delay * (1 - jitter)` and `delay * (1 + jitter)`.
+fn with_jitter(delay: Duration, jitter: f64) -> Duration {
+    if jitter == 0.0 {
+        return delay;
+    }
+
+    // A random value in [0, 1], no need for a cryptographically secure source here
+    let random = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+
+    // Saturate instead of panicking when the randomized delay does not fit in a `Duration`
+    Duration::try_from_secs_f64(delay.as_secs_f64() * (1.0 - jitter + 2.0 * jitter * random))
+        .unwrap_or(Duration::MAX)
+}
+
+/// The kinds of operations whose retries can be configured.
+///
+/// The retries of the channel handshake are not part of these, since their default policy
+/// depends on the chains, see [`RetryConfig::channel`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RetryKind {
+    Client,
+    Connection,
+    Packet,
+    Tx,
+}
+
+/// The retry policies of the workers and of the transaction submission.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetryConfig {
+    pub client: RetryPolicy,
+    pub connection: RetryPolicy,
+
+    /// The retry policy of the channel handshake workers. If not set, the delay is a tenth of
+    /// the greatest `max_block_time` of the two chains, and the overall amount of time spent
+    /// backing off is capped to 10 times that `max_block_time`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<RetryPolicy>,
+
+    pub packet: RetryPolicy,
+    pub tx: RetryPolicy,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            client: RetryPolicy::client_refresh(),
+            connection: RetryPolicy::connection_handshake(),
+            channel: None,
+            packet: RetryPolicy::packet(),
+            tx: RetryPolicy::tx(),
+        }
+    }
+}
+
+impl RetryConfig {
+    pub fn policy(&self, kind: RetryKind) -> &RetryPolicy {
+        match kind {
+            RetryKind::Client => &self.client,
+            RetryKind::Connection => &self.connection,
+            RetryKind::Packet => &self.packet,
+            RetryKind::Tx => &self.tx,
+        }
+    }
+}
+
+/// The retry policies of a chain which override the global ones.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetryOverrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client: Option<RetryPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection: Option<RetryPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<RetryPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packet: Option<RetryPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx: Option<RetryPolicy>,
+}
+
+impl RetryOverrides {
+    pub fn policy(&self, kind: RetryKind) -> Option<&RetryPolicy> {
+        match kind {
+            RetryKind::Client => self.client.as_ref(),
+            RetryKind::Connection => self.connection.as_ref(),
+            RetryKind::Packet => self.packet.as_ref(),
+            RetryKind::Tx => self.tx.as_ref(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(delays: &[u64]) -> Vec<Duration> {
+        delays.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    fn policy(backoff: Backoff) -> RetryPolicy {
+        RetryPolicy {
+            backoff,
+            initial_delay: Duration::from_millis(100),
+            delay_increment: None,
+            max_delay: Duration::from_millis(1000),
+            max_total_delay: None,
+            jitter: 0.0,
+            max_attempts: Some(8),
+        }
+    }
+
+    #[test]
+    fn constant_delays() {
+        assert_eq!(
+            policy(Backoff::Constant).delays().collect::<Vec<_>>(),
+            millis(&[100; 8])
+        );
+    }
+
+    #[test]
+    fn linear_delays() {
+        assert_eq!(
+            policy(Backoff::Linear).delays().collect::<Vec<_>>(),
+            millis(&[100, 200, 300, 400, 500, 600, 700, 800])
+        );
+
+        let with_increment = RetryPolicy {
+            delay_increment: Some(Duration::from_millis(250)),
+            ..policy(Backoff::Linear)
+        };
+
+        assert_eq!(
+            with_increment.delays().collect::<Vec<_>>(),
+            millis(&[100, 350, 600, 850, 1000, 1000, 1000, 1000])
+        );
+    }
+
+    #[test]
+    fn exponential_delays() {
+        assert_eq!(
+            policy(Backoff::Exponential).delays().collect::<Vec<_>>(),
+            millis(&[100, 200, 400, 800, 1000, 1000, 1000, 1000])
+        );
+    }
+
+    #[test]
+    fn fibonacci_delays() {
+        assert_eq!(
+            policy(Backoff::Fibonacci).delays().collect::<Vec<_>>(),
+            millis(&[100, 100, 200, 300, 500, 800, 1000, 1000])
+        );
+    }
+
+    #[test]
+    fn max_total_delay() {
+        let policy = RetryPolicy {
+            max_total_delay: Some(Duration::from_millis(1000)),
+            ..policy(Backoff::Exponential)
+        };
+
+        // The last delay is cut short so that the total is exactly 1s
+        assert_eq!(
+            policy.delays().collect::<Vec<_>>(),
+            millis(&[100, 200, 400, 300])
+        );
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        let policy = RetryPolicy {
+            jitter: 0.5,
+            max_attempts: Some(100),
+            ..policy(Backoff::Constant)
+        };
+
+        let delays = policy.delays().collect::<Vec<_>>();
+
+        assert_eq!(delays.len(), 100);
+        assert!(delays
+            .iter()
+            .all(|delay| (Duration::from_millis(50)..=Duration::from_millis(150)).contains(delay)));
+    }
+
+    #[test]
+    fn unbounded_exponential_delays_with_jitter() {
+        let policy = RetryPolicy {
+            jitter: 0.5,
+            max_delay: Duration::MAX,
+            max_attempts: None,
+            ..policy(Backoff::Exponential)
+        };
+
+        // The backoff saturates at `Duration::MAX` after about 64 delays
+        let delays = policy.delays().take(100).collect::<Vec<_>>();
+
+        assert_eq!(delays.len(), 100);
+        assert!(delays[70..].iter().all(|delay| *delay >= Duration::MAX / 2));
+
+        let capped = RetryPolicy {
+            max_delay: Duration::from_secs(60),
+            ..policy
+        };
+
+        assert!(capped
+            .delays()
+            .take(100)
+            .all(|delay| delay <= Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn invalid_jitter_is_ignored() {
+        let policy = RetryPolicy {
+            jitter: f64::NAN,
+            ..policy(Backoff::Exponential)
+        };
+
+        assert_eq!(
+            policy.delays().collect::<Vec<_>>(),
+            millis(&[100, 200, 400, 800, 1000, 1000, 1000, 1000])
+        );
+    }
+
+    #[test]
+    fn default_policies() {
+        let config = RetryConfig::default();
+
+        assert_eq!(
+            config.connection.delays().collect::<Vec<_>>(),
+            millis(&[200, 300, 400, 500, 500, 100])
+        );
+
+        assert_eq!(config.channel, None);
+
+        assert_eq!(
+            config.client.delays().take(8).collect::<Vec<_>>(),
+            millis(&[1000, 1000, 2000, 3000, 5000, 8000, 13000, 21000])
+        );
+
+        assert_eq!(config.packet.delays().collect::<Vec<_>>(), millis(&[0; 4]));
+        assert_eq!(config.tx.delays().collect::<Vec<_>>(), millis(&[300]));
+    }
+
+    #[test]
+    fn parse_retry_config() {
+        let config: RetryConfig = toml::from_str(
+            r#"
+            packet = { backoff = 'exponential', initial_delay = '500ms', max_delay = '10s', jitter = 0.1, max_attempts = 3 }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.packet.backoff, Backoff::Exponential);
+        assert_eq!(config.packet.initial_delay, Duration::from_millis(500));
+        assert_eq!(config.packet.max_attempts, Some(3));
+        assert_eq!(config.client, RetryPolicy::client_refresh());
+    }
+}
@@ -0,0 +1,129 @@
+//! Configuration of the order in which the packet workers relay pending packets.
+
+use core::fmt::{Display, Error as FmtError, Formatter};
+
+use serde_derive::{Deserialize, Serialize};
+
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+/// The policy used by a packet worker to order the packets sent on its channel.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SchedulingPolicy {
+    /// Relay the packets in the order in which they were sent
+    #[default]
+    Fifo,
+
+    /// Relay the packets with the highest ICS-29 receive fee first
+    Fee,
+
+    /// Relay the packets which are the closest to timing out first
+    Timeout,
+}
+
+impl Display for SchedulingPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::Fifo => write!(f, "fifo"),
+            Self::Fee => write!(f, "fee"),
+            Self::Timeout => write!(f, "timeout"),
+        }
+    }
+}
+
+/// The priority of the packets sent on a channel, relative to the other channels.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelPriority {
+    pub chain_id: ChainId,
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub priority: u32,
+}
+
+/// Scheduling of the packets relayed by the packet workers.
+///
+/// The `policy` orders the packets of a single channel, while the `channel_priorities`
+/// order the channels of a chain: when the workers of several channels are waiting to
+/// submit messages to the same chain, the messages of the channels with the highest
+/// priority are submitted first. Packets on ordered channels are always relayed in the
+/// order in which they were sent.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PacketScheduling {
+    pub policy: SchedulingPolicy,
+
+    /// Only the fees in this denomination are compared by the `fee` policy.
+    /// If unset, the amounts of all the denominations are added up.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_denom: Option<String>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub channel_priorities: Vec<ChannelPriority>,
+}
+
+impl PacketScheduling {
+    /// The priority of the given channel on the given chain, `0` if it is not configured.
+    pub fn channel_priority(
+        &self,
+        chain_id: &ChainId,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> u32 {
+        self.channel_priorities
+            .iter()
+            .find(|channel| {
+                &channel.chain_id == chain_id
+                    && &channel.port_id == port_id
+                    && &channel.channel_id == channel_id
+            })
+            .map_or(0, |channel| channel.priority)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_packet_scheduling() {
+        let scheduling: PacketScheduling = toml::from_str(
+            r#"
+            policy = 'fee'
+            fee_denom = 'uatom'
+            channel_priorities = [
+                { chain_id = 'ibc-0', port_id = 'transfer', channel_id = 'channel-1', priority = 10 },
+            ]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(scheduling.policy, SchedulingPolicy::Fee);
+        assert_eq!(scheduling.fee_denom.as_deref(), Some("uatom"));
+
+        let chain_id = ChainId::from_string("ibc-0");
+        let port_id = PortId::transfer();
+
+        assert_eq!(
+            scheduling.channel_priority(&chain_id, &port_id, &ChannelId::new(1)),
+            10
+        );
+        assert_eq!(
+            scheduling.channel_priority(&chain_id, &port_id, &ChannelId::new(0)),
+            0
+        );
+    }
+
+    #[test]
+    fn default_packet_scheduling() {
+        let scheduling: PacketScheduling = toml::from_str("").unwrap();
+
+        assert_eq!(scheduling, PacketScheduling::default());
+        assert_eq!(scheduling.policy, SchedulingPolicy::Fifo);
+    }
+
+    #[test]
+    fn reject_unknown_policy() {
+        assert!(toml::from_str::<PacketScheduling>("policy = 'random'").is_err());
+    }
+}
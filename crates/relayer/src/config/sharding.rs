@@ -0,0 +1,209 @@
+//! Configuration for running several Hermes instances against the same chains,
+//! each one relaying on a disjoint subset of the paths.
+
+use core::fmt::{Display, Error as FmtError, Formatter};
+
+use serde_derive::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::object::Object;
+
+/// The hashing strategy used to assign an [`Object`] to an instance.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShardingStrategy {
+    /// 64-bit FNV-1a hash of the object's short name
+    #[default]
+    Fnv,
+
+    /// First 8 bytes of the SHA-256 digest of the object's short name
+    Sha256,
+}
+
+impl ShardingStrategy {
+    /// Hash the given key, the result is stable across platforms and releases.
+    pub fn hash(&self, key: &str) -> u64 {
+        match self {
+            Self::Fnv => fnv1a(key.as_bytes()),
+            Self::Sha256 => {
+                let digest = Sha256::digest(key.as_bytes());
+
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(&digest[..8]);
+
+                u64::from_be_bytes(bytes)
+            }
+        }
+    }
+}
+
+impl Display for ShardingStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::Fnv => write!(f, "fnv"),
+            Self::Sha256 => write!(f, "sha256"),
+        }
+    }
+}
+
+/// Deterministic partitioning of the workers among several Hermes instances.
+///
+/// Every instance must be configured with the same `total_instances` and `strategy`,
+/// and a distinct `instance` index in `0..total_instances`. Each client, connection,
+/// channel, packet and cross-chain query worker is then owned by exactly one instance,
+/// without any coordination between them.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShardingConfig {
+    /// The index of this instance, in `0..total_instances`
+    pub instance: u64,
+
+    /// The total number of instances sharing the work
+    pub total_instances: u64,
+
+    /// The hashing strategy used to assign objects to instances
+    pub strategy: ShardingStrategy,
+}
+
+/// By default, a single instance owns all the objects.
+impl Default for ShardingConfig {
+    fn default() -> Self {
+        Self {
+            instance: 0,
+            total_instances: 1,
+            strategy: ShardingStrategy::default(),
+        }
+    }
+}
+
+impl ShardingConfig {
+    /// Whether the work is split across more than one instance.
+    pub fn is_enabled(&self) -> bool {
+        self.total_instances > 1
+    }
+
+    /// Returns the index of the instance in charge of the given [`Object`],
+    /// or `None` if the object is not subject to sharding.
+    ///
+    /// Wallet workers only report on the local relayer's key,
+    /// and are therefore never sharded.
+    pub fn shard_of(&self, object: &Object) -> Option<u64> {
+        match object {
+            Object::Wallet(_) => None,
+            _ => Some(self.shard_of_key(&object.short_name())),
+        }
+    }
+
+    /// Returns the index of the instance in charge of the given key.
+    pub fn shard_of_key(&self, key: &str) -> u64 {
+        if self.total_instances <= 1 {
+            return 0;
+        }
+
+        self.strategy.hash(key) % self.total_instances
+    }
+
+    /// Whether this instance is in charge of the given [`Object`].
+    pub fn owns(&self, object: &Object) -> bool {
+        self.shard_of(object)
+            .map_or(true, |shard| shard == self.instance)
+    }
+}
+
+impl Display for ShardingConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(
+            f,
+            "instance {} of {} (strategy: {})",
+            self.instance, self.total_instances, self.strategy
+        )
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+    use crate::object::{Packet, Wallet};
+
+    fn packet(channel: u64) -> Object {
+        Object::Packet(Packet {
+            dst_chain_id: ChainId::from_string("chain-b"),
+            src_chain_id: ChainId::from_string("chain-a"),
+            src_channel_id: ChannelId::new(channel),
+            src_port_id: PortId::transfer(),
+        })
+    }
+
+    fn sharding(instance: u64, total_instances: u64, strategy: ShardingStrategy) -> ShardingConfig {
+        ShardingConfig {
+            instance,
+            total_instances,
+            strategy,
+        }
+    }
+
+    #[test]
+    fn fnv_known_vectors() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x85944171f73967e8);
+    }
+
+    #[test]
+    fn default_owns_everything() {
+        let config = ShardingConfig::default();
+
+        assert!(!config.is_enabled());
+        assert!((0..50).all(|i| config.owns(&packet(i))));
+    }
+
+    #[test]
+    fn each_object_owned_by_exactly_one_instance() {
+        for strategy in [ShardingStrategy::Fnv, ShardingStrategy::Sha256] {
+            let instances = (0..3).map(|i| sharding(i, 3, strategy)).collect::<Vec<_>>();
+
+            for i in 0..100 {
+                let object = packet(i);
+                let owners = instances.iter().filter(|c| c.owns(&object)).count();
+
+                assert_eq!(
+                    owners,
+                    1,
+                    "object {} has {owners} owners",
+                    object.short_name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn assignment_is_deterministic() {
+        let a = sharding(0, 4, ShardingStrategy::Sha256);
+        let b = sharding(3, 4, ShardingStrategy::Sha256);
+
+        for i in 0..20 {
+            assert_eq!(a.shard_of(&packet(i)), b.shard_of(&packet(i)));
+        }
+    }
+
+    #[test]
+    fn wallets_are_not_sharded() {
+        let wallet = Object::Wallet(Wallet {
+            chain_id: ChainId::from_string("chain-a"),
+        });
+
+        assert!((0..3).all(|i| sharding(i, 3, ShardingStrategy::Fnv).owns(&wallet)));
+    }
+}
@@ -259,6 +259,131 @@ pub mod memo {
     }
 }
 
+pub use key_names::KeyNames;
+
+pub mod key_names {
+    use core::fmt::{Display, Error as FmtError, Formatter};
+
+    use serde::de::{Error as _, Unexpected};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// The names of the keys used by Hermes to sign transactions on a chain.
+    ///
+    /// Can be specified in the configuration either as a single key name,
+    /// or as a non-empty list of key names. The first key in the list is the
+    /// primary key, which is the one used as the relayer's address
+    /// (eg. for fee payee registration), while all keys are used in turn
+    /// to sign and submit transactions.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct KeyNames(Vec<String>);
+
+    impl KeyNames {
+        /// Returns `None` if the given list of key names is empty.
+        pub fn new(names: Vec<String>) -> Option<Self> {
+            if names.is_empty() {
+                None
+            } else {
+                Some(Self(names))
+            }
+        }
+
+        /// The name of the primary key
+        pub fn primary(&self) -> &str {
+            &self.0[0]
+        }
+
+        /// The number of keys, always at least one
+        pub fn len(&self) -> usize {
+            self.0.len()
+        }
+
+        /// Always `false`, as there is at least one key
+        pub fn is_empty(&self) -> bool {
+            false
+        }
+
+        /// Iterate over all the key names, starting with the primary key
+        pub fn iter(&self) -> impl Iterator<Item = &str> {
+            self.0.iter().map(String::as_str)
+        }
+
+        /// The same key names with the given key as primary key, in place of
+        /// the current one, which is dropped. If the given key was one of the
+        /// secondary keys, it is moved to the front of the list.
+        pub fn with_primary(&self, name: &str) -> Self {
+            let secondaries = self.0[1..].iter().filter(|key| *key != name).cloned();
+
+            Self(
+                core::iter::once(name.to_string())
+                    .chain(secondaries)
+                    .collect(),
+            )
+        }
+    }
+
+    impl From<String> for KeyNames {
+        fn from(name: String) -> Self {
+            Self(vec![name])
+        }
+    }
+
+    impl From<&str> for KeyNames {
+        fn from(name: &str) -> Self {
+            Self(vec![name.to_string()])
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawKeyNames {
+        Single(String),
+        Multiple(Vec<String>),
+    }
+
+    impl<'de> Deserialize<'de> for KeyNames {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            match RawKeyNames::deserialize(deserializer)? {
+                RawKeyNames::Single(name) => Ok(Self::from(name)),
+                RawKeyNames::Multiple(names) => {
+                    if names.iter().any(String::is_empty) {
+                        return Err(D::Error::invalid_value(
+                            Unexpected::Str(""),
+                            &"a non-empty key name",
+                        ));
+                    }
+
+                    Self::new(names).ok_or_else(|| {
+                        D::Error::invalid_length(0, &"a non-empty list of key names")
+                    })
+                }
+            }
+        }
+    }
+
+    /// A single key is serialized as a plain string, for backward compatibility.
+    impl Serialize for KeyNames {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            if self.0.len() == 1 {
+                self.0[0].serialize(serializer)
+            } else {
+                self.0.serialize(serializer)
+            }
+        }
+    }
+
+    impl Display for KeyNames {
+        fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+            write!(f, "{}", self.0.join(", "))
+        }
+    }
+}
+
 #[cfg(test)]
 #[allow(dead_code)] // the fields of the structs defined below are never accessed
 mod tests {
@@ -324,4 +449,63 @@ mod tests {
 
         assert!(err.contains("a string length of at most"));
     }
+
+    #[test]
+    fn parse_key_names() {
+        #[derive(Debug, Deserialize)]
+        struct DummyConfig {
+            key_name: KeyNames,
+        }
+
+        let single = toml::from_str::<DummyConfig>(r#"key_name = "wallet""#).unwrap();
+        assert_eq!(single.key_name, KeyNames::from("wallet"));
+        assert_eq!(single.key_name.primary(), "wallet");
+
+        let multiple =
+            toml::from_str::<DummyConfig>(r#"key_name = ["wallet", "wallet-1"]"#).unwrap();
+        assert_eq!(multiple.key_name.len(), 2);
+        assert_eq!(multiple.key_name.primary(), "wallet");
+        assert_eq!(
+            multiple.key_name.iter().collect::<Vec<_>>(),
+            vec!["wallet", "wallet-1"]
+        );
+    }
+
+    #[test]
+    fn parse_invalid_key_names() {
+        #[derive(Debug, Deserialize)]
+        struct DummyConfig {
+            key_name: KeyNames,
+        }
+
+        assert!(toml::from_str::<DummyConfig>("key_name = []").is_err());
+        assert!(toml::from_str::<DummyConfig>(r#"key_name = ["wallet", ""]"#).is_err());
+    }
+
+    #[test]
+    fn replace_primary_key_name() {
+        let names = KeyNames::new(vec!["a".into(), "b".into(), "c".into()]).unwrap();
+
+        assert_eq!(
+            names.with_primary("d").iter().collect::<Vec<_>>(),
+            vec!["d", "b", "c"]
+        );
+        assert_eq!(
+            names.with_primary("c").iter().collect::<Vec<_>>(),
+            vec!["c", "b"]
+        );
+        assert_eq!(KeyNames::from("a").with_primary("b"), KeyNames::from("b"));
+    }
+
+    #[test]
+    fn serialize_single_key_name_as_string() {
+        assert_eq!(
+            serde_json::to_string(&KeyNames::from("wallet")).unwrap(),
+            r#""wallet""#
+        );
+        assert_eq!(
+            serde_json::to_string(&KeyNames::new(vec!["a".into(), "b".into()]).unwrap()).unwrap(),
+            r#"["a","b"]"#
+        );
+    }
 }
@@ -269,6 +269,17 @@ define_error! {
             [ KeyringError ]
             |e| { format!("signature key not found: {}", e.key_name) },
 
+        KeyAlreadyPrimary
+            { key_name: String }
+            |e| { format!("key `{}` is already the primary key of the chain", e.key_name) },
+
+        LedgerKeyNotRelayable
+            { key_name: String }
+            |e| {
+                format!("key `{}` is held by a Ledger device, which cannot sign the transactions of the relayer unattended",
+                    e.key_name)
+            },
+
         Ics02
             [ client_error::Error ]
             |e| { format!("ICS 02 error: {}", e.source) },
@@ -359,6 +370,13 @@ define_error! {
             [ TraceError<EncodeError> ]
             |e| { format!("error encoding protocol buffer for {}", e.payload_type) },
 
+        AminoJsonUnsupportedMessage
+            { type_url: String }
+            |e| {
+                format!("message {} cannot be signed in the amino-JSON sign mode required by Ledger keys",
+                    e.type_url)
+            },
+
         TxSimulateGasEstimateExceeded
             {
                 chain_id: ChainId,
@@ -525,6 +543,46 @@ define_error! {
         EmptyBaseAccount
             |_| { "empty BaseAccount within EthAccount" },
 
+        UnknownFeeAllowanceType
+            { type_url: String }
+            |e| {
+                format!("failed to deserialize fee allowance of an unknown protobuf type: {0}", e.type_url)
+            },
+
+        FeeGranterNotConfigured
+            {
+                chain_id: ChainId,
+                key_name: String,
+            }
+            |e| {
+                format!("no fee granter is configured for key '{0}' on chain '{1}'",
+                    e.key_name, e.chain_id)
+            },
+
+        UnusableFeeGrant
+            {
+                chain_id: ChainId,
+                key_name: String,
+                granter: String,
+                reason: String,
+            }
+            |e| {
+                format!("the fee grant given by '{0}' to key '{1}' on chain '{2}' cannot be used to pay for transactions: {3}",
+                    e.granter, e.key_name, e.chain_id, e.reason)
+            },
+
+        UnusableAuthzGrant
+            {
+                chain_id: ChainId,
+                key_name: String,
+                granter: String,
+                reason: String,
+            }
+            |e| {
+                format!("key '{0}' on chain '{1}' cannot execute messages on behalf of authz granter '{2}': {3}",
+                    e.key_name, e.chain_id, e.granter, e.reason)
+            },
+
         EmptyQueryAccount
             { address: String }
             |e| { format!("Query/Account RPC returned an empty account for address: {}", e.address) },
@@ -45,9 +45,22 @@ define_error! {
             [ TraceError<RpcError> ]
             |_| { "subscription cancelled" },
 
+        CatchUpFailed
+            { from_height: u64, reason: String }
+            |e| { format!("failed to pull the events missed while reconnecting from height {0}: {1}", e.from_height, e.reason) },
+
         Rpc
             [ TraceError<RpcError> ]
             |_| { "RPC error" },
+
+        StreamConnectionFailed
+            { url: String }
+            [ TraceError<tonic::transport::Error> ]
+            |e| { format!("failed to connect to the event stream endpoint {0}", e.url) },
+
+        StreamFailed
+            [ TraceError<tonic::Status> ]
+            |_| { "event stream failed" },
     }
 }
 
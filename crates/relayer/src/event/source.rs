@@ -1,4 +1,5 @@
 pub mod rpc;
+pub mod stream;
 pub mod websocket;
 
 use std::{sync::Arc, time::Duration};
@@ -6,10 +7,9 @@ use std::{sync::Arc, time::Duration};
 use crossbeam_channel as channel;
 
 use futures::Stream;
-use tendermint_rpc::{
-    client::CompatMode, event::Event as RpcEvent, Error as RpcError, HttpClient, WebSocketClientUrl,
-};
+use tendermint_rpc::{event::Event as RpcEvent, Error as RpcError, Url, WebSocketClientUrl};
 use tokio::runtime::Runtime as TokioRuntime;
+use tonic::transport::Uri;
 
 use ibc_relayer_types::{
     core::ics02_client::height::Height, core::ics24_host::identifier::ChainId,
@@ -17,6 +17,9 @@ use ibc_relayer_types::{
 
 pub use super::error::{Error, ErrorDetail};
 
+use crate::chain::cosmos::failover::SharedEndpointPool;
+use crate::config::CompatMode;
+
 use super::IbcEventWithHeight;
 use crate::chain::{handle::Subscription, tracking::TrackingId};
 
@@ -25,18 +28,19 @@ pub type Result<T> = core::result::Result<T, Error>;
 pub enum EventSource {
     WebSocket(websocket::EventSource),
     Rpc(rpc::EventSource),
+    Stream(stream::EventSource),
 }
 
 impl EventSource {
     pub fn websocket(
         chain_id: ChainId,
-        ws_url: WebSocketClientUrl,
-        rpc_compat: CompatMode,
+        ws_endpoints: SharedEndpointPool<WebSocketClientUrl>,
+        compat_mode: CompatMode,
         batch_delay: Duration,
         rt: Arc<TokioRuntime>,
     ) -> Result<(Self, TxEventSourceCmd)> {
         let (mut source, tx) =
-            websocket::EventSource::new(chain_id, ws_url, rpc_compat, batch_delay, rt)?;
+            websocket::EventSource::new(chain_id, ws_endpoints, compat_mode, batch_delay, rt)?;
 
         source.init_subscriptions()?;
 
@@ -45,18 +49,30 @@ impl EventSource {
 
     pub fn rpc(
         chain_id: ChainId,
-        rpc_client: HttpClient,
+        rpc_endpoints: SharedEndpointPool<Url>,
+        compat_mode: CompatMode,
         poll_interval: Duration,
         rt: Arc<TokioRuntime>,
     ) -> Result<(Self, TxEventSourceCmd)> {
-        let (source, tx) = rpc::EventSource::new(chain_id, rpc_client, poll_interval, rt)?;
+        let (source, tx) =
+            rpc::EventSource::new(chain_id, rpc_endpoints, compat_mode, poll_interval, rt)?;
         Ok((Self::Rpc(source), tx))
     }
 
+    pub fn stream(
+        chain_id: ChainId,
+        url: Uri,
+        rt: Arc<TokioRuntime>,
+    ) -> Result<(Self, TxEventSourceCmd)> {
+        let (source, tx) = stream::EventSource::new(chain_id, url, rt)?;
+        Ok((Self::Stream(source), tx))
+    }
+
     pub fn run(self) {
         match self {
             Self::WebSocket(source) => source.run(),
             Self::Rpc(source) => source.run(),
+            Self::Stream(source) => source.run(),
         }
     }
 }
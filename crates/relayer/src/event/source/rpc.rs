@@ -11,7 +11,7 @@ use tracing::{debug, error, error_span, trace};
 
 use tendermint::abci;
 use tendermint::block::Height as BlockHeight;
-use tendermint_rpc::{Client, HttpClient};
+use tendermint_rpc::{Client, HttpClient, Url};
 
 use ibc_relayer_types::{
     core::{
@@ -22,10 +22,17 @@ use ibc_relayer_types::{
 };
 
 use crate::{
+    chain::cosmos::failover::SharedEndpointPool,
+    chain::cosmos::query::block_results::query_block_results,
     chain::tracking::TrackingId,
-    event::{bus::EventBus, source::Error, IbcEventWithHeight},
+    config::CompatMode,
+    event::{
+        bus::EventBus,
+        source::{Error, ErrorDetail},
+        IbcEventWithHeight,
+    },
     telemetry,
-    util::retry::ConstantGrowth,
+    util::{lock::LockExt, retry::ConstantGrowth},
 };
 
 use super::{EventBatch, EventSourceCmd, TxEventSourceCmd};
@@ -39,8 +46,14 @@ pub struct EventSource {
     /// Chain identifier
     chain_id: ChainId,
 
-    /// RPC client
-    rpc_client: HttpClient,
+    /// The RPC endpoints to fail over between
+    rpc_endpoints: SharedEndpointPool<Url>,
+
+    /// A client for each of the RPC endpoints, in the order of the pool
+    rpc_clients: Vec<HttpClient>,
+
+    /// The RPC dialect spoken by the node
+    compat_mode: CompatMode,
 
     /// Poll interval
     poll_interval: Duration,
@@ -61,17 +74,32 @@ pub struct EventSource {
 impl EventSource {
     pub fn new(
         chain_id: ChainId,
-        rpc_client: HttpClient,
+        rpc_endpoints: SharedEndpointPool<Url>,
+        compat_mode: CompatMode,
         poll_interval: Duration,
         rt: Arc<TokioRuntime>,
     ) -> Result<(Self, TxEventSourceCmd)> {
         let event_bus = EventBus::new();
         let (tx_cmd, rx_cmd) = channel::unbounded();
 
+        let rpc_clients = rpc_endpoints
+            .acquire_read()
+            .addrs()
+            .into_iter()
+            .map(|addr| {
+                HttpClient::builder(addr.try_into().map_err(Error::rpc)?)
+                    .compat_mode(compat_mode.rpc_compat())
+                    .build()
+                    .map_err(Error::rpc)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
         let source = Self {
             rt,
             chain_id,
-            rpc_client,
+            rpc_endpoints,
+            rpc_clients,
+            compat_mode,
             poll_interval,
             event_bus,
             rx_cmd,
@@ -92,7 +120,7 @@ impl EventSource {
             let mut backoff = poll_backoff(self.poll_interval);
 
             // Initialize the latest fetched height
-            if let Ok(latest_height) = latest_height(&self.rpc_client).await {
+            if let Ok(latest_height) = latest_height(self.rpc_client()).await {
                 self.last_fetched_height = latest_height;
             }
 
@@ -121,6 +149,11 @@ impl EventSource {
                     Err(e) => {
                         error!("event source encountered an error: {e}");
 
+                        // Fail over to another endpoint if the RPC endpoint in use does not answer
+                        if let ErrorDetail::Rpc(_) = e.detail() {
+                            self.rpc_endpoints.acquire_write().report_current_failure();
+                        }
+
                         // Let's backoff the little bit to give the chain some time to recover.
                         let delay = backoff.next().expect("backoff is an infinite iterator");
 
@@ -134,13 +167,18 @@ impl EventSource {
         debug!("shutting down event source");
     }
 
+    /// The client of the RPC endpoint in use
+    fn rpc_client(&self) -> &HttpClient {
+        &self.rpc_clients[self.rpc_endpoints.acquire_read().current_index()]
+    }
+
     async fn step(&mut self) -> Result<Next> {
         // Process any shutdown or subscription commands before we start doing any work
         if let Next::Abort = self.try_process_cmd() {
             return Ok(Next::Abort);
         }
 
-        let latest_height = latest_height(&self.rpc_client).await?;
+        let latest_height = latest_height(self.rpc_client()).await?;
 
         let batches = if latest_height > self.last_fetched_height {
             trace!(
@@ -203,7 +241,8 @@ impl EventSource {
         for height in heights {
             trace!("collecting events at height {height}");
 
-            let result = collect_events(&self.rpc_client, &self.chain_id, height).await;
+            let result =
+                collect_events(self.rpc_client(), self.compat_mode, &self.chain_id, height).await;
 
             match result {
                 Ok(batch) => {
@@ -289,13 +328,14 @@ fn dedupe(events: Vec<abci::Event>) -> Vec<abci::Event> {
         .collect()
 }
 
-/// Collect the IBC events from an RPC event
-async fn collect_events(
+/// Collect the IBC events emitted at the given height from `/block_results`
+pub async fn collect_events(
     rpc_client: &HttpClient,
+    compat_mode: CompatMode,
     chain_id: &ChainId,
     latest_block_height: BlockHeight,
 ) -> Result<Option<EventBatch>> {
-    let abci_events = fetch_all_events(rpc_client, latest_block_height).await?;
+    let abci_events = fetch_all_events(rpc_client, compat_mode, latest_block_height).await?;
     trace!("Found {} ABCI events before dedupe", abci_events.len());
 
     let abci_events = dedupe(abci_events);
@@ -326,9 +366,12 @@ async fn collect_events(
 
 async fn fetch_all_events(
     rpc_client: &HttpClient,
+    compat_mode: CompatMode,
     height: BlockHeight,
 ) -> Result<Vec<abci::Event>> {
-    let mut response = rpc_client.block_results(height).await.map_err(Error::rpc)?;
+    let mut response = query_block_results(rpc_client, compat_mode, height)
+        .await
+        .map_err(Error::rpc)?;
     let mut events = vec![];
 
     if let Some(begin_block_events) = &mut response.begin_block_events {
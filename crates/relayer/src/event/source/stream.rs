@@ -0,0 +1,483 @@
+//! A source of events consuming the events of each block of a chain from a gRPC
+//! streaming endpoint, such as an event indexer or a block streaming sidecar
+//! of a full node, which decouples Hermes from the WebSocket limits of the node.
+//!
+//! The endpoint must implement the following service:
+//!
+//! ```protobuf
+//! syntax = "proto3";
+//!
+//! package hermes.events.v1;
+//!
+//! service EventStream {
+//!   // Stream the events of each block of a chain from `start_height` on,
+//!   // or from the latest block if `start_height` is 0, as the blocks are committed.
+//!   rpc StreamBlockEvents(StreamBlockEventsRequest) returns (stream BlockEvents);
+//! }
+//!
+//! message StreamBlockEventsRequest {
+//!   string chain_id = 1;
+//!   uint64 start_height = 2;
+//! }
+//!
+//! message BlockEvents {
+//!   uint64 height = 1;
+//!   // The begin block events, the events of the successful transactions
+//!   // and the end block events of the block, in this order
+//!   repeated Event events = 2;
+//! }
+//!
+//! // Same as `tendermint.abci.Event`
+//! message Event {
+//!   string type = 1;
+//!   repeated EventAttribute attributes = 2;
+//! }
+//!
+//! // Same as `tendermint.abci.EventAttribute`
+//! message EventAttribute {
+//!   string key = 1;
+//!   string value = 2;
+//!   bool index = 3;
+//! }
+//! ```
+
+pub mod proto;
+
+use std::sync::Arc;
+
+use crossbeam_channel as channel;
+use tokio::runtime::Runtime as TokioRuntime;
+use tokio::time::{sleep, Duration};
+use tonic::transport::Uri;
+use tracing::{debug, error, error_span, trace};
+
+use ibc_relayer_types::{
+    core::{
+        ics02_client::{events::NewBlock, height::Height},
+        ics24_host::identifier::ChainId,
+    },
+    events::IbcEvent,
+};
+
+use crate::{
+    chain::cosmos::types::events::from_tx_response_event,
+    chain::tracking::TrackingId,
+    event::{bus::EventBus, source::Error, IbcEventWithHeight},
+    telemetry,
+    util::retry::ConstantGrowth,
+};
+
+use super::{EventBatch, EventSourceCmd, Result, TxEventSourceCmd};
+
+use self::proto::{BlockEvents, EventStreamClient, StreamBlockEventsRequest};
+
+/// How often to check for commands while waiting for the next block
+const CMD_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A gRPC streaming endpoint that serves as a source of events for a given chain.
+pub struct EventSource {
+    /// Chain identifier
+    chain_id: ChainId,
+
+    /// The URL of the event streaming endpoint
+    url: Uri,
+
+    /// Event bus for broadcasting events
+    event_bus: EventBus<Arc<Result<EventBatch>>>,
+
+    /// Channel where to receive commands
+    rx_cmd: channel::Receiver<EventSourceCmd>,
+
+    /// Tokio runtime
+    rt: Arc<TokioRuntime>,
+
+    /// Height of the last block whose events were broadcast,
+    /// from which to resume streaming after a reconnection
+    last_height: Option<u64>,
+}
+
+impl EventSource {
+    pub fn new(
+        chain_id: ChainId,
+        url: Uri,
+        rt: Arc<TokioRuntime>,
+    ) -> Result<(Self, TxEventSourceCmd)> {
+        let event_bus = EventBus::new();
+        let (tx_cmd, rx_cmd) = channel::unbounded();
+
+        let source = Self {
+            chain_id,
+            url,
+            event_bus,
+            rx_cmd,
+            rt,
+            last_height: None,
+        };
+
+        Ok((source, TxEventSourceCmd(tx_cmd)))
+    }
+
+    pub fn run(mut self) {
+        let _span = error_span!("event_source.stream", chain.id = %self.chain_id).entered();
+
+        debug!("collecting events");
+
+        let rt = self.rt.clone();
+
+        rt.block_on(async {
+            let mut backoff = reconnect_backoff();
+
+            // Continuously stream the events, so that when the stream ends
+            // or fails, we resume from the block following the last one.
+            loop {
+                match self.stream_events().await {
+                    Ok(Next::Abort) => break,
+
+                    Ok(Next::Continue | Next::Reconnect) => {
+                        debug!("event stream ended, reconnecting");
+
+                        backoff = reconnect_backoff();
+                    }
+
+                    Err(e) => {
+                        error!("event source encountered an error: {e}");
+
+                        let delay = backoff.next().expect("backoff is an infinite iterator");
+
+                        error!("retrying in {delay:?}...");
+                        sleep(delay).await;
+                    }
+                }
+            }
+        });
+
+        debug!("shutting down event source");
+    }
+
+    async fn stream_events(&mut self) -> Result<Next> {
+        // Process any shutdown or subscription commands before we start doing any work
+        if let Next::Abort = self.try_process_cmd() {
+            return Ok(Next::Abort);
+        }
+
+        let mut client = EventStreamClient::connect(self.url.clone())
+            .await
+            .map_err(|e| Error::stream_connection_failed(self.url.to_string(), e))?;
+
+        let request = StreamBlockEventsRequest {
+            chain_id: self.chain_id.to_string(),
+            start_height: self.last_height.map_or(0, |height| height + 1),
+        };
+
+        trace!("streaming the events from height {}", request.start_height);
+
+        let mut stream = client
+            .stream_block_events(request)
+            .await
+            .map_err(Error::stream_failed)?
+            .into_inner();
+
+        let mut cmd_check = tokio::time::interval(CMD_CHECK_INTERVAL);
+
+        loop {
+            if let Next::Abort = self.try_process_cmd() {
+                return Ok(Next::Abort);
+            }
+
+            let block = tokio::select! {
+                block = stream.message() => block.map_err(Error::stream_failed)?,
+                _ = cmd_check.tick() => continue,
+            };
+
+            let Some(block) = block else {
+                return Ok(Next::Reconnect);
+            };
+
+            // Skip the blocks replayed by the endpoint
+            if self
+                .last_height
+                .map_or(false, |height| block.height <= height)
+            {
+                continue;
+            }
+
+            // Before handling the batch, check if there are any pending shutdown or subscribe commands.
+            //
+            // This allows subscribers to receive the latest event batch even if they
+            // subscribe while the batch being fetched.
+            if let Next::Abort = self.try_process_cmd() {
+                return Ok(Next::Abort);
+            }
+
+            let height = block.height;
+            let batch = collect_events(&self.chain_id, block)?;
+
+            self.broadcast_batch(batch);
+            self.last_height = Some(height);
+        }
+    }
+
+    /// Process any pending commands, if any.
+    fn try_process_cmd(&mut self) -> Next {
+        if let Ok(cmd) = self.rx_cmd.try_recv() {
+            match cmd {
+                EventSourceCmd::Shutdown => return Next::Abort,
+
+                EventSourceCmd::Subscribe(tx) => {
+                    if let Err(e) = tx.send(self.event_bus.subscribe()) {
+                        error!("failed to send back subscription: {e}");
+                    }
+                }
+            }
+        }
+
+        Next::Continue
+    }
+
+    fn broadcast_batch(&mut self, batch: EventBatch) {
+        telemetry!(ws_events, &batch.chain_id, batch.events.len() as u64);
+
+        trace!(
+            chain = %batch.chain_id,
+            count = %batch.events.len(),
+            height = %batch.height,
+            "broadcasting batch of {} events",
+            batch.events.len()
+        );
+
+        self.event_bus.broadcast(Arc::new(Ok(batch)));
+    }
+}
+
+fn reconnect_backoff() -> impl Iterator<Item = Duration> {
+    ConstantGrowth::new(Duration::from_secs(1), Duration::from_secs(1))
+        .clamp(Duration::from_secs(10), usize::MAX)
+}
+
+/// Collect the IBC events of a block streamed by the endpoint
+fn collect_events(chain_id: &ChainId, block: BlockEvents) -> Result<EventBatch> {
+    let height = Height::new(chain_id.version(), block.height)
+        .map_err(|_| Error::collect_events_failed(format!("invalid height {}", block.height)))?;
+
+    let new_block_event =
+        IbcEventWithHeight::new(IbcEvent::NewBlock(NewBlock::new(height)), height);
+
+    let events = core::iter::once(new_block_event)
+        .chain(
+            block
+                .events
+                .into_iter()
+                .filter_map(|event| from_tx_response_event(height, &event.into())),
+        )
+        .collect();
+
+    Ok(EventBatch {
+        chain_id: chain_id.clone(),
+        tracking_id: TrackingId::new_uuid(),
+        height,
+        events,
+    })
+}
+
+pub enum Next {
+    Abort,
+    Continue,
+    Reconnect,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+    use std::thread;
+
+    use futures::stream::{self, BoxStream};
+    use tendermint::abci;
+    use tonic::body::BoxBody;
+    use tonic::codec::ProstCodec;
+    use tonic::codegen::{empty_body, http, Body, BoxFuture, Context, Poll, Service, StdError};
+    use tonic::server::{Grpc, NamedService, ServerStreamingService};
+    use tonic::{Request, Response, Status};
+
+    use ibc_relayer_types::core::ics03_connection::events::{Attributes, OpenInit};
+
+    /// A mock of an event streaming endpoint, which streams at most `blocks_per_stream`
+    /// of its blocks from the requested height on before ending the stream.
+    #[derive(Clone)]
+    struct MockEventStream {
+        blocks: Arc<Vec<BlockEvents>>,
+        blocks_per_stream: usize,
+        requests: Arc<Mutex<Vec<StreamBlockEventsRequest>>>,
+    }
+
+    impl ServerStreamingService<StreamBlockEventsRequest> for MockEventStream {
+        type Response = BlockEvents;
+        type ResponseStream = BoxStream<'static, core::result::Result<BlockEvents, Status>>;
+        type Future = BoxFuture<Response<Self::ResponseStream>, Status>;
+
+        fn call(&mut self, request: Request<StreamBlockEventsRequest>) -> Self::Future {
+            let request = request.into_inner();
+
+            let blocks: Vec<_> = self
+                .blocks
+                .iter()
+                .filter(|block| block.height >= request.start_height)
+                .take(self.blocks_per_stream)
+                .cloned()
+                .map(Ok)
+                .collect();
+
+            self.requests.lock().unwrap().push(request);
+
+            let stream: Self::ResponseStream = Box::pin(stream::iter(blocks));
+            Box::pin(async move { Ok(Response::new(stream)) })
+        }
+    }
+
+    impl<B> Service<http::Request<B>> for MockEventStream
+    where
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<BoxBody>;
+        type Error = core::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<core::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: http::Request<B>) -> Self::Future {
+            let service = self.clone();
+
+            if request.uri().path() == proto::STREAM_BLOCK_EVENTS_PATH {
+                Box::pin(async move {
+                    let mut grpc = Grpc::new(ProstCodec::default());
+                    Ok(grpc.server_streaming(service, request).await)
+                })
+            } else {
+                Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .header("grpc-status", "12")
+                        .header("content-type", "application/grpc")
+                        .body(empty_body())
+                        .unwrap())
+                })
+            }
+        }
+    }
+
+    impl NamedService for MockEventStream {
+        const NAME: &'static str = proto::SERVICE_NAME;
+    }
+
+    /// Serve the given mock on a local port, returning its URL
+    fn spawn_mock_server(rt: &TokioRuntime, service: MockEventStream) -> Uri {
+        let listener = rt
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let incoming = async_stream::stream! {
+            loop {
+                yield listener.accept().await.map(|(stream, _)| stream);
+            }
+        };
+
+        rt.spawn(
+            tonic::transport::Server::builder()
+                .add_service(service)
+                .serve_with_incoming(incoming),
+        );
+
+        format!("http://{addr}").parse().unwrap()
+    }
+
+    #[test]
+    fn streams_block_events_and_resumes_after_the_last_block() {
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+        let chain_id = ChainId::from_string("ibc-0");
+
+        let open_init = OpenInit::from(Attributes {
+            connection_id: Some("connection-0".parse().unwrap()),
+            client_id: "07-tendermint-0".parse().unwrap(),
+            counterparty_connection_id: None,
+            counterparty_client_id: "07-tendermint-1".parse().unwrap(),
+        });
+
+        let blocks = (1..=5)
+            .map(|height| BlockEvents {
+                height,
+                events: if height == 2 {
+                    vec![
+                        abci::Event::new("message", [("module", "ibc_connection")]).into(),
+                        abci::Event::from(open_init.clone()).into(),
+                    ]
+                } else {
+                    vec![]
+                },
+            })
+            .collect();
+
+        let service = MockEventStream {
+            blocks: Arc::new(blocks),
+            blocks_per_stream: 2,
+            requests: Arc::default(),
+        };
+
+        let url = spawn_mock_server(&rt, service.clone());
+        let (source, tx_cmd) = EventSource::new(chain_id, url, rt).unwrap();
+
+        // Subscribe before the source starts streaming, so as to receive every batch
+        let subscriber = {
+            let tx_cmd = tx_cmd.clone();
+            thread::spawn(move || tx_cmd.subscribe().unwrap())
+        };
+
+        thread::spawn(move || source.run());
+
+        let subscription = subscriber.join().unwrap();
+
+        let batches: Vec<_> = (0..5)
+            .map(|_| {
+                subscription
+                    .recv_timeout(Duration::from_secs(10))
+                    .unwrap()
+                    .as_ref()
+                    .as_ref()
+                    .unwrap()
+                    .clone()
+            })
+            .collect();
+
+        tx_cmd.shutdown().unwrap();
+
+        let heights: Vec<_> = batches
+            .iter()
+            .map(|batch| batch.height.revision_height())
+            .collect();
+        assert_eq!(heights, vec![1, 2, 3, 4, 5]);
+
+        for batch in &batches {
+            assert!(matches!(batch.events[0].event, IbcEvent::NewBlock(_)));
+        }
+
+        assert_eq!(batches[1].events.len(), 2);
+        assert!(matches!(
+            &batches[1].events[1].event,
+            IbcEvent::OpenInitConnection(event) if *event == open_init
+        ));
+
+        // Each stream ends after two blocks, and is resumed from the following block
+        let start_heights: Vec<_> = service.requests.lock().unwrap()[..3]
+            .iter()
+            .map(|request| request.start_height)
+            .collect();
+        assert_eq!(start_heights, vec![0, 3, 5]);
+    }
+}
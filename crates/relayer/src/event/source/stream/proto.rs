@@ -0,0 +1,131 @@
+//! Messages and client of the `hermes.events.v1.EventStream` gRPC service.
+//!
+//! The `Event` and `EventAttribute` messages are wire-compatible with the
+//! `tendermint.abci.Event` and `tendermint.abci.EventAttribute` messages of
+//! CometBFT 0.37 and later, so that an indexer can forward them as is.
+
+use http::uri::PathAndQuery;
+use tendermint::abci;
+use tonic::codec::ProstCodec;
+use tonic::transport::{Channel, Endpoint, Error as TransportError, Uri};
+use tonic::{Request, Response, Status, Streaming};
+
+/// The full name of the service, as found in the path of its methods
+pub const SERVICE_NAME: &str = "hermes.events.v1.EventStream";
+
+/// The path of the `StreamBlockEvents` method
+pub const STREAM_BLOCK_EVENTS_PATH: &str = "/hermes.events.v1.EventStream/StreamBlockEvents";
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamBlockEventsRequest {
+    /// The chain whose events to stream
+    #[prost(string, tag = "1")]
+    pub chain_id: String,
+
+    /// The height of the first block whose events to stream,
+    /// or 0 to start from the latest block
+    #[prost(uint64, tag = "2")]
+    pub start_height: u64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockEvents {
+    #[prost(uint64, tag = "1")]
+    pub height: u64,
+
+    /// The begin block events, the events of the successful transactions
+    /// and the end block events of the block, in this order
+    #[prost(message, repeated, tag = "2")]
+    pub events: Vec<Event>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Event {
+    #[prost(string, tag = "1")]
+    pub r#type: String,
+
+    #[prost(message, repeated, tag = "2")]
+    pub attributes: Vec<EventAttribute>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EventAttribute {
+    #[prost(string, tag = "1")]
+    pub key: String,
+
+    #[prost(string, tag = "2")]
+    pub value: String,
+
+    #[prost(bool, tag = "3")]
+    pub index: bool,
+}
+
+impl From<Event> for abci::Event {
+    fn from(event: Event) -> Self {
+        Self {
+            kind: event.r#type,
+            attributes: event
+                .attributes
+                .into_iter()
+                .map(|attribute| abci::EventAttribute {
+                    key: attribute.key,
+                    value: attribute.value,
+                    index: attribute.index,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<abci::Event> for Event {
+    fn from(event: abci::Event) -> Self {
+        Self {
+            r#type: event.kind,
+            attributes: event
+                .attributes
+                .into_iter()
+                .map(|attribute| EventAttribute {
+                    key: attribute.key,
+                    value: attribute.value,
+                    index: attribute.index,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A client of the `hermes.events.v1.EventStream` service
+#[derive(Clone, Debug)]
+pub struct EventStreamClient {
+    inner: tonic::client::Grpc<Channel>,
+}
+
+impl EventStreamClient {
+    pub async fn connect(url: Uri) -> Result<Self, TransportError> {
+        let channel = Endpoint::from(url).connect().await?;
+
+        Ok(Self {
+            inner: tonic::client::Grpc::new(channel),
+        })
+    }
+
+    /// Stream the events of each block of a chain from the requested height on,
+    /// as the blocks are committed
+    pub async fn stream_block_events(
+        &mut self,
+        request: StreamBlockEventsRequest,
+    ) -> Result<Response<Streaming<BlockEvents>>, Status> {
+        self.inner
+            .ready()
+            .await
+            .map_err(|e| Status::unknown(format!("service was not ready: {e}")))?;
+
+        self.inner
+            .server_streaming(
+                Request::new(request),
+                PathAndQuery::from_static(STREAM_BLOCK_EVENTS_PATH),
+                ProstCodec::default(),
+            )
+            .await
+    }
+}
@@ -12,26 +12,36 @@ use futures::{
 };
 use tokio::task::JoinHandle;
 use tokio::{runtime::Runtime as TokioRuntime, sync::mpsc};
-use tracing::{debug, error, info, instrument, trace};
+use tracing::{debug, error, info, instrument, trace, warn};
 
+use tendermint::block::Height as BlockHeight;
 use tendermint_rpc::{
-    client::CompatMode, event::Event as RpcEvent, query::Query, SubscriptionClient,
+    event::Event as RpcEvent, query::Query, Client, HttpClient, SubscriptionClient,
     WebSocketClient, WebSocketClientDriver, WebSocketClientUrl,
 };
 
-use ibc_relayer_types::{core::ics24_host::identifier::ChainId, events::IbcEvent};
+use ibc_relayer_types::{
+    core::{ics02_client::height::Height, ics24_host::identifier::ChainId},
+    events::IbcEvent,
+};
 
 use crate::{
+    chain::cosmos::failover::{websocket_rpc_url, SharedEndpointPool},
     chain::tracking::TrackingId,
+    config::CompatMode,
     event::{bus::EventBus, error::*, IbcEventWithHeight},
     telemetry,
     util::{
+        lock::LockExt,
         retry::{retry_with_index, RetryResult},
         stream::try_group_while_timeout,
     },
 };
 
-use super::{EventBatch, EventSourceCmd, Result, SubscriptionStream, TxEventSourceCmd};
+use super::{
+    rpc::{self, HeightRangeInclusive},
+    EventBatch, EventSourceCmd, Result, SubscriptionStream, TxEventSourceCmd,
+};
 
 use self::extract::extract_events;
 
@@ -50,6 +60,9 @@ mod retry_strategy {
     }
 }
 
+/// How often to check whether the endpoint in use was failed over from
+const ENDPOINT_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 /// A batch of events received from a WebSocket endpoint from a
 /// chain at a specific height.
 ///
@@ -76,41 +89,62 @@ pub struct EventSource {
     tx_err: mpsc::UnboundedSender<tendermint_rpc::Error>,
     /// Channel where to receive commands
     rx_cmd: channel::Receiver<EventSourceCmd>,
-    /// Node Address
+    /// The WebSocket endpoints to fail over between
+    ws_endpoints: SharedEndpointPool<WebSocketClientUrl>,
+    /// Address of the node the client is connected to
     ws_url: WebSocketClientUrl,
     /// RPC compatibility mode
-    rpc_compat: CompatMode,
+    compat_mode: CompatMode,
     /// Queries
     event_queries: Vec<Query>,
     /// All subscriptions combined in a single stream
     subscriptions: Box<SubscriptionStream>,
+    /// Height of the last batch of events broadcast, from which to
+    /// pull the events missed while reconnecting
+    last_height: Option<Height>,
+    /// Height up to which the missed events were pulled on the last reconnection,
+    /// the batches up to which are dropped when received from the new subscriptions
+    caught_up_height: Option<Height>,
     /// Tokio runtime
     rt: Arc<TokioRuntime>,
 }
 
 impl EventSource {
-    /// Create an event source, and connect to a node
+    /// Create an event source, and connect to the first of the given endpoints which answers
     #[instrument(
         name = "event_source.create",
         level = "error",
         skip_all,
-        fields(chain = %chain_id, url = %ws_url)
+        fields(chain = %chain_id)
     )]
     pub fn new(
         chain_id: ChainId,
-        ws_url: WebSocketClientUrl,
-        rpc_compat: CompatMode,
+        ws_endpoints: SharedEndpointPool<WebSocketClientUrl>,
+        compat_mode: CompatMode,
         batch_delay: Duration,
         rt: Arc<TokioRuntime>,
     ) -> Result<(Self, TxEventSourceCmd)> {
         let event_bus = EventBus::new();
         let (tx_cmd, rx_cmd) = channel::unbounded();
 
-        let builder = WebSocketClient::builder(ws_url.clone()).compat_mode(rpc_compat);
+        let attempts = ws_endpoints.acquire_read().len();
+        let mut attempt = 1;
 
-        let (client, driver) = rt
-            .block_on(builder.build())
-            .map_err(|_| Error::client_creation_failed(chain_id.clone(), ws_url.clone()))?;
+        let (ws_url, client, driver) = loop {
+            let ws_url = ws_endpoints.acquire_read().current().clone();
+            let builder =
+                WebSocketClient::builder(ws_url.clone()).compat_mode(compat_mode.rpc_compat());
+
+            match rt.block_on(builder.build()) {
+                Ok((client, driver)) => break (ws_url, client, driver),
+                Err(e) if attempt < attempts => {
+                    warn!("failed to connect to WebSocket endpoint {ws_url}: {e}");
+                    ws_endpoints.acquire_write().report_failure(&ws_url);
+                    attempt += 1;
+                }
+                Err(_) => return Err(Error::client_creation_failed(chain_id, ws_url)),
+            }
+        };
 
         let (tx_err, rx_err) = mpsc::unbounded_channel();
         let driver_handle = rt.spawn(run_driver(driver, tx_err.clone()));
@@ -129,9 +163,12 @@ impl EventSource {
             rx_err,
             tx_err,
             rx_cmd,
+            ws_endpoints,
             ws_url,
-            rpc_compat,
+            compat_mode,
             subscriptions: Box::new(futures::stream::empty()),
+            last_height: None,
+            caught_up_height: None,
         };
 
         Ok((source, TxEventSourceCmd(tx_cmd)))
@@ -172,10 +209,13 @@ impl EventSource {
         fields(chain = %self.chain_id)
     )]
     fn try_reconnect(&mut self) -> Result<()> {
+        self.ws_url = self.ws_endpoints.acquire_read().current().clone();
+
         trace!("trying to reconnect to WebSocket endpoint {}", self.ws_url);
 
         // Try to reconnect
-        let builder = WebSocketClient::builder(self.ws_url.clone()).compat_mode(self.rpc_compat);
+        let builder = WebSocketClient::builder(self.ws_url.clone())
+            .compat_mode(self.compat_mode.rpc_compat());
 
         let (mut client, driver) = self.rt.block_on(builder.build()).map_err(|_| {
             Error::client_creation_failed(self.chain_id.clone(), self.ws_url.clone())
@@ -231,12 +271,14 @@ impl EventSource {
             // Try to reconnect
             if let Err(e) = self.try_reconnect() {
                 trace!("error when reconnecting: {}", e);
+                self.report_failure();
                 return RetryResult::Retry(());
             }
 
             // Try to resubscribe
             if let Err(e) = self.try_resubscribe() {
                 trace!("error when resubscribing: {}", e);
+                self.report_failure();
                 return RetryResult::Retry(());
             }
 
@@ -244,10 +286,14 @@ impl EventSource {
         });
 
         match result {
-            Ok(()) => info!(
-                "successfully reconnected to WebSocket endpoint {}",
-                self.ws_url
-            ),
+            Ok(()) => {
+                info!(
+                    "successfully reconnected to WebSocket endpoint {}",
+                    self.ws_url
+                );
+
+                self.catch_up();
+            }
             Err(e) => error!(
                 "failed to reconnect to {} after {} retries",
                 self.ws_url, e.tries
@@ -255,6 +301,86 @@ impl EventSource {
         }
     }
 
+    /// Pull the events emitted between the last batch broadcast and the latest height
+    /// from the `/block_results` endpoint of the node, and broadcast them in order,
+    /// before the events received from the new subscriptions.
+    ///
+    /// If the events cannot be pulled, the error is propagated to the subscribers
+    /// so that the [`Supervisor`] clears the pending packets instead.
+    #[instrument(
+        name = "event_source.catch_up",
+        level = "error",
+        skip_all,
+        fields(chain = %self.chain_id)
+    )]
+    fn catch_up(&mut self) {
+        let Some(last_height) = self.last_height else {
+            return;
+        };
+
+        let from_height = last_height.revision_height() + 1;
+        let rt = self.rt.clone();
+
+        let latest = self.rpc_client().and_then(|client| {
+            let latest_height = rt
+                .block_on(client.abci_info())
+                .map_err(Error::rpc)?
+                .last_block_height;
+
+            Ok((client, latest_height))
+        });
+
+        let (client, latest_height) = match latest {
+            Ok(latest) => latest,
+            Err(e) => return self.catch_up_failed(from_height, e),
+        };
+
+        let Some(heights) = missed_heights(last_height, latest_height) else {
+            return;
+        };
+
+        debug!("pulling the events missed from height {from_height} to {latest_height}");
+
+        for height in heights {
+            let result = rt.block_on(rpc::collect_events(
+                &client,
+                self.compat_mode,
+                &self.chain_id,
+                height,
+            ));
+
+            match result {
+                Ok(Some(batch)) => self.broadcast_batch(batch),
+                Ok(None) => {}
+                Err(e) => return self.catch_up_failed(height.value(), e),
+            }
+        }
+
+        self.caught_up_height = Some(Height::from_tm(latest_height, &self.chain_id));
+    }
+
+    /// A client of the RPC endpoint of the node the WebSocket client is connected to
+    fn rpc_client(&self) -> Result<HttpClient> {
+        let rpc_addr = websocket_rpc_url(&self.ws_url).ok_or_else(|| {
+            Error::collect_events_failed(format!(
+                "no RPC address for WebSocket endpoint {}",
+                self.ws_url
+            ))
+        })?;
+
+        HttpClient::builder(rpc_addr.try_into().map_err(Error::rpc)?)
+            .compat_mode(self.compat_mode.rpc_compat())
+            .build()
+            .map_err(Error::rpc)
+    }
+
+    fn catch_up_failed(&mut self, from_height: u64, e: Error) {
+        let e = Error::catch_up_failed(from_height, e.to_string());
+        error!("{e}");
+
+        self.propagate_error(e);
+    }
+
     /// Event source loop
     #[allow(clippy::while_let_loop)]
     #[instrument(
@@ -306,6 +432,8 @@ impl EventSource {
         // Needed to be able to poll the stream
         pin_mut!(batches);
 
+        let mut endpoint_check = tokio::time::interval(ENDPOINT_CHECK_INTERVAL);
+
         loop {
             // Process any shutdown or subscription commands before we start doing any work.
             if let Next::Abort = self.try_process_cmd() {
@@ -315,6 +443,16 @@ impl EventSource {
             let result = tokio::select! {
                 Some(batch) = batches.next() => batch,
                 Some(e) = self.rx_err.recv() => Err(Error::web_socket_driver(e)),
+                _ = endpoint_check.tick() => {
+                    if self.ws_endpoints.acquire_read().current() != &self.ws_url {
+                        info!("leaving unhealthy WebSocket endpoint {}", self.ws_url);
+
+                        // Reconnect to the WebSocket endpoint failed over to
+                        return Next::Reconnect;
+                    }
+
+                    continue;
+                }
             };
 
             // Before handling the batch, check if there are any pending shutdown or subscribe commands.
@@ -330,8 +468,13 @@ impl EventSource {
             }
 
             match result {
+                Ok(batch) if self.caught_up_height.map_or(false, |h| batch.height <= h) => {
+                    trace!(height = %batch.height, "dropping batch already pulled when catching up");
+                }
                 Ok(batch) => self.broadcast_batch(batch),
                 Err(e) => {
+                    self.report_failure();
+
                     if let ErrorDetail::SubscriptionCancelled(reason) = e.detail() {
                         error!("subscription cancelled, reason: {}", reason);
 
@@ -350,6 +493,14 @@ impl EventSource {
         }
     }
 
+    /// Record that the endpoint the client is connected to failed,
+    /// failing over to another endpoint if there is any.
+    fn report_failure(&self) {
+        self.ws_endpoints
+            .acquire_write()
+            .report_failure(&self.ws_url);
+    }
+
     /// Propagate error to subscribers.
     ///
     /// The main use case for propagating RPC errors is for the [`Supervisor`]
@@ -365,6 +516,10 @@ impl EventSource {
     fn broadcast_batch(&mut self, batch: EventBatch) {
         telemetry!(ws_events, &batch.chain_id, batch.events.len() as u64);
 
+        if self.last_height.map_or(true, |h| batch.height > h) {
+            self.last_height = Some(batch.height);
+        }
+
         trace!(
             chain = %batch.chain_id,
             count = %batch.events.len(),
@@ -452,6 +607,14 @@ fn sort_events(events: &mut [IbcEventWithHeight]) {
     })
 }
 
+/// The heights after the given last height, up to the given latest height,
+/// or `None` if no height was missed
+fn missed_heights(last_height: Height, latest_height: BlockHeight) -> Option<HeightRangeInclusive> {
+    let start = BlockHeight::try_from(last_height.revision_height() + 1).ok()?;
+
+    (start <= latest_height).then(|| HeightRangeInclusive::new(start, latest_height))
+}
+
 async fn run_driver(
     driver: WebSocketClientDriver,
     tx: mpsc::UnboundedSender<tendermint_rpc::Error>,
@@ -468,3 +631,22 @@ pub enum Next {
     Continue,
     Reconnect,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missed_heights_after_last_height() {
+        let last_height = Height::new(0, 10).unwrap();
+
+        let heights: Vec<u64> = missed_heights(last_height, BlockHeight::from(13_u32))
+            .unwrap()
+            .map(|height| height.value())
+            .collect();
+        assert_eq!(heights, vec![11, 12, 13]);
+
+        assert!(missed_heights(last_height, BlockHeight::from(10_u32)).is_none());
+        assert!(missed_heights(last_height, BlockHeight::from(9_u32)).is_none());
+    }
+}
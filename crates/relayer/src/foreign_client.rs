@@ -786,6 +786,42 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         }
     }
 
+    /// Returns how long the client can go without being updated before it expires,
+    /// ie. the time left before its latest consensus state falls outside of its trusting period.
+    /// Returns zero if the client is already expired or frozen, and `None` if the client
+    /// has no trusting period.
+    pub fn time_to_expiry(&self) -> Result<Option<Duration>, ForeignClientError> {
+        let (client_state, _) = self
+            .dst_chain
+            .query_client_state(
+                QueryClientStateRequest {
+                    client_id: self.id().clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| {
+                ForeignClientError::client_query(self.id().clone(), self.dst_chain.id(), e)
+            })?;
+
+        let Some(trusting_period) = client_state.trusting_period() else {
+            return Ok(None);
+        };
+
+        if client_state.is_frozen() {
+            return Ok(Some(Duration::ZERO));
+        }
+
+        let elapsed = match self
+            .check_consensus_state_trusting_period(&client_state, &client_state.latest_height())?
+        {
+            ConsensusStateTrusted::NotTrusted { elapsed, .. } => elapsed,
+            ConsensusStateTrusted::Trusted { elapsed } => elapsed,
+        };
+
+        Ok(Some(trusting_period.saturating_sub(elapsed)))
+    }
+
     pub fn is_expired_or_frozen(&self) -> bool {
         match self.validated_client_state() {
             Ok(_) => false,
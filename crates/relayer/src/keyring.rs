@@ -1,14 +1,19 @@
 pub mod errors;
+pub mod key_utils;
+pub mod ledger;
 pub use any_signing_key_pair::AnySigningKeyPair;
+pub use cosmos_key_pair::CosmosKeyPair;
 pub use ed25519_key_pair::Ed25519KeyPair;
 pub use key_type::KeyType;
+pub use ledger_key_pair::LedgerKeyPair;
 pub use secp256k1_key_pair::Secp256k1KeyPair;
 pub use signing_key_pair::{SigningKeyPair, SigningKeyPairSized};
 
 mod any_signing_key_pair;
+mod cosmos_key_pair;
 mod ed25519_key_pair;
 mod key_type;
-mod key_utils;
+mod ledger_key_pair;
 mod pub_key;
 mod secp256k1_key_pair;
 mod signing_key_pair;
@@ -276,6 +281,17 @@ impl KeyRing<Secp256k1KeyPair> {
     }
 }
 
+impl KeyRing<CosmosKeyPair> {
+    pub fn new_cosmos(
+        store: Store,
+        account_prefix: &str,
+        chain_id: &ChainId,
+        ks_folder: &Option<PathBuf>,
+    ) -> Result<Self, Error> {
+        Self::new(store, account_prefix, chain_id, ks_folder)
+    }
+}
+
 impl KeyRing<Ed25519KeyPair> {
     pub fn new_ed25519(
         store: Store,
@@ -290,7 +306,7 @@ impl KeyRing<Ed25519KeyPair> {
 pub fn list_keys(config: &ChainConfig) -> Result<Vec<(String, AnySigningKeyPair)>, Error> {
     let keys = match config.r#type {
         ChainType::CosmosSdk => {
-            let keyring = KeyRing::new_secp256k1(
+            let keyring = KeyRing::new_cosmos(
                 Store::Test,
                 &config.account_prefix,
                 &config.id,
@@ -0,0 +1,114 @@
+use core::any::Any;
+
+use hdpath::StandardHDPath;
+use ibc_proto::cosmos::tx::signing::v1beta1::SignMode;
+use secp256k1::PublicKey;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    errors::Error, AnySigningKeyPair, KeyFile, KeyType, LedgerKeyPair, Secp256k1KeyPair,
+    SigningKeyPair,
+};
+use crate::config::AddressType;
+
+/// The key signing the transactions submitted to a Cosmos SDK chain,
+/// either held in the keyring or on a Ledger device.
+// Since this uses Serde's untagged enums, the serialized formats of
+// both kinds of keys must be incompatible with each other.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum CosmosKeyPair {
+    Secp256k1(Secp256k1KeyPair),
+    Ledger(LedgerKeyPair),
+}
+
+impl CosmosKeyPair {
+    pub fn public_key(&self) -> &PublicKey {
+        match self {
+            Self::Secp256k1(key_pair) => &key_pair.public_key,
+            Self::Ledger(key_pair) => &key_pair.public_key,
+        }
+    }
+
+    /// The mode in which the transactions signed by this key must be encoded for signing
+    pub fn sign_mode(&self) -> SignMode {
+        match self {
+            Self::Secp256k1(_) => SignMode::Direct,
+            Self::Ledger(_) => SignMode::LegacyAminoJson,
+        }
+    }
+
+    /// Whether the key is held by a Ledger device, which must approve every signature
+    pub fn is_ledger(&self) -> bool {
+        matches!(self, Self::Ledger(_))
+    }
+}
+
+impl SigningKeyPair for CosmosKeyPair {
+    const KEY_TYPE: KeyType = KeyType::Secp256k1;
+
+    fn from_key_file(key_file: KeyFile, hd_path: &StandardHDPath) -> Result<Self, Error> {
+        Secp256k1KeyPair::from_key_file(key_file, hd_path).map(Self::Secp256k1)
+    }
+
+    fn from_mnemonic(
+        mnemonic: &str,
+        hd_path: &StandardHDPath,
+        address_type: &AddressType,
+        account_prefix: &str,
+    ) -> Result<Self, Error> {
+        Secp256k1KeyPair::from_mnemonic(mnemonic, hd_path, address_type, account_prefix)
+            .map(Self::Secp256k1)
+    }
+
+    fn account(&self) -> String {
+        match self {
+            Self::Secp256k1(key_pair) => key_pair.account(),
+            Self::Ledger(key_pair) => key_pair.account(),
+        }
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        match self {
+            Self::Secp256k1(key_pair) => key_pair.sign(message),
+            Self::Ledger(key_pair) => key_pair.sign(message),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl From<Secp256k1KeyPair> for CosmosKeyPair {
+    fn from(key_pair: Secp256k1KeyPair) -> Self {
+        Self::Secp256k1(key_pair)
+    }
+}
+
+impl From<LedgerKeyPair> for CosmosKeyPair {
+    fn from(key_pair: LedgerKeyPair) -> Self {
+        Self::Ledger(key_pair)
+    }
+}
+
+impl From<CosmosKeyPair> for AnySigningKeyPair {
+    fn from(key_pair: CosmosKeyPair) -> Self {
+        match key_pair {
+            CosmosKeyPair::Secp256k1(key_pair) => Self::Secp256k1(key_pair),
+            CosmosKeyPair::Ledger(key_pair) => Self::Ledger(key_pair),
+        }
+    }
+}
+
+impl TryFrom<AnySigningKeyPair> for CosmosKeyPair {
+    type Error = AnySigningKeyPair;
+
+    fn try_from(key_pair: AnySigningKeyPair) -> Result<Self, Self::Error> {
+        match key_pair {
+            AnySigningKeyPair::Secp256k1(key_pair) => Ok(Self::Secp256k1(key_pair)),
+            AnySigningKeyPair::Ledger(key_pair) => Ok(Self::Ledger(key_pair)),
+            key_pair => Err(key_pair),
+        }
+    }
+}
@@ -147,6 +147,34 @@ define_error! {
           }
           |e| {
               format!("Unsupported address type {} for key type {}", e.address_type, e.key_type)
-          }
+          },
+
+        LedgerKeyImport
+            |_| { "Ledger keys cannot be restored from a key file or a mnemonic, add them with `keys add --ledger` instead" },
+
+        LedgerDeviceNotFound
+            |_| { "no Ledger device found, make sure it is connected and unlocked" },
+
+        LedgerTransport
+            { description: String }
+            [ TraceError<IoError> ]
+            |e| {
+                format!("I/O error while exchanging with the Ledger device: {}",
+                    e.description)
+            },
+
+        LedgerStatus
+            { status: u16 }
+            |e| {
+                format!("the Ledger device returned status 0x{:04x}: {}",
+                    e.status, super::ledger::status_description(e.status))
+            },
+
+        LedgerInvalidResponse
+            { reason: String }
+            |e| {
+                format!("invalid response from the Ledger device: {}",
+                    e.reason)
+            },
     }
 }
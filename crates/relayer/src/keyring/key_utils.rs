@@ -1,4 +1,5 @@
 use bech32::{FromBase32, ToBase32};
+use bip39::{Language, Mnemonic, MnemonicType};
 use tiny_keccak::{Hasher, Keccak};
 
 use super::errors::Error;
@@ -13,6 +14,11 @@ pub fn encode_bech32(account_prefix: &str, address: &[u8]) -> Result<String, Err
         .map_err(Error::bech32)
 }
 
+/// Generate a new random 24-word BIP-39 mnemonic, in English
+pub fn generate_mnemonic() -> String {
+    Mnemonic::new(MnemonicType::Words24, Language::English).into_phrase()
+}
+
 pub fn keccak256_hash(bytes: &[u8]) -> [u8; 32] {
     let mut hasher = Keccak::v256();
     hasher.update(bytes);
@@ -0,0 +1,322 @@
+//! Client of the Cosmos application of Ledger hardware wallets.
+//!
+//! The application derives secp256k1 keys on the device and signs transactions
+//! in the amino-JSON sign mode, after having displayed them to the user.
+//! The APDUs it accepts are documented at
+//! <https://github.com/cosmos/ledger-cosmos/blob/main/docs/APDUSPEC.md>.
+
+pub mod transport;
+
+use hdpath::StandardHDPath;
+use secp256k1::ecdsa::Signature;
+use secp256k1::PublicKey;
+use serde::{Deserialize, Serialize};
+
+use self::transport::{ApduAnswer, ApduCommand, ApduTransport, HidTransport, SpeculosTransport};
+use super::errors::Error;
+
+/// The class of the APDUs of the Cosmos application
+const CLA: u8 = 0x55;
+
+const INS_SIGN_SECP256K1: u8 = 0x02;
+const INS_GET_ADDR_SECP256K1: u8 = 0x04;
+
+/// Values of `P1` delimiting the chunks of a message to sign
+const P1_INIT: u8 = 0x00;
+const P1_ADD: u8 = 0x01;
+const P1_LAST: u8 = 0x02;
+
+/// The `P2` of a sign command for the amino-JSON sign mode
+const P2_SIGN_MODE_AMINO_JSON: u8 = 0x00;
+
+/// The largest chunk of a message to sign sent in a single APDU
+const CHUNK_SIZE: usize = 250;
+
+const HARDENED: u32 = 0x8000_0000;
+
+/// Where to find the Ledger device holding a key
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "transport", rename_all = "lowercase")]
+pub enum LedgerDevice {
+    /// A device connected over USB
+    #[default]
+    Hid,
+
+    /// A Speculos emulator listening for APDUs on the given TCP address
+    Speculos { address: String },
+}
+
+impl LedgerDevice {
+    pub fn open(&self) -> Result<LedgerCosmosApp<Box<dyn ApduTransport>>, Error> {
+        let transport: Box<dyn ApduTransport> = match self {
+            Self::Hid => Box::new(HidTransport::open()?),
+            Self::Speculos { address } => Box::new(SpeculosTransport::connect(address)?),
+        };
+
+        Ok(LedgerCosmosApp::new(transport))
+    }
+}
+
+impl<T: ApduTransport + ?Sized> ApduTransport for Box<T> {
+    fn exchange(&mut self, command: &ApduCommand) -> Result<ApduAnswer, Error> {
+        (**self).exchange(command)
+    }
+}
+
+/// The Cosmos application of a Ledger device, reached over the given transport
+pub struct LedgerCosmosApp<T> {
+    transport: T,
+}
+
+impl<T: ApduTransport> LedgerCosmosApp<T> {
+    pub fn new(transport: T) -> Self {
+        Self { transport }
+    }
+
+    /// Get the public key and the address of the account at the given
+    /// derivation path, optionally asking the user to confirm the address
+    /// on the device.
+    pub fn get_address(
+        &mut self,
+        hd_path: &StandardHDPath,
+        account_prefix: &str,
+        show_on_device: bool,
+    ) -> Result<(PublicKey, String), Error> {
+        let mut data = vec![account_prefix.len() as u8];
+        data.extend_from_slice(account_prefix.as_bytes());
+        data.extend_from_slice(&serialize_path(hd_path));
+
+        let answer = self.exchange(INS_GET_ADDR_SECP256K1, u8::from(show_on_device), 0, data)?;
+
+        if answer.len() < 33 {
+            return Err(Error::ledger_invalid_response(format!(
+                "address answer of {} bytes is too short",
+                answer.len()
+            )));
+        }
+
+        let public_key = PublicKey::from_slice(&answer[..33])
+            .map_err(|e| Error::ledger_invalid_response(format!("invalid public key: {e}")))?;
+
+        let address = String::from_utf8(answer[33..].to_vec())
+            .map_err(|e| Error::ledger_invalid_response(format!("invalid address: {e}")))?;
+
+        Ok((public_key, address))
+    }
+
+    /// Sign the given amino-JSON sign bytes with the key at the given
+    /// derivation path, once the user approved them on the device,
+    /// returning the signature in its compact 64-byte form.
+    pub fn sign(&mut self, hd_path: &StandardHDPath, message: &[u8]) -> Result<Vec<u8>, Error> {
+        self.exchange(
+            INS_SIGN_SECP256K1,
+            P1_INIT,
+            P2_SIGN_MODE_AMINO_JSON,
+            serialize_path(hd_path).to_vec(),
+        )?;
+
+        let chunks: Vec<_> = message.chunks(CHUNK_SIZE).collect();
+        let mut answer = Vec::new();
+
+        for (i, chunk) in chunks.iter().enumerate() {
+            let p1 = if i + 1 == chunks.len() {
+                P1_LAST
+            } else {
+                P1_ADD
+            };
+
+            answer = self.exchange(
+                INS_SIGN_SECP256K1,
+                p1,
+                P2_SIGN_MODE_AMINO_JSON,
+                chunk.to_vec(),
+            )?;
+        }
+
+        let signature = Signature::from_der(&answer)
+            .map_err(|e| Error::ledger_invalid_response(format!("invalid signature: {e}")))?;
+
+        Ok(signature.serialize_compact().to_vec())
+    }
+
+    fn exchange(&mut self, ins: u8, p1: u8, p2: u8, data: Vec<u8>) -> Result<Vec<u8>, Error> {
+        let command = ApduCommand {
+            cla: CLA,
+            ins,
+            p1,
+            p2,
+            data,
+        };
+
+        let answer = self.transport.exchange(&command)?;
+
+        if answer.is_ok() {
+            Ok(answer.data)
+        } else {
+            Err(Error::ledger_status(answer.status))
+        }
+    }
+}
+
+/// Serialize a derivation path the way the Cosmos application expects it,
+/// as five little-endian `u32`, the first three of which are hardened.
+fn serialize_path(hd_path: &StandardHDPath) -> [u8; 20] {
+    let components = [
+        hd_path.purpose().as_value().as_number() | HARDENED,
+        hd_path.coin_type() | HARDENED,
+        hd_path.account() | HARDENED,
+        hd_path.change(),
+        hd_path.index(),
+    ];
+
+    let mut bytes = [0u8; 20];
+    for (i, component) in components.iter().enumerate() {
+        bytes[i * 4..(i + 1) * 4].copy_from_slice(&component.to_le_bytes());
+    }
+    bytes
+}
+
+/// A human-readable description of the status words of the Cosmos application
+pub fn status_description(status: u16) -> &'static str {
+    match status {
+        0x9000 => "success",
+        0x6400 => "execution error",
+        0x6700 => "wrong APDU length",
+        0x6982 => "the device is locked or the message to sign is empty",
+        0x6983 => "the message to sign is too large for the device",
+        0x6984 => "the device could not parse the message to sign",
+        0x6986 => "the transaction was rejected on the device",
+        0x6b00 => "invalid APDU parameters",
+        0x6d00 => "instruction not supported, make sure the Cosmos application is up to date",
+        0x6e00 | 0x6e01 | 0x6511 => "the Cosmos application is not open on the device",
+        0x5515 => "the device is locked",
+        _ => "unknown error",
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    use core::str::FromStr;
+
+    use secp256k1::{Message, Secp256k1, SecretKey};
+    use sha2::{Digest, Sha256};
+
+    use crate::keyring::key_utils::encode_bech32;
+    use crate::keyring::secp256k1_key_pair::{get_address, Secp256k1AddressType};
+
+    /// An in-memory stand-in for the Cosmos application of a Ledger device,
+    /// which signs everything it is asked to with a fixed key
+    pub struct MockCosmosApp {
+        secret_key: SecretKey,
+        message: Vec<u8>,
+        pub reject: bool,
+    }
+
+    impl Default for MockCosmosApp {
+        fn default() -> Self {
+            Self {
+                secret_key: SecretKey::from_slice(&[7; 32]).unwrap(),
+                message: Vec::new(),
+                reject: false,
+            }
+        }
+    }
+
+    impl MockCosmosApp {
+        pub fn public_key(&self) -> PublicKey {
+            PublicKey::from_secret_key(&Secp256k1::new(), &self.secret_key)
+        }
+
+        fn answer(&mut self, command: &ApduCommand) -> Result<Vec<u8>, u16> {
+            match (command.cla, command.ins, command.p1) {
+                (CLA, INS_GET_ADDR_SECP256K1, _) => {
+                    let hrp_len = command.data[0] as usize;
+                    let hrp = core::str::from_utf8(&command.data[1..1 + hrp_len]).unwrap();
+                    let public_key = self.public_key();
+                    let address = get_address(&public_key, Secp256k1AddressType::Cosmos);
+
+                    let mut answer = public_key.serialize().to_vec();
+                    answer.extend_from_slice(encode_bech32(hrp, &address).unwrap().as_bytes());
+                    Ok(answer)
+                }
+                (CLA, INS_SIGN_SECP256K1, P1_INIT) => {
+                    self.message.clear();
+                    Ok(Vec::new())
+                }
+                (CLA, INS_SIGN_SECP256K1, P1_ADD) => {
+                    self.message.extend_from_slice(&command.data);
+                    Ok(Vec::new())
+                }
+                (CLA, INS_SIGN_SECP256K1, P1_LAST) if self.reject => Err(0x6986),
+                (CLA, INS_SIGN_SECP256K1, P1_LAST) => {
+                    self.message.extend_from_slice(&command.data);
+
+                    let digest = Sha256::digest(&self.message);
+                    let message = Message::from_slice(&digest).unwrap();
+                    let signature =
+                        Secp256k1::signing_only().sign_ecdsa(&message, &self.secret_key);
+                    Ok(signature.serialize_der().to_vec())
+                }
+                (CLA, _, _) => Err(0x6d00),
+                _ => Err(0x6e00),
+            }
+        }
+    }
+
+    impl ApduTransport for MockCosmosApp {
+        fn exchange(&mut self, command: &ApduCommand) -> Result<ApduAnswer, Error> {
+            Ok(match self.answer(command) {
+                Ok(data) => ApduAnswer {
+                    data,
+                    status: ApduAnswer::STATUS_OK,
+                },
+                Err(status) => ApduAnswer {
+                    data: Vec::new(),
+                    status,
+                },
+            })
+        }
+    }
+
+    #[test]
+    fn serializes_hardened_path() {
+        let path = StandardHDPath::from_str("m/44'/118'/0'/0/3").unwrap();
+
+        assert_eq!(
+            serialize_path(&path),
+            [
+                0x2c, 0x00, 0x00, 0x80, 0x76, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+                0x00, 0x00, 0x03, 0x00, 0x00, 0x00
+            ]
+        );
+    }
+
+    #[test]
+    fn gets_address_and_signs_in_chunks() {
+        let path = StandardHDPath::from_str("m/44'/118'/0'/0/0").unwrap();
+        let mut app = LedgerCosmosApp::new(MockCosmosApp::default());
+
+        let (public_key, address) = app.get_address(&path, "cosmos", false).unwrap();
+        assert_eq!(public_key, app.transport.public_key());
+        assert!(address.starts_with("cosmos1"));
+
+        let message = vec![b'x'; 3 * CHUNK_SIZE + 1];
+        let signature = app.sign(&path, &message).unwrap();
+        assert_eq!(app.transport.message, message);
+
+        let digest = Sha256::digest(&message);
+        Secp256k1::verification_only()
+            .verify_ecdsa(
+                &Message::from_slice(&digest).unwrap(),
+                &Signature::from_compact(&signature).unwrap(),
+                &public_key,
+            )
+            .unwrap();
+
+        app.transport.reject = true;
+        let err = app.sign(&path, &message).unwrap_err();
+        assert!(err.to_string().contains("rejected"));
+    }
+}
@@ -0,0 +1,299 @@
+//! Transports exchanging APDUs with a Ledger device or with an emulator.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+
+use crate::keyring::errors::Error;
+
+/// The vendor id of the USB devices made by Ledger
+pub const LEDGER_VENDOR_ID: u16 = 0x2c97;
+
+/// The HID channel on which the APDUs are exchanged
+const HID_CHANNEL: u16 = 0x0101;
+
+/// The tag of the HID packets carrying APDUs
+const HID_TAG_APDU: u8 = 0x05;
+
+/// The size of an HID packet, without the report id
+const HID_PACKET_SIZE: usize = 64;
+
+/// The first bytes of the report descriptor of the HID interface
+/// of a Ledger device on which the APDUs are exchanged, i.e.
+/// the `0xffa0` vendor-defined usage page.
+const APDU_USAGE_PAGE: [u8; 3] = [0x06, 0xa0, 0xff];
+
+/// A command sent to an application of a Ledger device
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ApduCommand {
+    pub cla: u8,
+    pub ins: u8,
+    pub p1: u8,
+    pub p2: u8,
+    pub data: Vec<u8>,
+}
+
+impl ApduCommand {
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(5 + self.data.len());
+        bytes.extend_from_slice(&[self.cla, self.ins, self.p1, self.p2, self.data.len() as u8]);
+        bytes.extend_from_slice(&self.data);
+        bytes
+    }
+}
+
+/// The answer of an application of a Ledger device to a command
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ApduAnswer {
+    pub data: Vec<u8>,
+    pub status: u16,
+}
+
+impl ApduAnswer {
+    /// The status of a command that succeeded
+    pub const STATUS_OK: u16 = 0x9000;
+
+    pub fn from_bytes(mut bytes: Vec<u8>) -> Result<Self, Error> {
+        if bytes.len() < 2 {
+            return Err(Error::ledger_invalid_response(format!(
+                "answer of {} bytes is missing its status word",
+                bytes.len()
+            )));
+        }
+
+        let status = u16::from_be_bytes([bytes[bytes.len() - 2], bytes[bytes.len() - 1]]);
+        bytes.truncate(bytes.len() - 2);
+
+        Ok(Self {
+            data: bytes,
+            status,
+        })
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.status == Self::STATUS_OK
+    }
+}
+
+/// A channel over which APDUs are exchanged with a Ledger device
+pub trait ApduTransport {
+    fn exchange(&mut self, command: &ApduCommand) -> Result<ApduAnswer, Error>;
+}
+
+/// Exchanges APDUs with a Ledger device connected over USB,
+/// through its `/dev/hidrawN` device node on Linux.
+#[derive(Debug)]
+pub struct HidTransport {
+    device: File,
+}
+
+impl HidTransport {
+    /// Open the first Ledger device found among the HID devices of the system
+    pub fn open() -> Result<Self, Error> {
+        let path = find_hidraw_device()?.ok_or_else(Error::ledger_device_not_found)?;
+        Self::open_path(&path)
+    }
+
+    pub fn open_path(path: &Path) -> Result<Self, Error> {
+        let device = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(|e| {
+                Error::ledger_transport(format!("failed to open {}", path.display()), e)
+            })?;
+
+        Ok(Self { device })
+    }
+}
+
+impl ApduTransport for HidTransport {
+    fn exchange(&mut self, command: &ApduCommand) -> Result<ApduAnswer, Error> {
+        for packet in wrap_hid_packets(&command.serialize()) {
+            // The first byte written to a hidraw device is the report id
+            let mut report = [0u8; HID_PACKET_SIZE + 1];
+            report[1..].copy_from_slice(&packet);
+
+            self.device
+                .write_all(&report)
+                .map_err(|e| Error::ledger_transport("failed to write HID packet".into(), e))?;
+        }
+
+        let mut unwrapper = HidUnwrapper::default();
+        loop {
+            let mut packet = [0u8; HID_PACKET_SIZE];
+            self.device
+                .read_exact(&mut packet)
+                .map_err(|e| Error::ledger_transport("failed to read HID packet".into(), e))?;
+
+            if let Some(answer) = unwrapper.push(&packet)? {
+                return ApduAnswer::from_bytes(answer);
+            }
+        }
+    }
+}
+
+/// Exchanges APDUs with a Speculos emulator, over its APDU TCP port.
+///
+/// Each APDU is sent prefixed with its length as a big-endian `u32`,
+/// and each answer is received prefixed with the length of its data,
+/// which does not account for the two bytes of the status word.
+#[derive(Debug)]
+pub struct SpeculosTransport {
+    stream: TcpStream,
+}
+
+impl SpeculosTransport {
+    pub fn connect(address: &str) -> Result<Self, Error> {
+        let stream = TcpStream::connect(address).map_err(|e| {
+            Error::ledger_transport(format!("failed to connect to emulator at {address}"), e)
+        })?;
+
+        Ok(Self { stream })
+    }
+}
+
+impl ApduTransport for SpeculosTransport {
+    fn exchange(&mut self, command: &ApduCommand) -> Result<ApduAnswer, Error> {
+        let apdu = command.serialize();
+
+        let mut request = (apdu.len() as u32).to_be_bytes().to_vec();
+        request.extend_from_slice(&apdu);
+
+        self.stream
+            .write_all(&request)
+            .map_err(|e| Error::ledger_transport("failed to send APDU to emulator".into(), e))?;
+
+        let mut len = [0u8; 4];
+        self.stream
+            .read_exact(&mut len)
+            .map_err(|e| Error::ledger_transport("failed to read answer length".into(), e))?;
+
+        let mut answer = vec![0u8; u32::from_be_bytes(len) as usize + 2];
+        self.stream
+            .read_exact(&mut answer)
+            .map_err(|e| Error::ledger_transport("failed to read answer".into(), e))?;
+
+        ApduAnswer::from_bytes(answer)
+    }
+}
+
+/// Split an APDU into the HID packets carrying it to a Ledger device
+pub fn wrap_hid_packets(apdu: &[u8]) -> Vec<[u8; HID_PACKET_SIZE]> {
+    // The first packet carries the length of the whole APDU
+    let mut payload = (apdu.len() as u16).to_be_bytes().to_vec();
+    payload.extend_from_slice(apdu);
+
+    payload
+        .chunks(HID_PACKET_SIZE - 5)
+        .enumerate()
+        .map(|(sequence, chunk)| {
+            let mut packet = [0u8; HID_PACKET_SIZE];
+            packet[..2].copy_from_slice(&HID_CHANNEL.to_be_bytes());
+            packet[2] = HID_TAG_APDU;
+            packet[3..5].copy_from_slice(&(sequence as u16).to_be_bytes());
+            packet[5..5 + chunk.len()].copy_from_slice(chunk);
+            packet
+        })
+        .collect()
+}
+
+/// Reassembles an answer from the HID packets received from a Ledger device
+#[derive(Debug, Default)]
+pub struct HidUnwrapper {
+    expected_len: usize,
+    sequence: u16,
+    answer: Vec<u8>,
+}
+
+impl HidUnwrapper {
+    /// Add a packet to the answer, returning the answer once complete
+    pub fn push(&mut self, packet: &[u8; HID_PACKET_SIZE]) -> Result<Option<Vec<u8>>, Error> {
+        let channel = u16::from_be_bytes([packet[0], packet[1]]);
+        let sequence = u16::from_be_bytes([packet[3], packet[4]]);
+
+        if channel != HID_CHANNEL || packet[2] != HID_TAG_APDU || sequence != self.sequence {
+            return Err(Error::ledger_invalid_response(format!(
+                "unexpected HID packet header {:02x?}",
+                &packet[..5]
+            )));
+        }
+
+        let mut data = &packet[5..];
+        if sequence == 0 {
+            self.expected_len = u16::from_be_bytes([data[0], data[1]]) as usize;
+            data = &data[2..];
+        }
+
+        let remaining = self.expected_len - self.answer.len();
+        self.answer
+            .extend_from_slice(&data[..remaining.min(data.len())]);
+        self.sequence += 1;
+
+        if self.answer.len() == self.expected_len {
+            Ok(Some(core::mem::take(&mut self.answer)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Look for the hidraw device node of the APDU interface of a Ledger device
+fn find_hidraw_device() -> Result<Option<PathBuf>, Error> {
+    let class = Path::new("/sys/class/hidraw");
+    if !class.exists() {
+        return Ok(None);
+    }
+
+    let entries = fs::read_dir(class)
+        .map_err(|e| Error::ledger_transport("failed to list HID devices".into(), e))?;
+
+    let vendor = format!(":{:08X}:", LEDGER_VENDOR_ID);
+
+    for entry in entries.flatten() {
+        let device = entry.path().join("device");
+
+        let is_ledger = fs::read_to_string(device.join("uevent"))
+            .map(|uevent| {
+                uevent
+                    .lines()
+                    .any(|line| line.starts_with("HID_ID=") && line.contains(&vendor))
+            })
+            .unwrap_or(false);
+
+        let is_apdu_interface = fs::read(device.join("report_descriptor"))
+            .map(|descriptor| descriptor.starts_with(&APDU_USAGE_PAGE))
+            .unwrap_or(false);
+
+        if is_ledger && is_apdu_interface {
+            return Ok(Some(Path::new("/dev").join(entry.file_name())));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hid_packets_round_trip() {
+        let apdu: Vec<u8> = (0..=200).collect();
+
+        let packets = wrap_hid_packets(&apdu);
+        assert_eq!(packets.len(), 4);
+        assert_eq!(&packets[0][..7], &[0x01, 0x01, 0x05, 0x00, 0x00, 0x00, 201]);
+        assert_eq!(&packets[3][..5], &[0x01, 0x01, 0x05, 0x00, 0x03]);
+
+        let mut unwrapper = HidUnwrapper::default();
+        let answers: Vec<_> = packets
+            .iter()
+            .map(|packet| unwrapper.push(packet).unwrap())
+            .collect();
+
+        assert!(answers[..3].iter().all(Option::is_none));
+        assert_eq!(answers[3].as_deref(), Some(apdu.as_slice()));
+    }
+}
@@ -0,0 +1,192 @@
+use core::any::Any;
+use core::str::FromStr;
+
+use hdpath::StandardHDPath;
+use secp256k1::PublicKey;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    errors::Error,
+    ledger::LedgerDevice,
+    secp256k1_key_pair::{get_address, Secp256k1AddressType},
+    KeyFile, KeyType, SigningKeyPair,
+};
+use crate::config::AddressType;
+
+/// A secp256k1 key held by the Cosmos application of a Ledger device.
+///
+/// Only the public part of the key is stored in the keyring, every signature
+/// is produced by the device after the user approved the transaction on it.
+/// The device only signs transactions in the amino-JSON sign mode, hence
+/// the message given to [`SigningKeyPair::sign`] must be the amino-JSON
+/// sign bytes of the transaction.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LedgerKeyPair {
+    pub public_key: PublicKey,
+    address: [u8; 20],
+    account: String,
+    hd_path: String,
+    device: LedgerDevice,
+}
+
+impl LedgerKeyPair {
+    /// Fetch the key at the given derivation path from the device,
+    /// optionally asking the user to confirm its address on the device.
+    pub fn from_device(
+        device: LedgerDevice,
+        hd_path: &StandardHDPath,
+        address_type: &AddressType,
+        account_prefix: &str,
+        show_on_device: bool,
+    ) -> Result<Self, Error> {
+        // The Cosmos application only derives Cosmos addresses
+        if address_type != &AddressType::Cosmos {
+            return Err(Error::unsupported_address_type(
+                address_type.clone(),
+                Self::KEY_TYPE,
+            ));
+        }
+
+        let (public_key, account) =
+            device
+                .open()?
+                .get_address(hd_path, account_prefix, show_on_device)?;
+
+        let address = get_address(&public_key, Secp256k1AddressType::Cosmos);
+
+        Ok(Self {
+            public_key,
+            address,
+            account,
+            hd_path: hd_path.to_string(),
+            device,
+        })
+    }
+
+    pub fn device(&self) -> &LedgerDevice {
+        &self.device
+    }
+
+    fn hd_path(&self) -> Result<StandardHDPath, Error> {
+        StandardHDPath::from_str(&self.hd_path)
+            .map_err(|_| Error::invalid_hd_path(self.hd_path.clone()))
+    }
+}
+
+impl SigningKeyPair for LedgerKeyPair {
+    const KEY_TYPE: KeyType = KeyType::Secp256k1;
+
+    fn from_key_file(_key_file: KeyFile, _hd_path: &StandardHDPath) -> Result<Self, Error> {
+        Err(Error::ledger_key_import())
+    }
+
+    fn from_mnemonic(
+        _mnemonic: &str,
+        _hd_path: &StandardHDPath,
+        _address_type: &AddressType,
+        _account_prefix: &str,
+    ) -> Result<Self, Error> {
+        Err(Error::ledger_key_import())
+    }
+
+    fn account(&self) -> String {
+        self.account.to_owned()
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        self.device.open()?.sign(&self.hd_path()?, message)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use crate::keyring::ledger::tests::MockCosmosApp;
+    use crate::keyring::ledger::transport::{ApduCommand, ApduTransport};
+    use crate::keyring::CosmosKeyPair;
+
+    /// Serve the mock Cosmos application over the APDU port protocol of Speculos
+    fn spawn_emulator() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        thread::spawn(move || {
+            let mut app = MockCosmosApp::default();
+
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+
+                let mut len = [0u8; 4];
+                while stream.read_exact(&mut len).is_ok() {
+                    let mut apdu = vec![0u8; u32::from_be_bytes(len) as usize];
+                    stream.read_exact(&mut apdu).unwrap();
+
+                    let command = ApduCommand {
+                        cla: apdu[0],
+                        ins: apdu[1],
+                        p1: apdu[2],
+                        p2: apdu[3],
+                        data: apdu[5..].to_vec(),
+                    };
+                    let answer = app.exchange(&command).unwrap();
+
+                    let mut reply = (answer.data.len() as u32).to_be_bytes().to_vec();
+                    reply.extend_from_slice(&answer.data);
+                    reply.extend_from_slice(&answer.status.to_be_bytes());
+                    stream.write_all(&reply).unwrap();
+                }
+            }
+        });
+
+        address
+    }
+
+    #[test]
+    fn adds_and_signs_with_emulated_device() {
+        let device = LedgerDevice::Speculos {
+            address: spawn_emulator(),
+        };
+        let hd_path = StandardHDPath::from_str("m/44'/118'/0'/0/0").unwrap();
+
+        let key_pair =
+            LedgerKeyPair::from_device(device, &hd_path, &AddressType::Cosmos, "cosmos", false)
+                .unwrap();
+        assert_eq!(key_pair.public_key, MockCosmosApp::default().public_key());
+        assert!(key_pair.account().starts_with("cosmos1"));
+
+        let signature = key_pair.sign(br#"{"msgs":[]}"#).unwrap();
+        assert_eq!(signature.len(), 64);
+
+        // The key files of Ledger keys are told apart from those of software keys
+        let key_file = serde_json::to_string(&key_pair).unwrap();
+        let stored: CosmosKeyPair = serde_json::from_str(&key_file).unwrap();
+        assert!(matches!(stored, CosmosKeyPair::Ledger(_)));
+        assert_eq!(stored.account(), key_pair.account());
+    }
+
+    #[test]
+    fn rejects_ethermint_addresses() {
+        let hd_path = StandardHDPath::from_str("m/44'/60'/0'/0/0").unwrap();
+        let address_type = AddressType::Ethermint {
+            pk_type: "/ethermint.crypto.v1.ethsecp256k1.PubKey".to_string(),
+        };
+
+        assert!(LedgerKeyPair::from_device(
+            LedgerDevice::Hid,
+            &hd_path,
+            &address_type,
+            "evmos",
+            false
+        )
+        .is_err());
+    }
+}
@@ -15,8 +15,9 @@ pub mod cli;
 pub mod error;
 pub mod operational_data;
 pub mod packet_events;
+pub mod scheduling;
 
-mod pending;
+pub mod pending;
 mod relay_path;
 mod relay_sender;
 mod relay_summary;
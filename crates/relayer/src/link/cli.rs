@@ -18,7 +18,8 @@ use crate::event::IbcEventWithHeight;
 use crate::link::error::LinkError;
 use crate::link::operational_data::{OperationalData, TrackedEvents};
 use crate::link::packet_events::{
-    query_packet_events_with, query_send_packet_events, query_write_ack_events,
+    query_packet_events_between, query_packet_events_with, query_send_packet_events,
+    query_write_ack_events,
 };
 use crate::link::relay_path::RelayPath;
 use crate::link::relay_sender::SyncSender;
@@ -168,6 +169,59 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
         )
     }
 
+    /// Implements the `clear packets --from-height --to-height` CLI.
+    ///
+    /// Relays again the packets sent and acknowledged on the source chain between
+    /// the given heights, by feeding the events of these heights to the relay path
+    /// as if they had just been received from the event source. The packets which
+    /// have already been relayed are skipped by the relay path.
+    pub fn relay_packet_events_between(
+        &self,
+        from_height: Height,
+        to_height: Height,
+    ) -> Result<Vec<IbcEvent>, LinkError> {
+        let _span = error_span!(
+            "relay_packet_events_between",
+            src_chain = %self.a_to_b.src_chain().id(),
+            src_port = %self.a_to_b.src_port_id(),
+            src_channel = %self.a_to_b.src_channel_id(),
+            dst_chain = %self.a_to_b.dst_chain().id(),
+            %from_height,
+            %to_height,
+        )
+        .entered();
+
+        let batches = query_packet_events_between(
+            self.a_to_b.src_chain(),
+            &self.a_to_b.path_id,
+            from_height,
+            to_height,
+        );
+
+        let mut results = vec![];
+
+        for batch in batches {
+            self.a_to_b
+                .update_schedule(batch.map_err(LinkError::relayer)?)?;
+
+            // In case of zero connection delay, the op. data will already be ready
+            let (src_ods, dst_ods) = self.a_to_b.try_fetch_scheduled_operational_data()?;
+            self.a_to_b
+                .relay_and_accumulate_results(Vec::from(src_ods), &mut results)?;
+            self.a_to_b
+                .relay_and_accumulate_results(Vec::from(dst_ods), &mut results)?;
+        }
+
+        // In case of non-zero connection delay, we block here waiting for all op.data
+        // until the connection delay elapses
+        while let Some(odata) = self.a_to_b.fetch_scheduled_operational_data()? {
+            self.a_to_b
+                .relay_and_accumulate_results(vec![odata], &mut results)?;
+        }
+
+        Ok(results)
+    }
+
     fn relay_packet_messages<QueryFn>(
         &self,
         sequences: Vec<Sequence>,
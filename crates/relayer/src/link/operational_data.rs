@@ -213,7 +213,7 @@ impl OperationalData {
             .chain(self.batch.iter().map(|gm| gm.msg.clone()))
             .collect();
 
-        let tm = TrackedMsgs::new(msgs, self.tracking_id);
+        let tm = TrackedMsgs::new(msgs, self.tracking_id).with_priority(relay_path.priority());
 
         info!("assembled batch of {} message(s)", tm.messages().len());
 
@@ -8,8 +8,12 @@ use ibc_relayer_types::events::WithBlockDataType;
 use ibc_relayer_types::Height;
 
 use crate::chain::handle::ChainHandle;
-use crate::chain::requests::{Qualified, QueryHeight, QueryPacketEventDataRequest};
+use crate::chain::requests::{
+    Qualified, QueryBlockPacketEventsRequest, QueryHeight, QueryPacketEventDataRequest,
+};
+use crate::chain::tracking::TrackingId;
 use crate::error::Error;
+use crate::event::source::EventBatch;
 use crate::event::IbcEventWithHeight;
 use crate::path::PathIdentifiers;
 use crate::util::collate::CollatedIterExt;
@@ -137,3 +141,53 @@ pub fn query_write_ack_events<ChainA: ChainHandle>(
 
     query_packet_events(src_chain, query)
 }
+
+/// Returns an iterator on the batches of packet events emitted on the given path by the
+/// source chain at each height between `from_height` and `to_height` (inclusive), as
+/// the event source would have produced them. Used to relay again the packets of a
+/// range of heights, eg. after an outage.
+///
+/// The events are pulled from a single query of the `block_results` of each height,
+/// the heights without any packet event on the path are skipped.
+pub fn query_packet_events_between<'a, ChainA: ChainHandle>(
+    src_chain: &'a ChainA,
+    path: &'a PathIdentifiers,
+    from_height: Height,
+    to_height: Height,
+) -> impl Iterator<Item = Result<EventBatch, Error>> + 'a {
+    let revision_number = from_height.revision_number();
+
+    (from_height.revision_height()..=to_height.revision_height()).filter_map(move |height| {
+        let height = match Height::new(revision_number, height) {
+            Ok(height) => height,
+            Err(e) => return Some(Err(Error::ics02(e))),
+        };
+
+        let events = src_chain.query_block_packet_events(QueryBlockPacketEventsRequest {
+            port_id: path.counterparty_port_id.clone(),
+            channel_id: path.counterparty_channel_id.clone(),
+            counterparty_port_id: path.port_id.clone(),
+            counterparty_channel_id: path.channel_id.clone(),
+            height,
+        });
+
+        match events {
+            Ok(events) if events.is_empty() => None,
+            Ok(events) => {
+                info!(
+                    %height,
+                    "pulled {} packet events to relay again",
+                    events.len(),
+                );
+
+                Some(Ok(EventBatch {
+                    chain_id: src_chain.id(),
+                    tracking_id: TrackingId::new_cleared_uuid(),
+                    height,
+                    events,
+                }))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    })
+}
@@ -1,7 +1,10 @@
+use alloc::collections::VecDeque;
 use core::iter::Iterator;
 use core::time::Duration;
+use std::sync::{RwLock, Weak};
 use std::time::Instant;
 
+use tendermint::Hash as TxHash;
 use tracing::{debug, error, trace, trace_span};
 
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
@@ -12,6 +15,7 @@ use crate::chain::tracking::TrackingId;
 use crate::error::Error as RelayerError;
 use crate::link::{error::LinkError, RelayPath};
 use crate::telemetry;
+use crate::util::lock::LockExt;
 use crate::util::queue::Queue;
 use crate::{
     chain::handle::ChainHandle,
@@ -20,6 +24,36 @@ use crate::{
 
 pub const TIMEOUT: Duration = Duration::from_secs(300);
 
+/// A view on the transactions which the pending queue of a [`PendingTxs`] is waiting on,
+/// which can be held outside of the link owning the queue, without keeping the queue alive.
+#[derive(Clone, Debug)]
+pub struct PendingTxHashes {
+    chain_id: ChainId,
+    queue: Weak<RwLock<VecDeque<PendingData>>>,
+}
+
+impl PendingTxHashes {
+    /// The chain to which the transactions were submitted
+    pub fn chain_id(&self) -> &ChainId {
+        &self.chain_id
+    }
+
+    /// The hashes of the transactions waiting to be confirmed,
+    /// none once the link owning the queue was dropped.
+    pub fn tx_hashes(&self) -> Vec<TxHash> {
+        let Some(queue) = self.queue.upgrade() else {
+            return vec![];
+        };
+
+        let queue = queue.acquire_read();
+
+        queue
+            .iter()
+            .flat_map(|pending| pending.tx_hashes.0.clone())
+            .collect()
+    }
+}
+
 /// A wrapper over an [`OperationalData`] that is pending.
 /// Additionally holds all the necessary information
 /// to query for confirmations:
@@ -69,6 +103,14 @@ impl<Chain> PendingTxs<Chain> {
 }
 
 impl<Chain: ChainHandle> PendingTxs<Chain> {
+    /// A view on the transactions this queue is waiting on
+    pub fn tx_hashes(&self) -> PendingTxHashes {
+        PendingTxHashes {
+            chain_id: self.chain_id(),
+            queue: self.pending_queue.downgrade(),
+        }
+    }
+
     pub fn chain_id(&self) -> ChainId {
         self.chain.id()
     }
@@ -1,6 +1,7 @@
 use alloc::collections::BTreeMap as HashMap;
 use alloc::collections::VecDeque;
 use std::ops::Sub;
+use std::thread;
 use std::time::{Duration, Instant};
 
 use ibc_proto::google::protobuf::Any;
@@ -42,6 +43,7 @@ use crate::chain::tracking::TrackedMsgs;
 use crate::chain::tracking::TrackingId;
 use crate::channel::error::ChannelError;
 use crate::channel::Channel;
+use crate::config::{PacketScheduling, RetryPolicy};
 use crate::event::source::EventBatch;
 use crate::event::IbcEventWithHeight;
 use crate::foreign_client::{ForeignClient, ForeignClientError};
@@ -52,9 +54,12 @@ use crate::link::operational_data::{
 use crate::link::packet_events::query_packet_events_with;
 use crate::link::packet_events::query_send_packet_events;
 use crate::link::packet_events::query_write_ack_events;
-use crate::link::pending::PendingTxs;
+use crate::link::pending::{PendingTxHashes, PendingTxs};
 use crate::link::relay_sender::{AsyncReply, SubmitReply};
 use crate::link::relay_summary::RelaySummary;
+use crate::link::scheduling::{
+    schedule_send_packets, scheduler_for, PacketScheduler, SchedulingContext,
+};
 use crate::link::{pending, relay_sender};
 use crate::path::PathIdentifiers;
 use crate::telemetry;
@@ -108,6 +113,17 @@ pub struct RelayPath<ChainA: ChainHandle, ChainB: ChainHandle> {
     // transactions if [`confirm_txes`] is true.
     pending_txs_src: PendingTxs<ChainA>,
     pending_txs_dst: PendingTxs<ChainB>,
+
+    // The delays between the attempts to clear or relay packets.
+    retry: RetryPolicy,
+
+    // Orders the packets sent on unordered channels,
+    // if `None` they are relayed in the order in which they were sent.
+    scheduler: Option<Box<dyn PacketScheduler>>,
+
+    // The configured priority of the channel, relative to the other channels
+    // whose messages are submitted to the same chains.
+    priority: u32,
 }
 
 impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
@@ -152,9 +168,42 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             confirm_txes: with_tx_confirmation,
             pending_txs_src: PendingTxs::new(src_chain, src_channel_id, src_port_id, dst_chain_id),
             pending_txs_dst: PendingTxs::new(dst_chain, dst_channel_id, dst_port_id, src_chain_id),
+
+            retry: RetryPolicy::packet(),
+            scheduler: None,
+            priority: 0,
         })
     }
 
+    /// Use the given policy to retry clearing or relaying packets.
+    pub fn set_retry(&mut self, retry: RetryPolicy) {
+        self.retry = retry;
+    }
+
+    /// Use the given configuration to order the packets relayed on this path.
+    pub fn set_scheduling(&mut self, scheduling: &PacketScheduling) {
+        self.scheduler = scheduler_for(scheduling);
+        self.priority = scheduling.channel_priority(
+            &self.src_chain().id(),
+            self.src_port_id(),
+            self.src_channel_id(),
+        );
+    }
+
+    /// The configured priority of the channel, see [`TrackedMsgs::priority`].
+    pub fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    /// Views on the transactions submitted by this path to either chain,
+    /// which are waiting to be confirmed
+    pub fn pending_tx_hashes(&self) -> Vec<PendingTxHashes> {
+        vec![
+            self.pending_txs_src.tx_hashes(),
+            self.pending_txs_dst.tx_hashes(),
+        ]
+    }
+
     pub fn src_chain(&self) -> &ChainA {
         self.channel.src_chain()
     }
@@ -418,16 +467,20 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         let tracking_id = TrackingId::new_cleared_uuid();
         telemetry!(received_event_batch, tracking_id);
 
-        for i in 1..=MAX_RETRIES {
+        let mut delays = self.retry.delays();
+
+        for attempt in 1.. {
             let cleared_recv = self.schedule_recv_packet_and_timeout_msgs(height, tracking_id);
             let cleared_ack = self.schedule_packet_ack_msgs(height, tracking_id);
 
             match cleared_recv.and(cleared_ack) {
                 Ok(()) => return Ok(()),
-                Err(e) => error!(
-                    "failed to clear packets, retry {}/{}: {}",
-                    i, MAX_RETRIES, e
-                ),
+                Err(e) => error!("failed to clear packets, attempt {}: {}", attempt, e),
+            }
+
+            match delays.next() {
+                Some(delay) => thread::sleep(delay),
+                None => break,
             }
         }
 
@@ -460,6 +513,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         )
         .entered();
 
+        if let Some(scheduler) = &self.scheduler {
+            scheduler.observe(&batch.events);
+        }
+
         // Collect relevant events from the incoming batch & adjust their height.
         let events = self.filter_relaying_events(batch.events, batch.tracking_id);
 
@@ -525,6 +582,24 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         let dst_latest_height = dst_latest_info.height;
 
+        // Relay the most valuable or urgent packets first, unless the channel is ordered
+        let scheduled;
+        let input = match &self.scheduler {
+            Some(scheduler) if self.unordered_channel() => {
+                let ctx = SchedulingContext {
+                    dst_status: dst_latest_info.clone(),
+                    dst_block_time: self.dst_max_block_time()?,
+                };
+
+                let mut events = input.to_vec();
+                schedule_send_packets(scheduler.as_ref(), &mut events, &ctx);
+
+                scheduled = events;
+                &scheduled[..]
+            }
+            _ => input,
+        };
+
         // Operational data targeting the source chain (e.g., Timeout packets)
         let mut src_od = OperationalData::new(
             dst_latest_height,
@@ -645,9 +720,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         let _span = span!(Level::INFO, "relay", odata = %initial_od.info()).entered();
 
         let mut odata = initial_od;
+        let mut delays = self.retry.delays();
 
-        for i in 0..MAX_RETRIES {
-            debug!(retry.current = i + 1, retry.max = MAX_RETRIES, "retrying");
+        for attempt in 1.. {
+            debug!(retry.current = attempt, "retrying");
 
             // Consume the operational data by attempting to send its messages
             match self.send_from_operational_data::<S>(&odata) {
@@ -674,13 +750,19 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 Err(LinkError(error::LinkErrorDetail::Send(e), _)) => {
                     // This error means we could retry
                     error!("error {}", e.event);
-                    if i + 1 == MAX_RETRIES {
-                        error!("{}/{} retries exhausted. giving up", i + 1, MAX_RETRIES)
-                    } else {
-                        // If we haven't exhausted all retries, regenerate the op. data & retry
-                        match self.regenerate_operational_data(odata.clone()) {
-                            None => return Ok(S::Reply::empty()), // Nothing to retry
-                            Some(new_od) => odata = new_od,
+                    match delays.next() {
+                        None => {
+                            error!("{} attempts exhausted. giving up", attempt);
+                            break;
+                        }
+                        Some(delay) => {
+                            // If we haven't exhausted all retries, regenerate the op. data & retry
+                            match self.regenerate_operational_data(odata.clone()) {
+                                None => return Ok(S::Reply::empty()), // Nothing to retry
+                                Some(new_od) => odata = new_od,
+                            }
+
+                            thread::sleep(delay);
                         }
                     }
                 }
@@ -975,7 +1057,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         info!( "sending update_client to client hosted on source chain for height {} (retries left: {})", src_chain_height, retries_left );
 
         let dst_update = self.build_update_client_on_dst(src_chain_height)?;
-        let tm = TrackedMsgs::new(dst_update, tracking_id);
+        let tm = TrackedMsgs::new(dst_update, tracking_id).with_priority(self.priority);
         let dst_tx_events = self
             .dst_chain()
             .send_messages_and_wait_commit(tm)
@@ -1042,7 +1124,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         info!("sending update_client to client hosted on source chain for height {} (retries left: {})", dst_chain_height, retries_left);
 
         let src_update = self.build_update_client_on_src(dst_chain_height)?;
-        let tm = TrackedMsgs::new(src_update, tracking_id);
+        let tm = TrackedMsgs::new(src_update, tracking_id).with_priority(self.priority);
         let src_tx_events = self
             .src_chain()
             .send_messages_and_wait_commit(tm)
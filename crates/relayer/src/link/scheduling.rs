@@ -0,0 +1,363 @@
+//! Ordering of the packets relayed on a path, see [`PacketScheduling`].
+
+use core::cmp::Ordering;
+use core::time::Duration;
+
+use moka::sync::Cache;
+
+use ibc_relayer_types::applications::ics29_fee::events::IncentivizedPacket;
+use ibc_relayer_types::applications::transfer::Amount;
+use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
+use ibc_relayer_types::events::IbcEvent;
+
+use crate::chain::endpoint::ChainStatus;
+use crate::config::scheduling::{PacketScheduling, SchedulingPolicy};
+use crate::event::IbcEventWithHeight;
+
+const FEES_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+const FEES_CACHE_MAX_CAPACITY: u64 = 10_000;
+
+/// The state of the destination chain against which the packets are ordered.
+#[derive(Clone, Debug)]
+pub struct SchedulingContext {
+    pub dst_status: ChainStatus,
+    pub dst_block_time: Duration,
+}
+
+/// A policy deciding which packets of a path are relayed first.
+pub trait PacketScheduler: Send + Sync {
+    /// Takes note of the events seen on the source chain, eg. the fees
+    /// attached to the packets by the `IncentivizedPacket` events.
+    fn observe(&self, _events: &[IbcEventWithHeight]) {}
+
+    /// Returns `Ordering::Less` if packet `a` must be relayed before packet `b`.
+    fn compare(&self, a: &Packet, b: &Packet, ctx: &SchedulingContext) -> Ordering;
+}
+
+/// Builds the scheduler for the configured policy,
+/// or `None` if the packets are relayed in the order in which they were sent.
+pub fn scheduler_for(config: &PacketScheduling) -> Option<Box<dyn PacketScheduler>> {
+    match config.policy {
+        SchedulingPolicy::Fifo => None,
+        SchedulingPolicy::Fee => Some(Box::new(FeeScheduler::new(config.fee_denom.clone()))),
+        SchedulingPolicy::Timeout => Some(Box::new(TimeoutScheduler)),
+    }
+}
+
+/// Reorders the `SendPacket` events using the given scheduler.
+/// The other events are left at their position in the list.
+pub fn schedule_send_packets(
+    scheduler: &dyn PacketScheduler,
+    events: &mut [IbcEventWithHeight],
+    ctx: &SchedulingContext,
+) {
+    let positions = events
+        .iter()
+        .enumerate()
+        .filter(|(_, event)| matches!(event.event, IbcEvent::SendPacket(_)))
+        .map(|(position, _)| position)
+        .collect::<Vec<_>>();
+
+    let mut send_packets = positions
+        .iter()
+        .map(|position| events[*position].clone())
+        .collect::<Vec<_>>();
+
+    // The sort is stable, so that packets which compare equal keep their relative order
+    send_packets.sort_by(|a, b| match (&a.event, &b.event) {
+        (IbcEvent::SendPacket(a), IbcEvent::SendPacket(b)) => {
+            scheduler.compare(&a.packet, &b.packet, ctx)
+        }
+        _ => Ordering::Equal,
+    });
+
+    for (position, event) in positions.into_iter().zip(send_packets) {
+        events[position] = event;
+    }
+}
+
+/// Relays the packets with the highest receive fee first.
+///
+/// The fees are learnt from the `IncentivizedPacket` events, the packets
+/// whose fee has not been seen are considered to have no fee.
+pub struct FeeScheduler {
+    fee_denom: Option<String>,
+    fees: Cache<Sequence, Amount>,
+}
+
+impl FeeScheduler {
+    pub fn new(fee_denom: Option<String>) -> Self {
+        Self {
+            fee_denom,
+            fees: Cache::builder()
+                .time_to_live(FEES_CACHE_TTL)
+                .max_capacity(FEES_CACHE_MAX_CAPACITY)
+                .build(),
+        }
+    }
+
+    fn recv_fee(&self, packet: &IncentivizedPacket) -> Amount {
+        packet
+            .total_recv_fee
+            .iter()
+            .filter(|coin| {
+                self.fee_denom
+                    .as_ref()
+                    .map_or(true, |denom| &coin.denom == denom)
+            })
+            .map(|coin| coin.amount)
+            .sum()
+    }
+
+    fn fee(&self, packet: &Packet) -> Amount {
+        self.fees
+            .get(&packet.sequence)
+            .unwrap_or_else(|| Amount::from(0u64))
+    }
+}
+
+impl PacketScheduler for FeeScheduler {
+    fn observe(&self, events: &[IbcEventWithHeight]) {
+        for event in events {
+            if let IbcEvent::IncentivizedPacket(packet) = &event.event {
+                self.fees.insert(packet.sequence, self.recv_fee(packet));
+            }
+        }
+    }
+
+    fn compare(&self, a: &Packet, b: &Packet, _ctx: &SchedulingContext) -> Ordering {
+        self.fee(b).cmp(&self.fee(a))
+    }
+}
+
+/// Relays the packets which are the closest to timing out on the destination chain first.
+pub struct TimeoutScheduler;
+
+impl TimeoutScheduler {
+    /// An estimate of the time left before the packet times out,
+    /// or `None` if it never times out.
+    fn time_left(packet: &Packet, ctx: &SchedulingContext) -> Option<Duration> {
+        let dst_height = ctx.dst_status.height;
+
+        let by_height = match packet.timeout_height {
+            TimeoutHeight::Never => None,
+            TimeoutHeight::At(height) => {
+                match height.revision_number().cmp(&dst_height.revision_number()) {
+                    Ordering::Less => Some(Duration::ZERO),
+                    Ordering::Greater => None,
+                    Ordering::Equal => {
+                        let blocks = height
+                            .revision_height()
+                            .saturating_sub(dst_height.revision_height());
+
+                        let blocks = u32::try_from(blocks).unwrap_or(u32::MAX);
+
+                        Some(ctx.dst_block_time.saturating_mul(blocks))
+                    }
+                }
+            }
+        };
+
+        let by_timestamp = if packet.timeout_timestamp.nanoseconds() == 0 {
+            None
+        } else {
+            Some(
+                packet
+                    .timeout_timestamp
+                    .duration_since(&ctx.dst_status.timestamp)
+                    .unwrap_or(Duration::ZERO),
+            )
+        };
+
+        match (by_height, by_timestamp) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+impl PacketScheduler for TimeoutScheduler {
+    fn compare(&self, a: &Packet, b: &Packet, ctx: &SchedulingContext) -> Ordering {
+        match (Self::time_left(a, ctx), Self::time_left(b, ctx)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::applications::transfer::RawCoin;
+    use ibc_relayer_types::core::ics04_channel::events::SendPacket;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
+    use ibc_relayer_types::timestamp::Timestamp;
+    use ibc_relayer_types::Height;
+
+    fn packet(sequence: u64) -> Packet {
+        Packet {
+            sequence: sequence.into(),
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::default(),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::default(),
+            data: vec![],
+            timeout_height: TimeoutHeight::Never,
+            timeout_timestamp: Timestamp::none(),
+        }
+    }
+
+    fn with_height(event: IbcEvent) -> IbcEventWithHeight {
+        IbcEventWithHeight::new(event, Height::new(0, 10).unwrap())
+    }
+
+    fn send_packet(packet: Packet) -> IbcEventWithHeight {
+        with_height(IbcEvent::SendPacket(SendPacket { packet }))
+    }
+
+    fn incentivized(sequence: u64, fees: &[(&str, u64)]) -> IbcEventWithHeight {
+        with_height(IbcEvent::IncentivizedPacket(IncentivizedPacket {
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::default(),
+            sequence: sequence.into(),
+            total_recv_fee: fees
+                .iter()
+                .map(|(denom, amount)| RawCoin::new(denom.to_string(), *amount))
+                .collect(),
+            total_ack_fee: vec![],
+            total_timeout_fee: vec![],
+        }))
+    }
+
+    fn context() -> SchedulingContext {
+        SchedulingContext {
+            dst_status: ChainStatus {
+                height: Height::new(0, 100).unwrap(),
+                timestamp: Timestamp::from_nanoseconds(1_000_000_000_000).unwrap(),
+            },
+            dst_block_time: Duration::from_secs(5),
+        }
+    }
+
+    fn sequences(events: &[IbcEventWithHeight]) -> Vec<Option<u64>> {
+        events
+            .iter()
+            .map(|event| match &event.event {
+                IbcEvent::SendPacket(send_packet) => Some(send_packet.packet.sequence.into()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fee_scheduler_relays_highest_fees_first() {
+        let scheduler = FeeScheduler::new(Some("uatom".to_string()));
+
+        let mut events = vec![
+            incentivized(1, &[("uatom", 10)]),
+            send_packet(packet(1)),
+            incentivized(2, &[("uatom", 30), ("stake", 1000)]),
+            send_packet(packet(2)),
+            send_packet(packet(3)),
+            incentivized(4, &[("uatom", 20)]),
+            send_packet(packet(4)),
+        ];
+
+        scheduler.observe(&events);
+        schedule_send_packets(&scheduler, &mut events, &context());
+
+        // The `IncentivizedPacket` events stay where they are, packet 3 has no fee
+        assert_eq!(
+            sequences(&events),
+            vec![None, Some(2), None, Some(4), Some(1), None, Some(3)]
+        );
+    }
+
+    #[test]
+    fn fee_scheduler_adds_up_all_denoms_by_default() {
+        let scheduler = FeeScheduler::new(None);
+
+        let mut events = vec![
+            incentivized(1, &[("uatom", 10), ("stake", 10)]),
+            incentivized(2, &[("uatom", 15)]),
+            send_packet(packet(1)),
+            send_packet(packet(2)),
+        ];
+
+        scheduler.observe(&events);
+        schedule_send_packets(&scheduler, &mut events, &context());
+
+        assert_eq!(sequences(&events), vec![None, None, Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn timeout_scheduler_relays_closest_timeouts_first() {
+        let ctx = context();
+        let now = ctx.dst_status.timestamp;
+
+        // Times out in 50 blocks, ie. 250s
+        let by_height = Packet {
+            timeout_height: TimeoutHeight::At(Height::new(0, 150).unwrap()),
+            ..packet(1)
+        };
+
+        // Times out in 100s
+        let by_timestamp = Packet {
+            timeout_timestamp: (now + Duration::from_secs(100)).unwrap(),
+            ..packet(2)
+        };
+
+        // Never times out
+        let never = packet(3);
+
+        // Has already timed out
+        let expired = Packet {
+            timeout_height: TimeoutHeight::At(Height::new(0, 90).unwrap()),
+            ..packet(4)
+        };
+
+        let mut events = vec![
+            send_packet(never),
+            send_packet(by_height),
+            send_packet(by_timestamp),
+            send_packet(expired),
+        ];
+
+        schedule_send_packets(&TimeoutScheduler, &mut events, &ctx);
+
+        assert_eq!(sequences(&events), vec![Some(4), Some(2), Some(1), Some(3)]);
+    }
+
+    #[test]
+    fn timeout_scheduler_does_not_truncate_distant_heights() {
+        let ctx = context();
+        let now = ctx.dst_status.timestamp;
+
+        // Times out in 2^32 + 1 blocks, which must not be mistaken for a single block
+        let distant = Packet {
+            timeout_height: TimeoutHeight::At(Height::new(0, 100 + (1 << 32) + 1).unwrap()),
+            ..packet(1)
+        };
+
+        // Times out in 100s
+        let by_timestamp = Packet {
+            timeout_timestamp: (now + Duration::from_secs(100)).unwrap(),
+            ..packet(2)
+        };
+
+        let mut events = vec![send_packet(distant), send_packet(by_timestamp)];
+
+        schedule_send_packets(&TimeoutScheduler, &mut events, &ctx);
+
+        assert_eq!(sequences(&events), vec![Some(2), Some(1)]);
+    }
+
+    #[test]
+    fn fifo_has_no_scheduler() {
+        assert!(scheduler_for(&PacketScheduling::default()).is_none());
+    }
+}
@@ -0,0 +1,493 @@
+//! Governance proposals recovering an expired or frozen client with a substitute client.
+
+use flex_error::define_error;
+use prost::Message;
+use serde_json::{json, Value};
+
+use tendermint::Hash as TxHash;
+
+use ibc_proto::cosmos::base::v1beta1::Coin;
+use ibc_proto::cosmos::gov::v1beta1::MsgSubmitProposal as LegacyMsgSubmitProposal;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::client::v1::ClientUpdateProposal;
+use ibc_relayer_types::clients::ics07_tendermint::client_state::ClientState as TmClientState;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+use ibc_relayer_types::downcast;
+use ibc_relayer_types::signer::Signer;
+
+use crate::chain::handle::ChainHandle;
+use crate::chain::requests::{IncludeProof, QueryClientStateRequest, QueryHeight};
+use crate::chain::tracking::TrackedMsgs;
+use crate::client_state::AnyClientState;
+use crate::error::Error;
+use crate::foreign_client::{extract_client_id, CreateOptions, ForeignClient, ForeignClientError};
+use crate::upgrade_chain::{gov_module_address, proto as gov_proto};
+
+define_error! {
+    RecoverClientError {
+        Query
+            [ Error ]
+            |_| { "error during a query" },
+
+        Key
+            [ Error ]
+            |_| { "key error" },
+
+        ForeignClient
+            [ ForeignClientError ]
+            |_| { "foreign client error" },
+
+        Submit
+            { chain_id: ChainId }
+            [ Error ]
+            |e| {
+                format!("failed while submitting the client recovery proposal to chain {0}", e.chain_id)
+            },
+
+        GovAuthority
+            [ Error ]
+            |_| { "failed to derive the address of the governance module" },
+
+        TendermintOnly
+            |_| { "only Tendermint clients can be recovered" },
+
+        SubjectActive
+            { client_id: ClientId }
+            |e| {
+                format!("subject client {0} is active, only expired or frozen clients can be recovered", e.client_id)
+            },
+
+        SubstituteNotActive
+            { client_id: ClientId }
+            |e| {
+                format!("substitute client {0} is expired or frozen", e.client_id)
+            },
+
+        IncompatibleSubstitute
+            {
+                subject: ClientId,
+                substitute: ClientId,
+                reason: String,
+            }
+            |e| {
+                format!("substitute client {0} cannot replace subject client {1}: {2}",
+                    e.substitute, e.subject, e.reason)
+            },
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ClientRecoveryOptions {
+    pub subject_client_id: ClientId,
+    /// The client replacing the subject client; a new client is created if `None`
+    pub substitute_client_id: Option<ClientId>,
+    pub amount: u64,
+    pub denom: String,
+}
+
+/// A governance proposal replacing the state of an expired or frozen client,
+/// the subject, with the state of an active client, the substitute.
+#[derive(Clone, Debug)]
+pub struct ClientRecoveryProposal {
+    pub subject_client_id: ClientId,
+    pub substitute_client_id: ClientId,
+    pub deposit: Coin,
+    pub proposer: Signer,
+    /// The address of the governance module, which executes the recovery
+    pub authority: String,
+    /// Whether the client is recovered with a `MsgRecoverClient`, since ibc-go v8,
+    /// or with a legacy `ClientUpdateProposal`
+    pub recover_client_msg: bool,
+}
+
+/// Checks that the subject client hosted by `host_chain` can be recovered, creates a
+/// substitute client with matching parameters unless one is given in the options, checks
+/// that the substitute can replace the subject, and builds the recovery proposal.
+pub fn build_client_recovery_proposal<HostChain: ChainHandle, CounterpartyChain: ChainHandle>(
+    host_chain: &HostChain,
+    counterparty_chain: &CounterpartyChain,
+    opts: &ClientRecoveryOptions,
+) -> Result<ClientRecoveryProposal, RecoverClientError> {
+    let subject_state = query_tendermint_client_state(host_chain, &opts.subject_client_id)?;
+
+    let subject = ForeignClient::restore(
+        opts.subject_client_id.clone(),
+        host_chain.clone(),
+        counterparty_chain.clone(),
+    );
+
+    if !subject.is_expired_or_frozen() {
+        return Err(RecoverClientError::subject_active(
+            opts.subject_client_id.clone(),
+        ));
+    }
+
+    let substitute_client_id = match &opts.substitute_client_id {
+        Some(substitute_client_id) => substitute_client_id.clone(),
+        None => {
+            // Create the substitute client with the parameters of the subject client
+            let options = CreateOptions {
+                max_clock_drift: Some(subject_state.max_clock_drift),
+                trusting_period: Some(subject_state.trusting_period),
+                trust_threshold: Some(subject_state.trust_threshold),
+            };
+
+            let event = subject
+                .build_create_client_and_send(options)
+                .map_err(RecoverClientError::foreign_client)?;
+
+            extract_client_id(&event.event)
+                .map_err(RecoverClientError::foreign_client)?
+                .clone()
+        }
+    };
+
+    let substitute = ForeignClient::restore(
+        substitute_client_id.clone(),
+        host_chain.clone(),
+        counterparty_chain.clone(),
+    );
+
+    if substitute.is_expired_or_frozen() {
+        return Err(RecoverClientError::substitute_not_active(
+            substitute_client_id,
+        ));
+    }
+
+    let substitute_state = query_tendermint_client_state(host_chain, &substitute_client_id)?;
+
+    check_substitute(&subject_state, &substitute_state).map_err(|reason| {
+        RecoverClientError::incompatible_substitute(
+            opts.subject_client_id.clone(),
+            substitute_client_id.clone(),
+            reason,
+        )
+    })?;
+
+    let proposer = host_chain.get_signer().map_err(RecoverClientError::key)?;
+
+    let config = host_chain.config().map_err(RecoverClientError::query)?;
+
+    let authority =
+        gov_module_address(&config.account_prefix).map_err(RecoverClientError::gov_authority)?;
+
+    let ibc_version = host_chain
+        .ibc_version()
+        .map_err(RecoverClientError::query)?;
+
+    Ok(ClientRecoveryProposal {
+        subject_client_id: opts.subject_client_id.clone(),
+        substitute_client_id,
+        deposit: Coin {
+            denom: opts.denom.clone(),
+            amount: opts.amount.to_string(),
+        },
+        proposer,
+        authority,
+        recover_client_msg: ibc_version.map_or(false, |version| version.major >= 8),
+    })
+}
+
+impl ClientRecoveryProposal {
+    fn title(&self) -> String {
+        format!("Recover client {}", self.subject_client_id)
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "Replace the state of the expired or frozen client {} with the state of client {}",
+            self.subject_client_id, self.substitute_client_id
+        )
+    }
+
+    /// Submits the proposal to `host_chain`, signed with the key of the chain.
+    pub fn send(&self, host_chain: &impl ChainHandle) -> Result<TxHash, RecoverClientError> {
+        // As for upgrade proposals, no IBC event confirms the submission of the proposal.
+        let responses = host_chain
+            .send_messages_and_wait_check_tx(TrackedMsgs::new_single(
+                self.to_any(),
+                "client recovery",
+            ))
+            .map_err(|e| RecoverClientError::submit(host_chain.id(), e))?;
+
+        Ok(responses[0].hash)
+    }
+
+    /// The message submitting the proposal
+    pub fn to_any(&self) -> Any {
+        if self.recover_client_msg {
+            let recover = proto::MsgRecoverClient {
+                subject_client_id: self.subject_client_id.to_string(),
+                substitute_client_id: self.substitute_client_id.to_string(),
+                signer: self.authority.clone(),
+            };
+
+            let msg = gov_proto::MsgSubmitProposal {
+                messages: vec![Any {
+                    type_url: proto::MSG_RECOVER_CLIENT_TYPE_URL.to_string(),
+                    value: recover.encode_to_vec(),
+                }],
+                initial_deposit: vec![self.deposit.clone()],
+                proposer: self.proposer.to_string(),
+                metadata: "".to_string(),
+                title: self.title(),
+                summary: self.summary(),
+            };
+
+            Any {
+                type_url: gov_proto::MSG_SUBMIT_PROPOSAL_TYPE_URL.to_string(),
+                value: msg.encode_to_vec(),
+            }
+        } else {
+            let proposal = ClientUpdateProposal {
+                title: self.title(),
+                description: self.summary(),
+                subject_client_id: self.subject_client_id.to_string(),
+                substitute_client_id: self.substitute_client_id.to_string(),
+            };
+
+            let msg = LegacyMsgSubmitProposal {
+                content: Some(Any {
+                    type_url: proto::CLIENT_UPDATE_PROPOSAL_TYPE_URL.to_string(),
+                    value: proposal.encode_to_vec(),
+                }),
+                initial_deposit: vec![self.deposit.clone()],
+                proposer: self.proposer.to_string(),
+            };
+
+            Any {
+                type_url: proto::LEGACY_MSG_SUBMIT_PROPOSAL_TYPE_URL.to_string(),
+                value: msg.encode_to_vec(),
+            }
+        }
+    }
+
+    /// The proposal in the JSON format of the `tx gov submit-proposal` command of the
+    /// chain binary, for the proposal to be submitted with another key than the relayer's.
+    ///
+    /// Legacy proposals are wrapped in a `MsgExecLegacyContent`, which requires Cosmos SDK v0.46 or later.
+    pub fn to_json(&self) -> Value {
+        let message = if self.recover_client_msg {
+            json!({
+                "@type": proto::MSG_RECOVER_CLIENT_TYPE_URL,
+                "subject_client_id": self.subject_client_id.to_string(),
+                "substitute_client_id": self.substitute_client_id.to_string(),
+                "signer": self.authority,
+            })
+        } else {
+            json!({
+                "@type": proto::MSG_EXEC_LEGACY_CONTENT_TYPE_URL,
+                "content": {
+                    "@type": proto::CLIENT_UPDATE_PROPOSAL_TYPE_URL,
+                    "title": self.title(),
+                    "description": self.summary(),
+                    "subject_client_id": self.subject_client_id.to_string(),
+                    "substitute_client_id": self.substitute_client_id.to_string(),
+                },
+                "authority": self.authority,
+            })
+        };
+
+        json!({
+            "messages": [message],
+            "metadata": "",
+            "deposit": format!("{}{}", self.deposit.amount, self.deposit.denom),
+            "title": self.title(),
+            "summary": self.summary(),
+        })
+    }
+}
+
+fn query_tendermint_client_state(
+    chain: &impl ChainHandle,
+    client_id: &ClientId,
+) -> Result<TmClientState, RecoverClientError> {
+    let (client_state, _) = chain
+        .query_client_state(
+            QueryClientStateRequest {
+                client_id: client_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(RecoverClientError::query)?;
+
+    downcast!(client_state => AnyClientState::Tendermint)
+        .ok_or_else(RecoverClientError::tendermint_only)
+}
+
+/// Checks that the substitute client can replace the subject client, following the rules of ibc-go:
+/// all the parameters of the clients but their chain identifier, trusting period, latest and
+/// frozen heights must match, and the substitute must be ahead of the subject.
+fn check_substitute(subject: &TmClientState, substitute: &TmClientState) -> Result<(), String> {
+    if substitute.latest_height() <= subject.latest_height() {
+        return Err(format!(
+            "the latest height of the substitute ({}) must be greater than the latest height of the subject ({})",
+            substitute.latest_height(),
+            subject.latest_height()
+        ));
+    }
+
+    // The substitute with the fields which are allowed to differ taken from the subject
+    let comparable_substitute = TmClientState {
+        chain_id: subject.chain_id.clone(),
+        trusting_period: subject.trusting_period,
+        latest_height: subject.latest_height,
+        frozen_height: subject.frozen_height,
+        ..substitute.clone()
+    };
+
+    if &comparable_substitute != subject {
+        return Err(format!(
+            "the parameters of the clients differ: {subject:?} != {substitute:?}"
+        ));
+    }
+
+    Ok(())
+}
+
+// The protobuf message below is not part of `ibc-proto`, it is taken from:
+// - https://github.com/cosmos/ibc-go/blob/v8.0.0/proto/ibc/core/client/v1/tx.proto
+mod proto {
+    use prost::Message;
+
+    pub const MSG_RECOVER_CLIENT_TYPE_URL: &str = "/ibc.core.client.v1.MsgRecoverClient";
+    pub const CLIENT_UPDATE_PROPOSAL_TYPE_URL: &str = "/ibc.core.client.v1.ClientUpdateProposal";
+    pub const LEGACY_MSG_SUBMIT_PROPOSAL_TYPE_URL: &str = "/cosmos.gov.v1beta1.MsgSubmitProposal";
+    pub const MSG_EXEC_LEGACY_CONTENT_TYPE_URL: &str = "/cosmos.gov.v1.MsgExecLegacyContent";
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct MsgRecoverClient {
+        #[prost(string, tag = "1")]
+        pub subject_client_id: String,
+        #[prost(string, tag = "2")]
+        pub substitute_client_id: String,
+        #[prost(string, tag = "3")]
+        pub signer: String,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::time::Duration;
+
+    use ibc_relayer_types::Height;
+
+    use ibc_relayer_types::clients::ics07_tendermint::client_state::AllowUpdate;
+    use ibc_relayer_types::core::ics02_client::trust_threshold::TrustThreshold;
+
+    fn client_state(chain_id: &str, latest_height: u64) -> TmClientState {
+        TmClientState::new(
+            ChainId::from_string(chain_id),
+            TrustThreshold::ONE_THIRD,
+            Duration::from_secs(14 * 24 * 60 * 60),
+            Duration::from_secs(21 * 24 * 60 * 60),
+            Duration::from_secs(40),
+            Height::new(1, latest_height).unwrap(),
+            Default::default(),
+            vec!["upgrade".to_string(), "upgradedIBCState".to_string()],
+            AllowUpdate {
+                after_expiry: true,
+                after_misbehaviour: true,
+            },
+        )
+        .unwrap()
+    }
+
+    fn proposal(recover_client_msg: bool) -> ClientRecoveryProposal {
+        ClientRecoveryProposal {
+            subject_client_id: "07-tendermint-0".parse().unwrap(),
+            substitute_client_id: "07-tendermint-1".parse().unwrap(),
+            deposit: Coin {
+                denom: "stake".to_string(),
+                amount: "10000000".to_string(),
+            },
+            proposer: "cosmos1proposer".parse().unwrap(),
+            authority: gov_module_address("cosmos").unwrap(),
+            recover_client_msg,
+        }
+    }
+
+    #[test]
+    fn substitute_matching_subject() {
+        let subject = client_state("ibc-1", 100);
+
+        let mut substitute = client_state("ibc-1", 200);
+        substitute.trusting_period = Duration::from_secs(7 * 24 * 60 * 60);
+
+        assert!(check_substitute(&subject, &substitute).is_ok());
+    }
+
+    #[test]
+    fn substitute_behind_subject() {
+        let subject = client_state("ibc-1", 100);
+        let substitute = client_state("ibc-1", 100);
+
+        assert!(check_substitute(&subject, &substitute).is_err());
+    }
+
+    #[test]
+    fn substitute_with_other_parameters() {
+        let subject = client_state("ibc-1", 100);
+
+        let mut substitute = client_state("ibc-1", 200);
+        substitute.unbonding_period = Duration::from_secs(28 * 24 * 60 * 60);
+
+        assert!(check_substitute(&subject, &substitute).is_err());
+    }
+
+    #[test]
+    fn recover_client_msg_is_executed_by_gov_authority() {
+        let proposal = proposal(true);
+
+        let any = proposal.to_any();
+        assert_eq!(any.type_url, gov_proto::MSG_SUBMIT_PROPOSAL_TYPE_URL);
+
+        let submit = gov_proto::MsgSubmitProposal::decode(any.value.as_slice()).unwrap();
+        assert_eq!(submit.proposer, "cosmos1proposer");
+        assert_eq!(submit.messages.len(), 1);
+        assert_eq!(
+            submit.messages[0].type_url,
+            proto::MSG_RECOVER_CLIENT_TYPE_URL
+        );
+
+        let recover = proto::MsgRecoverClient::decode(submit.messages[0].value.as_slice()).unwrap();
+        assert_eq!(recover.subject_client_id, "07-tendermint-0");
+        assert_eq!(recover.substitute_client_id, "07-tendermint-1");
+        assert_eq!(recover.signer, proposal.authority);
+    }
+
+    #[test]
+    fn legacy_client_update_proposal() {
+        let any = proposal(false).to_any();
+        assert_eq!(any.type_url, proto::LEGACY_MSG_SUBMIT_PROPOSAL_TYPE_URL);
+
+        let submit = LegacyMsgSubmitProposal::decode(any.value.as_slice()).unwrap();
+        let content = submit.content.unwrap();
+        assert_eq!(content.type_url, proto::CLIENT_UPDATE_PROPOSAL_TYPE_URL);
+
+        let update = ClientUpdateProposal::decode(content.value.as_slice()).unwrap();
+        assert_eq!(update.subject_client_id, "07-tendermint-0");
+        assert_eq!(update.substitute_client_id, "07-tendermint-1");
+    }
+
+    #[test]
+    fn proposal_json() {
+        let json = proposal(true).to_json();
+
+        assert_eq!(json["deposit"], "10000000stake");
+        assert_eq!(
+            json["messages"][0]["@type"],
+            proto::MSG_RECOVER_CLIENT_TYPE_URL
+        );
+        assert_eq!(json["messages"][0]["subject_client_id"], "07-tendermint-0");
+
+        let json = proposal(false).to_json();
+        assert_eq!(
+            json["messages"][0]["content"]["substitute_client_id"],
+            "07-tendermint-1"
+        );
+    }
+}
@@ -5,8 +5,13 @@ use crate::{
     config::Config,
     rest::request::ReplySender,
     rest::request::{Request, VersionInfo},
-    supervisor::dump_state::SupervisorState,
+    supervisor::{
+        clear_packets::{ClearPacketsOptions, ClearedPackets},
+        dump_state::SupervisorState,
+        rotate_key::{KeyRotation, RotateKeyOptions},
+    },
 };
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
 pub mod request;
 
@@ -31,6 +36,18 @@ pub type Receiver = crossbeam_channel::Receiver<Request>;
 //  e.g., adjusting chain config, removing chains, etc.
 pub enum Command {
     DumpState(ReplySender<SupervisorState>),
+
+    ClearPackets {
+        chain_id: ChainId,
+        options: ClearPacketsOptions,
+        reply_to: ReplySender<ClearedPackets>,
+    },
+
+    RotateKey {
+        chain_id: ChainId,
+        options: RotateKeyOptions,
+        reply_to: ReplySender<KeyRotation>,
+    },
 }
 
 /// Process incoming REST requests.
@@ -82,6 +99,50 @@ pub fn process_incoming_requests(config: &Config, channel: &Receiver) -> Option<
 
                 return Some(Command::DumpState(reply_to));
             }
+
+            Request::ClearPackets {
+                chain_id,
+                options,
+                reply_to,
+            } => {
+                trace!("ClearPackets {}", chain_id);
+
+                if config.find_chain(&chain_id).is_none() {
+                    reply_to
+                        .send(Err(RestApiError::ChainConfigNotFound(chain_id)))
+                        .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+                } else {
+                    return Some(Command::ClearPackets {
+                        chain_id,
+                        options,
+                        reply_to,
+                    });
+                }
+            }
+
+            Request::RotateKey {
+                chain_id,
+                options,
+                reply_to,
+            } => {
+                trace!("RotateKey {}", chain_id);
+
+                if !config.rest.key_rotation {
+                    reply_to
+                        .send(Err(RestApiError::KeyRotationDisabled))
+                        .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+                } else if config.find_chain(&chain_id).is_none() {
+                    reply_to
+                        .send(Err(RestApiError::ChainConfigNotFound(chain_id)))
+                        .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+                } else {
+                    return Some(Command::RotateKey {
+                        chain_id,
+                        options,
+                        reply_to,
+                    });
+                }
+            }
         },
         Err(e) => {
             if !matches!(e, TryRecvError::Empty) {
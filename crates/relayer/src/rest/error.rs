@@ -23,6 +23,17 @@ pub enum RestApiError {
     #[error("failed while parsing the request body into a chain configuration: {0}")]
     InvalidChainConfig(String),
 
+    #[error("failed to clear the packets: {0}")]
+    ClearPackets(String),
+
+    #[error("failed to rotate the key: {0}")]
+    KeyRotation(String),
+
+    #[error(
+        "key rotation is disabled, see `key_rotation` in the `[rest]` section of the configuration"
+    )]
+    KeyRotationDisabled,
+
     #[error("not implemented")]
     Unimplemented,
 }
@@ -36,6 +47,9 @@ impl RestApiError {
             RestApiError::ChainConfigNotFound(_) => "ChainConfigNotFound",
             RestApiError::InvalidChainId(_, _) => "InvalidChainId",
             RestApiError::InvalidChainConfig(_) => "InvalidChainConfig",
+            RestApiError::ClearPackets(_) => "ClearPackets",
+            RestApiError::KeyRotation(_) => "KeyRotation",
+            RestApiError::KeyRotationDisabled => "KeyRotationDisabled",
             RestApiError::Unimplemented => "Unimplemented",
         }
     }
@@ -2,7 +2,15 @@ use serde::{Deserialize, Serialize};
 
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
-use crate::{config::ChainConfig, rest::RestApiError, supervisor::dump_state::SupervisorState};
+use crate::{
+    config::ChainConfig,
+    rest::RestApiError,
+    supervisor::{
+        clear_packets::{ClearPacketsOptions, ClearedPackets},
+        dump_state::SupervisorState,
+        rotate_key::{KeyRotation, RotateKeyOptions},
+    },
+};
 
 pub type ReplySender<T> = crossbeam_channel::Sender<Result<T, RestApiError>>;
 pub type ReplyReceiver<T> = crossbeam_channel::Receiver<Result<T, RestApiError>>;
@@ -36,4 +44,16 @@ pub enum Request {
         chain_id: ChainId,
         reply_to: ReplySender<ChainConfig>,
     },
+
+    ClearPackets {
+        chain_id: ChainId,
+        options: ClearPacketsOptions,
+        reply_to: ReplySender<ClearedPackets>,
+    },
+
+    RotateKey {
+        chain_id: ChainId,
+        options: RotateKeyOptions,
+        reply_to: ReplySender<KeyRotation>,
+    },
 }
@@ -7,7 +7,7 @@ use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
 use crate::{
     chain::{cosmos::CosmosSdkChain, handle::ChainHandle, runtime::ChainRuntime, ChainType},
-    config::Config,
+    config::{Config, RetryKind},
     error::Error as RelayerError,
 };
 
@@ -47,11 +47,15 @@ pub fn spawn_chain_runtime<Handle: ChainHandle>(
     chain_id: &ChainId,
     rt: Arc<TokioRuntime>,
 ) -> Result<Handle, SpawnError> {
-    let chain_config = config
+    let mut chain_config = config
         .find_chain(chain_id)
         .cloned()
         .ok_or_else(|| SpawnError::missing_chain_config(chain_id.clone()))?;
 
+    // The chain runtime only sees the configuration of its own chain,
+    // so the `[global.retry]` policy for its transactions is resolved here.
+    chain_config.retry.tx = Some(config.retry_policy(chain_id, RetryKind::Tx));
+
     let handle = match chain_config.r#type {
         ChainType::CosmosSdk => ChainRuntime::<CosmosSdkChain>::spawn::<Handle>(chain_config, rt),
     }
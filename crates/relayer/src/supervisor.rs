@@ -22,6 +22,7 @@ use crate::{
         source::{self, Error as EventError, ErrorDetail as EventErrorDetail, EventBatch},
         IbcEventWithHeight,
     },
+    keyring::KeyRing,
     object::Object,
     registry::{Registry, SharedRegistry},
     rest,
@@ -49,6 +50,12 @@ pub mod spawn;
 pub mod cmd;
 use cmd::SupervisorCmd;
 
+pub mod clear_packets;
+use clear_packets::{clear_packets, ClearPacketsOptions, ClearedPackets};
+
+pub mod rotate_key;
+use rotate_key::{rotate_key, KeyRotation, RotateKeyOptions};
+
 use self::{scan::ChainScanner, spawn::SpawnContext};
 
 type ArcBatch = Arc<source::Result<EventBatch>>;
@@ -131,6 +138,46 @@ impl SupervisorHandle {
 
         Ok(state)
     }
+
+    /// Ask the supervisor to relay again the packets sent and acknowledged on the given channel
+    /// between two heights of its chain, see [`SupervisorCmd::ClearPackets`]
+    pub fn clear_packets(
+        &self,
+        chain_id: ChainId,
+        options: ClearPacketsOptions,
+    ) -> Result<ClearedPackets, Error> {
+        let (reply_to, rx) = crossbeam_channel::bounded(1);
+
+        self.sender
+            .send(SupervisorCmd::ClearPackets {
+                chain_id,
+                options,
+                reply_to,
+            })
+            .map_err(|_| Error::handle_send())?;
+
+        rx.recv().map_err(|_| Error::handle_recv())?
+    }
+
+    /// Ask the supervisor to make the given key the primary key of the given chain,
+    /// see [`SupervisorCmd::RotateKey`]
+    pub fn rotate_key(
+        &self,
+        chain_id: ChainId,
+        options: RotateKeyOptions,
+    ) -> Result<KeyRotation, Error> {
+        let (reply_to, rx) = crossbeam_channel::bounded(1);
+
+        self.sender
+            .send(SupervisorCmd::RotateKey {
+                chain_id,
+                options,
+                reply_to,
+            })
+            .map_err(|_| Error::handle_send())?;
+
+        rx.recv().map_err(|_| Error::handle_recv())?
+    }
 }
 
 /// Whether the supervisor should scan the chains for clients, connections, and channels.
@@ -156,6 +203,8 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
     cmd_rx: Receiver<SupervisorCmd>,
     options: SupervisorOptions,
 ) -> Result<Vec<TaskHandle>, Error> {
+    reject_ledger_keys(&config)?;
+
     if options.health_check {
         health_check(&config, &mut registry.write());
     }
@@ -199,18 +248,30 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
     let batch_tasks = spawn_batch_workers(
         &config,
         registry.clone(),
-        client_state_filter,
+        client_state_filter.clone(),
         workers.clone(),
         subscriptions,
     );
 
-    let cmd_task = spawn_cmd_worker(registry.clone(), workers.clone(), cmd_rx);
+    let cmd_task = spawn_cmd_worker(
+        &config,
+        registry.clone(),
+        client_state_filter.clone(),
+        workers.clone(),
+        cmd_rx,
+    );
 
     let mut tasks = vec![cmd_task];
     tasks.extend(batch_tasks);
 
     if let Some(rest_rx) = rest_rx {
-        let rest_task = spawn_rest_worker(config, registry, workers.clone(), rest_rx);
+        let rest_task = spawn_rest_worker(
+            config,
+            registry,
+            client_state_filter,
+            workers.clone(),
+            rest_rx,
+        );
         tasks.push(rest_task);
     }
 
@@ -261,10 +322,14 @@ fn spawn_batch_workers<Chain: ChainHandle>(
 }
 
 pub fn spawn_cmd_worker<Chain: ChainHandle>(
+    config: &Config,
     registry: SharedRegistry<Chain>,
+    client_state_filter: Arc<RwLock<FilterPolicy>>,
     workers: Arc<RwLock<WorkerMap>>,
     cmd_rx: Receiver<SupervisorCmd>,
 ) -> TaskHandle {
+    let config = config.clone();
+
     spawn_background_task(
         error_span!("worker.cmd"),
         Some(Duration::from_millis(500)),
@@ -274,6 +339,47 @@ pub fn spawn_cmd_worker<Chain: ChainHandle>(
                     SupervisorCmd::DumpState(reply_to) => {
                         dump_state(&registry.read(), &workers.acquire_read(), reply_to);
                     }
+                    SupervisorCmd::ClearPackets {
+                        chain_id,
+                        options,
+                        reply_to,
+                    } => {
+                        let config = config.clone();
+                        let registry = registry.clone();
+                        let client_state_filter = client_state_filter.clone();
+                        let workers = workers.clone();
+
+                        // Querying the events at each height of a long range may take a while,
+                        // during which the other commands should still be served
+                        std::thread::spawn(move || {
+                            let result = clear_packets(
+                                &config,
+                                &registry,
+                                &client_state_filter,
+                                &workers,
+                                &chain_id,
+                                &options,
+                            );
+
+                            let _ = reply_to.send(result);
+                        });
+                    }
+                    SupervisorCmd::RotateKey {
+                        chain_id,
+                        options,
+                        reply_to,
+                    } => {
+                        let registry = registry.clone();
+                        let workers = workers.clone();
+
+                        // Waiting for the transactions in flight to be confirmed may take a while,
+                        // during which the other commands should still be served
+                        std::thread::spawn(move || {
+                            let result = rotate_key(&registry, &workers, &chain_id, &options);
+
+                            let _ = reply_to.send(result);
+                        });
+                    }
                 }
             }
 
@@ -285,6 +391,7 @@ pub fn spawn_cmd_worker<Chain: ChainHandle>(
 pub fn spawn_rest_worker<Chain: ChainHandle>(
     config: Config,
     registry: SharedRegistry<Chain>,
+    client_state_filter: Arc<RwLock<FilterPolicy>>,
     workers: Arc<RwLock<WorkerMap>>,
     rest_rx: rest::Receiver,
 ) -> TaskHandle {
@@ -292,7 +399,7 @@ pub fn spawn_rest_worker<Chain: ChainHandle>(
         error_span!("rest"),
         Some(Duration::from_millis(500)),
         move || -> Result<Next, TaskError<Infallible>> {
-            handle_rest_requests(&config, &registry.read(), &workers.acquire_read(), &rest_rx);
+            handle_rest_requests(&config, &registry, &client_state_filter, &workers, &rest_rx);
 
             Ok(Next::Continue)
         },
@@ -351,6 +458,16 @@ fn relay_on_object<Chain: ChainHandle>(
     chain_id: &ChainId,
     object: &Object,
 ) -> bool {
+    // The object is assigned to another instance, leave it to that instance.
+    if !config.sharding.owns(object) {
+        trace!(
+            "skipping object {}, reason: it is assigned to another instance",
+            object.short_name()
+        );
+
+        return false;
+    }
+
     // No filter is enabled, bail fast.
     if !channel_filter_enabled(config) && !client_filter_enabled(config) {
         return true;
@@ -444,7 +561,7 @@ pub fn collect_events(
     let mut collected =
         CollectedEvents::new(batch.height, batch.chain_id.clone(), batch.tracking_id);
 
-    let mode = config.mode;
+    let mode = &config.mode;
 
     for event_with_height in &batch.events {
         match &event_with_height.event {
@@ -612,6 +729,34 @@ fn chain_scanner<'a, Chain: ChainHandle>(
     ChainScanner::new(config, registry, client_state_filter, full_scan)
 }
 
+/// Reject the configurations in which a chain signs with a key held by a Ledger device,
+/// as the device would have to approve every transaction submitted by the relayer.
+///
+/// The keys which cannot be loaded are left to the chain runtimes to report.
+fn reject_ledger_keys(config: &Config) -> Result<(), Error> {
+    for chain_config in &config.chains {
+        let Ok(keyring) = KeyRing::new_cosmos(
+            chain_config.key_store_type,
+            &chain_config.account_prefix,
+            &chain_config.id,
+            &chain_config.key_store_folder,
+        ) else {
+            continue;
+        };
+
+        for key_name in chain_config.key_name.iter() {
+            if matches!(keyring.get_key(key_name), Ok(key_pair) if key_pair.is_ledger()) {
+                return Err(Error::ledger_key(
+                    chain_config.id.clone(),
+                    key_name.to_string(),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Perform a health check on all connected chains
 fn health_check<Chain: ChainHandle>(config: &Config, registry: &mut Registry<Chain>) {
     use HealthCheck::*;
@@ -701,28 +846,78 @@ fn state<Chain: ChainHandle>(registry: &Registry<Chain>, workers: &WorkerMap) ->
 
 fn handle_rest_requests<Chain: ChainHandle>(
     config: &Config,
-    registry: &Registry<Chain>,
-    workers: &WorkerMap,
+    registry: &SharedRegistry<Chain>,
+    client_state_filter: &Arc<RwLock<FilterPolicy>>,
+    workers: &Arc<RwLock<WorkerMap>>,
     rest_rx: &rest::Receiver,
 ) {
     if let Some(cmd) = rest::process_incoming_requests(config, rest_rx) {
-        handle_rest_cmd(registry, workers, cmd);
+        handle_rest_cmd(config, registry, client_state_filter, workers, cmd);
     }
 }
 
 #[instrument(name = "supervisor.handle_rest_cmd", level = "error", skip_all)]
 fn handle_rest_cmd<Chain: ChainHandle>(
-    registry: &Registry<Chain>,
-    workers: &WorkerMap,
+    config: &Config,
+    registry: &SharedRegistry<Chain>,
+    client_state_filter: &Arc<RwLock<FilterPolicy>>,
+    workers: &Arc<RwLock<WorkerMap>>,
     m: rest::Command,
 ) {
     match m {
         rest::Command::DumpState(reply) => {
-            let state = state(registry, workers);
+            let state = state(&registry.read(), &workers.acquire_read());
             reply
                 .send(Ok(state))
                 .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
         }
+        rest::Command::ClearPackets {
+            chain_id,
+            options,
+            reply_to,
+        } => {
+            let config = config.clone();
+            let registry = registry.clone();
+            let client_state_filter = client_state_filter.clone();
+            let workers = workers.clone();
+
+            // Querying the events at each height of a long range may take a while,
+            // during which the other REST requests should still be served
+            std::thread::spawn(move || {
+                let result = clear_packets(
+                    &config,
+                    &registry,
+                    &client_state_filter,
+                    &workers,
+                    &chain_id,
+                    &options,
+                )
+                .map_err(|e| rest::RestApiError::ClearPackets(e.to_string()));
+
+                reply_to
+                    .send(result)
+                    .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+            });
+        }
+        rest::Command::RotateKey {
+            chain_id,
+            options,
+            reply_to,
+        } => {
+            let registry = registry.clone();
+            let workers = workers.clone();
+
+            // Waiting for the transactions in flight to be confirmed may take a while,
+            // during which the other REST requests should still be served
+            std::thread::spawn(move || {
+                let result = rotate_key(&registry, &workers, &chain_id, &options)
+                    .map_err(|e| rest::RestApiError::KeyRotation(e.to_string()));
+
+                reply_to
+                    .send(result)
+                    .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+            });
+        }
     }
 }
 
@@ -904,6 +1099,12 @@ fn handle_batch<Chain: ChainHandle>(
             let _ = clear_pending_packets(workers, &chain_id)
                 .map_err(|e| error!("error during clearing pending packets: {}", e));
         }
+        Err(e @ EventError(EventErrorDetail::CatchUpFailed(_), _)) => {
+            warn!("{e}, clearing pending packets");
+
+            let _ = clear_pending_packets(workers, &chain_id)
+                .map_err(|e| error!("error during clearing pending packets: {}", e));
+        }
         Err(e) => {
             error!("error when receiving event batch: {}", e)
         }
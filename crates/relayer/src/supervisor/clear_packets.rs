@@ -0,0 +1,175 @@
+//! Relaying again the packets of a range of heights while the relayer is running.
+
+use alloc::sync::Arc;
+use std::sync::RwLock;
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer_types::Height;
+
+use crate::{
+    chain::{
+        handle::ChainHandle,
+        requests::{IncludeProof, QueryChannelRequest, QueryHeight},
+    },
+    config::Config,
+    link::packet_events::query_packet_events_between,
+    path::PathIdentifiers,
+    registry::SharedRegistry,
+    util::lock::LockExt,
+    worker::WorkerMap,
+};
+
+use super::{client_state_filter::FilterPolicy, process_batch, Error};
+
+/// The channel and heights of the packets to relay again
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClearPacketsOptions {
+    /// The port of the channel end on the chain
+    pub port_id: PortId,
+
+    /// The channel end on the chain
+    pub channel_id: ChannelId,
+
+    /// The first height of the chain whose packets are relayed again
+    pub from_height: u64,
+
+    /// The last height of the chain whose packets are relayed again (inclusive)
+    pub to_height: u64,
+}
+
+/// The outcome of clearing the packets of a range of heights
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClearedPackets {
+    pub chain_id: ChainId,
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+
+    /// The number of heights at which packet events were found
+    pub heights: usize,
+}
+
+/// Pulls the packet events emitted on the given channel between two heights of its chain,
+/// and processes them as if they had just been received from the event source of the chain,
+/// so that the packet workers relay the packets which have not been relayed yet.
+pub fn clear_packets<Chain: ChainHandle>(
+    config: &Config,
+    registry: &SharedRegistry<Chain>,
+    client_state_filter: &Arc<RwLock<FilterPolicy>>,
+    workers: &Arc<RwLock<WorkerMap>>,
+    chain_id: &ChainId,
+    options: &ClearPacketsOptions,
+) -> Result<ClearedPackets, Error> {
+    let (from_height, to_height) = height_range(chain_id, options)?;
+
+    let chain = registry.get_or_spawn(chain_id).map_err(Error::spawn)?;
+
+    let (channel_end, _) = chain
+        .query_channel(
+            QueryChannelRequest {
+                port_id: options.port_id.clone(),
+                channel_id: options.channel_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(Error::relayer)?;
+
+    // The packet events are queried on the source chain of the path
+    let path = PathIdentifiers {
+        port_id: channel_end.remote.port_id.clone(),
+        channel_id: channel_end
+            .remote
+            .channel_id
+            .clone()
+            .ok_or_else(Error::missing_counterparty_channel_id)?,
+        counterparty_port_id: options.port_id.clone(),
+        counterparty_channel_id: options.channel_id.clone(),
+    };
+
+    info!(
+        chain = %chain_id,
+        port = %options.port_id,
+        channel = %options.channel_id,
+        %from_height,
+        %to_height,
+        "clearing packets from the events between the given heights"
+    );
+
+    let mut heights = 0;
+
+    for batch in query_packet_events_between(&chain, &path, from_height, to_height) {
+        let batch = batch.map_err(Error::relayer)?;
+
+        process_batch(
+            config,
+            &mut registry.write(),
+            &mut client_state_filter.acquire_write(),
+            &mut workers.acquire_write(),
+            chain.clone(),
+            &batch,
+        )?;
+
+        heights += 1;
+    }
+
+    Ok(ClearedPackets {
+        chain_id: chain_id.clone(),
+        port_id: options.port_id.clone(),
+        channel_id: options.channel_id.clone(),
+        heights,
+    })
+}
+
+/// The heights of the chain between which the packets are relayed again
+fn height_range(
+    chain_id: &ChainId,
+    options: &ClearPacketsOptions,
+) -> Result<(Height, Height), Error> {
+    let invalid_range = || Error::invalid_height_range(options.from_height, options.to_height);
+
+    if options.from_height > options.to_height {
+        return Err(invalid_range());
+    }
+
+    let from_height =
+        Height::new(chain_id.version(), options.from_height).map_err(|_| invalid_range())?;
+    let to_height =
+        Height::new(chain_id.version(), options.to_height).map_err(|_| invalid_range())?;
+
+    Ok((from_height, to_height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(from_height: u64, to_height: u64) -> ClearPacketsOptions {
+        ClearPacketsOptions {
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::new(0),
+            from_height,
+            to_height,
+        }
+    }
+
+    #[test]
+    fn heights_use_the_revision_of_the_chain() {
+        let chain_id = ChainId::from_string("chain-3");
+
+        let (from_height, to_height) = height_range(&chain_id, &options(10, 20)).unwrap();
+
+        assert_eq!(from_height, Height::new(3, 10).unwrap());
+        assert_eq!(to_height, Height::new(3, 20).unwrap());
+    }
+
+    #[test]
+    fn invalid_height_ranges_are_rejected() {
+        let chain_id = ChainId::from_string("chain-3");
+
+        assert!(height_range(&chain_id, &options(20, 10)).is_err());
+        assert!(height_range(&chain_id, &options(0, 10)).is_err());
+    }
+}
@@ -23,11 +23,11 @@ use crate::spawn::SpawnError;
 
 /// The lower bound trust threshold value. Clients with a trust threshold less
 /// than this will not be allowed due to security concerns.
-const LOWER_BOUND: TrustThreshold = TrustThreshold::ONE_THIRD;
+pub const LOWER_BOUND: TrustThreshold = TrustThreshold::ONE_THIRD;
 
-/// The lower bound trust threshold value. Clients with a trust threshold greater
+/// The upper bound trust threshold value. Clients with a trust threshold greater
 /// than this will not be allowed due to cost-efficiency concerns.
-const UPPER_BOUND: TrustThreshold = TrustThreshold::TWO_THIRDS;
+pub const UPPER_BOUND: TrustThreshold = TrustThreshold::TWO_THIRDS;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Permission {
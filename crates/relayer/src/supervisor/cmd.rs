@@ -1,8 +1,30 @@
 use crossbeam_channel::Sender;
 
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use super::clear_packets::{ClearPacketsOptions, ClearedPackets};
 use super::dump_state::SupervisorState;
+use super::rotate_key::{KeyRotation, RotateKeyOptions};
+use super::Error;
 
 #[derive(Clone, Debug)]
 pub enum SupervisorCmd {
     DumpState(Sender<SupervisorState>),
+
+    /// Relay again the packets sent and acknowledged on the given channel
+    /// between two heights of its chain,
+    /// see [`clear_packets`](super::clear_packets::clear_packets).
+    ClearPackets {
+        chain_id: ChainId,
+        options: ClearPacketsOptions,
+        reply_to: Sender<Result<ClearedPackets, Error>>,
+    },
+
+    /// Make the given key the primary key of the given chain,
+    /// see [`rotate_key`](super::rotate_key::rotate_key).
+    RotateKey {
+        chain_id: ChainId,
+        options: RotateKeyOptions,
+        reply_to: Sender<Result<KeyRotation, Error>>,
+    },
 }
@@ -55,10 +55,30 @@ define_error! {
         MissingCounterpartyChannelId
             |_| { "failed due to missing counterparty channel id" },
 
+        InvalidHeightRange
+            {
+                from_height: u64,
+                to_height: u64,
+            }
+            |e| {
+                format_args!("invalid range of heights from {0} to {1}: the heights must be non-zero, and the first one not greater than the last one",
+                    e.from_height, e.to_height)
+            },
+
         Relayer
             [ RelayerError ]
             |_| { "relayer error" },
 
+        LedgerKey
+            {
+                chain_id: ChainId,
+                key_name: String,
+            }
+            |e| {
+                format_args!("key `{0}` of chain {1} is held by a Ledger device, which would have to approve every transaction of the relayer: use a key of the keyring instead",
+                    e.key_name, e.chain_id)
+            },
+
         NoChainsAvailable
             |_| { "supervisor was not able to connect to any chains" },
 
@@ -0,0 +1,304 @@
+//! Rotation of the primary key of a chain while the relayer is running.
+
+use alloc::collections::BTreeSet;
+use alloc::sync::Arc;
+use core::time::Duration;
+use std::sync::RwLock;
+use std::thread;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use tendermint::Hash as TxHash;
+use tracing::{info, warn};
+
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+use crate::{
+    account::Balance,
+    chain::{
+        handle::ChainHandle,
+        requests::{IncludeProof, QueryChannelRequest, QueryHeight},
+    },
+    link::pending,
+    object::{Object, Packet},
+    registry::SharedRegistry,
+    telemetry,
+    util::lock::LockExt,
+    worker::WorkerMap,
+};
+
+use super::Error;
+
+/// How often to check whether the transactions in flight were confirmed
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The options of a key rotation
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RotateKeyOptions {
+    /// The name of the key of the keyring which becomes the primary key
+    pub new_key: String,
+
+    /// Whether to send the balances of the old key to the new one
+    #[serde(default)]
+    pub sweep: bool,
+}
+
+/// The outcome of a key rotation
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRotation {
+    pub chain_id: ChainId,
+    pub old_key_name: String,
+    pub old_address: String,
+    pub new_key_name: String,
+    pub new_address: String,
+
+    /// Whether the transactions in flight when the key was rotated were all confirmed
+    pub drained: bool,
+
+    /// The balances sent from the old key to the new one, if they were swept
+    pub swept: Option<Vec<Balance>>,
+
+    /// The channels on which the counterparty payee was registered for the new key
+    pub registered_payees: Vec<PayeeRegistration>,
+}
+
+/// A counterparty payee registered after a key rotation
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayeeRegistration {
+    pub chain_id: ChainId,
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub counterparty_payee: String,
+}
+
+/// Make the given key the primary key of the given chain, without restarting the relayer:
+///
+/// 1. the new key signs all the transactions submitted to the chain from now on,
+/// 2. the transactions which the packet workers were waiting on before the switch
+///    are given up to [`pending::TIMEOUT`] to be confirmed, after which they are
+///    either submitted again with the new key or given up on,
+/// 3. if asked to, and once the transactions of the old key were all confirmed,
+///    the balances of the old key are sent to the new key,
+/// 4. the counterparty payees are registered again for the new key on all the
+///    fee-enabled channels of the chain.
+pub fn rotate_key<Chain: ChainHandle>(
+    registry: &SharedRegistry<Chain>,
+    workers: &Arc<RwLock<WorkerMap>>,
+    chain_id: &ChainId,
+    options: &RotateKeyOptions,
+) -> Result<KeyRotation, Error> {
+    let chain = registry.get_or_spawn(chain_id).map_err(Error::spawn)?;
+
+    let old_key_name = chain
+        .config()
+        .map_err(Error::relayer)?
+        .key_name
+        .primary()
+        .to_string();
+    let old_address = chain.get_key().map_err(Error::relayer)?.account();
+
+    // The transactions in flight before the switch are the ones signed by the old key
+    let in_flight = workers.acquire_read().pending_tx_hashes(chain_id);
+
+    chain
+        .rotate_key(options.new_key.clone())
+        .map_err(Error::relayer)?;
+
+    let new_address = chain.get_key().map_err(Error::relayer)?.account();
+
+    telemetry!(add_visible_fee_address, new_address.clone());
+
+    info!(
+        chain = %chain_id,
+        %old_key_name,
+        new_key_name = %options.new_key,
+        "rotated key, waiting for the transactions in flight to be confirmed"
+    );
+
+    let drained = wait_for_confirmations(workers, chain_id, in_flight, pending::TIMEOUT);
+
+    let swept = if !options.sweep {
+        None
+    } else if drained {
+        let swept = chain
+            .sweep_balances(old_key_name.clone())
+            .map_err(Error::relayer)?;
+
+        Some(swept)
+    } else {
+        warn!(
+            chain = %chain_id,
+            "not sweeping the balances of key `{}`, as some of its transactions are still in flight",
+            old_key_name
+        );
+
+        None
+    };
+
+    let registered_payees = register_payees(registry, workers, chain_id);
+
+    Ok(KeyRotation {
+        chain_id: chain_id.clone(),
+        old_key_name,
+        old_address,
+        new_key_name: options.new_key.clone(),
+        new_address,
+        drained,
+        swept,
+        registered_payees,
+    })
+}
+
+/// Wait until none of the given transactions is pending on the given chain
+/// anymore, returning whether that happened before the given timeout.
+fn wait_for_confirmations(
+    workers: &Arc<RwLock<WorkerMap>>,
+    chain_id: &ChainId,
+    in_flight: BTreeSet<TxHash>,
+    timeout: Duration,
+) -> bool {
+    let start = Instant::now();
+
+    // A pending transaction briefly leaves its queue while its confirmation
+    // is checked, hence it is only deemed confirmed if missing twice in a row
+    let mut missing = 0;
+
+    while !in_flight.is_empty() {
+        if in_flight.is_disjoint(&workers.acquire_read().pending_tx_hashes(chain_id)) {
+            missing += 1;
+
+            if missing == 2 {
+                return true;
+            }
+        } else {
+            missing = 0;
+        }
+
+        if start.elapsed() > timeout {
+            return false;
+        }
+
+        thread::sleep(DRAIN_POLL_INTERVAL);
+    }
+
+    true
+}
+
+/// Register again the counterparty payees of all the fee-enabled channels
+/// of the packet workers relaying to or from the given chain, so that the
+/// fees are paid to the current primary key of the chain.
+fn register_payees<Chain: ChainHandle>(
+    registry: &SharedRegistry<Chain>,
+    workers: &Arc<RwLock<WorkerMap>>,
+    chain_id: &ChainId,
+) -> Vec<PayeeRegistration> {
+    let paths = workers
+        .acquire_read()
+        .objects_for_chain(chain_id)
+        .into_iter()
+        .filter_map(|object| match object {
+            Object::Packet(path) => Some(path),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    paths
+        .iter()
+        .filter_map(|path| match register_payee(registry, path) {
+            Ok(registration) => registration,
+            Err(e) => {
+                warn!(
+                    "failed to register the counterparty payee for path {}: {}",
+                    path.short_name(),
+                    e
+                );
+
+                None
+            }
+        })
+        .collect()
+}
+
+/// Register the address of the source chain of the given path as counterparty
+/// payee on its destination chain, if the channel is fee-enabled, the same way
+/// as the packet workers do when they start.
+fn register_payee<Chain: ChainHandle>(
+    registry: &SharedRegistry<Chain>,
+    path: &Packet,
+) -> Result<Option<PayeeRegistration>, Error> {
+    let src_chain = registry
+        .get_or_spawn(&path.src_chain_id)
+        .map_err(Error::spawn)?;
+    let dst_chain = registry
+        .get_or_spawn(&path.dst_chain_id)
+        .map_err(Error::spawn)?;
+
+    let (channel_end, _) = src_chain
+        .query_channel(
+            QueryChannelRequest {
+                port_id: path.src_port_id.clone(),
+                channel_id: path.src_channel_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(Error::relayer)?;
+
+    if !channel_end.version.supports_fee() {
+        return Ok(None);
+    }
+
+    let port_id = channel_end.remote.port_id.clone();
+    let channel_id = channel_end
+        .remote
+        .channel_id
+        .clone()
+        .ok_or_else(Error::missing_counterparty_channel_id)?;
+
+    let counterparty_payee = src_chain.get_signer().map_err(Error::relayer)?;
+
+    dst_chain
+        .maybe_register_counterparty_payee(
+            channel_id.clone(),
+            port_id.clone(),
+            counterparty_payee.clone(),
+        )
+        .map_err(Error::relayer)?;
+
+    Ok(Some(PayeeRegistration {
+        chain_id: path.dst_chain_id.clone(),
+        port_id,
+        channel_id,
+        counterparty_payee: counterparty_payee.to_string(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nothing_in_flight_is_drained() {
+        let chain_id = ChainId::from_string("chain-rotate-0");
+
+        assert!(wait_for_confirmations(
+            &Arc::new(RwLock::new(WorkerMap::new())),
+            &chain_id,
+            BTreeSet::new(),
+            Duration::ZERO
+        ));
+    }
+
+    #[test]
+    fn unknown_transactions_are_confirmed() {
+        let chain_id = ChainId::from_string("chain-rotate-1");
+        let in_flight = BTreeSet::from([TxHash::None]);
+
+        assert!(wait_for_confirmations(
+            &Arc::new(RwLock::new(WorkerMap::new())),
+            &chain_id,
+            in_flight,
+            Duration::from_secs(10)
+        ));
+    }
+}
@@ -29,6 +29,7 @@ use crate::{
         filter::{ChannelFilters, ChannelPolicy},
         ChainConfig, Config,
     },
+    object::{Channel, Client, Connection, Object, Packet},
     path::PathIdentifiers,
     registry::Registry,
     supervisor::client_state_filter::{FilterPolicy, Permission},
@@ -97,6 +98,18 @@ pub struct ChainsScan {
     pub chains: Vec<Result<ChainScan, Error>>,
 }
 
+impl ChainsScan {
+    /// Returns the [`Object`]s for which workers may be spawned
+    /// for the clients, connections and channels found by the scan.
+    pub fn objects(&self) -> Vec<Object> {
+        self.chains
+            .iter()
+            .flatten()
+            .flat_map(|scan| scan.objects())
+            .collect()
+    }
+}
+
 impl Display for ChainsScan {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         for scan in self.chains.iter().flatten() {
@@ -149,6 +162,48 @@ impl ChainScan {
             clients: BTreeMap::new(),
         }
     }
+
+    /// Returns the client, connection, channel and packet [`Object`]s
+    /// for which workers may be spawned for this chain.
+    pub fn objects(&self) -> Vec<Object> {
+        let mut objects = Vec::new();
+
+        for client in self.clients.values() {
+            let counterparty_chain_id = client.counterparty_chain_id();
+
+            objects.push(Object::Client(Client {
+                dst_chain_id: self.chain_id.clone(),
+                dst_client_id: client.id().clone(),
+                src_chain_id: counterparty_chain_id.clone(),
+            }));
+
+            for connection in client.connections.values() {
+                objects.push(Object::Connection(Connection {
+                    dst_chain_id: counterparty_chain_id.clone(),
+                    src_chain_id: self.chain_id.clone(),
+                    src_connection_id: connection.id().clone(),
+                }));
+
+                for channel in connection.channels.values() {
+                    objects.push(Object::Channel(Channel {
+                        dst_chain_id: counterparty_chain_id.clone(),
+                        src_chain_id: self.chain_id.clone(),
+                        src_channel_id: channel.id().clone(),
+                        src_port_id: channel.port().clone(),
+                    }));
+
+                    objects.push(Object::Packet(Packet {
+                        dst_chain_id: counterparty_chain_id.clone(),
+                        src_chain_id: self.chain_id.clone(),
+                        src_channel_id: channel.id().clone(),
+                        src_port_id: channel.port().clone(),
+                    }));
+                }
+            }
+        }
+
+        objects
+    }
 }
 
 #[derive(Clone, Debug)]
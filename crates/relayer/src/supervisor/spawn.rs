@@ -1,4 +1,4 @@
-use tracing::{error, info};
+use tracing::{debug, error, info};
 
 use ibc_relayer_types::core::{
     ics02_client::client_state::ClientState, ics03_connection::connection::IdentifiedConnectionEnd,
@@ -41,6 +41,21 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
         }
     }
 
+    /// Whether this instance is in charge of the given [`Object`],
+    /// as per the sharding configuration.
+    fn owns(&self, object: &Object) -> bool {
+        let owned = self.config.sharding.owns(object);
+
+        if !owned {
+            debug!(
+                "not spawning worker {}, reason: it is assigned to another instance",
+                object.short_name()
+            );
+        }
+
+        owned
+    }
+
     pub fn spawn_workers(&mut self, scan: ChainsScan) {
         let _span = tracing::error_span!("spawn").entered();
 
@@ -197,6 +212,10 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
                 src_connection_id: connection.connection_id,
             });
 
+            if !self.owns(&connection_object) {
+                return Ok(false);
+            }
+
             self.workers
                 .spawn(chain, counterparty_chain, &connection_object, self.config)
                 .then(|| {
@@ -256,17 +275,27 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
                     src_chain_id: client.client_state.chain_id(),
                 });
 
-                self.workers
-                    .spawn(
-                        counterparty_chain.clone(),
-                        chain.clone(),
-                        &client_object,
-                        self.config,
-                    )
-                    .then(|| info!("spawned client worker: {}", client_object.short_name()));
+                if self.owns(&client_object) {
+                    self.workers
+                        .spawn(
+                            counterparty_chain.clone(),
+                            chain.clone(),
+                            &client_object,
+                            self.config,
+                        )
+                        .then(|| info!("spawned client worker: {}", client_object.short_name()));
+                }
             }
 
             if mode.packets.enabled {
+                // Create the Packet object
+                let path_object = Object::Packet(Packet {
+                    dst_chain_id: counterparty_chain.id(),
+                    src_chain_id: chain.id(),
+                    src_channel_id: channel_scan.channel.channel_id.clone(),
+                    src_port_id: channel_scan.channel.port_id.clone(),
+                });
+
                 let has_packets = || {
                     !channel_scan
                         .unreceived_packets_on_counterparty(&chain, &counterparty_chain)
@@ -281,16 +310,9 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
                         .is_empty()
                 };
 
-                // If there are any outstanding packets or acks to send, spawn the worker
-                if has_packets() || has_acks() {
-                    // Create the Packet object and spawn worker
-                    let path_object = Object::Packet(Packet {
-                        dst_chain_id: counterparty_chain.id(),
-                        src_chain_id: chain.id(),
-                        src_channel_id: channel_scan.channel.channel_id.clone(),
-                        src_port_id: channel_scan.channel.port_id.clone(),
-                    });
-
+                // If this instance owns the path and there are any outstanding
+                // packets or acks to send, spawn the worker
+                if self.owns(&path_object) && (has_packets() || has_acks()) {
                     self.workers
                         .spawn(
                             chain.clone(),
@@ -331,6 +353,10 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
                     src_port_id: channel_scan.channel.port_id,
                 });
 
+                if !self.owns(&channel_object) {
+                    return Ok(false);
+                }
+
                 self.workers
                     .spawn(chain, counterparty_chain, &channel_object, self.config)
                     .then(|| info!("spawned channel worker: {}", channel_object.short_name()));
@@ -7,21 +7,30 @@ use core::time::Duration;
 use flex_error::{define_error, DetailOnly};
 use ibc_proto::cosmos::base::v1beta1::Coin;
 use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::applications::transfer::denom::{
+    is_receiver_chain_source, PrefixedDenom, TracePrefix,
+};
 use ibc_relayer_types::applications::transfer::error::Error as Ics20Error;
 use ibc_relayer_types::applications::transfer::msgs::transfer::MsgTransfer;
 use ibc_relayer_types::applications::transfer::Amount;
+use ibc_relayer_types::core::ics02_client::client_state::ClientState;
 use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc_relayer_types::events::IbcEvent;
 use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::timestamp::{Timestamp, TimestampOverflowError};
 use ibc_relayer_types::tx_msg::Msg;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use subtle_encoding::hex;
 
+use crate::chain::counterparty::channel_connection_client;
 use crate::chain::endpoint::ChainStatus;
 use crate::chain::handle::ChainHandle;
 use crate::chain::tracking::TrackedMsgs;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
+use crate::supervisor::Error as SupervisorError;
 
 define_error! {
     TransferError {
@@ -68,6 +77,44 @@ define_error! {
 
         ZeroTimeout
             | _ | { "packet timeout height and packet timeout timestamp cannot both be 0" },
+
+        InvalidHop
+            { hop: String, reason: String }
+            |e| {
+                format!("invalid hop `{}`, expected `CHAIN_ID:PORT_ID/CHANNEL_ID`: {}",
+                    e.hop, e.reason)
+            },
+
+        HopChannel
+            { chain_id: ChainId, port_id: PortId, channel_id: ChannelId }
+            [ SupervisorError ]
+            |e| {
+                format!("failed to check channel {}/{} of chain {}",
+                    e.port_id, e.channel_id, e.chain_id)
+            },
+
+        HopChannelNotOpen
+            { chain_id: ChainId, port_id: PortId, channel_id: ChannelId }
+            |e| {
+                format!("channel {}/{} of chain {} is not open",
+                    e.port_id, e.channel_id, e.chain_id)
+            },
+
+        HopChainMismatch
+            { chain_id: ChainId, port_id: PortId, channel_id: ChannelId, expected: ChainId, actual: ChainId }
+            |e| {
+                format!("channel {}/{} of chain {} leads to chain {}, not to chain {}",
+                    e.port_id, e.channel_id, e.chain_id, e.actual, e.expected)
+            },
+
+        MissingHops
+            |_| { "no hop to forward the transfer through" },
+
+        MissingHopChain
+            { chain_id: ChainId }
+            |e| {
+                format!("no chain handle was given for hop chain {}", e.chain_id)
+            },
     }
 }
 
@@ -232,3 +279,372 @@ pub fn build_and_send_transfer_messages<SrcChain: ChainHandle, DstChain: ChainHa
     let msgs = build_transfer_messages(src_chain, dst_chain, opts)?;
     send_messages(src_chain, msgs)
 }
+
+/// A hop of a transfer forwarded by the packet-forward-middleware: the chain
+/// which receives the packet, and the port and channel of that chain on which
+/// the packet is forwarded to the next chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForwardHop {
+    pub chain_id: ChainId,
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+}
+
+impl FromStr for ForwardHop {
+    type Err = TransferError;
+
+    /// Parse a hop of the form `CHAIN_ID:PORT_ID/CHANNEL_ID`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: String| TransferError::invalid_hop(s.to_string(), reason);
+
+        let (chain_id, path) = s
+            .rsplit_once(':')
+            .ok_or_else(|| invalid("missing `:` after the chain identifier".to_string()))?;
+
+        let (port_id, channel_id) = path
+            .split_once('/')
+            .ok_or_else(|| invalid("missing `/` between the port and channel".to_string()))?;
+
+        let chain_id = ChainId::from_str(chain_id).map_err(|e| invalid(e.to_string()))?;
+        let port_id = PortId::from_str(port_id).map_err(|e| invalid(e.to_string()))?;
+        let channel_id = ChannelId::from_str(channel_id).map_err(|e| invalid(e.to_string()))?;
+
+        Ok(Self {
+            chain_id,
+            port_id,
+            channel_id,
+        })
+    }
+}
+
+/// How a transfer is forwarded by the packet-forward-middleware of the
+/// intermediate chains, before reaching its destination chain
+#[derive(Clone, Debug)]
+pub struct ForwardOptions {
+    pub hops: Vec<ForwardHop>,
+    /// The timeout of the packets sent by each hop
+    pub timeout: Duration,
+    /// How many times each hop retries to forward the packet if it fails
+    pub retries: u8,
+}
+
+/// The route of a forwarded transfer, once checked against the channels of its chains
+#[derive(Clone, Debug)]
+pub struct ForwardRoute {
+    /// The receiver of the transfer on the first hop chain
+    pub receiver: String,
+    /// The memo instructing the hops to forward the transfer
+    pub memo: String,
+    /// The denomination of the coins received on the destination chain,
+    /// with its trace path, eg. `transfer/channel-1/transfer/channel-0/uatom`
+    pub denom_trace: PrefixedDenom,
+    /// The `ibc/` denomination of the coins received on the destination chain
+    pub denom: String,
+}
+
+/// One of the packets of a forwarded transfer: the port and channel it is
+/// sent on, and the port and channel of the chain receiving it
+struct ForwardLeg {
+    src_port_id: PortId,
+    src_channel_id: ChannelId,
+    dst_port_id: PortId,
+    dst_channel_id: ChannelId,
+}
+
+/// Check the route of a transfer from `src_chain` to `dst_chain` through the given hops,
+/// ie. that each of its channels is open and leads to the next chain of the route,
+/// and build the memo instructing the hops to forward the transfer.
+///
+/// The chain handles of the hops must be given in the order of the hops.
+pub fn build_forward_route<SrcChain: ChainHandle, HopChain: ChainHandle, DstChain: ChainHandle>(
+    src_chain: &SrcChain,
+    hop_chains: &[HopChain],
+    dst_chain: &DstChain,
+    opts: &TransferOptions,
+    forward: &ForwardOptions,
+) -> Result<ForwardRoute, TransferError> {
+    if forward.hops.is_empty() {
+        return Err(TransferError::missing_hops());
+    }
+
+    let hop_chain = |hop: &ForwardHop| {
+        hop_chains
+            .iter()
+            .find(|chain| chain.id() == hop.chain_id)
+            .ok_or_else(|| TransferError::missing_hop_chain(hop.chain_id.clone()))
+    };
+
+    let next_chain_ids = forward
+        .hops
+        .iter()
+        .map(|hop| hop.chain_id.clone())
+        .chain(core::iter::once(dst_chain.id()));
+
+    let mut legs = Vec::with_capacity(forward.hops.len() + 1);
+    let mut receivers = Vec::with_capacity(forward.hops.len());
+
+    for (index, next_chain_id) in next_chain_ids.enumerate() {
+        let leg = match index.checked_sub(1).map(|i| &forward.hops[i]) {
+            None => check_forward_leg(
+                src_chain,
+                &opts.src_port_id,
+                &opts.src_channel_id,
+                &next_chain_id,
+            )?,
+            Some(hop) => {
+                let chain = hop_chain(hop)?;
+                receivers.push(chain.get_signer().map_err(TransferError::key)?);
+
+                check_forward_leg(chain, &hop.port_id, &hop.channel_id, &next_chain_id)?
+            }
+        };
+
+        legs.push(leg);
+    }
+
+    let final_receiver = match &opts.receiver {
+        Some(receiver) => Signer::from_str(receiver).map_err(TransferError::receiver_address)?,
+        None => dst_chain.get_signer().map_err(TransferError::key)?,
+    };
+
+    // Each hop forwards the tokens to the receiver of the next hop,
+    // and the last one to the final receiver
+    let first_receiver = receivers.remove(0);
+    receivers.push(final_receiver);
+
+    let memo = build_forward_memo(&forward.hops, &receivers, forward.timeout, forward.retries);
+
+    let denom = match opts.denom.strip_prefix("ibc/") {
+        Some(hash) => {
+            let trace = src_chain
+                .query_denom_trace(hash.to_string())
+                .map_err(TransferError::relayer)?;
+
+            if trace.path.is_empty() {
+                trace.base_denom
+            } else {
+                format!("{}/{}", trace.path, trace.base_denom)
+            }
+        }
+        None => opts.denom.clone(),
+    };
+
+    let denom_trace = trace_forwarded_denom(
+        PrefixedDenom::from_str(&denom).map_err(TransferError::token_transfer)?,
+        &legs,
+    );
+
+    Ok(ForwardRoute {
+        receiver: first_receiver.to_string(),
+        memo: memo.to_string(),
+        denom: ibc_denom(&denom_trace),
+        denom_trace,
+    })
+}
+
+/// Check that the given channel of the given chain is open and leads to the
+/// chain with the given identifier, returning the leg sent on that channel.
+fn check_forward_leg<Chain: ChainHandle>(
+    chain: &Chain,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    next_chain_id: &ChainId,
+) -> Result<ForwardLeg, TransferError> {
+    let channel = channel_connection_client(chain, port_id, channel_id).map_err(|e| {
+        TransferError::hop_channel(chain.id(), port_id.clone(), channel_id.clone(), e)
+    })?;
+
+    let channel_end = &channel.channel.channel_end;
+
+    let dst_channel_id = match &channel_end.remote.channel_id {
+        Some(dst_channel_id) if channel_end.is_open() => dst_channel_id.clone(),
+        _ => {
+            return Err(TransferError::hop_channel_not_open(
+                chain.id(),
+                port_id.clone(),
+                channel_id.clone(),
+            ))
+        }
+    };
+
+    let counterparty_chain_id = channel.client.client_state.chain_id();
+    if &counterparty_chain_id != next_chain_id {
+        return Err(TransferError::hop_chain_mismatch(
+            chain.id(),
+            port_id.clone(),
+            channel_id.clone(),
+            next_chain_id.clone(),
+            counterparty_chain_id,
+        ));
+    }
+
+    Ok(ForwardLeg {
+        src_port_id: port_id.clone(),
+        src_channel_id: channel_id.clone(),
+        dst_port_id: channel_end.remote.port_id.clone(),
+        dst_channel_id,
+    })
+}
+
+/// Build the packet-forward-middleware memo forwarding a transfer through the
+/// given hops, each of them forwarding the tokens to the corresponding receiver,
+/// ie. the receiver on the chain of the next hop, or on the destination chain.
+fn build_forward_memo(
+    hops: &[ForwardHop],
+    receivers: &[Signer],
+    timeout: Duration,
+    retries: u8,
+) -> Value {
+    hops.iter()
+        .zip(receivers)
+        .rev()
+        .fold(None, |next: Option<Value>, (hop, receiver)| {
+            let mut forward = json!({
+                "receiver": receiver.as_ref(),
+                "port": hop.port_id.as_str(),
+                "channel": hop.channel_id.as_str(),
+                "timeout": format!("{}s", timeout.as_secs()),
+                "retries": retries,
+            });
+
+            if let Some(next) = next {
+                forward["next"] = next;
+            }
+
+            Some(json!({ "forward": forward }))
+        })
+        .unwrap_or(Value::Null)
+}
+
+/// The denomination of the coins received at the end of the given legs,
+/// following the ICS-20 rules for adding or removing trace prefixes.
+fn trace_forwarded_denom(mut denom: PrefixedDenom, legs: &[ForwardLeg]) -> PrefixedDenom {
+    for leg in legs {
+        if is_receiver_chain_source(leg.src_port_id.clone(), leg.src_channel_id.clone(), &denom) {
+            denom.remove_trace_prefix(&TracePrefix::new(
+                leg.src_port_id.clone(),
+                leg.src_channel_id.clone(),
+            ));
+        } else {
+            denom.add_trace_prefix(TracePrefix::new(
+                leg.dst_port_id.clone(),
+                leg.dst_channel_id.clone(),
+            ));
+        }
+    }
+
+    denom
+}
+
+/// The denomination of the given coins on chain, ie. `ibc/{hash}` where `hash`
+/// is the upper-case hex encoded SHA-256 hash of the trace path and base denomination,
+/// or the base denomination itself for native coins.
+fn ibc_denom(denom: &PrefixedDenom) -> String {
+    let full = denom.to_string();
+
+    if !full.contains('/') {
+        return full;
+    }
+
+    let hash = Sha256::digest(full.as_bytes());
+    format!("ibc/{}", String::from_utf8_lossy(&hex::encode_upper(hash)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hop(chain_id: &str, channel_id: u64) -> ForwardHop {
+        ForwardHop {
+            chain_id: ChainId::from_string(chain_id),
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::new(channel_id),
+        }
+    }
+
+    fn leg(src_channel_id: u64, dst_channel_id: u64) -> ForwardLeg {
+        ForwardLeg {
+            src_port_id: PortId::transfer(),
+            src_channel_id: ChannelId::new(src_channel_id),
+            dst_port_id: PortId::transfer(),
+            dst_channel_id: ChannelId::new(dst_channel_id),
+        }
+    }
+
+    #[test]
+    fn parse_hop() {
+        assert_eq!(
+            "osmosis-1:transfer/channel-42"
+                .parse::<ForwardHop>()
+                .unwrap(),
+            hop("osmosis-1", 42)
+        );
+
+        assert!("osmosis-1".parse::<ForwardHop>().is_err());
+        assert!("osmosis-1:transfer".parse::<ForwardHop>().is_err());
+        assert!("osmosis-1:transfer/channel".parse::<ForwardHop>().is_err());
+    }
+
+    #[test]
+    fn forward_memo_nests_the_hops() {
+        let receivers = [
+            Signer::from_str("osmo1receiver").unwrap(),
+            Signer::from_str("juno1receiver").unwrap(),
+        ];
+
+        let memo = build_forward_memo(
+            &[hop("osmosis-1", 1), hop("juno-1", 2)],
+            &receivers,
+            Duration::from_secs(600),
+            2,
+        );
+
+        assert_eq!(
+            memo,
+            json!({
+                "forward": {
+                    "receiver": "osmo1receiver",
+                    "port": "transfer",
+                    "channel": "channel-1",
+                    "timeout": "600s",
+                    "retries": 2,
+                    "next": {
+                        "forward": {
+                            "receiver": "juno1receiver",
+                            "port": "transfer",
+                            "channel": "channel-2",
+                            "timeout": "600s",
+                            "retries": 2,
+                        }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn forwarded_denom_is_traced() {
+        // A native coin gains the prefix of each chain it is received on
+        let denom = trace_forwarded_denom("uatom".parse().unwrap(), &[leg(0, 10), leg(1, 20)]);
+        assert_eq!(
+            denom.to_string(),
+            "transfer/channel-20/transfer/channel-10/uatom"
+        );
+
+        // A voucher sent back to the chain it came from loses its prefix
+        let denom = trace_forwarded_denom(
+            "transfer/channel-0/uosmo".parse().unwrap(),
+            &[leg(0, 10), leg(1, 20)],
+        );
+        assert_eq!(denom.to_string(), "transfer/channel-20/uosmo");
+    }
+
+    #[test]
+    fn ibc_denom_hashes_the_trace() {
+        assert_eq!(ibc_denom(&"uatom".parse().unwrap()), "uatom");
+        assert_eq!(
+            ibc_denom(&"transfer/channel-141/uosmo".parse().unwrap()),
+            "ibc/14F9BC3E44B8A9C1BE1FB08980FAB87034C9905EF17CF2F5008FC085218811CC"
+        );
+    }
+}
@@ -3,11 +3,15 @@
 use core::time::Duration;
 use std::ops::Add;
 
+use bech32::{ToBase32, Variant};
 use bytes::BufMut;
 use flex_error::define_error;
+use prost::Message;
+use sha2::{Digest, Sha256};
 
 use tendermint::Hash as TxHash;
 
+use ibc_proto::cosmos::base::v1beta1::Coin;
 use ibc_proto::cosmos::gov::v1beta1::MsgSubmitProposal;
 use ibc_proto::cosmos::upgrade::v1beta1::Plan;
 use ibc_proto::google::protobuf::Any;
@@ -15,6 +19,7 @@ use ibc_proto::ibc::core::client::v1::UpgradeProposal;
 use ibc_relayer_types::clients::ics07_tendermint::client_state::UpgradeOptions;
 use ibc_relayer_types::core::ics02_client::client_state::ClientState;
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::{downcast, Height};
 
 use crate::chain::handle::ChainHandle;
@@ -49,6 +54,10 @@ define_error! {
         TendermintOnly
             |_| { "only Tendermint clients can be upgraded" },
 
+        GovAuthority
+            [ Error ]
+            |_| { "failed to derive the address of the governance module" },
+
         UpgradeHeightRevision
             { revision: u64 }
             |r| {
@@ -113,16 +122,61 @@ pub fn build_and_send_ibc_upgrade_proposal(
         opts.upgraded_chain_id.clone(),
     );
 
+    let upgraded_client_state = Any::from(AnyClientState::from(client_state));
+
+    let plan = Plan {
+        name: opts.upgrade_plan_name.clone(),
+        height: plan_height.revision_height() as i64,
+        info: "".to_string(),
+        ..Default::default() // deprecated fields - time & upgraded_client_state
+    };
+
+    // build the msg submit proposal
+    let proposer = dst_chain.get_signer().map_err(UpgradeChainError::key)?;
+
+    let coins = Coin {
+        denom: opts.denom.clone(),
+        amount: opts.amount.to_string(),
+    };
+
+    let ibc_version = dst_chain.ibc_version().map_err(UpgradeChainError::query)?;
+
+    // Since ibc-go v8, the legacy `UpgradeProposal` is replaced with a `MsgIBCSoftwareUpgrade`
+    // executed by the governance module, submitted in a `x/gov` v1 proposal.
+    let any_msg = if ibc_version.map_or(false, |version| version.major >= 8) {
+        let config = dst_chain.config().map_err(UpgradeChainError::query)?;
+
+        let authority =
+            gov_module_address(&config.account_prefix).map_err(UpgradeChainError::gov_authority)?;
+
+        software_upgrade_proposal(plan, upgraded_client_state, coins, &proposer, authority)
+    } else {
+        legacy_upgrade_proposal(plan, upgraded_client_state, coins, &proposer)
+    };
+
+    // Can't use send_messages_and_wait_commit because no IBC events
+    // corresponding to the transaction can be recognized to confirm the
+    // upgrade.
+    // https://github.com/informalsystems/hermes/issues/1288#issuecomment-1066884163
+
+    let responses = dst_chain
+        .send_messages_and_wait_check_tx(TrackedMsgs::new_single(any_msg, "upgrade"))
+        .map_err(|e| UpgradeChainError::submit(dst_chain.id(), e))?;
+
+    Ok(responses[0].hash)
+}
+
+fn legacy_upgrade_proposal(
+    plan: Plan,
+    upgraded_client_state: Any,
+    deposit: Coin,
+    proposer: &Signer,
+) -> Any {
     let proposal = UpgradeProposal {
         title: "proposal 0".to_string(),
         description: "upgrade the chain software and unbonding period".to_string(),
-        upgraded_client_state: Some(Any::from(AnyClientState::from(client_state))),
-        plan: Some(Plan {
-            name: opts.upgrade_plan_name.clone(),
-            height: plan_height.revision_height() as i64,
-            info: "".to_string(),
-            ..Default::default() // deprecated fields - time & upgraded_client_state
-        }),
+        upgraded_client_state: Some(upgraded_client_state),
+        plan: Some(plan),
     };
 
     let proposal = Proposal::Default(proposal);
@@ -134,37 +188,97 @@ pub fn build_and_send_ibc_upgrade_proposal(
         value: buf_proposal,
     };
 
-    // build the msg submit proposal
-    let proposer = dst_chain.get_signer().map_err(UpgradeChainError::key)?;
-
-    let coins = ibc_proto::cosmos::base::v1beta1::Coin {
-        denom: opts.denom.clone(),
-        amount: opts.amount.to_string(),
-    };
-
     let msg = MsgSubmitProposal {
         content: Some(any_proposal),
-        initial_deposit: vec![coins],
+        initial_deposit: vec![deposit],
         proposer: proposer.to_string(),
     };
 
     let mut buf_msg = Vec::new();
     prost::Message::encode(&msg, &mut buf_msg).unwrap();
-    let any_msg = Any {
+    Any {
         type_url: "/cosmos.gov.v1beta1.MsgSubmitProposal".to_string(),
         value: buf_msg,
+    }
+}
+
+fn software_upgrade_proposal(
+    plan: Plan,
+    upgraded_client_state: Any,
+    deposit: Coin,
+    proposer: &Signer,
+    authority: String,
+) -> Any {
+    let upgrade = proto::MsgIbcSoftwareUpgrade {
+        plan: Some(plan),
+        upgraded_client_state: Some(upgraded_client_state),
+        signer: authority,
     };
 
-    // Can't use send_messages_and_wait_commit because no IBC events
-    // corresponding to the transaction can be recognized to confirm the
-    // upgrade.
-    // https://github.com/informalsystems/hermes/issues/1288#issuecomment-1066884163
+    let msg = proto::MsgSubmitProposal {
+        messages: vec![Any {
+            type_url: proto::MSG_IBC_SOFTWARE_UPGRADE_TYPE_URL.to_string(),
+            value: upgrade.encode_to_vec(),
+        }],
+        initial_deposit: vec![deposit],
+        proposer: proposer.to_string(),
+        metadata: "".to_string(),
+        title: "proposal 0".to_string(),
+        summary: "upgrade the chain software and unbonding period".to_string(),
+    };
 
-    let responses = dst_chain
-        .send_messages_and_wait_check_tx(TrackedMsgs::new_single(any_msg, "upgrade"))
-        .map_err(|e| UpgradeChainError::submit(dst_chain.id(), e))?;
+    Any {
+        type_url: proto::MSG_SUBMIT_PROPOSAL_TYPE_URL.to_string(),
+        value: msg.encode_to_vec(),
+    }
+}
 
-    Ok(responses[0].hash)
+/// The address of the governance module account, which is the authority
+/// allowed to execute the `MsgIBCSoftwareUpgrade` message.
+pub(crate) fn gov_module_address(account_prefix: &str) -> Result<String, Error> {
+    let digest = Sha256::digest(b"gov");
+
+    bech32::encode(account_prefix, (&digest[..20]).to_base32(), Variant::Bech32)
+        .map_err(Error::bech32_encoding)
+}
+
+// The protobuf messages below are not part of `ibc-proto`, they are taken from:
+// - https://github.com/cosmos/ibc-go/blob/v8.0.0/proto/ibc/core/client/v1/tx.proto
+// - https://github.com/cosmos/cosmos-sdk/blob/v0.47.0/proto/cosmos/gov/v1/tx.proto
+pub(crate) mod proto {
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+    use ibc_proto::cosmos::upgrade::v1beta1::Plan;
+    use ibc_proto::google::protobuf::Any;
+    use prost::Message;
+
+    pub const MSG_IBC_SOFTWARE_UPGRADE_TYPE_URL: &str = "/ibc.core.client.v1.MsgIBCSoftwareUpgrade";
+    pub const MSG_SUBMIT_PROPOSAL_TYPE_URL: &str = "/cosmos.gov.v1.MsgSubmitProposal";
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct MsgIbcSoftwareUpgrade {
+        #[prost(message, optional, tag = "1")]
+        pub plan: Option<Plan>,
+        #[prost(message, optional, tag = "2")]
+        pub upgraded_client_state: Option<Any>,
+        #[prost(string, tag = "3")]
+        pub signer: String,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct MsgSubmitProposal {
+        #[prost(message, repeated, tag = "1")]
+        pub messages: Vec<Any>,
+        #[prost(message, repeated, tag = "2")]
+        pub initial_deposit: Vec<Coin>,
+        #[prost(string, tag = "3")]
+        pub proposer: String,
+        #[prost(string, tag = "4")]
+        pub metadata: String,
+        #[prost(string, tag = "5")]
+        pub title: String,
+        #[prost(string, tag = "6")]
+        pub summary: String,
+    }
 }
 
 enum Proposal {
@@ -186,3 +300,55 @@ impl Proposal {
         .to_owned()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derive_gov_module_address() {
+        assert_eq!(
+            gov_module_address("cosmos").unwrap(),
+            "cosmos10d07y265gmmuvt4z0w9aw880jnsr700j6zn9kn"
+        );
+    }
+
+    #[test]
+    fn software_upgrade_is_executed_by_gov_authority() {
+        let plan = Plan {
+            name: "v2".to_string(),
+            height: 100,
+            ..Default::default()
+        };
+
+        let deposit = Coin {
+            denom: "stake".to_string(),
+            amount: "10000000".to_string(),
+        };
+
+        let proposer = "cosmos1proposer".parse().unwrap();
+        let authority = gov_module_address("cosmos").unwrap();
+
+        let any = software_upgrade_proposal(
+            plan.clone(),
+            Any::default(),
+            deposit,
+            &proposer,
+            authority.clone(),
+        );
+        assert_eq!(any.type_url, proto::MSG_SUBMIT_PROPOSAL_TYPE_URL);
+
+        let proposal = proto::MsgSubmitProposal::decode(any.value.as_slice()).unwrap();
+        assert_eq!(proposal.proposer, "cosmos1proposer");
+        assert_eq!(proposal.messages.len(), 1);
+        assert_eq!(
+            proposal.messages[0].type_url,
+            proto::MSG_IBC_SOFTWARE_UPGRADE_TYPE_URL
+        );
+
+        let upgrade =
+            proto::MsgIbcSoftwareUpgrade::decode(proposal.messages[0].value.as_slice()).unwrap();
+        assert_eq!(upgrade.signer, authority);
+        assert_eq!(upgrade.plan, Some(plan));
+    }
+}
@@ -1,5 +1,5 @@
 use alloc::collections::VecDeque;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, RwLock, Weak};
 
 use crate::util::lock::LockExt;
 
@@ -50,6 +50,11 @@ impl<T> Queue<T> {
     pub fn take(&self) -> VecDeque<T> {
         self.0.acquire_write().drain(..).collect()
     }
+
+    /// A reference to the queue which does not keep it alive
+    pub fn downgrade(&self) -> Weak<RwLock<VecDeque<T>>> {
+        Arc::downgrade(&self.0)
+    }
 }
 
 impl<T: Clone> Queue<T> {
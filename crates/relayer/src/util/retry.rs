@@ -61,13 +61,13 @@ pub fn clamp_total(
         move |elapsed, delay| {
             let next = if *elapsed >= max_total_delay {
                 None
-            } else if (*elapsed + delay) > max_total_delay {
+            } else if elapsed.saturating_add(delay) > max_total_delay {
                 Some(max_total_delay - *elapsed)
             } else {
                 Some(delay)
             };
 
-            *elapsed += delay;
+            *elapsed = elapsed.saturating_add(delay);
             next
         },
     )
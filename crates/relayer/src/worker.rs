@@ -9,7 +9,7 @@ use crate::foreign_client::ForeignClient;
 use crate::link::{Link, LinkParameters, Resubmit};
 use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
-    config::Config,
+    config::{Config, RetryKind},
     object::Object,
 };
 
@@ -61,6 +61,7 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
     config: &Config,
 ) -> WorkerHandle {
     let mut task_handles = Vec::new();
+    let mut pending_txs = Vec::new();
 
     let (cmd_tx, data) = match &object {
         Object::Client(client) => {
@@ -68,7 +69,11 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
 
             let (mut refresh, mut misbehaviour) = (false, false);
 
-            let refresh_task = client::spawn_refresh_client(client.clone());
+            let refresh_task = client::spawn_refresh_client(
+                client.clone(),
+                config.retry_policy(object.dst_chain_id(), RetryKind::Client),
+                config.mode.clients.expiry_warning_threshold,
+            );
             if let Some(refresh_task) = refresh_task {
                 task_handles.push(refresh_task);
                 refresh = true;
@@ -96,21 +101,30 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
         }
         Object::Connection(connection) => {
             let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
-            let connection_task =
-                connection::spawn_connection_worker(connection.clone(), chains, cmd_rx);
+            let connection_task = connection::spawn_connection_worker(
+                connection.clone(),
+                chains,
+                cmd_rx,
+                config.retry_policy(&connection.dst_chain_id, RetryKind::Connection),
+            );
             task_handles.push(connection_task);
 
             (Some(cmd_tx), None)
         }
         Object::Channel(channel) => {
             let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
-            let channel_task = channel::spawn_channel_worker(channel.clone(), chains, cmd_rx);
+            let channel_task = channel::spawn_channel_worker(
+                channel.clone(),
+                chains,
+                cmd_rx,
+                config.channel_retry_policy(&channel.dst_chain_id),
+            );
             task_handles.push(channel_task);
 
             (Some(cmd_tx), None)
         }
         Object::Packet(path) => {
-            let packets_config = config.mode.packets;
+            let packets_config = &config.mode.packets;
             let link_res = Link::new_from_opts(
                 chains.a.clone(),
                 chains.b,
@@ -123,11 +137,17 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
             );
 
             match link_res {
-                Ok(link) => {
+                Ok(mut link) => {
+                    link.a_to_b.set_scheduling(&packets_config.scheduling);
+                    link.a_to_b
+                        .set_retry(config.retry_policy(&path.dst_chain_id, RetryKind::Packet));
+
                     let channel_ordering = link.a_to_b.channel().ordering;
                     let should_clear_on_start =
                         packets_config.clear_on_start || channel_ordering == Ordering::Ordered;
 
+                    pending_txs = link.a_to_b.pending_tx_hashes();
+
                     let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
                     let link = Arc::new(Mutex::new(link));
                     let resubmit = Resubmit::from_clear_interval(packets_config.clear_interval);
@@ -202,5 +222,5 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
         }
     };
 
-    WorkerHandle::new(id, object, data, cmd_tx, task_handles)
+    WorkerHandle::new(id, object, data, cmd_tx, task_handles, pending_txs)
 }
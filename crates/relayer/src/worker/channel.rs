@@ -7,6 +7,7 @@ use crate::util::retry::RetryResult;
 use crate::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
 use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
+    config::RetryPolicy,
     object::Channel,
     util::retry::retry_with_index,
 };
@@ -28,17 +29,31 @@ fn max_block_times<ChainA: ChainHandle, ChainB: ChainHandle>(
     a_block_time.max(b_block_time)
 }
 
+/// The delays between the retries of a handshake step: the ones of the configured
+/// policy if any, or else the default ones derived from the chains' `max_block_time`.
+fn retry_delays<ChainA: ChainHandle, ChainB: ChainHandle>(
+    retry: &Option<RetryPolicy>,
+    chains: &ChainHandlePair<ChainA, ChainB>,
+) -> Box<dyn Iterator<Item = Duration>> {
+    match retry {
+        Some(retry) => Box::new(retry.delays()),
+        None => Box::new(channel_handshake_retry::default_strategy(max_block_times(
+            chains,
+        ))),
+    }
+}
+
 pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     channel: Channel,
     chains: ChainHandlePair<ChainA, ChainB>,
     cmd_rx: Receiver<WorkerCmd>,
+    retry: Option<RetryPolicy>,
 ) -> TaskHandle {
     let mut complete_handshake_on_new_block = true;
     spawn_background_task(
         error_span!("worker.channel", channel = %channel.short_name()),
         Some(Duration::from_millis(200)),
         move || {
-            let max_block_times = max_block_times(&chains);
             if let Ok(cmd) = cmd_rx.try_recv() {
                 match cmd {
                     WorkerCmd::IbcEvents { batch } => {
@@ -49,9 +64,8 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
 
                         complete_handshake_on_new_block = false;
                         if let Some(event_with_height) = last_event {
-                            retry_with_index(
-                                channel_handshake_retry::default_strategy(max_block_times),
-                                |index| match RelayChannel::restore_from_event(
+                            retry_with_index(retry_delays(&retry, &chains), |index| {
+                                match RelayChannel::restore_from_event(
                                     chains.a.clone(),
                                     chains.b.clone(),
                                     event_with_height.event.clone(),
@@ -59,8 +73,8 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                                     Ok(mut handshake_channel) => handshake_channel
                                         .step_event(&event_with_height.event, index),
                                     Err(_) => RetryResult::Retry(index),
-                                },
-                            )
+                                }
+                            })
                             .map_err(|e| TaskError::Fatal(RunError::retry(e)))
                         } else {
                             Ok(Next::Continue)
@@ -78,9 +92,8 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                             .map_err(|e| TaskError::Fatal(RunError::ics02(e)))?;
 
                         complete_handshake_on_new_block = false;
-                        retry_with_index(
-                            channel_handshake_retry::default_strategy(max_block_times),
-                            |index| match RelayChannel::restore_from_state(
+                        retry_with_index(retry_delays(&retry, &chains), |index| {
+                            match RelayChannel::restore_from_state(
                                 chains.a.clone(),
                                 chains.b.clone(),
                                 channel.clone(),
@@ -90,8 +103,8 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                                     handshake_channel.step_state(state, index)
                                 }
                                 Err(_) => RetryResult::Retry(index),
-                            },
-                        )
+                            }
+                        })
                         .map_err(|e| TaskError::Fatal(RunError::retry(e)))
                     }
 
@@ -1,7 +1,6 @@
 use core::convert::Infallible;
 use core::time::Duration;
 use crossbeam_channel::Receiver;
-use retry::delay::Fibonacci;
 use retry::retry_with_index;
 use std::time::Instant;
 use tracing::{debug, debug_span, error_span, trace, warn};
@@ -9,22 +8,23 @@ use tracing::{debug, debug_span, error_span, trace, warn};
 use ibc_relayer_types::core::ics02_client::events::UpdateClient;
 use ibc_relayer_types::events::IbcEvent;
 
-use crate::util::retry::clamp_total;
+use crate::telemetry;
 use crate::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
 use crate::{
     chain::handle::ChainHandle,
+    config::RetryPolicy,
     foreign_client::{ForeignClient, MisbehaviourResults},
 };
 
 use super::WorkerCmd;
 
 const REFRESH_INTERVAL: Duration = Duration::from_secs(2); // 2 seconds
-const INITIAL_BACKOFF: Duration = Duration::from_secs(1); // 1 second
-const MAX_REFRESH_DELAY: Duration = Duration::from_secs(60 * 60); // 1 hour
-const MAX_REFRESH_TOTAL_DELAY: Duration = Duration::from_secs(60 * 60 * 24); // 1 day
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(60); // 1 minute
 
 pub fn spawn_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
     mut client: ForeignClient<ChainA, ChainB>,
+    retry: RetryPolicy,
+    expiry_warning_threshold: Duration,
 ) -> Option<TaskHandle> {
     if client.is_expired_or_frozen() {
         warn!(
@@ -38,6 +38,7 @@ pub fn spawn_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
     // Compute the refresh interval as a fraction of the client's trusting period
     // If the trusting period or the client state is not retrieved, fallback to a default value.
     let mut next_refresh = Instant::now() + REFRESH_INTERVAL;
+    let mut next_expiry_check = Instant::now();
     Some(spawn_background_task(
         error_span!(
             "worker.client.refresh",
@@ -47,6 +48,11 @@ pub fn spawn_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
         ),
         Some(Duration::from_secs(1)),
         move || {
+            if Instant::now() >= next_expiry_check {
+                check_expiry(&client, expiry_warning_threshold);
+                next_expiry_check = Instant::now() + EXPIRY_CHECK_INTERVAL;
+            }
+
             // This is used for integration tests until `spawn_background_task`
             // uses async instead of threads
             if Instant::now() < next_refresh {
@@ -54,7 +60,15 @@ pub fn spawn_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
             }
 
             // Use retry mechanism only if `client.refresh()` fails.
-            let res = retry_with_index(refresh_strategy(), |_| client.refresh());
+            // Check how close the client is to expiry after each failure,
+            // so that repeated failures do not go unnoticed.
+            let res = retry_with_index(retry.delays(), |_| {
+                let res = client.refresh();
+                if res.is_err() {
+                    check_expiry(&client, expiry_warning_threshold);
+                }
+                res
+            });
 
             match res {
                 // If `client.refresh()` was successful, update the `next_refresh` call.
@@ -71,6 +85,40 @@ pub fn spawn_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
     ))
 }
 
+/// Record how long the client can go without being updated before it expires,
+/// and warn if it is about to expire, ie. within the given threshold.
+fn check_expiry<ChainA: ChainHandle, ChainB: ChainHandle>(
+    client: &ForeignClient<ChainA, ChainB>,
+    warning_threshold: Duration,
+) {
+    let time_to_expiry = match client.time_to_expiry() {
+        Ok(Some(time_to_expiry)) => time_to_expiry,
+        Ok(None) => return,
+        Err(e) => {
+            debug!("failed to compute the time to expiry of the client: {e}");
+            return;
+        }
+    };
+
+    telemetry!(
+        client_time_to_expiry,
+        &client.src_chain.id(),
+        &client.dst_chain.id(),
+        &client.id,
+        time_to_expiry.as_secs()
+    );
+
+    if time_to_expiry.is_zero() {
+        warn!("client is expired or frozen");
+    } else if time_to_expiry < warning_threshold {
+        warn!(
+            ?time_to_expiry,
+            ?warning_threshold,
+            "client is about to expire, it must be updated before its trusting period elapses",
+        );
+    }
+}
+
 pub fn detect_misbehavior_task<ChainA: ChainHandle, ChainB: ChainHandle>(
     receiver: Receiver<WorkerCmd>,
     client: ForeignClient<ChainB, ChainA>,
@@ -169,11 +217,3 @@ fn on_client_update<ChainA: ChainHandle, ChainB: ChainHandle>(
         }
     }
 }
-
-fn refresh_strategy() -> impl Iterator<Item = Duration> {
-    clamp_total(
-        Fibonacci::from(INITIAL_BACKOFF),
-        MAX_REFRESH_DELAY,
-        MAX_REFRESH_TOTAL_DELAY,
-    )
-}
@@ -6,9 +6,9 @@ use crate::connection::Connection as RelayConnection;
 use crate::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
 use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
+    config::RetryPolicy,
     object::Connection,
     util::retry::retry_with_index,
-    worker::retry_strategy,
 };
 
 use super::error::RunError;
@@ -18,6 +18,7 @@ pub fn spawn_connection_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     connection: Connection,
     chains: ChainHandlePair<ChainA, ChainB>,
     cmd_rx: Receiver<WorkerCmd>,
+    retry: RetryPolicy,
 ) -> TaskHandle {
     let mut complete_handshake_on_new_block = true;
     spawn_background_task(
@@ -42,7 +43,7 @@ pub fn spawn_connection_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                             )
                             .map_err(|e| TaskError::Fatal(RunError::connection(e)))?;
 
-                            retry_with_index(retry_strategy::worker_default_strategy(), |index| {
+                            retry_with_index(retry.delays(), |index| {
                                 handshake_connection.step_event(&event_with_height.event, index)
                             })
                             .map_err(|e| TaskError::Fatal(RunError::retry(e)))
@@ -72,7 +73,7 @@ pub fn spawn_connection_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
 
                         complete_handshake_on_new_block = false;
 
-                        retry_with_index(retry_strategy::worker_default_strategy(), |index| {
+                        retry_with_index(retry.delays(), |index| {
                             handshake_connection.step_state(state, index)
                         })
                         .map_err(|e| TaskError::Fatal(RunError::retry(e)))
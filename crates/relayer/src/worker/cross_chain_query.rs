@@ -8,27 +8,38 @@ use crate::error::Error;
 use crate::event::IbcEventWithHeight;
 use crate::foreign_client::ForeignClient;
 use crate::object::CrossChainQuery;
+use crate::telemetry;
 use crate::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
 use crate::worker::WorkerCmd;
 
 use crossbeam_channel::Receiver;
+use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::applications::ics31_icq::events::CrossChainQueryPacket;
+use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
 use ibc_relayer_types::core::ics02_client::height::Height;
+use ibc_relayer_types::core::ics24_host::identifier::ConnectionId;
 use std::time::Duration;
-use tracing::{info, info_span};
+use tracing::{info, info_span, warn};
 use uuid::Uuid;
 
+impl From<&CrossChainQueryPacket> for CrossChainQueryRequest {
+    fn from(packet: &CrossChainQueryPacket) -> Self {
+        CrossChainQueryRequest {
+            chain_id: packet.chain_id.clone(),
+            query_id: packet.query_id.to_string(),
+            query_type: packet.query_type.clone(),
+            request: packet.request.clone(),
+            height: packet.height,
+        }
+    }
+}
+
 impl TryFrom<&IbcEventWithHeight> for CrossChainQueryRequest {
     type Error = Error;
 
     fn try_from(ibc_event_with_height: &IbcEventWithHeight) -> Result<Self, Self::Error> {
         match ibc_event_with_height.event.cross_chain_query_packet() {
-            Some(packet) => Ok(CrossChainQueryRequest {
-                chain_id: packet.chain_id.clone(),
-                query_id: packet.query_id.to_string(),
-                query_type: packet.query_type.clone(),
-                request: packet.request.clone(),
-                height: packet.height,
-            }),
+            Some(packet) => Ok(packet.into()),
             None => Err(Error::ics31(
                 ibc_relayer_types::applications::ics31_icq::error::Error::parse(),
             )),
@@ -66,72 +77,202 @@ fn handle_cross_chain_query<ChainA: ChainHandle, ChainB: ChainHandle>(
     cross_chain_query: &CrossChainQuery,
 ) -> Result<(), TaskError<RunError>> {
     if let WorkerCmd::IbcEvents { batch } = &cmd {
-        let queries: Vec<CrossChainQueryRequest> = batch
+        let packets: Vec<&CrossChainQueryPacket> = batch
             .events
             .iter()
-            .filter_map(|ev| ev.try_into().ok())
+            .filter_map(|ev| ev.event.cross_chain_query_packet())
             .collect();
 
+        if packets.is_empty() {
+            return Ok(());
+        }
+
+        // The queries served are restricted by the configuration of the querying chain
+        let icq_config = chain_a_handle
+            .config()
+            .map_err(|e| TaskError::Ignore(RunError::relayer(e)))?
+            .icq;
+
+        let (packets, filtered): (Vec<_>, Vec<_>) = packets
+            .into_iter()
+            .partition(|packet| icq_config.allows_query(&packet.query_type));
+
+        for packet in &filtered {
+            warn!(
+                "skipping query {} of type '{}': not allowed by the `icq` configuration of chain {}",
+                packet.query_id,
+                packet.query_type,
+                chain_a_handle.id()
+            );
+        }
+        telemetry!(record_failed_queries(
+            &chain_a_handle,
+            &chain_b_handle,
+            "filtered",
+            filtered.len()
+        ));
+
+        if packets.is_empty() {
+            return Ok(());
+        }
+
+        let queries: Vec<CrossChainQueryRequest> =
+            packets.iter().map(|&packet| packet.into()).collect();
+
         // Handle of queried chain has to query data from it's RPC
         info!("request: {}", cross_chain_query.short_name());
-        let response = chain_b_handle.cross_chain_query(queries);
-        if let Ok(cross_chain_query_responses) = response {
-            // Run only when cross chain query response is not empty
-            if !cross_chain_query_responses.is_empty() {
-                // Find connection between querying chain and queried chain
-                let connection_end = chain_a_handle
-                    .query_connection(
-                        QueryConnectionRequest {
-                            connection_id: cross_chain_query.connection_id.clone(),
-                            height: QueryHeight::Latest,
-                        },
-                        IncludeProof::No,
-                    )
-                    .map_err(|_| TaskError::Fatal(RunError::query()))?
-                    .0;
-
-                // Retrieve client based on client id
-                let client_a = ForeignClient::find(
-                    chain_b_handle.clone(),
-                    chain_a_handle.clone(),
-                    connection_end.client_id(),
-                )
-                .map_err(|_| TaskError::Fatal(RunError::query()))?;
-
-                let target_height = Height::new(
-                    chain_b_handle.id().version(),
-                    cross_chain_query_responses.get(0).unwrap().height as u64,
-                )
-                .map_err(|_| TaskError::Fatal(RunError::query()))?
-                .increment();
-
-                // Push update client msg
-                let mut chain_a_msgs = client_a
-                    .wait_and_build_update_client(target_height)
-                    .map_err(|_| TaskError::Fatal(RunError::query()))?;
-
-                for response in cross_chain_query_responses {
-                    info!("response arrived: query_id: {}", response.query_id);
-                    // After updating client, send response tx to querying chain
-                    chain_a_msgs.push(
-                        response
-                            .try_to_any(
-                                chain_a_handle
-                                    .get_signer()
-                                    .map_err(|_| TaskError::Fatal(RunError::query()))?,
-                            )
-                            .map_err(|_| TaskError::Fatal(RunError::query()))?,
-                    );
-                }
-
-                chain_a_handle
-                    .send_messages_and_wait_check_tx(TrackedMsgs::new_uuid(
-                        chain_a_msgs,
-                        Uuid::new_v4(),
-                    ))
-                    .map_err(|_| TaskError::Ignore(RunError::query()))?;
+        let cross_chain_query_responses = match chain_b_handle.cross_chain_query(queries) {
+            Ok(responses) => responses,
+            Err(e) => {
+                warn!("failed to query chain {}: {}", chain_b_handle.id(), e);
+                telemetry!(record_failed_queries(
+                    &chain_a_handle,
+                    &chain_b_handle,
+                    "query",
+                    packets.len()
+                ));
+                return Ok(());
             }
+        };
+
+        telemetry!(record_failed_queries(
+            &chain_a_handle,
+            &chain_b_handle,
+            "query",
+            packets.len() - cross_chain_query_responses.len(),
+        ));
+
+        let (cross_chain_query_responses, too_large): (Vec<_>, Vec<_>) =
+            cross_chain_query_responses
+                .into_iter()
+                .partition(|response| icq_config.allows_response_size(response.result.len()));
+
+        for response in &too_large {
+            warn!(
+                "dropping the response to query {}: its result of {} bytes exceeds the maximum response size",
+                response.query_id,
+                response.result.len()
+            );
+        }
+        telemetry!(record_failed_queries(
+            &chain_a_handle,
+            &chain_b_handle,
+            "response_too_large",
+            too_large.len(),
+        ));
+
+        // Run only when cross chain query response is not empty
+        if !cross_chain_query_responses.is_empty() {
+            let served = cross_chain_query_responses.len();
+
+            let chain_a_msgs = build_cross_chain_query_responses(
+                &chain_a_handle,
+                &chain_b_handle,
+                &cross_chain_query.connection_id,
+                cross_chain_query_responses,
+            )
+            .map_err(TaskError::Fatal)?;
+
+            info!(
+                "submitting {} query responses to chain {}",
+                served,
+                chain_a_handle.id()
+            );
+
+            if let Err(e) = chain_a_handle.send_messages_and_wait_check_tx(TrackedMsgs::new_uuid(
+                chain_a_msgs,
+                Uuid::new_v4(),
+            )) {
+                telemetry!(record_failed_queries(
+                    &chain_a_handle,
+                    &chain_b_handle,
+                    "submit",
+                    served
+                ));
+                return Err(TaskError::Ignore(RunError::relayer(e)));
+            }
+
+            telemetry!(
+                cross_chain_queries_served,
+                &chain_a_handle.id(),
+                &chain_b_handle.id(),
+                served as u64
+            );
         }
     }
     Ok(())
 }
+
+/// Build the messages answering the given queries of the querying chain `chain_a`
+/// with the responses of the queried chain `chain_b`: an update of the client of
+/// `chain_b` underlying the given connection of `chain_a`, followed by the responses.
+pub fn build_cross_chain_query_responses<ChainA: ChainHandle, ChainB: ChainHandle>(
+    chain_a_handle: &ChainA,
+    chain_b_handle: &ChainB,
+    connection_id: &ConnectionId,
+    responses: Vec<CrossChainQueryResponse>,
+) -> Result<Vec<Any>, RunError> {
+    let Some(first_response) = responses.first() else {
+        return Ok(vec![]);
+    };
+
+    // Find connection between querying chain and queried chain
+    let connection_end = chain_a_handle
+        .query_connection(
+            QueryConnectionRequest {
+                connection_id: connection_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(RunError::relayer)?
+        .0;
+
+    // Retrieve client based on client id
+    let client_a = ForeignClient::find(
+        chain_b_handle.clone(),
+        chain_a_handle.clone(),
+        connection_end.client_id(),
+    )
+    .map_err(RunError::foreign_client)?;
+
+    let target_height = Height::new(chain_b_handle.id().version(), first_response.height as u64)
+        .map_err(RunError::ics02)?
+        .increment();
+
+    // Push update client msg
+    let mut chain_a_msgs = client_a
+        .wait_and_build_update_client(target_height)
+        .map_err(RunError::foreign_client)?;
+
+    let signer = chain_a_handle.get_signer().map_err(RunError::relayer)?;
+
+    for response in responses {
+        info!("response arrived: query_id: {}", response.query_id);
+        // After updating client, send response tx to querying chain
+        chain_a_msgs.push(
+            response
+                .try_to_any(signer.clone())
+                .map_err(|e| RunError::relayer(Error::ics31(e)))?,
+        );
+    }
+
+    Ok(chain_a_msgs)
+}
+
+#[cfg(feature = "telemetry")]
+fn record_failed_queries<ChainA: ChainHandle, ChainB: ChainHandle>(
+    chain_a_handle: &ChainA,
+    chain_b_handle: &ChainB,
+    reason: &'static str,
+    count: usize,
+) {
+    if count > 0 {
+        ibc_telemetry::global().cross_chain_queries_failed(
+            &chain_a_handle.id(),
+            &chain_b_handle.id(),
+            reason,
+            count as u64,
+        );
+    }
+}
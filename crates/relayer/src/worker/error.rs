@@ -4,6 +4,8 @@ use ibc_relayer_types::core::ics02_client::error::Error as Ics02Error;
 
 use crate::channel::ChannelError;
 use crate::connection::ConnectionError;
+use crate::error::Error as RelayerError;
+use crate::foreign_client::ForeignClientError;
 use crate::link::error::LinkError;
 
 define_error! {
@@ -24,6 +26,14 @@ define_error! {
             [ LinkError ]
             | _ | { "link error" },
 
+        ForeignClient
+            [ ForeignClientError ]
+            | _ | { "foreign client error" },
+
+        Relayer
+            [ RelayerError ]
+            | _ | { "relayer error" },
+
         Retry
             { retries: retry::Error<u64> }
             | e | { format_args!("worker failed after {} retries", e.retries) },
@@ -4,6 +4,7 @@ use core::mem;
 use crossbeam_channel::Sender;
 use serde::Deserialize;
 use serde::Serialize;
+use tendermint::Hash as TxHash;
 use tracing::{debug, trace};
 
 use ibc_relayer_types::{
@@ -13,6 +14,7 @@ use ibc_relayer_types::{
 
 use crate::chain::tracking::TrackingId;
 use crate::event::IbcEventWithHeight;
+use crate::link::pending::PendingTxHashes;
 use crate::util::lock::{LockExt, RwArc};
 use crate::util::task::TaskHandle;
 use crate::{event::source::EventBatch, object::Object};
@@ -31,6 +33,7 @@ pub struct WorkerHandle {
     data: Option<WorkerData>,
     tx: RwArc<Option<Sender<WorkerCmd>>>,
     task_handles: Vec<TaskHandle>,
+    pending_txs: Vec<PendingTxHashes>,
 }
 
 impl WorkerHandle {
@@ -40,6 +43,7 @@ impl WorkerHandle {
         data: Option<WorkerData>,
         tx: Option<Sender<WorkerCmd>>,
         task_handles: Vec<TaskHandle>,
+        pending_txs: Vec<PendingTxHashes>,
     ) -> Self {
         Self {
             id,
@@ -47,6 +51,7 @@ impl WorkerHandle {
             data,
             tx: <RwArc<_>>::new_lock(tx),
             task_handles,
+            pending_txs,
         }
     }
 
@@ -91,6 +96,16 @@ impl WorkerHandle {
         self.try_send_command(WorkerCmd::ClearPendingPackets);
     }
 
+    /// The hashes of the transactions submitted by the worker to the given chain,
+    /// which are waiting to be confirmed.
+    pub fn pending_tx_hashes(&self, chain_id: &ChainId) -> Vec<TxHash> {
+        self.pending_txs
+            .iter()
+            .filter(|pending| pending.chain_id() == chain_id)
+            .flat_map(PendingTxHashes::tx_hashes)
+            .collect()
+    }
+
     /// Shutdown all worker tasks without waiting for them to terminate.
     pub fn shutdown(&self) {
         for task in self.task_handles.iter() {
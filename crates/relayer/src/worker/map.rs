@@ -1,9 +1,11 @@
 use alloc::collections::btree_map::BTreeMap as HashMap;
+use alloc::collections::BTreeSet;
 use core::mem;
 
 use ibc_relayer_types::core::ics02_client::events::NewBlock;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::Height;
+use tendermint::Hash as TxHash;
 use tracing::{debug, trace};
 
 use crate::{
@@ -194,6 +196,15 @@ impl WorkerMap {
             .collect()
     }
 
+    /// The hashes of the transactions submitted to the given chain by the workers,
+    /// which are waiting to be confirmed.
+    pub fn pending_tx_hashes(&self, chain_id: &ChainId) -> BTreeSet<TxHash> {
+        self.workers
+            .values()
+            .flat_map(|handle| handle.pending_tx_hashes(chain_id))
+            .collect()
+    }
+
     /// Return all the handles to the workers tracked in this map.
     pub fn handles(&self) -> impl Iterator<Item = &WorkerHandle> {
         self.workers.values()
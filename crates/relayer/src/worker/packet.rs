@@ -24,6 +24,7 @@ use tracing::{error, error_span, trace};
 use ibc_relayer_types::Height;
 
 use crate::chain::handle::ChainHandle;
+use crate::chain::node_lag::NodeLag;
 use crate::config::filter::FeePolicy;
 use crate::event::source::EventBatch;
 use crate::foreign_client::HasExpiredOrFrozenError;
@@ -44,6 +45,24 @@ const INCENTIVIZED_CACHE_MAX_CAPACITY: u64 = 1000;
 // packet cmd worker.
 const IDLE_TIMEOUT_BLOCKS: u64 = 100;
 
+/// The node lags of the source and destination chains of the link, which the packet
+/// workers poll to pause while the node of either chain lags too far behind its chain.
+fn link_node_lags<ChainA: ChainHandle, ChainB: ChainHandle>(
+    link: &Link<ChainA, ChainB>,
+) -> Vec<NodeLag> {
+    [
+        link.a_to_b.src_chain().get_node_lag(),
+        link.a_to_b.dst_chain().get_node_lag(),
+    ]
+    .into_iter()
+    .filter_map(Result::ok)
+    .collect()
+}
+
+fn is_node_lagging(node_lags: &[NodeLag]) -> bool {
+    node_lags.iter().any(NodeLag::is_degraded)
+}
+
 fn handle_link_error_in_task(e: LinkError) -> TaskError<RunError> {
     if e.is_expired_or_frozen_error() {
         // If the client is expired or frozen, terminate the packet worker
@@ -73,7 +92,14 @@ pub fn spawn_packet_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
         )
     };
 
+    let node_lags = link_node_lags(&link.lock().unwrap());
+
     spawn_background_task(span, Some(Duration::from_millis(1000)), move || {
+        if is_node_lagging(&node_lags) {
+            trace!("node of the source or destination chain is lagging, skipping step");
+            return Ok(Next::Continue);
+        }
+
         handle_execute_schedule(&mut link.lock().unwrap(), &path, resubmit)?;
         Ok(Next::Continue)
     })
@@ -106,7 +132,15 @@ pub fn spawn_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
 
     let mut idle_worker_timer = 0;
 
+    let node_lags = link_node_lags(&link.lock().unwrap());
+
     spawn_background_task(span, Some(Duration::from_millis(200)), move || {
+        // The commands are left in the channel while a node is lagging,
+        // to be handled once it has caught up
+        if is_node_lagging(&node_lags) {
+            return Ok(Next::Continue);
+        }
+
         if let Ok(cmd) = cmd_rx.try_recv() {
             let is_new_batch = cmd.is_ibc_events();
 
@@ -169,7 +203,13 @@ pub fn spawn_incentivized_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHa
             .build(),
     );
 
+    let node_lags = link_node_lags(&link.lock().unwrap());
+
     spawn_background_task(span, Some(Duration::from_millis(200)), move || {
+        if is_node_lagging(&node_lags) {
+            return Ok(Next::Continue);
+        }
+
         if let Ok(cmd) = cmd_rx.try_recv() {
             handle_incentivized_packet_cmd(
                 &mut link.lock().unwrap(),
@@ -1,7 +1,7 @@
-use crate::util::retry::{clamp_total, ConstantGrowth};
+use crate::config::RetryPolicy;
 use core::time::Duration;
 
-/// A basic worker retry strategy.
+/// A basic worker retry strategy, the default one of the connection workers.
 ///
 /// The backoff delay is initially 200ms and grows
 /// by 100ms at each step. The backoff delay is
@@ -10,8 +10,7 @@ use core::time::Duration;
 /// is capped to 2 seconds.
 /// See the `default_strategy` test below.
 pub fn worker_default_strategy() -> impl Iterator<Item = Duration> {
-    let strategy = ConstantGrowth::new(Duration::from_millis(200), Duration::from_millis(100));
-    clamp_total(strategy, Duration::from_millis(500), Duration::from_secs(2))
+    RetryPolicy::connection_handshake().delays()
 }
 
 #[cfg(test)]
@@ -1,9 +1,13 @@
 use std::time::Duration;
 
+use tendermint::Time;
 use tracing::{error_span, trace, warn};
 
 use crate::{
+    account::FeeAllowanceStatus,
     chain::handle::ChainHandle,
+    config::ChainConfig,
+    keyring::list_keys,
     telemetry,
     util::task::{spawn_background_task, Next, TaskError, TaskHandle},
 };
@@ -16,38 +20,128 @@ pub fn spawn_wallet_worker<Chain: ChainHandle>(chain: Chain) -> TaskHandle {
             TaskError::Fatal(format!("failed to get key in use by the relayer: {e}"))
         })?;
 
-        let balance = chain.query_balance(None, None).map_err(|e| {
-            TaskError::Ignore(format!("failed to query balance for the account: {e}"))
+        report_balance(&chain, None, &key.account())?;
+
+        let config = chain.config().map_err(|e| {
+            TaskError::Ignore(format!("failed to get the configuration of the chain: {e}"))
         })?;
 
-        match balance.amount.parse::<f64>() {
-            Ok(amount) => {
-                telemetry!(
-                    wallet_balance,
-                    &chain.id(),
-                    &key.account(),
-                    amount,
-                    &balance.denom,
-                );
-                trace!(%amount, denom = %balance.denom, account = %key.account(), "wallet balance");
-                telemetry!(
-                    update_period_fees,
-                    &chain.id(),
-                    &key.account(),
-                    &balance.denom
-                );
-            }
-            Err(e) => {
-                warn!(
-                    %balance.amount, denom = %balance.denom, account = %key.account(),
-                    "unable to parse the wallet balance into a f64, the balance will therefore not be reported to telemetry. Reason: {}", e
-                );
+        report_fee_allowance(&chain, &config, config.key_name.primary(), &key.account())?;
+
+        // Report the balance and fee allowance of the secondary wallets, if any
+        if config.key_name.len() > 1 {
+            let keys = list_keys(&config).map_err(|e| {
+                TaskError::Ignore(format!("failed to list the keys of the chain: {e}"))
+            })?;
+
+            for key_name in config.key_name.iter().skip(1) {
+                match keys.iter().find(|(name, _)| name == key_name) {
+                    Some((_, key)) => {
+                        report_balance(&chain, Some(key_name.to_string()), &key.account())?;
+                        report_fee_allowance(&chain, &config, key_name, &key.account())?;
+                    }
+                    None => warn!(
+                        key_name,
+                        "key not found in the keyring, its balance will not be reported to telemetry"
+                    ),
+                }
             }
         }
+
         Ok(Next::Continue)
     })
 }
 
+/// Report the remaining fee allowance of the given key, if a fee granter is configured for it,
+/// and warn if the allowance cannot be used to pay for transactions anymore.
+fn report_fee_allowance<Chain: ChainHandle>(
+    chain: &Chain,
+    config: &ChainConfig,
+    key_name: &str,
+    account: &str,
+) -> Result<(), TaskError<String>> {
+    let granter = match config.fee_granter_for(key_name) {
+        Some(granter) => granter,
+        None => return Ok(()),
+    };
+
+    let allowance = chain
+        .query_fee_allowance(Some(key_name.to_string()))
+        .map_err(|e| {
+            TaskError::Ignore(format!(
+                "failed to query fee allowance for the account: {e}"
+            ))
+        })?;
+
+    let allowance = match allowance {
+        Some(allowance) => allowance,
+        None => {
+            warn!(
+                key_name, %account, %granter,
+                "no fee allowance found for the account, transactions paid for by the fee granter will be rejected"
+            );
+
+            return Ok(());
+        }
+    };
+
+    let denom = &config.gas_price.denom;
+
+    if let Some(_remaining) = allowance.remaining(denom) {
+        telemetry!(
+            fee_grant_remaining,
+            &chain.id(),
+            account,
+            granter,
+            _remaining,
+            denom
+        );
+    }
+
+    match allowance.status(Time::now()) {
+        FeeAllowanceStatus::Active => {
+            trace!(key_name, %account, %granter, "fee allowance is active")
+        }
+        status => warn!(
+            key_name, %account, %granter, %status,
+            "fee allowance cannot be used anymore, transactions paid for by the fee granter will be rejected"
+        ),
+    }
+
+    Ok(())
+}
+
+fn report_balance<Chain: ChainHandle>(
+    chain: &Chain,
+    key_name: Option<String>,
+    account: &str,
+) -> Result<(), TaskError<String>> {
+    let balance = chain
+        .query_balance(key_name, None)
+        .map_err(|e| TaskError::Ignore(format!("failed to query balance for the account: {e}")))?;
+
+    match balance.amount.parse::<f64>() {
+        Ok(amount) => {
+            telemetry!(wallet_balance, &chain.id(), account, amount, &balance.denom,);
+            trace!(%amount, denom = %balance.denom, %account, "wallet balance");
+            telemetry!(
+                update_period_fees,
+                &chain.id(),
+                &account.to_string(),
+                &balance.denom
+            );
+        }
+        Err(e) => {
+            warn!(
+                %balance.amount, denom = %balance.denom, %account,
+                "unable to parse the wallet balance into a f64, the balance will therefore not be reported to telemetry. Reason: {}", e
+            );
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use ibc_relayer_types::bigint::U256;
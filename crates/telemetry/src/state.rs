@@ -104,6 +104,9 @@ pub struct TelemetryState {
     /// Number of misbehaviours detected and submitted per client
     client_misbehaviours_submitted: Counter<u64>,
 
+    /// How long each client can go without being updated before it expires, per client. Seconds.
+    client_time_to_expiry: ObservableGauge<u64>,
+
     /// Number of confirmed receive packets per channel
     receive_packets_confirmed: Counter<u64>,
 
@@ -119,18 +122,51 @@ pub struct TelemetryState {
     /// Number of cache hits for queries submitted by Hermes, per chain and query type
     queries_cache_hits: Counter<u64>,
 
+    /// Number of cache misses for queries submitted by Hermes, per chain and query type
+    queries_cache_misses: Counter<u64>,
+
+    /// Number of entries evicted from the query caches, per chain and query type
+    queries_cache_evictions: Counter<u64>,
+
+    /// Number of entries in the query caches, per chain and query type
+    queries_cache_size: ObservableGauge<u64>,
+
     /// Number of times Hermes reconnected to the websocket endpoint, per chain
     ws_reconnect: Counter<u64>,
 
     /// How many IBC events did Hermes receive via the WebSocket subscription, per chain
     ws_events: Counter<u64>,
 
+    /// Whether each endpoint of a chain was healthy when last probed, per chain, kind and endpoint
+    endpoint_healthy: ObservableGauge<u64>,
+
+    /// How long the last health probe of each endpoint of a chain took, per chain, kind and endpoint. Milliseconds.
+    endpoint_latency: ObservableGauge<u64>,
+
+    /// Number of times Hermes failed over from an endpoint to another, per chain and kind of endpoint
+    endpoint_failovers: Counter<u64>,
+
+    /// How many blocks the node queried for a chain lagged behind the alternate endpoints of the chain at the last check, per chain
+    node_lag_blocks: ObservableGauge<u64>,
+
+    /// Number of interchain queries (ICS-31) answered, per querying and queried chain
+    cross_chain_queries_served: Counter<u64>,
+
+    /// Number of interchain queries (ICS-31) which could not be answered, per querying and queried chain and reason
+    cross_chain_queries_failed: Counter<u64>,
+
     /// Number of messages submitted to a specific chain
     messages_submitted: Counter<u64>,
 
     /// The balance of each wallet Hermes uses per chain
     wallet_balance: ObservableGauge<f64>,
 
+    /// Number of transactions submitted by each wallet Hermes uses per chain
+    wallet_txs_submitted: Counter<u64>,
+
+    /// The remaining amount each wallet Hermes uses may spend from its fee grant, per chain
+    fee_grant_remaining: ObservableGauge<f64>,
+
     /// Indicates the latency for all transactions submitted to a specific chain,
     /// i.e. the difference between the moment when Hermes received a batch of events
     /// until the corresponding transaction(s) were submitted. Milliseconds.
@@ -238,6 +274,12 @@ impl TelemetryState {
                 .with_description("Number of misbehaviours detected and submitted")
                 .init(),
 
+            client_time_to_expiry: meter
+                .u64_observable_gauge("client_time_to_expiry_seconds")
+                .with_unit(Unit::new("seconds"))
+                .with_description("How long each client can go without being updated before it expires (seconds)")
+                .init(),
+
             receive_packets_confirmed: meter
                 .u64_counter("receive_packets_confirmed")
                 .with_description("Number of confirmed receive packets. Available if relayer runs with Tx confirmation enabled")
@@ -265,6 +307,21 @@ impl TelemetryState {
                 .with_description("Number of cache hits for queries submitted by Hermes")
                 .init(),
 
+            queries_cache_misses: meter
+                .u64_counter("queries_cache_misses")
+                .with_description("Number of cache misses for queries submitted by Hermes")
+                .init(),
+
+            queries_cache_evictions: meter
+                .u64_counter("queries_cache_evictions")
+                .with_description("Number of entries evicted from the query caches")
+                .init(),
+
+            queries_cache_size: meter
+                .u64_observable_gauge("queries_cache_size")
+                .with_description("Number of entries in the query caches")
+                .init(),
+
             ws_reconnect: meter
                 .u64_counter("ws_reconnect")
                 .with_description("Number of times Hermes reconnected to the websocket endpoint")
@@ -275,6 +332,37 @@ impl TelemetryState {
                 .with_description("How many IBC events did Hermes receive via the websocket subscription")
                 .init(),
 
+            endpoint_healthy: meter
+                .u64_observable_gauge("endpoint_healthy")
+                .with_description("Whether each endpoint of a chain was healthy when last probed (1) or not (0)")
+                .init(),
+
+            endpoint_latency: meter
+                .u64_observable_gauge("endpoint_latency")
+                .with_unit(Unit::new("milliseconds"))
+                .with_description("How long the last health probe of each endpoint of a chain took (milliseconds)")
+                .init(),
+
+            endpoint_failovers: meter
+                .u64_counter("endpoint_failovers")
+                .with_description("Number of times Hermes failed over from an endpoint of a chain to another")
+                .init(),
+
+            node_lag_blocks: meter
+                .u64_observable_gauge("node_lag_blocks")
+                .with_description("How many blocks the node queried for a chain lagged behind the alternate endpoints of the chain at the last check")
+                .init(),
+
+            cross_chain_queries_served: meter
+                .u64_counter("cross_chain_queries_served")
+                .with_description("Number of interchain queries (ICS-31) answered")
+                .init(),
+
+            cross_chain_queries_failed: meter
+                .u64_counter("cross_chain_queries_failed")
+                .with_description("Number of interchain queries (ICS-31) which could not be answered")
+                .init(),
+
             messages_submitted: meter
                 .u64_counter("messages_submitted")
                 .with_description("Number of messages submitted to a specific chain")
@@ -285,6 +373,16 @@ impl TelemetryState {
                 .with_description("The balance of each wallet Hermes uses per chain. Please note that when converting the balance to f64 a loss in precision might be introduced in the displayed value")
                 .init(),
 
+            wallet_txs_submitted: meter
+                .u64_counter("wallet_txs_submitted")
+                .with_description("Number of transactions submitted by each wallet Hermes uses per chain")
+                .init(),
+
+            fee_grant_remaining: meter
+                .f64_observable_gauge("fee_grant_remaining")
+                .with_description("The remaining amount each wallet Hermes uses may spend from its fee grant, per chain. Please note that when converting the amount to f64 a loss in precision might be introduced in the displayed value")
+                .init(),
+
             send_packet_events: meter
                 .u64_counter("send_packet_events")
                 .with_description("Number of SendPacket events received")
@@ -383,6 +481,15 @@ impl TelemetryState {
         self.ws_events.add(&cx, 0, labels);
         self.messages_submitted.add(&cx, 0, labels);
 
+        for kind in ["rpc", "grpc", "websocket"] {
+            let labels = &[
+                KeyValue::new("chain", chain_id.to_string()),
+                KeyValue::new("kind", kind),
+            ];
+
+            self.endpoint_failovers.add(&cx, 0, labels);
+        }
+
         self.init_queries(chain_id);
     }
 
@@ -483,6 +590,9 @@ impl TelemetryState {
             ];
 
             self.queries_cache_hits.add(&cx, 0, labels);
+            self.queries_cache_misses.add(&cx, 0, labels);
+            self.queries_cache_evictions.add(&cx, 0, labels);
+            self.queries_cache_size.observe(&cx, 0, labels);
         }
     }
 
@@ -531,6 +641,25 @@ impl TelemetryState {
         self.client_misbehaviours_submitted.add(&cx, count, labels);
     }
 
+    /// How long the given client can go without being updated before it expires
+    pub fn client_time_to_expiry(
+        &self,
+        src_chain: &ChainId,
+        dst_chain: &ChainId,
+        client: &ClientId,
+        seconds: u64,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("dst_chain", dst_chain.to_string()),
+            KeyValue::new("client", client.to_string()),
+        ];
+
+        self.client_time_to_expiry.observe(&cx, seconds, labels);
+    }
+
     /// Number of receive packets relayed, per channel
     #[allow(clippy::too_many_arguments)]
     pub fn receive_packets_confirmed(
@@ -640,6 +769,42 @@ impl TelemetryState {
         self.queries_cache_hits.add(&cx, 1, labels);
     }
 
+    /// Number of cache misses for queries emitted by the relayer, per chain and query type
+    pub fn queries_cache_misses(&self, chain_id: &ChainId, query_type: &'static str) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("query_type", query_type),
+        ];
+
+        self.queries_cache_misses.add(&cx, 1, labels);
+    }
+
+    /// Number of entries evicted from the query caches, per chain and query type
+    pub fn queries_cache_evictions(&self, chain_id: &ChainId, query_type: &'static str) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("query_type", query_type),
+        ];
+
+        self.queries_cache_evictions.add(&cx, 1, labels);
+    }
+
+    /// Number of entries in the query caches, per chain and query type
+    pub fn queries_cache_size(&self, chain_id: &ChainId, query_type: &'static str, size: u64) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("query_type", query_type),
+        ];
+
+        self.queries_cache_size.observe(&cx, size, labels);
+    }
+
     /// Number of time the relayer had to reconnect to the WebSocket endpoint, per chain
     pub fn ws_reconnect(&self, chain_id: &ChainId) {
         let cx = Context::current();
@@ -649,6 +814,85 @@ impl TelemetryState {
         self.ws_reconnect.add(&cx, 1, labels);
     }
 
+    /// Record the result of the last health probe of an endpoint of a chain
+    pub fn endpoint_health(
+        &self,
+        chain_id: &ChainId,
+        kind: &'static str,
+        endpoint: &str,
+        healthy: bool,
+        latency_millis: u64,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("kind", kind),
+            KeyValue::new("endpoint", endpoint.to_string()),
+        ];
+
+        self.endpoint_healthy
+            .observe(&cx, u64::from(healthy), labels);
+        self.endpoint_latency.observe(&cx, latency_millis, labels);
+    }
+
+    /// Number of times Hermes failed over from an endpoint to another, per chain and kind of endpoint
+    pub fn endpoint_failovers(&self, chain_id: &ChainId, kind: &'static str) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("kind", kind),
+        ];
+
+        self.endpoint_failovers.add(&cx, 1, labels);
+    }
+
+    /// How many blocks the node queried for a chain lagged behind the alternate endpoints of the chain at the last check
+    pub fn node_lag_blocks(&self, chain_id: &ChainId, blocks: u64) {
+        let cx = Context::current();
+
+        let labels = &[KeyValue::new("chain", chain_id.to_string())];
+
+        self.node_lag_blocks.observe(&cx, blocks, labels);
+    }
+
+    /// Number of interchain queries (ICS-31) answered, per querying and queried chain
+    pub fn cross_chain_queries_served(
+        &self,
+        chain_id: &ChainId,
+        counterparty_chain_id: &ChainId,
+        count: u64,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("counterparty", counterparty_chain_id.to_string()),
+        ];
+
+        self.cross_chain_queries_served.add(&cx, count, labels);
+    }
+
+    /// Number of interchain queries (ICS-31) which could not be answered, per querying and queried chain and reason
+    pub fn cross_chain_queries_failed(
+        &self,
+        chain_id: &ChainId,
+        counterparty_chain_id: &ChainId,
+        reason: &'static str,
+        count: u64,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("counterparty", counterparty_chain_id.to_string()),
+            KeyValue::new("reason", reason),
+        ];
+
+        self.cross_chain_queries_failed.add(&cx, count, labels);
+    }
+
     /// How many IBC events did Hermes receive via the WebSocket subscription, per chain
     pub fn ws_events(&self, chain_id: &ChainId, count: u64) {
         let cx = Context::current();
@@ -681,6 +925,40 @@ impl TelemetryState {
         self.wallet_balance.observe(&cx, amount, labels);
     }
 
+    /// How many transactions were submitted by the given wallet on the chain
+    pub fn wallet_txs_submitted(&self, chain_id: &ChainId, account: &str) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("account", account.to_string()),
+        ];
+
+        self.wallet_txs_submitted.add(&cx, 1, labels);
+    }
+
+    /// The remaining amount the given wallet may spend from the fee grant given by `granter`,
+    /// per denom and chain. Only reported for grants with a spend limit.
+    pub fn fee_grant_remaining(
+        &self,
+        chain_id: &ChainId,
+        account: &str,
+        granter: &str,
+        amount: f64,
+        denom: &str,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("account", account.to_string()),
+            KeyValue::new("granter", granter.to_string()),
+            KeyValue::new("denom", denom.to_string()),
+        ];
+
+        self.fee_grant_remaining.observe(&cx, amount, labels);
+    }
+
     pub fn received_event_batch(&self, tracking_id: impl ToString) {
         self.in_flight_events
             .insert(tracking_id.to_string(), Instant::now());
@@ -1101,9 +1379,14 @@ impl AggregatorSelector for CustomAggregatorSelector {
     fn aggregator_for(&self, descriptor: &Descriptor) -> Option<Arc<dyn Aggregator + Send + Sync>> {
         match descriptor.name() {
             "wallet_balance" => Some(Arc::new(last_value())),
+            "fee_grant_remaining" => Some(Arc::new(last_value())),
             "backlog_oldest_sequence" => Some(Arc::new(last_value())),
             "backlog_oldest_timestamp" => Some(Arc::new(last_value())),
             "backlog_size" => Some(Arc::new(last_value())),
+            "queries_cache_size" => Some(Arc::new(last_value())),
+            "endpoint_healthy" => Some(Arc::new(last_value())),
+            "endpoint_latency" => Some(Arc::new(last_value())),
+            "node_lag_blocks" => Some(Arc::new(last_value())),
             // Prometheus' supports only collector for histogram, sum, and last value aggregators.
             // https://docs.rs/opentelemetry-prometheus/0.10.0/src/opentelemetry_prometheus/lib.rs.html#411-418
             // TODO: Once quantile sketches are supported, replace histograms with that.
@@ -22,6 +22,7 @@ impl TestOverrides for CleanPacketWorkersTest {
                 enabled: false,
                 refresh: false,
                 misbehaviour: false,
+                ..Default::default()
             },
             connections: config::Connections { enabled: false },
             channels: config::Channels { enabled: false },
@@ -85,6 +86,7 @@ impl TestOverrides for CleanChannelWorkersTest {
                 enabled: false,
                 refresh: false,
                 misbehaviour: false,
+                ..Default::default()
             },
             connections: config::Connections { enabled: false },
             channels: config::Channels { enabled: true },
@@ -103,6 +103,7 @@ impl TestOverrides for ExpirationTestOverrides {
                 enabled: true,
                 refresh: true,
                 misbehaviour: true,
+                ..Default::default()
             },
             connections: config::Connections { enabled: true },
             channels: config::Channels { enabled: true },
@@ -35,6 +35,7 @@ impl TestOverrides for SupervisorTest {
                 enabled: true,
                 refresh: true,
                 misbehaviour: true,
+                ..Default::default()
             },
             connections: config::Connections { enabled: true },
             channels: config::Channels { enabled: true },
@@ -169,6 +170,7 @@ impl TestOverrides for SupervisorScanTest {
                 enabled: false, // disable client workers, otherwise we have to scan
                 refresh: true,
                 misbehaviour: true,
+                ..Default::default()
             },
             connections: config::Connections { enabled: true },
             channels: config::Channels { enabled: true },
@@ -8,7 +8,7 @@ use std::str;
 use toml;
 use tracing::debug;
 
-use ibc_relayer::keyring::{Secp256k1KeyPair, SigningKeyPair};
+use ibc_relayer::keyring::{CosmosKeyPair, SigningKeyPair};
 
 use crate::chain::cli::bootstrap::{
     add_genesis_account, add_genesis_validator, add_wallet, collect_gen_txs, initialize,
@@ -207,8 +207,8 @@ impl ChainBootstrapMethodsExt for ChainDriver {
         let hd_path = StandardHDPath::from_str(self.chain_type.hd_path())
             .map_err(|e| eyre!("failed to create StandardHDPath: {:?}", e))?;
 
-        let key = Secp256k1KeyPair::from_seed_file(&seed_content, &hd_path)
-            .map_err(handle_generic_error)?;
+        let key =
+            CosmosKeyPair::from_seed_file(&seed_content, &hd_path).map_err(handle_generic_error)?;
 
         Ok(Wallet::new(wallet_id.to_string(), wallet_address, key))
     }
@@ -26,10 +26,12 @@ use tracing::Span;
 use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
-use ibc_relayer::account::Balance;
+use ibc_relayer::account::{Balance, FeeAllowance};
+use ibc_relayer::cache::Cache;
 use ibc_relayer::chain::client::ClientSettings;
 use ibc_relayer::chain::endpoint::{ChainStatus, HealthCheck};
 use ibc_relayer::chain::handle::{ChainHandle, ChainRequest, Subscription};
+use ibc_relayer::chain::node_lag::NodeLag;
 use ibc_relayer::chain::requests::*;
 use ibc_relayer::chain::tracking::TrackedMsgs;
 use ibc_relayer::client_state::{AnyClientState, IdentifiedAnyClientState};
@@ -113,6 +115,14 @@ where
         self.value().config()
     }
 
+    fn get_cache(&self) -> Result<Cache, Error> {
+        self.value().get_cache()
+    }
+
+    fn get_node_lag(&self) -> Result<NodeLag, Error> {
+        self.value().get_node_lag()
+    }
+
     fn get_key(&self) -> Result<AnySigningKeyPair, Error> {
         self.value().get_key()
     }
@@ -121,6 +131,14 @@ where
         self.value().add_key(key_name, key)
     }
 
+    fn rotate_key(&self, key_name: String) -> Result<(), Error> {
+        self.value().rotate_key(key_name)
+    }
+
+    fn sweep_balances(&self, key_name: String) -> Result<Vec<Balance>, Error> {
+        self.value().sweep_balances(key_name)
+    }
+
     fn ibc_version(&self) -> Result<Option<semver::Version>, Error> {
         self.value().ibc_version()
     }
@@ -384,6 +402,13 @@ where
         self.value().query_packet_events(request)
     }
 
+    fn query_block_packet_events(
+        &self,
+        request: QueryBlockPacketEventsRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.value().query_block_packet_events(request)
+    }
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -403,6 +428,10 @@ where
         self.value().query_all_balances(key_name)
     }
 
+    fn query_fee_allowance(&self, key_name: Option<String>) -> Result<Option<FeeAllowance>, Error> {
+        self.value().query_fee_allowance(key_name)
+    }
+
     fn maybe_register_counterparty_payee(
         &self,
         channel_id: ChannelId,
@@ -424,6 +453,13 @@ where
         self.value().cross_chain_query(request)
     }
 
+    fn query_pending_cross_chain_queries(
+        &self,
+        request: QueryPendingCrossChainQueriesRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.value().query_pending_cross_chain_queries(request)
+    }
+
     fn query_incentivized_packet(
         &self,
         request: QueryIncentivizedPacketRequest,
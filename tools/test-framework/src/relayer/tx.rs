@@ -7,7 +7,7 @@ use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_relayer::chain::cosmos::gas::calculate_fee;
 use ibc_relayer::chain::cosmos::types::config::TxConfig;
 use ibc_relayer::chain::cosmos::types::gas::GasConfig;
-use ibc_relayer::config::{AddressType, GasPrice};
+use ibc_relayer::config::{AddressType, GasPrice, RetryPolicy};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use tendermint_rpc::Url;
 
@@ -62,5 +62,7 @@ pub fn new_tx_config_for_test(
         max_msg_num,
         max_tx_size,
         extension_options,
+        authz_granter: None,
+        retry: RetryPolicy::tx(),
     })
 }
@@ -143,11 +143,13 @@ impl FullNode {
                 url: WebSocketClientUrl::from_str(&self.chain_driver.websocket_address())?,
                 batch_delay: config::default::batch_delay(),
             },
+            compat_mode: None,
             rpc_timeout: config::default::rpc_timeout(),
             trusted_node: false,
             genesis_restart: None,
             account_prefix: self.chain_driver.account_prefix.clone(),
-            key_name: self.wallets.relayer.id.0.clone(),
+            key_name: self.wallets.relayer.id.0.clone().into(),
+            wallet_selection: Default::default(),
             key_store_type: Store::Test,
             key_store_folder: Some(hermes_keystore_dir.into()),
             store_prefix: "ibc".to_string(),
@@ -156,6 +158,8 @@ impl FullNode {
             gas_adjustment: None,
             gas_multiplier: Some(GasMultiplier::unsafe_new(1.2)),
             fee_granter: None,
+            fee_granters: Default::default(),
+            authz_granter: None,
             max_msg_num: Default::default(),
             max_tx_size: Default::default(),
             max_grpc_decoding_size: config::default::max_grpc_decoding_size(),
@@ -170,7 +174,13 @@ impl FullNode {
             memo_prefix: Default::default(),
             proof_specs: Default::default(),
             extension_options: Default::default(),
+            retry: Default::default(),
+            cache: Default::default(),
+            failover: Default::default(),
+            icq: Default::default(),
+            max_node_lag: None,
             sequential_batch_tx: false,
+            dry_run: false,
         })
     }
 
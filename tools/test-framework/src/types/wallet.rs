@@ -3,7 +3,7 @@
 */
 
 use core::fmt::{self, Display};
-use ibc_relayer::keyring::Secp256k1KeyPair;
+use ibc_relayer::keyring::CosmosKeyPair;
 
 use crate::types::env::{prefix_writer, EnvWriter, ExportEnv};
 use crate::types::tagged::*;
@@ -36,7 +36,7 @@ pub struct Wallet {
     // TODO: Parameterize this type on `SigningKeyPair`
     /// The wallet key information in the form of `SigningKeyPair`
     /// that is used by the relayer.
-    pub key: Secp256k1KeyPair,
+    pub key: CosmosKeyPair,
 }
 
 /**
@@ -82,7 +82,7 @@ pub trait TaggedWallet<Chain> {
     fn address(&self) -> MonoTagged<Chain, &WalletAddress>;
 
     /// Get the `SigningKeyPair` tagged with the given `Chain`.
-    fn key(&self) -> MonoTagged<Chain, &Secp256k1KeyPair>;
+    fn key(&self) -> MonoTagged<Chain, &CosmosKeyPair>;
 }
 
 /**
@@ -107,7 +107,7 @@ pub trait TaggedTestWalletsExt<Chain> {
 
 impl Wallet {
     /// Create a new [`Wallet`]
-    pub fn new(id: String, address: String, key: Secp256k1KeyPair) -> Self {
+    pub fn new(id: String, address: String, key: CosmosKeyPair) -> Self {
         Self {
             id: WalletId(id),
             address: WalletAddress(address),
@@ -131,7 +131,7 @@ impl<Chain> TaggedWallet<Chain> for MonoTagged<Chain, Wallet> {
         self.map_ref(|w| &w.address)
     }
 
-    fn key(&self) -> MonoTagged<Chain, &Secp256k1KeyPair> {
+    fn key(&self) -> MonoTagged<Chain, &CosmosKeyPair> {
         self.map_ref(|w| &w.key)
     }
 }
@@ -145,7 +145,7 @@ impl<'a, Chain> TaggedWallet<Chain> for MonoTagged<Chain, &'a Wallet> {
         self.map_ref(|w| &w.address)
     }
 
-    fn key(&self) -> MonoTagged<Chain, &Secp256k1KeyPair> {
+    fn key(&self) -> MonoTagged<Chain, &CosmosKeyPair> {
         self.map_ref(|w| &w.key)
     }
 }